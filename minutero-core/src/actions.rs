@@ -0,0 +1,166 @@
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+use crate::export::MinutaDocument;
+use crate::roster::fold;
+use crate::settings::AppSettings;
+
+// ── Tareas pendientes entre reuniones ──────────────────────────────────────
+//
+// Al guardar una minuta se buscan sus acuerdos: "me encargo de…", "Ana se
+// ocupa de…", "hay que…", "I'll…", "action item: …", con responsable (quien
+// lo dice, o el asistente que se nombra) y fecha si se menciona ("para el
+// viernes", "by Friday"). Son reglas, no un modelo: sale alguna frase de más,
+// que se borra a mano. Las tareas de todas las reuniones se guardan en
+// `actions.json`, junto a `settings.json`, y las abiertas se pueden volcar al
+// orden del día de la siguiente (ver `template::render_agenda`).
+
+const ACTIONS_FILE: &str = "actions.json";
+
+/// Las salas guardan en su propio hilo: leer, añadir y escribir va junto.
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Tarea más corta que esto no es una tarea ("me encargo de eso").
+const MIN_TASK_CHARS: usize = 12;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: u64,
+    pub task: String,
+    pub owner: Option<String>,
+    /// Plazo tal como se dijo ("para el viernes").
+    pub due: Option<String>,
+    /// Título y fecha de la minuta de la que sale.
+    pub session: String,
+    pub date: String,
+    /// Momento de la reunión en que se dijo.
+    pub timestamp: Option<String>,
+    pub done: bool,
+}
+
+/// Compromisos en primera persona: el responsable es quien habla.
+static FIRST_PERSON: LazyLock<Regex> = LazyLock::new(|| pattern(
+    r"\b(?:me encargo de|me ocupo de|me comprometo a|me apunto|i'll|i will|i'm going to|i can take care of)\s+(?P<task>.+)",
+));
+/// "Ana se encarga de…": el responsable es el asistente nombrado.
+static NAMED: LazyLock<Regex> = LazyLock::new(|| pattern(
+    r"\b(?P<owner>\w+)\s+(?:se encarga de|se ocupa de|se compromete a|va a|tiene que|will|is going to|needs to|to follow up on)\s+(?P<task>.+)",
+));
+/// Acuerdos sin responsable.
+static UNASSIGNED: LazyLock<Regex> = LazyLock::new(|| pattern(
+    r"\b(?:hay que|tenemos que|queda pendiente|tarea:|acción:|action item:?|we need to|to-?do:)\s+(?P<task>.+)",
+));
+static DUE: LazyLock<Regex> = LazyLock::new(|| pattern(concat!(
+    r"\b(?:(?:para|antes de|antes del|el|hasta el)\s+(?:el\s+|la\s+)?",
+    r"(?:lunes|martes|miércoles|jueves|viernes|sábado|domingo|\d{1,2} de \w+|fin de mes|semana que viene|próxima semana)",
+    r"|(?:by|before|on|until|due)\s+(?:the\s+)?",
+    r"(?:monday|tuesday|wednesday|thursday|friday|saturday|sunday|tomorrow|next week|end of (?:the )?(?:day|week|month)|\w+ \d{1,2}(?:st|nd|rd|th)?)",
+    r"|la semana que viene|la próxima semana|tomorrow|next week)\b",
+)));
+
+fn pattern(source: &str) -> Regex {
+    RegexBuilder::new(source).case_insensitive(true).build().expect("patrón de tareas válido")
+}
+
+/// Tareas que se acuerdan en `doc`, sin `id`.
+pub fn extract(doc: &MinutaDocument) -> Vec<ActionItem> {
+    let first_names: Vec<String> = doc.attendees.iter()
+        .filter_map(|name| name.split_whitespace().next().map(fold))
+        .collect();
+    let mut items = Vec::new();
+    for entry in &doc.entries {
+        for sentence in entry.text.split(['.', '?', '!', ';']) {
+            let found = if let Some(c) = FIRST_PERSON.captures(sentence) {
+                Some((entry.speaker.clone(), c["task"].to_string()))
+            } else if let Some(c) = NAMED.captures(sentence).filter(|c| first_names.contains(&fold(&c["owner"]))) {
+                let owner = doc.attendees.iter().find(|a| a.split_whitespace().next().map(fold) == Some(fold(&c["owner"])));
+                Some((owner.cloned(), c["task"].to_string()))
+            } else {
+                UNASSIGNED.captures(sentence).map(|c| (None, c["task"].to_string()))
+            };
+            let Some((owner, task)) = found else { continue };
+            let task = task.trim().trim_end_matches(',').to_string();
+            if task.chars().count() < MIN_TASK_CHARS {
+                continue;
+            }
+            items.push(ActionItem {
+                id: 0,
+                due: DUE.find(&task).map(|m| m.as_str().to_string()),
+                task,
+                owner,
+                session: doc.title.clone(),
+                date: doc.date.clone(),
+                timestamp: entry.timestamp.clone(),
+                done: false,
+            });
+        }
+    }
+    items
+}
+
+/// Todas las tareas guardadas. Si el archivo no existe o está corrupto, ninguna.
+pub fn load() -> Vec<ActionItem> {
+    std::fs::read_to_string(ACTIONS_FILE)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(items: &[ActionItem]) -> Result<()> {
+    std::fs::write(ACTIONS_FILE, serde_json::to_string_pretty(items)?)?;
+    Ok(())
+}
+
+/// Aplica `change` a la lista guardada y la vuelve a escribir.
+fn update(change: impl FnOnce(&mut Vec<ActionItem>)) -> Result<()> {
+    let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut items = load();
+    change(&mut items);
+    save(&items)
+}
+
+/// Añade las tareas de `doc`. Una tarea abierta con el mismo texto y de una
+/// reunión con el mismo título no se repite: volver a guardar la minuta (o la
+/// reunión semanal que la vuelve a mencionar) no la duplica. Devuelve cuántas
+/// son nuevas.
+pub fn record(doc: &MinutaDocument) -> Result<usize> {
+    let found = extract(doc);
+    if found.is_empty() {
+        return Ok(0);
+    }
+    let mut added = 0;
+    update(|items| {
+        let mut next = items.iter().map(|i| i.id).max().unwrap_or(0) + 1;
+        for mut item in found {
+            let known = items.iter().any(|i| !i.done && i.session == item.session && i.task == item.task);
+            if !known {
+                item.id = next;
+                next += 1;
+                added += 1;
+                items.push(item);
+            }
+        }
+    })?;
+    Ok(added)
+}
+
+/// Tras guardar una minuta: apunta sus tareas, si el seguimiento está activado.
+pub fn track(doc: &MinutaDocument, settings: &AppSettings) {
+    if !settings.track_actions {
+        return;
+    }
+    match record(doc) {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("{} tareas nuevas de '{}'", n, doc.title),
+        Err(e) => tracing::error!("Error al guardar las tareas de la minuta: {:?}", e),
+    }
+}
+
+pub fn set_done(id: u64, done: bool) -> Result<()> {
+    update(|items| items.iter_mut().filter(|i| i.id == id).for_each(|i| i.done = done))
+}
+
+pub fn remove(id: u64) -> Result<()> {
+    update(|items| items.retain(|i| i.id != id))
+}
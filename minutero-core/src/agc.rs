@@ -0,0 +1,58 @@
+// ── Control automático de ganancia (AGC) ───────────────────────────────────
+//
+// Sustituye a normalizar cada fragmento por separado, que en los fragmentos
+// sin voz subía el ruido de fondo hasta el mismo nivel que el habla. Aquí la
+// ganancia se calcula muestra a muestra a partir de la envolvente de la
+// señal: baja rápido cuando alguien levanta la voz (attack), sube despacio
+// cuando baja (release) y se congela por debajo de la puerta de ruido, así
+// que un silencio largo no se amplifica. Un limitador al final evita que los
+// picos que se cuelan antes de que la ganancia baje saturen.
+
+/// Nivel de pico al que se lleva la voz.
+const TARGET_PEAK: f32 = 0.5;
+/// Ganancia máxima (≈ +26 dB), para micrófonos muy bajos.
+const MAX_GAIN: f32 = 20.0;
+const MIN_GAIN: f32 = 0.1;
+/// Por debajo de este nivel (≈ −50 dBFS) se considera silencio y la ganancia
+/// no sube.
+const NOISE_GATE: f32 = 0.003;
+const ATTACK_SECS: f32 = 0.01;
+const RELEASE_SECS: f32 = 0.8;
+/// Techo del limitador.
+const LIMIT: f32 = 0.95;
+
+/// Estado del AGC de un stream; se conserva entre bloques.
+pub struct Agc {
+    envelope: f32,
+    gain: f32,
+    attack: f32,
+    release: f32,
+}
+
+impl Agc {
+    pub fn new(sample_rate: u32) -> Self {
+        let coefficient = |secs: f32| 1.0 - (-1.0 / (secs * sample_rate as f32)).exp();
+        Self {
+            envelope: 0.0,
+            gain: 1.0,
+            attack: coefficient(ATTACK_SECS),
+            release: coefficient(RELEASE_SECS),
+        }
+    }
+
+    pub fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            let level = sample.abs();
+            let rate = if level > self.envelope { self.attack } else { self.release };
+            self.envelope += rate * (level - self.envelope);
+
+            if self.envelope > NOISE_GATE {
+                let desired = (TARGET_PEAK / self.envelope).clamp(MIN_GAIN, MAX_GAIN);
+                let rate = if desired < self.gain { self.attack } else { self.release };
+                self.gain += rate * (desired - self.gain);
+            }
+
+            *sample = (*sample * self.gain).clamp(-LIMIT, LIMIT);
+        }
+    }
+}
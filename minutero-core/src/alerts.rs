@@ -0,0 +1,21 @@
+// ── Alertas por palabra clave ──────────────────────────────────────────────
+//
+// Lista de vigilancia ("presupuesto", tu nombre, "action item"…). Se compara
+// sin distinguir mayúsculas contra cada segmento ya finalizado.
+
+/// Separa la lista de vigilancia (una palabra por línea o separadas por comas).
+pub fn parse_keywords(list: &str) -> Vec<String> {
+    list.split(['\n', ','])
+        .map(|k| k.trim().to_lowercase())
+        .filter(|k| !k.is_empty())
+        .collect()
+}
+
+/// Palabras clave de `keywords` (ya en minúsculas) que aparecen en `text`.
+pub fn matched_keywords<'a>(keywords: &'a [String], text: &str) -> Vec<&'a str> {
+    let lower = text.to_lowercase();
+    keywords.iter()
+        .filter(|k| lower.contains(k.as_str()))
+        .map(String::as_str)
+        .collect()
+}
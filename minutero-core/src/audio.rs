@@ -0,0 +1,2461 @@
+use anyhow::Result;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::Host;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use crossbeam_channel::{select, Receiver, RecvTimeoutError, Sender, TrySendError};
+use std::cell::Cell;
+use std::path::Path;
+use std::io::{Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperSegment, WhisperState};
+use tokio::runtime::Runtime;
+use futures_util::StreamExt;
+use reqwest::{Client, Proxy};
+use std::process::{Command, Stdio};
+use crate::i18n::tr;
+use crate::agc::Agc;
+use crate::dsp;
+#[cfg(target_os = "linux")]
+use crate::system_audio::linux_sound_server;
+#[cfg(target_os = "linux")]
+use crate::capture_tools::{self, CaptureTool};
+use crate::hallucinations::HallucinationFilter;
+use crate::postprocess::PostProcessor;
+use crate::process_loopback::{is_process_source, ProcessCapture, PROCESS_PREFIX};
+use crate::wasapi_exclusive::ExclusiveCapture;
+use crate::preload;
+use crate::stop::StopSignal;
+use crate::pool::{ChunkJob, WhisperPool};
+use crate::resources;
+use crate::discord::{self, VoiceEvent};
+use crate::preroll::{PreRollAudio, PreRollBuffers};
+use crate::recording::{MixRecorder, MixSource};
+use crate::speaker_id::VoiceMatcher;
+use crate::data::{
+    AudioMessage, InterlocutorProfile, ModelSource, RestartPolicy, TranscriptionConfig, SourceType, StreamErrorKind, DeviceInfo, UiSender, WordTiming, PcmEncoding,
+    mean_probability,
+    WHISPER_SAMPLE_RATE, MAX_ENERGY_SAVER_SECS, MAX_LATENCY_SECS, SILENCE_THRESHOLD
+};
+
+// ── Enumeración de dispositivos ────────────────────────────────────────────
+
+/// Hosts de cpal compilados para esta plataforma (ALSA, JACK, WASAPI, ASIO…).
+pub fn host_names() -> Vec<&'static str> {
+    cpal::available_hosts().into_iter().map(|id| id.name()).collect()
+}
+
+/// Host elegido en Configuración; el predeterminado si es automático o ya no
+/// está disponible.
+pub fn select_host(name: Option<&str>) -> Host {
+    name.and_then(|n| cpal::available_hosts().into_iter().find(|id| id.name() == n))
+        .and_then(|id| cpal::host_from_id(id).ok())
+        .unwrap_or_else(cpal::default_host)
+}
+
+/// `host_name` = host de cpal elegido, `None` = automático (en Linux,
+/// PulseAudio/PipeWire si lo hay).
+pub fn get_available_devices(host_name: Option<&str>, is_input: bool) -> Vec<DeviceInfo> {
+    // Sin PulseAudio/PipeWire se listan los dispositivos ALSA de cpal
+    #[cfg(target_os = "linux")]
+    if is_input && host_name.is_none() && linux_sound_server().is_some() {
+        return get_linux_input_devices();
+    }
+
+    let host = select_host(host_name);
+    let mut devices: Vec<DeviceInfo> = Vec::new();
+
+    let iter = if is_input { host.input_devices() } else { host.output_devices() };
+
+    if let Ok(device_list) = iter {
+        let mut real_index = 0;
+        for device in device_list {
+            if let Ok(desc) = device.description() {
+                let name = desc.name().to_string();
+
+                // En Linux filtramos monitores del listado de inputs normales
+                // (los monitores se listan aparte vía system_audio)
+                #[cfg(target_os = "linux")]
+                if is_input && (name.contains(".monitor") || name.contains("Monitor of")) {
+                    continue;
+                }
+                // El pseudo-dispositivo `null` de ALSA no captura nada
+                if name == "null" {
+                    continue;
+                }
+
+                devices.push(DeviceInfo {
+                    id: real_index,
+                    name: name.clone(),
+                    // technical_name en todas las plataformas para poder
+                    // encontrar el dispositivo por nombre en cpal más tarde
+                    technical_name: Some(name),
+                    channels: if is_input { device.default_input_config() } else { device.default_output_config() }
+                        .map(|c| c.channels())
+                        .unwrap_or(0),
+                });
+                real_index += 1;
+            }
+        }
+    }
+
+    tracing::info!(
+        "Dispositivos de {} (cpal {}): {:?}",
+        if is_input { "entrada" } else { "salida" },
+        host.id().name(),
+        devices.iter().map(|d| &d.name).collect::<Vec<_>>(),
+    );
+    devices
+}
+
+#[cfg(target_os = "linux")]
+fn get_linux_input_devices() -> Vec<DeviceInfo> {
+    let mut devices = vec![];
+
+    let output = Command::new("pactl")
+        .args(&["list", "sources", "short"])
+        .output();
+
+    if let Ok(out) = &output {
+        let sources = String::from_utf8_lossy(&out.stdout);
+        tracing::debug!("pactl list sources short:\n{}", sources.trim_end());
+
+        for line in sources.lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 2 {
+                let tech_name = parts[1].to_string();
+
+                if !tech_name.contains(".monitor") && tech_name.starts_with("alsa_input") {
+                    let mut description = tech_name.clone();
+
+                    if let Ok(desc_out) = Command::new("pactl").args(&["list", "sources"]).output() {
+                        let full_list = String::from_utf8_lossy(&desc_out.stdout);
+                        let mut found = false;
+                        for desc_line in full_list.lines() {
+                            if desc_line.contains(&format!("Name: {}", tech_name)) {
+                                found = true;
+                            } else if found && desc_line.trim().starts_with("Description:") {
+                                description = desc_line.replace("Description:", "").trim().to_string();
+                                break;
+                            }
+                        }
+                    }
+
+                    devices.push(DeviceInfo {
+                        id: devices.len(),
+                        name: description,
+                        technical_name: Some(tech_name),
+                        channels: pactl_channels(&parts),
+                    });
+                }
+            }
+        }
+    }
+
+    if let Err(e) = &output {
+        tracing::warn!("No se pudo ejecutar pactl: {:?}", e);
+    }
+    tracing::info!("Micrófonos detectados: {:?}", devices.iter().map(|d| &d.name).collect::<Vec<_>>());
+    devices
+}
+
+// ── Hilo principal de audio ────────────────────────────────────────────────
+
+pub fn audio_thread_main(
+    model_name: String,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    profiles: Vec<InterlocutorProfile>,
+    config: TranscriptionConfig,
+    recorder: Option<MixRecorder>,
+    preroll: PreRollAudio,
+) -> Result<()> {
+    tx_ui.send(AudioMessage::Status(tr!("audio.checking_model")))?;
+
+    #[cfg(target_os = "linux")]
+    match linux_sound_server() {
+        Some(server) => tracing::info!("Captura con {:?} ({})", capture_tools::candidates(Some(server), config.capture_tool), server),
+        None => tx_ui.send(AudioMessage::Status(tr!("audio.alsa_fallback")))?,
+    }
+    if let Some(host) = &config.audio_host {
+        tracing::info!("Host de cpal elegido: {}", host);
+    }
+
+    // Antes de cargar nada: si los modelos no caben, mejor un error que el OOM killer
+    let compare_name = config.compare_model.as_deref().filter(|m| *m != model_name);
+    let estimate = resources::estimate(&model_name, compare_name, profiles.len(), &config.model_source, &config.limits);
+    let _reservation = resources::reserve(&estimate)?;
+    if estimate.is_tight() {
+        tx_ui.send(AudioMessage::Status(tr!("resources.tight", required = resources::format_mb(estimate.required_mb))))?;
+    }
+
+    preload::wait();
+    let cached = model_is_cached(&model_name, &config.model_source);
+    let Some(model_path) = download_unless_stopped(&model_name, &config.model_source, &stop_signal, |downloaded, total| {
+        let _ = tx_ui.send(AudioMessage::ModelDownloadProgress { model: model_name.clone(), downloaded, total });
+    })? else {
+        tx_ui.send(AudioMessage::Stopped { pending: Vec::new() })?;
+        return Ok(());
+    };
+    if !cached {
+        tx_ui.send(AudioMessage::ModelDownloaded(model_name.clone()))?;
+    }
+    let compare = match config.compare_model.as_ref().filter(|m| **m != model_name) {
+        Some(compare_name) => {
+            let cached = model_is_cached(compare_name, &config.model_source);
+            let Some(path) = download_unless_stopped(compare_name, &config.model_source, &stop_signal, |downloaded, total| {
+                let _ = tx_ui.send(AudioMessage::ModelDownloadProgress { model: compare_name.clone(), downloaded, total });
+            })? else {
+                tx_ui.send(AudioMessage::Stopped { pending: Vec::new() })?;
+                return Ok(());
+            };
+            if !cached {
+                tx_ui.send(AudioMessage::ModelDownloaded(compare_name.clone()))?;
+            }
+            tracing::info!("Comparando '{}' con '{}'", model_name, compare_name);
+            Some((compare_name.clone(), path))
+        }
+        None => None,
+    };
+
+    // Origen común de los tiempos de todas las fuentes (ver StreamClock).
+    // Con pre-grabación, la sesión empieza con el audio guardado.
+    let session_start = preroll.origin().unwrap_or_else(Instant::now);
+    if preroll.longest_secs() > 0.0 {
+        tx_ui.send(AudioMessage::Status(tr!("audio.preroll_used", secs = format!("{:.0}", preroll.longest_secs()))))?;
+    }
+    let pool = match config.tuning.workers {
+        0 => None,
+        workers => Some(Arc::new(WhisperPool::start(&model_path, workers as usize, &config)?)),
+    };
+    let sink = StreamSink::Transcribe {
+        model: (model_name.clone(), model_path),
+        compare,
+        pool,
+        recorder: recorder.clone(),
+        preroll: Arc::new(preroll),
+    };
+    let mut streams = Vec::new();
+    for group in capture_groups(profiles) {
+        let tx_func = tx_ui.clone();
+        let stop    = stop_signal.clone();
+        let sink    = sink.clone();
+        let config  = config.clone();
+        let name    = group.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", ");
+
+        let handle = thread::spawn(move || supervise_group(group, sink, tx_func, stop, config, session_start));
+        streams.push((name, handle));
+    }
+
+    stop_signal.wait();
+
+    tx_ui.send(AudioMessage::Status(tr!("audio.stopping")))?;
+    let pending = join_streams(streams, SHUTDOWN_TIMEOUT);
+    if let Some(recorder) = recorder {
+        match recorder.finish() {
+            Ok(path) => tracing::info!("Grabación guardada en {}", path.display()),
+            Err(e) => tx_ui.send(AudioMessage::Error(tr!("audio.recording_error", e = format!("{:?}", e))))?,
+        }
+    }
+    tx_ui.send(AudioMessage::Stopped { pending })?;
+    Ok(())
+}
+
+/// Primera espera antes de reabrir una fuente; se dobla en cada reintento.
+const RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+/// Tras este tiempo capturando bien, un fallo vuelve a contar desde cero.
+const RESTART_STABLE: Duration = Duration::from_secs(60);
+
+/// Política del grupo: la más estricta de sus perfiles, que comparten
+/// dispositivo y caen juntos. Con reintentos, los del que más admita.
+fn group_policy(group: &[InterlocutorProfile]) -> (RestartPolicy, u32) {
+    let policy = |p: RestartPolicy| group.iter().any(|profile| profile.on_error == p);
+    if policy(RestartPolicy::FailSession) {
+        (RestartPolicy::FailSession, 0)
+    } else if policy(RestartPolicy::Retry) {
+        let max = group.iter()
+            .filter(|p| p.on_error == RestartPolicy::Retry)
+            .map(|p| p.max_restarts)
+            .max()
+            .unwrap_or_default();
+        (RestartPolicy::Retry, max)
+    } else {
+        (RestartPolicy::Continue, 0)
+    }
+}
+
+/// Captura de un grupo hasta parar, aplicando su política si falla (ver
+/// `RestartPolicy`).
+fn supervise_group(
+    group: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) {
+    let name = group.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", ");
+    let (policy, max_restarts) = group_policy(&group);
+    let mut attempt = 0;
+    loop {
+        let started = Instant::now();
+        let result = run_capture_group(group.clone(), sink.clone(), tx_ui.clone(), stop_signal.clone(), config.clone(), session_start);
+        let Err(e) = result else { return };
+        let _ = tx_ui.send(stream_error(&name, &e));
+        if stop_signal.is_stopped() {
+            return;
+        }
+        if started.elapsed() >= RESTART_STABLE {
+            attempt = 0;
+        }
+        attempt += 1;
+
+        match policy {
+            RestartPolicy::Continue => return,
+            RestartPolicy::FailSession => {
+                tracing::warn!("{} ha fallado; se para la sesión", name);
+                let _ = tx_ui.send(AudioMessage::Error(tr!("restart.session_failed", name = name)));
+                stop_signal.stop();
+                return;
+            }
+            RestartPolicy::Retry => {
+                if attempt > max_restarts {
+                    tracing::warn!("{}: sin más reintentos ({})", name, max_restarts);
+                    for profile in &group {
+                        let _ = tx_ui.send(AudioMessage::StreamGaveUp { profile: profile.name.clone(), max: max_restarts });
+                    }
+                    let _ = tx_ui.send(AudioMessage::Status(tr!("restart.gave_up", name = name, n = max_restarts)));
+                    return;
+                }
+                let delay = RESTART_BACKOFF.saturating_mul(1 << (attempt - 1).min(5)).min(MAX_RESTART_BACKOFF);
+                for profile in &group {
+                    let _ = tx_ui.send(AudioMessage::StreamRestarting {
+                        profile: profile.name.clone(),
+                        attempt,
+                        max: max_restarts,
+                        delay_secs: delay.as_secs_f32(),
+                    });
+                }
+                tracing::info!("{}: reintento {}/{} en {:?}", name, attempt, max_restarts, delay);
+                let _ = tx_ui.send(AudioMessage::Status(tr!(
+                    "restart.retrying",
+                    name = name, n = attempt, max = max_restarts, secs = delay.as_secs(),
+                )));
+                if stop_signal.sleep(delay) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Descarga `model_name` si hace falta, salvo que se pida parar antes:
+/// entonces devuelve `None` y borra lo descargado a medias, que si no
+/// pasaría por un modelo completo la próxima vez.
+fn download_unless_stopped(
+    model_name: &str,
+    source: &ModelSource,
+    stop_signal: &StopSignal,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<Option<String>> {
+    let cached = model_is_cached(model_name, source);
+    Runtime::new()?.block_on(async {
+        tokio::select! {
+            biased;
+            path = download_whisper_model(model_name, source, on_progress) => path.map(Some),
+            _ = stop_signal.cancelled() => {
+                if !cached {
+                    let _ = std::fs::remove_file(Path::new("models").join(model_file_name(model_name)));
+                }
+                Ok(None)
+            }
+        }
+    })
+}
+
+/// Error de una fuente con su tipo, para `AudioMessage::StreamError`.
+#[derive(Debug)]
+struct StreamFailure {
+    kind: StreamErrorKind,
+    message: String,
+}
+
+impl std::fmt::Display for StreamFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for StreamFailure {}
+
+pub(crate) fn stream_failure(kind: StreamErrorKind, message: String) -> anyhow::Error {
+    StreamFailure { kind, message }.into()
+}
+
+/// Aviso estructurado del error `e` de la fuente (o grupo) `profile`.
+fn stream_error(profile: &str, e: &anyhow::Error) -> AudioMessage {
+    let kind = if let Some(failure) = e.downcast_ref::<StreamFailure>() {
+        failure.kind
+    } else if e.is::<cpal::BuildStreamError>() || e.is::<cpal::DefaultStreamConfigError>() || e.is::<cpal::PlayStreamError>() {
+        StreamErrorKind::Device
+    } else if e.is::<std::io::Error>() {
+        StreamErrorKind::Capture
+    } else {
+        StreamErrorKind::Other
+    };
+    AudioMessage::StreamError {
+        profile: profile.to_string(),
+        kind,
+        message: tr!("audio.stream_error", name = profile, e = format!("{:?}", e)),
+    }
+}
+
+/// Captura en espera para la pre-grabación: abre los dispositivos de
+/// `profiles` y llena `buffers` hasta `stop_signal`, sin transcribir nada.
+pub fn preroll_thread_main(
+    profiles: Vec<InterlocutorProfile>,
+    config: TranscriptionConfig,
+    buffers: PreRollBuffers,
+    stop_signal: StopSignal,
+    tx: UiSender,
+) {
+    let session_start = Instant::now();
+    let mut streams = Vec::new();
+    for group in capture_groups(profiles) {
+        let (tx, stop, config) = (tx.clone(), stop_signal.clone(), config.clone());
+        let sink = StreamSink::PreRoll(buffers.clone());
+        let name = group.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", ");
+        streams.push((name.clone(), thread::spawn(move || {
+            if let Err(e) = run_capture_group(group, sink, tx.clone(), stop, config, session_start) {
+                tracing::warn!("Pre-grabación de {}: {:?}", name, e);
+                let _ = tx.send(stream_error(&name, &e));
+            }
+        })));
+    }
+    stop_signal.wait();
+    join_streams(streams, SHUTDOWN_TIMEOUT);
+}
+
+/// Margen para que cada stream termine el fragmento que esté transcribiendo.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Espera a los hilos de captura hasta `timeout`. Devuelve los nombres de los
+/// que siguen vivos; no se pueden matar, pero al cerrar la aplicación su
+/// programa de captura muere con ella (ver `kill_with_parent`).
+fn join_streams(streams: Vec<(String, thread::JoinHandle<()>)>, timeout: Duration) -> Vec<String> {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline && streams.iter().any(|(_, h)| !h.is_finished()) {
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    let mut pending = Vec::new();
+    for (name, handle) in streams {
+        if handle.is_finished() {
+            let _ = handle.join();
+        } else {
+            pending.push(name);
+        }
+    }
+    pending
+}
+
+// ── Grupos de captura y reparto por canal ─────────────────────────────────
+//
+// Cada dispositivo se abre una sola vez. Los perfiles con canal explícito
+// sobre el mismo dispositivo (una mesa de mezclas USB con un micro por
+// persona) forman un grupo: el hilo de captura separa los canales y cada uno
+// va al hilo de transcripción de su perfil. Sin canal, el perfil va solo y
+// recibe la mezcla a mono.
+
+fn capture_groups(profiles: Vec<InterlocutorProfile>) -> Vec<Vec<InterlocutorProfile>> {
+    let mut groups: Vec<Vec<InterlocutorProfile>> = Vec::new();
+    for profile in profiles {
+        let shared = profile.channel.is_some()
+            .then(|| groups.iter_mut().find(|g| {
+                g[0].channel.is_some()
+                    && g[0].technical_name == profile.technical_name
+                    && g[0].source_type == profile.source_type
+            }))
+            .flatten();
+        match shared {
+            Some(group) => group.push(profile),
+            None => groups.push(vec![profile]),
+        }
+    }
+    groups
+}
+
+/// Qué se hace con el audio de cada perfil de un grupo.
+#[derive(Clone)]
+enum StreamSink {
+    /// Transcribir en vivo. `model` = (nombre, ruta) del modelo Whisper ya
+    /// descargado, y `compare` el de comparación si lo hay; `preroll` va por
+    /// delante de lo que se capture.
+    Transcribe {
+        model: (String, String),
+        compare: Option<(String, String)>,
+        /// Trabajadores compartidos; `None` = un estado Whisper por perfil.
+        pool: Option<Arc<WhisperPool>>,
+        recorder: Option<MixRecorder>,
+        preroll: Arc<PreRollAudio>,
+    },
+    /// Solo guardar los últimos segundos, antes de empezar la captura.
+    PreRoll(PreRollBuffers),
+}
+
+impl StreamSink {
+    /// Lo mismo, pero pasando los fragmentos por `pool` y sin comparar.
+    fn with_pool(&self, pool: Arc<WhisperPool>) -> Self {
+        let mut sink = self.clone();
+        if let StreamSink::Transcribe { compare, pool: slot, .. } = &mut sink {
+            *compare = None;
+            *slot = Some(pool);
+        }
+        sink
+    }
+}
+
+fn run_capture_group(
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    match profiles[0].source_type {
+        SourceType::Network => return run_capture_network(profiles, sink, tx_ui, stop_signal, config, session_start),
+        SourceType::Pipe => return run_capture_pipe(profiles, sink, tx_ui, stop_signal, config, session_start),
+        SourceType::Discord => return run_capture_discord(profiles, sink, tx_ui, stop_signal, config, session_start),
+        SourceType::Input | SourceType::Output => {}
+    }
+    if is_process_source(profiles[0].technical_name.as_deref()) {
+        return run_capture_process(profiles, sink, tx_ui, stop_signal, config, session_start);
+    }
+
+    #[cfg(target_os = "linux")]
+    if uses_capture_tool(&profiles[0], &config) {
+        return run_capture_linux(profiles, sink, tx_ui, stop_signal, config, session_start);
+    }
+
+    run_capture_cpal(profiles, sink, tx_ui, stop_signal, config, session_start)
+}
+
+// Las colas entre la captura y cada transcriptor son acotadas: si Whisper no
+// da abasto durante mucho rato, la captura descarta lo más antiguo en vez de
+// llenar la memoria. El siguiente bloque lleva la cuenta de lo descartado
+// para que el transcriptor lo avise y mantenga los tiempos.
+
+/// Audio que puede esperar en la cola de un perfil. Está por encima de
+/// `MAX_LATENCY_SECS`, que es donde actúa el retraso máximo configurable;
+/// esto solo pone techo a la memoria.
+const MAX_QUEUED_SECS: u32 = 150;
+/// Techo de bloques en cola, por si llegan muchos muy pequeños.
+const MAX_QUEUED_BLOCKS: usize = 16 * 1024;
+/// Bloques que el callback de cpal puede adelantar al hilo de captura.
+const CALLBACK_QUEUE_BLOCKS: usize = 1024;
+/// Búferes reservados de antemano para el callback de cpal.
+const CALLBACK_RING_BLOCKS: usize = 8;
+
+/// Búferes vacíos que esperan a reutilizarse en cada anillo.
+const RING_BLOCKS: usize = 64;
+
+/// Búferes que circulan entre quien llena bloques y quien los consume: en
+/// vez de soltarse vuelven vacíos, así que tras los primeros segundos la
+/// captura ya no pide memoria (y el callback de cpal, en un hilo de tiempo
+/// real del sistema, no espera al asignador).
+#[derive(Clone)]
+struct BufferRing {
+    tx: Sender<Vec<f32>>,
+    rx: Receiver<Vec<f32>>,
+}
+
+impl BufferRing {
+    /// Anillo con `blocks` búferes ya reservados de `capacity` muestras.
+    fn new(blocks: usize, capacity: usize) -> Self {
+        let (tx, rx) = crossbeam_channel::bounded(RING_BLOCKS.max(blocks));
+        for _ in 0..blocks {
+            let _ = tx.try_send(Vec::with_capacity(capacity));
+        }
+        Self { tx, rx }
+    }
+
+    /// Un búfer vacío: uno devuelto o, si no queda ninguno, uno nuevo.
+    fn take(&self) -> Vec<f32> {
+        self.rx.try_recv().unwrap_or_default()
+    }
+
+    /// Como `take`, pero el búfer vuelve solo al anillo al soltarse.
+    fn lease(&self) -> RingBuf {
+        RingBuf { buf: self.take(), ring: self.clone() }
+    }
+
+    /// Devuelve `buf` al anillo; si ya está lleno, se suelta.
+    fn give(&self, mut buf: Vec<f32>) {
+        buf.clear();
+        let _ = self.tx.try_send(buf);
+    }
+}
+
+/// Búfer prestado por un `BufferRing`. Se usa como un `Vec` y vuelve al
+/// anillo al soltarse, por cualquier camino.
+struct RingBuf {
+    buf: Vec<f32>,
+    ring: BufferRing,
+}
+
+impl std::ops::Deref for RingBuf {
+    type Target = Vec<f32>;
+    fn deref(&self) -> &Vec<f32> {
+        &self.buf
+    }
+}
+
+impl std::ops::DerefMut for RingBuf {
+    fn deref_mut(&mut self) -> &mut Vec<f32> {
+        &mut self.buf
+    }
+}
+
+impl Drop for RingBuf {
+    fn drop(&mut self) {
+        self.ring.give(std::mem::take(&mut self.buf));
+    }
+}
+
+/// Cola hacia el hilo de transcripción de un perfil dentro de su grupo.
+struct ChannelRoute {
+    channel: Option<u16>,
+    tx: Sender<CapturedBlock>,
+    /// Para sacar lo más antiguo cuando la cola está llena.
+    rx: Receiver<CapturedBlock>,
+    queued: Arc<AtomicUsize>,
+    max_queued: usize,
+    /// Descartado y aún no entregado al transcriptor, en muestras.
+    dropped: Cell<usize>,
+    /// Búferes que devuelve el transcriptor.
+    ring: BufferRing,
+}
+
+/// Bloque de audio de un perfil y el momento en que lo entregó la captura.
+struct CapturedBlock {
+    samples: RingBuf,
+    arrived: Instant,
+    /// Muestras descartadas justo antes de este bloque.
+    dropped: usize,
+}
+
+/// Extremo del transcriptor de la cola de un perfil.
+struct BlockReceiver {
+    rx: Receiver<CapturedBlock>,
+    queued: Arc<AtomicUsize>,
+}
+
+fn block_queue(channel: Option<u16>, sample_rate: u32) -> (ChannelRoute, BlockReceiver) {
+    let (tx, rx) = crossbeam_channel::bounded(MAX_QUEUED_BLOCKS);
+    let queued = Arc::new(AtomicUsize::new(0));
+    let route = ChannelRoute {
+        channel,
+        tx,
+        rx: rx.clone(),
+        queued: queued.clone(),
+        max_queued: (sample_rate * MAX_QUEUED_SECS) as usize,
+        dropped: Cell::new(0),
+        ring: BufferRing::new(0, 0),
+    };
+    (route, BlockReceiver { rx, queued })
+}
+
+impl ChannelRoute {
+    /// Búfer vacío para el siguiente bloque; vuelve aquí cuando el
+    /// transcriptor lo suelta.
+    fn buffer(&self) -> RingBuf {
+        self.ring.lease()
+    }
+
+    /// Encola sin bloquear nunca la captura.
+    fn send(&self, samples: RingBuf, arrived: Instant) {
+        let mut dropped = self.dropped.take();
+        while self.queued.load(Ordering::SeqCst) + samples.len() > self.max_queued || self.tx.is_full() {
+            match self.rx.try_recv() {
+                Ok(old) => {
+                    self.queued.fetch_sub(old.samples.len(), Ordering::SeqCst);
+                    dropped += old.dropped + old.samples.len();
+                }
+                Err(_) => break,
+            }
+        }
+        let len = samples.len();
+        self.queued.fetch_add(len, Ordering::SeqCst);
+        // Si el transcriptor ha caído ya lo ha notificado; el resto sigue.
+        if let Err(TrySendError::Full(block) | TrySendError::Disconnected(block)) =
+            self.tx.try_send(CapturedBlock { samples, arrived, dropped })
+        {
+            self.queued.fetch_sub(len, Ordering::SeqCst);
+            self.dropped.set(block.dropped + len);
+        }
+    }
+}
+
+impl BlockReceiver {
+    /// Siguiente bloque, esperando como mucho `timeout`. Pedir parar corta
+    /// la espera como si se cerrara la cola.
+    fn recv_timeout(&self, timeout: Duration, stop_signal: &StopSignal) -> Result<CapturedBlock, RecvTimeoutError> {
+        let block = select! {
+            recv(self.rx) -> block => block.map_err(|_| RecvTimeoutError::Disconnected)?,
+            recv(stop_signal.channel()) -> _ => return Err(RecvTimeoutError::Disconnected),
+            default(timeout) => return Err(RecvTimeoutError::Timeout),
+        };
+        self.queued.fetch_sub(block.samples.len(), Ordering::SeqCst);
+        Ok(block)
+    }
+
+    /// Muestras capturadas que aún no ha recogido el transcriptor.
+    fn queued(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    fn recv(&self) -> Option<CapturedBlock> {
+        let block = self.rx.recv().ok()?;
+        self.queued.fetch_sub(block.samples.len(), Ordering::SeqCst);
+        Some(block)
+    }
+}
+
+/// Arranca un hilo de transcripción por perfil, alimentado con audio mono a
+/// `sample_rate` Hz (o, en pre-grabación, uno que lo guarda en su búfer).
+/// Cada hilo avisa de sus propios errores.
+fn spawn_transcribers(
+    profiles: &[InterlocutorProfile],
+    sink: &StreamSink,
+    sample_rate: u32,
+    tx_ui: &UiSender,
+    stop_signal: &StopSignal,
+    config: &TranscriptionConfig,
+    session_start: Instant,
+) -> (Vec<ChannelRoute>, Vec<thread::JoinHandle<()>>) {
+    let mut routes = Vec::new();
+    let mut workers = Vec::new();
+    for profile in profiles {
+        let (route, rx) = block_queue(profile.channel, sample_rate);
+        routes.push(route);
+
+        let (model, compare, recorder, preroll) = match sink {
+            StreamSink::Transcribe { model, compare, pool, recorder, preroll } => {
+                let runner = match pool {
+                    Some(pool) => ModelSlot::Pool(pool.clone(), model.0.clone()),
+                    None => ModelSlot::Own(model.clone()),
+                };
+                (runner, compare.clone(), recorder, preroll)
+            }
+            StreamSink::PreRoll(buffers) => {
+                let (id, buffers) = (profile.id, buffers.clone());
+                workers.push(thread::spawn(move || {
+                    let mut resampler = StreamResampler::new(sample_rate, WHISPER_SAMPLE_RATE);
+                    let mut resampled = Vec::new();
+                    while let Some(block) = rx.recv() {
+                        resampler.process_into(&block.samples, &mut resampled);
+                        buffers.push(id, &resampled, block.arrived);
+                    }
+                }));
+                continue;
+            }
+        };
+
+        let profile = profile.clone();
+        let tx_func = tx_ui.clone();
+        let tx_err  = tx_ui.clone();
+        let stop    = stop_signal.clone();
+        let config  = config.clone();
+        let preroll = preroll.samples(profile.id).to_vec();
+        // La grabación de este perfil empieza donde empieza su pre-grabación
+        let since   = Instant::now() - Duration::from_secs_f64(preroll.len() as f64 / WHISPER_SAMPLE_RATE as f64);
+        let mix     = recorder.as_ref().map(|r| r.source(&profile.name, since));
+        workers.push(thread::spawn(move || {
+            let name = profile.name.clone();
+            let preroll = resample(&preroll, WHISPER_SAMPLE_RATE, sample_rate);
+            let clock = StreamClock::new(session_start, sample_rate, preroll.len());
+            if let Err(e) = transcribe_stream(profile, model, compare, rx, clock, preroll, tx_func, stop, config, mix) {
+                let _ = tx_err.send(stream_error(&name, &e));
+            }
+        }));
+    }
+    (routes, workers)
+}
+
+/// Reparte un bloque entrelazado: a cada perfil su canal, o la mezcla a mono.
+fn route_frames(interleaved: &[f32], channels: usize, routes: &[ChannelRoute]) {
+    let arrived = Instant::now();
+    for route in routes {
+        let mut samples = route.buffer();
+        match route.channel {
+            Some(c) => samples.extend(interleaved.iter().skip(c as usize).step_by(channels)),
+            None => dsp::to_mono_into(interleaved, channels, &mut samples),
+        }
+        route.send(samples, arrived);
+    }
+}
+
+/// Cierra los canales de reparto y espera a que cada perfil acabe su fragmento.
+fn finish_transcribers(routes: Vec<ChannelRoute>, workers: Vec<thread::JoinHandle<()>>) {
+    drop(routes);
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+fn check_channels(profiles: &[InterlocutorProfile], device: &str, channels: usize) -> Result<()> {
+    match profiles.iter().filter_map(|p| p.channel).find(|&c| c as usize >= channels) {
+        Some(c) => Err(stream_failure(StreamErrorKind::Device, tr!("audio.channel_missing", name = device, channels = channels, n = c + 1))),
+        None => Ok(()),
+    }
+}
+
+fn stream_started(profile: &InterlocutorProfile, device: &str, format: &str) -> AudioMessage {
+    let format = match profile.channel {
+        Some(c) => format!("{}, {}", format, tr!("audio.channel", n = c + 1)),
+        None => format.to_string(),
+    };
+    AudioMessage::StreamStarted {
+        profile: profile.name.clone(),
+        source_type: profile.source_type.clone(),
+        device: device.to_string(),
+        format,
+    }
+}
+
+// Ahorro de energía: tras `energy_saver_secs` sin voz, una fuente deja de
+// llamar a Whisper y espera más entre lecturas. El umbral de silencio de
+// `transcribe_chunk` mira el fragmento normalizado, así que el ruido de
+// fondo lo pasa; aquí se mira el nivel real de cada bloque. El primero que
+// lo supera la despierta, con un poco de audio previo para no cortar el
+// arranque de la frase.
+
+/// Nivel (RMS, sin normalizar) a partir del cual un bloque tiene voz: unos
+/// -46 dBFS, por encima del ruido de un micrófono en una sala tranquila.
+const SPEECH_RMS: f32 = 0.005;
+/// Espera entre lecturas de una fuente en reposo.
+const RESTING_POLL: Duration = Duration::from_millis(500);
+/// Audio que se guarda en reposo para cuando vuelva la voz, en segundos.
+const RESTING_KEEP_SECS: f32 = 0.5;
+
+/// Acumula el audio de un perfil, lo pasa a 16 kHz y transcribe ventanas de
+/// la duración y el solape configurados en `config.tuning`. Termina al parar o cuando la
+/// captura deja de enviar. Con `mix` el audio se añade también a la
+/// grabación de la reunión. `preroll` (a `sample_rate`) se transcribe antes
+/// que lo capturado. Con `compare` cada ventana se transcribe también con
+/// ese modelo.
+#[allow(clippy::too_many_arguments)]
+fn transcribe_stream(
+    profile: InterlocutorProfile,
+    model: ModelSlot,
+    compare: Option<(String, String)>,
+    audio_rx: BlockReceiver,
+    mut clock: StreamClock,
+    preroll: Vec<f32>,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    mut mix: Option<MixSource>,
+) -> Result<()> {
+    let sample_rate = clock.sample_rate;
+    let mut model = match model {
+        ModelSlot::Own((name, path)) => ChunkRunner::Own(StreamModel::load(&name, &path)?),
+        ModelSlot::Pool(pool, name) => {
+            let priority = config.tuning.mic_priority && profile.source_type == SourceType::Input;
+            ChunkRunner::Pool { pool, name, priority }
+        }
+    };
+    let mut compare = compare.map(|(name, path)| StreamModel::load(&name, &path)).transpose()?;
+    let post = PostProcessor::new(&config.preset, config.style);
+    let hallucinations = HallucinationFilter::new(&config.tuning);
+    let voices = (profile.identify_voices && !config.voices.is_empty()).then_some(&config.voices);
+    let mut agc = profile.agc.then(|| Agc::new(sample_rate));
+
+    // La pre-grabación se acumula ya, pero no se transcribe hasta que llegue
+    // el primer bloque en vivo y el reloj sepa dónde colocarla.
+    let mut accumulated = preroll;
+    // La grabación va a 16 kHz con un solo remuestreador para toda la
+    // captura, para que no pierda una fracción de muestra en cada bloque
+    let mut to_mix = StreamResampler::new(sample_rate, WHISPER_SAMPLE_RATE);
+    let mut mixed = Vec::new();
+    if let Some(mix) = &mut mix {
+        to_mix.process_into(&accumulated, &mut mixed);
+        mix.push(&mixed);
+    }
+    if let Some(agc) = &mut agc {
+        agc.process(&mut accumulated);
+    }
+    let (target, overlap) = config.tuning.chunk_samples(sample_rate);
+    // Audio nuevo que aporta cada fragmento: el presupuesto de tiempo real
+    let step_secs = (target - overlap) as f32 / sample_rate as f32;
+    // Posición de accumulated[0] en la sesión, en muestras
+    let mut chunk_start: usize = 0;
+    let max_latency = Duration::from_secs_f32(config.tuning.max_latency_secs.clamp(0.0, MAX_LATENCY_SECS));
+    // Audio descartado desde el último aviso, en muestras
+    let mut skipped: usize = 0;
+    let mut meter = LevelMeter::default();
+    let energy_saver = Duration::from_secs(config.tuning.energy_saver_secs.min(MAX_ENERGY_SAVER_SECS) as u64);
+    let resting_keep = (RESTING_KEEP_SECS * sample_rate as f32) as usize;
+    // Desde cuándo no hay voz, y si la fuente está en reposo
+    let mut quiet_since: Option<Instant> = None;
+    let mut resting = false;
+    // Fragmento a 16 kHz para Whisper; el búfer pasa de uno al siguiente
+    let mut audio = Vec::new();
+
+    loop {
+        if stop_signal.is_stopped() { break; }
+
+        let poll = if resting { RESTING_POLL } else { Duration::from_millis(100) };
+        match audio_rx.recv_timeout(poll, &stop_signal) {
+            Ok(CapturedBlock { mut samples, arrived, dropped }) => {
+                clock.observe(dropped + samples.len(), arrived);
+                if !energy_saver.is_zero() {
+                    if calculate_rms(&samples) >= SPEECH_RMS {
+                        quiet_since = None;
+                        if resting {
+                            resting = false;
+                            tracing::info!("{}: vuelve la voz, se reanuda la transcripción", profile.name);
+                        }
+                    } else if !resting && quiet_since.get_or_insert(arrived).elapsed() >= energy_saver {
+                        resting = true;
+                        tracing::info!("{}: sin voz, en reposo", profile.name);
+                        tx_ui.send(AudioMessage::Status(tr!(
+                            "perf.resting",
+                            name = profile.name, secs = energy_saver.as_secs(),
+                        )))?;
+                    }
+                }
+                if let Some((rms, peak)) = meter.measure(&samples) {
+                    tx_ui.send(AudioMessage::Level { profile: profile.name.clone(), rms, peak })?;
+                }
+                if dropped > 0 {
+                    tx_ui.send(AudioMessage::Status(tr!(
+                        "perf.dropped",
+                        name = profile.name, secs = format!("{:.0}", dropped as f32 / sample_rate as f32),
+                    )))?;
+                    // Lo descartado queda como silencio en la grabación y
+                    // no se pega lo anterior con lo posterior.
+                    if let Some(mix) = &mut mix {
+                        to_mix.process_into(&vec![0.0; dropped], &mut mixed);
+                        mix.push(&mixed);
+                    }
+                    chunk_start += accumulated.len() + dropped;
+                    accumulated.clear();
+                }
+                if let Some(mix) = &mut mix {
+                    to_mix.process_into(&samples, &mut mixed);
+                    mix.push(&mixed);
+                }
+                // Silenciado: se sigue midiendo y grabando, no se transcribe
+                if !config.mute.is_audible(&profile.name) {
+                    chunk_start += accumulated.len() + samples.len();
+                    accumulated.clear();
+                    continue;
+                }
+                // En reposo solo se guarda el final, por si empieza a hablar
+                if resting {
+                    accumulated.extend_from_slice(&samples);
+                    let excess = accumulated.len().saturating_sub(resting_keep);
+                    chunk_start += excess;
+                    accumulated.drain(..excess);
+                    continue;
+                }
+                // Si Whisper no da abasto, se salta lo que lleva demasiado
+                // esperando en vez de ir cada vez más retrasado. La
+                // grabación lo conserva.
+                if !max_latency.is_zero() && arrived.elapsed() > max_latency {
+                    skipped += accumulated.len() + samples.len();
+                    chunk_start += accumulated.len() + samples.len();
+                    accumulated.clear();
+                    continue;
+                }
+                if skipped > 0 {
+                    tx_ui.send(AudioMessage::Status(tr!(
+                        "perf.skipped",
+                        name = profile.name, secs = format!("{:.0}", skipped as f32 / sample_rate as f32),
+                    )))?;
+                    skipped = 0;
+                }
+                if let Some(agc) = &mut agc {
+                    agc.process(&mut samples);
+                }
+                accumulated.extend_from_slice(&samples);
+
+                // Con pre-grabación puede haber varios fragmentos pendientes
+                while accumulated.len() >= target {
+                    if stop_signal.is_stopped() { break; }
+                    if sample_rate != WHISPER_SAMPLE_RATE {
+                        resample_into(&accumulated[..target], sample_rate, WHISPER_SAMPLE_RATE, &mut audio);
+                    } else {
+                        audio.clear();
+                        audio.extend_from_slice(&accumulated[..target]);
+                    }
+                    // Con AGC ya va nivelado; sin él se normaliza el fragmento entero.
+                    if agc.is_none() {
+                        dsp::normalize_in_place(&mut audio);
+                    }
+
+                    let start_secs = clock.session_secs(chunk_start);
+                    let (segment, elapsed) = model.run(&audio, start_secs, &config, &post, &hallucinations, &profile.name, voices);
+                    let text = match &segment {
+                        Some(AudioMessage::Segment { text, .. }) => text.clone(),
+                        _ => String::new(),
+                    };
+                    if let Some(segment) = segment {
+                        tx_ui.send(segment)?;
+                    }
+                    let latency_secs = clock.latency_secs(chunk_start + target);
+                    if let Some(compare) = &mut compare {
+                        let started = Instant::now();
+                        let compared = transcribe_chunk(&audio, start_secs, &mut compare.state, &config, &post, &hallucinations, &profile.name, None);
+                        let compared_text = match compared {
+                            Some(AudioMessage::Segment { text, .. }) => text,
+                            _ => String::new(),
+                        };
+                        if !text.is_empty() || !compared_text.is_empty() {
+                            tx_ui.send(AudioMessage::Comparison {
+                                profile: profile.name.clone(),
+                                start_secs,
+                                model: model.name().to_string(),
+                                text,
+                                rtf: elapsed.as_secs_f32() / step_secs,
+                                compared_model: compare.name.clone(),
+                                compared_text,
+                                compared_rtf: started.elapsed().as_secs_f32() / step_secs,
+                            })?;
+                        }
+                    }
+                    model.record_chunk(elapsed, step_secs, &config, &profile.name, &tx_ui)?;
+                    chunk_start += target - overlap;
+                    accumulated.drain(..target - overlap);
+                    tx_ui.send(AudioMessage::StreamHealth {
+                        profile: profile.name.clone(),
+                        rtf: elapsed.as_secs_f32() / step_secs,
+                        backlog_secs: (accumulated.len() + audio_rx.queued()) as f32 / sample_rate as f32,
+                        latency_secs,
+                    })?;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Cada cuánto se envía el nivel de entrada de un stream.
+const LEVEL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Nivel de entrada (antes del AGC) acumulado entre dos envíos.
+#[derive(Default)]
+struct LevelMeter {
+    sum_squares: f64,
+    count: usize,
+    peak: f32,
+    last_sent: Option<Instant>,
+}
+
+impl LevelMeter {
+    /// Anota un bloque; cada `LEVEL_INTERVAL` devuelve (rms, pico).
+    fn measure(&mut self, samples: &[f32]) -> Option<(f32, f32)> {
+        self.sum_squares += dsp::sum_squares(samples) as f64;
+        self.count += samples.len();
+        self.peak = self.peak.max(dsp::peak(samples));
+        if self.last_sent.is_some_and(|t| t.elapsed() < LEVEL_INTERVAL) || self.count == 0 {
+            return None;
+        }
+        let level = ((self.sum_squares / self.count as f64).sqrt() as f32, self.peak.min(1.0));
+        *self = Self { last_sent: Some(Instant::now()), ..Self::default() };
+        Some(level)
+    }
+}
+
+// ── Reloj de cada stream ──────────────────────────────────────────────────
+//
+// Cada tarjeta muestrea con su propio cristal: 48 000 Hz en una pueden ser
+// 48 002 en otra, y en una reunión de dos horas micrófono y loopback acaban
+// separados varios segundos si los tiempos se cuentan por muestras. Aquí se
+// anota cuándo llega cada bloque (medido en el hilo de captura, no al
+// transcribir) y, pasado un rato, se usa la frecuencia real medida contra el
+// reloj de pared para convertir muestras en segundos de sesión. Así las
+// intervenciones de todas las fuentes se intercalan bien en la minuta.
+
+/// Tiempo mínimo de medida antes de corregir; antes se usa la nominal.
+const DRIFT_MIN_SECS: f64 = 30.0;
+/// Desviación máxima creíble. Más allá es un corte de la captura, no deriva.
+const DRIFT_MAX_RATIO: f64 = 0.01;
+
+struct StreamClock {
+    session_start: Instant,
+    sample_rate: u32,
+    /// Muestras de pre-grabación por delante de la primera en vivo. No
+    /// cuentan para medir la deriva.
+    lead: usize,
+    /// Segundo de sesión de la primera muestra en vivo del stream.
+    start: Option<f64>,
+    received: usize,
+    /// Muestras por segundo de pared medidas.
+    measured_rate: f64,
+}
+
+impl StreamClock {
+    fn new(session_start: Instant, sample_rate: u32, lead: usize) -> Self {
+        Self { session_start, sample_rate, lead, start: None, received: 0, measured_rate: sample_rate as f64 }
+    }
+
+    /// Anota un bloque de `len` muestras entregado en `arrived`.
+    fn observe(&mut self, len: usize, arrived: Instant) {
+        let now = arrived.saturating_duration_since(self.session_start).as_secs_f64();
+        self.received += len;
+        let Some(start) = self.start else {
+            // El bloque acaba de llegar: su primera muestra sonó `len` antes
+            self.start = Some((now - len as f64 / self.sample_rate as f64).max(0.0));
+            return;
+        };
+        let elapsed = now - start;
+        if elapsed >= DRIFT_MIN_SECS {
+            let nominal = self.sample_rate as f64;
+            let rate = self.received as f64 / elapsed;
+            self.measured_rate = rate.clamp(nominal * (1.0 - DRIFT_MAX_RATIO), nominal * (1.0 + DRIFT_MAX_RATIO));
+        }
+    }
+
+    /// Segundo de sesión de la muestra `sample` del stream (contando la
+    /// pre-grabación).
+    fn session_secs(&self, sample: usize) -> f64 {
+        let start = self.start.unwrap_or(0.0) - self.lead as f64 / self.sample_rate as f64;
+        (start + sample as f64 / self.measured_rate).max(0.0)
+    }
+
+    /// Segundos desde que se captó la muestra `sample` hasta ahora. `None`
+    /// si es de la pre-grabación: esa llega con retraso a propósito.
+    fn latency_secs(&self, sample: usize) -> Option<f32> {
+        if sample <= self.lead {
+            return None;
+        }
+        let now = self.session_start.elapsed().as_secs_f64();
+        Some((now - self.session_secs(sample)).max(0.0) as f32)
+    }
+}
+
+// ── Captura Linux (parecord / pw-record / ffmpeg) ─────────────────────────
+
+/// Reinicios seguidos del programa de captura antes de pasar al siguiente.
+#[cfg(target_os = "linux")]
+const MAX_CAPTURE_RESTARTS: u32 = 5;
+/// Tiempo grabando tras el cual una caída ya no cuenta como seguida.
+#[cfg(target_os = "linux")]
+const CAPTURE_STABLE: Duration = Duration::from_secs(60);
+/// Un programa que muere antes de esto no funciona en este sistema.
+#[cfg(target_os = "linux")]
+const CAPTURE_STARTUP: Duration = Duration::from_secs(2);
+
+/// Con PulseAudio/PipeWire se captura con un programa externo (ver
+/// `capture_tools`), salvo los micrófonos cuando se ha elegido a mano un
+/// host de cpal (p. ej. ALSA o JACK para saltarse Pulse). Los monitores solo
+/// existen en Pulse. Sin servidor se usa cpal, salvo que se haya fijado
+/// ffmpeg.
+#[cfg(target_os = "linux")]
+fn uses_capture_tool(profile: &InterlocutorProfile, config: &TranscriptionConfig) -> bool {
+    match linux_sound_server() {
+        Some(_) => config.audio_host.is_none() || profile.source_type == SourceType::Output,
+        None => config.capture_tool == Some(CaptureTool::Ffmpeg),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn run_capture_linux(
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    let device_name = profiles[0].technical_name.clone()
+        .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("audio.no_tech_name")))?;
+    let server = linux_sound_server();
+    let tools = capture_tools::candidates(server, config.capture_tool);
+    if tools.is_empty() {
+        let message = match config.capture_tool {
+            Some(tool) => tr!("audio.capture_tool_not_installed", program = tool.program()),
+            None => tr!("audio.capture_tool_missing"),
+        };
+        return Err(stream_failure(StreamErrorKind::Device, message));
+    }
+
+    // Lo que recibe el programa (fuente de Pulse o PCM de ALSA) y los
+    // canales del dispositivo
+    let (source, device_channels) = match server {
+        Some(_) => {
+            let check = Command::new("pactl").args(&["list", "sources", "short"]).output()?;
+            let sources = String::from_utf8_lossy(&check.stdout);
+            let Some(source) = sources.lines().find(|l| l.split_whitespace().nth(1) == Some(device_name.as_str())) else {
+                return Err(stream_failure(StreamErrorKind::Device, tr!("audio.device_not_found", name = device_name, list = sources)));
+            };
+            (device_name.clone(), pactl_channels(&source.split_whitespace().collect::<Vec<_>>()))
+        }
+        None => alsa_pcm(&device_name)
+            .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("audio.device_not_found_cpal", name = device_name)))?,
+    };
+
+    // Para separar canales se graba con todos los del dispositivo; si no,
+    // el programa ya entrega la mezcla a mono.
+    let channels = if profiles.iter().any(|p| p.channel.is_some()) {
+        device_channels.max(1) as usize
+    } else {
+        1
+    };
+    check_channels(&profiles, &device_name, channels)?;
+
+    let mut capture = ToolCapture { tools, current: 0, source, pulse: server.is_some(), channels };
+    let child = capture.spawn(&profiles, &device_name, &tx_ui)?;
+    let (routes, workers) = spawn_transcribers(&profiles, &sink, WHISPER_SAMPLE_RATE, &tx_ui, &stop_signal, &config, session_start);
+    let result = capture.supervise(child, &routes, &stop_signal, &profiles, &device_name, &tx_ui);
+    finish_transcribers(routes, workers);
+    result
+}
+
+/// PCM (`hw:CARD=…`) y canales del dispositivo de ALSA que cpal llama
+/// `name`, para grabarlo con ffmpeg.
+#[cfg(target_os = "linux")]
+fn alsa_pcm(name: &str) -> Option<(String, u16)> {
+    let host = cpal::host_from_id(cpal::HostId::Alsa).ok()?;
+    host.input_devices().ok()?.find_map(|device| {
+        let desc = device.description().ok()?;
+        if desc.name() != name {
+            return None;
+        }
+        let channels = device.default_input_config().map_or(0, |c| c.channels());
+        Some((desc.driver().unwrap_or(name).to_string(), channels))
+    })
+}
+
+/// Programa de captura en uso y los que quedan por probar.
+#[cfg(target_os = "linux")]
+struct ToolCapture {
+    tools: Vec<CaptureTool>,
+    current: usize,
+    /// Fuente de PulseAudio o PCM de ALSA, tal como lo recibe el programa.
+    source: String,
+    pulse: bool,
+    channels: usize,
+}
+
+#[cfg(target_os = "linux")]
+impl ToolCapture {
+    fn tool(&self) -> CaptureTool {
+        self.tools[self.current]
+    }
+
+    /// Lanza el programa actual o, si no arranca, el siguiente que lo haga,
+    /// y avisa del formato a la interfaz.
+    fn spawn(&mut self, profiles: &[InterlocutorProfile], device_name: &str, tx_ui: &UiSender) -> Result<ChildGuard> {
+        loop {
+            let tool = self.tool();
+            let mut cmd = tool.command(&self.source, self.channels, self.pulse);
+            kill_with_parent(&mut cmd);
+            match cmd.spawn() {
+                Ok(child) => {
+                    let format = if self.channels == 1 { "16kHz mono".to_string() } else { format!("16kHz, {}ch", self.channels) };
+                    let format = format!("{} {}", tool.program(), format);
+                    for profile in profiles {
+                        tx_ui.send(stream_started(profile, device_name, &format))?;
+                    }
+                    return Ok(ChildGuard { child, program: tool.program() });
+                }
+                Err(e) => {
+                    let error = stream_failure(StreamErrorKind::Device, tr!("audio.capture_tool_start", program = tool.program(), e = format!("{:?}", e)));
+                    if !self.next(&error, device_name, tx_ui)? {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pasa al siguiente programa; `false` si no quedan.
+    fn next(&mut self, error: &anyhow::Error, device_name: &str, tx_ui: &UiSender) -> Result<bool> {
+        if self.current + 1 >= self.tools.len() {
+            return Ok(false);
+        }
+        let failed = self.tool();
+        self.current += 1;
+        tracing::warn!("{} no funciona con {} ({}); se prueba {}", failed.program(), device_name, error, self.tool().program());
+        tx_ui.send(AudioMessage::Status(tr!(
+            "audio.capture_tool_switch",
+            from = failed.program(), to = self.tool().program(), name = device_name,
+        )))?;
+        Ok(true)
+    }
+
+    /// Reparte lo que graba `child` hasta parar. Si el programa muere antes
+    /// se vuelve a lanzar, con el hueco relleno de silencio para no
+    /// descuadrar los tiempos. Si muere nada más arrancar o no deja de
+    /// caerse, se pasa al siguiente; sin más programas, la fuente falla.
+    fn supervise(
+        &mut self,
+        mut child: ChildGuard,
+        routes: &[ChannelRoute],
+        stop_signal: &StopSignal,
+        profiles: &[InterlocutorProfile],
+        device_name: &str,
+        tx_ui: &UiSender,
+    ) -> Result<()> {
+        let mut restarts = 0;
+        loop {
+            let started = Instant::now();
+            let error = match pump_child(&mut child, self.channels, routes, stop_signal) {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+            let died = Instant::now();
+            let ran = started.elapsed();
+            if ran >= CAPTURE_STABLE {
+                restarts = 0;
+            }
+            restarts += 1;
+
+            if ran < CAPTURE_STARTUP || restarts > MAX_CAPTURE_RESTARTS {
+                if !self.next(&error, device_name, tx_ui)? {
+                    return Err(error);
+                }
+                restarts = 0;
+            } else {
+                let program = self.tool().program();
+                tracing::warn!("{} se ha caído ({}); reinicio {}/{}", program, error, restarts, MAX_CAPTURE_RESTARTS);
+                tx_ui.send(AudioMessage::Status(tr!(
+                    "audio.capture_restart",
+                    program = program, name = device_name, n = restarts, max = MAX_CAPTURE_RESTARTS,
+                )))?;
+                // Cada vez se espera un poco más, por si el servidor se está
+                // reiniciando
+                let retry_at = died + Duration::from_millis(500 * restarts as u64);
+                if stop_signal.sleep(retry_at.saturating_duration_since(Instant::now())) {
+                    return Ok(());
+                }
+            }
+            child = self.spawn(profiles, device_name, tx_ui)?;
+            let gap = (died.elapsed().as_secs_f64() * WHISPER_SAMPLE_RATE as f64) as usize;
+            route_frames(&vec![0.0; gap * self.channels], self.channels, routes);
+        }
+    }
+}
+
+/// Reparte el PCM s16le de `child` (parecord, pw-record o ffmpeg) hasta
+/// parar. Que el proceso termine antes es un error.
+fn pump_child(
+    child: &mut ChildGuard,
+    channels: usize,
+    routes: &[ChannelRoute],
+    stop_signal: &StopSignal,
+) -> Result<()> {
+    let stdout = child.child.stdout.take()
+        .ok_or_else(|| stream_failure(StreamErrorKind::Capture, tr!("audio.process_stdout", program = child.program)))?;
+
+    // Al volver se suelta el proceso y su lector termina con él
+    if !pump_pcm(stdout, PcmEncoding::S16le, channels, routes, stop_signal)? {
+        let status = child.child.wait().map(|s| s.to_string()).unwrap_or_default();
+        return Err(stream_failure(StreamErrorKind::Capture, tr!("audio.process_died", program = child.program, status = status)));
+    }
+    Ok(())
+}
+
+/// Bytes por lectura de un proceso o tubería.
+const READ_BYTES: usize = 4096;
+/// Lecturas que el lector puede adelantar al reparto.
+const READ_QUEUE: usize = 64;
+
+/// Lee `reader` en su propio hilo. Un `read` bloqueado (un proceso de
+/// captura atascado, una tubería sin escritor) no se puede interrumpir, así
+/// que quien reparte espera en la cola y puede dejarla al parar; el lector
+/// termina solo cuando su fuente se cierra. Los búferes vuelven por `spare`.
+fn spawn_reader(mut reader: impl Read + Send + 'static) -> (Receiver<std::io::Result<Vec<u8>>>, Sender<Vec<u8>>) {
+    let (tx, rx) = crossbeam_channel::bounded(READ_QUEUE);
+    let (spare_tx, spare) = crossbeam_channel::bounded::<Vec<u8>>(READ_QUEUE);
+    thread::spawn(move || loop {
+        let mut buf = spare.try_recv().unwrap_or_default();
+        buf.resize(READ_BYTES, 0);
+        match reader.read(&mut buf) {
+            Ok(n) => {
+                buf.truncate(n);
+                if tx.send(Ok(buf)).is_err() || n == 0 {
+                    break;
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(10));
+            }
+            Err(e) => {
+                let _ = tx.send(Err(e));
+                break;
+            }
+        }
+    });
+    (rx, spare_tx)
+}
+
+/// Lee PCM crudo de `reader` hasta parar y reparte las tramas completas.
+/// Devuelve `false` si el otro extremo cerró antes de parar.
+fn pump_pcm(
+    reader: impl Read + Send + 'static,
+    encoding: PcmEncoding,
+    channels: usize,
+    routes: &[ChannelRoute],
+    stop_signal: &StopSignal,
+) -> Result<bool> {
+    let sample_bytes = encoding.bytes();
+    let frame_bytes = channels * sample_bytes;
+    let mut pending: Vec<u8> = Vec::new();
+    let mut samples: Vec<f32> = Vec::with_capacity(READ_BYTES / sample_bytes);
+    let (reads, spare) = spawn_reader(reader);
+
+    loop {
+        if stop_signal.is_stopped() { break; }
+
+        let read = select! {
+            recv(reads) -> read => read.unwrap_or(Ok(Vec::new())),
+            recv(stop_signal.channel()) -> _ => break,
+        };
+        match read {
+            Ok(buf) if buf.is_empty() && stop_signal.is_stopped() => break,
+            Ok(buf) if buf.is_empty() => return Ok(false),
+            Ok(buf) => {
+                pending.extend_from_slice(&buf);
+                let _ = spare.try_send(buf);
+                // Una lectura puede cortar una trama; el resto espera a la siguiente.
+                let complete = pending.len() - pending.len() % frame_bytes;
+                samples.clear();
+                samples.extend(pending[..complete]
+                    .chunks_exact(sample_bytes)
+                    .map(|b| match encoding {
+                        PcmEncoding::S16le => i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+                        PcmEncoding::F32le => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                    }));
+                pending.drain(..complete);
+                route_frames(&samples, channels, routes);
+            }
+            Err(e) => return Err(stream_failure(StreamErrorKind::Capture, tr!("audio.read_error", e = format!("{:?}", e)))),
+        }
+    }
+
+    Ok(true)
+}
+
+/// Proceso de captura (`parecord`, `pw-record`, `ffmpeg`) que se mata y se
+/// recoge al soltarlo, también cuando el stream sale por error.
+struct ChildGuard {
+    child: std::process::Child,
+    program: &'static str,
+}
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Si la aplicación muere sin pasar por Drop (kill -9, pánico en otro
+/// hilo), el kernel mata también al proceso de captura en vez de dejarlo
+/// grabando. Solo Linux.
+fn kill_with_parent(cmd: &mut Command) {
+    #[cfg(target_os = "linux")]
+    unsafe {
+        use std::os::unix::process::CommandExt;
+        cmd.pre_exec(|| {
+            libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+            Ok(())
+        });
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = cmd;
+}
+
+// ── Audio de una aplicación (Windows Process Loopback) ────────────────────
+
+fn run_capture_process(
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    let tech_name = profiles[0].technical_name.clone().unwrap_or_default();
+    let exe = tech_name.trim_start_matches(PROCESS_PREFIX);
+
+    let mut capture = ProcessCapture::start(exe)?;
+    let channels = capture.channels();
+    check_channels(&profiles, exe, channels)?;
+
+    let format = format!("Process Loopback {}Hz, {}ch", capture.sample_rate(), channels);
+    for profile in &profiles {
+        tx_ui.send(stream_started(profile, exe, &format))?;
+    }
+
+    let (routes, workers) = spawn_transcribers(&profiles, &sink, capture.sample_rate(), &tx_ui, &stop_signal, &config, session_start);
+    let mut result = Ok(());
+    let mut samples = Vec::new();
+    while !stop_signal.is_stopped() {
+        match capture.read(&mut samples) {
+            Ok(()) if samples.is_empty() => {
+                stop_signal.sleep(Duration::from_millis(10));
+            }
+            Ok(()) => route_frames(&samples, channels, &routes),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+    finish_transcribers(routes, workers);
+    result
+}
+
+// ── Fuentes de red (radio por internet, Icecast, RTP) ─────────────────────
+//
+// ffmpeg abre la URL (http(s)://, Icecast, HLS, rtp://, un .sdp…) y la
+// entrega ya decodificada a 16 kHz mono por stdout; desde ahí sigue el mismo
+// camino que un micrófono. En HTTP se reconecta solo si el servidor corta.
+// No se decodifica dentro del proceso: HLS, RTP/SDP y la variedad de códecs
+// de las radios piden un demuxer completo, así que esta fuente necesita
+// `ffmpeg` en el PATH (igual que la transcripción de vídeo) y, si no está,
+// falla al iniciar con un error que lo dice.
+
+fn run_capture_network(
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    let url = profiles[0].technical_name.clone()
+        .filter(|u| !u.trim().is_empty())
+        .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("audio.no_url")))?;
+    let url = url.trim();
+
+    let mut child = spawn_ffmpeg_stream(url)?;
+    for profile in &profiles {
+        tx_ui.send(stream_started(profile, url, "ffmpeg 16kHz mono"))?;
+    }
+
+    let (routes, workers) = spawn_transcribers(&profiles, &sink, WHISPER_SAMPLE_RATE, &tx_ui, &stop_signal, &config, session_start);
+    let result = pump_child(&mut child, 1, &routes, &stop_signal);
+    finish_transcribers(routes, workers);
+    result
+}
+
+// ── PCM crudo por stdin o tubería con nombre ──────────────────────────────
+//
+// Para herramientas externas (ffmpeg, receptores SDR, scripts propios): se
+// leen muestras sin cabecera en el formato configurado en el perfil, p. ej.
+//   ffmpeg -i … -f s16le -ar 16000 -ac 1 - | minutero
+//   mkfifo /tmp/minutero.fifo && rtl_fm … > /tmp/minutero.fifo
+// Abrir una tubería con nombre espera a que alguien escriba en ella. Cuando
+// el otro extremo cierra, la fuente termina sin error.
+
+fn run_capture_pipe(
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    let path = profiles[0].technical_name.as_deref().map(str::trim).unwrap_or_default();
+    let stdin = path.is_empty() || path == "-";
+    let name = if stdin { "stdin" } else { path };
+    let pcm = profiles[0].pcm;
+    let channels = pcm.channels.max(1) as usize;
+    check_channels(&profiles, name, channels)?;
+
+    tx_ui.send(AudioMessage::Status(tr!("audio.pipe_waiting", name = name)))?;
+    let reader: Box<dyn Read + Send> = if stdin {
+        Box::new(std::io::stdin())
+    } else {
+        Box::new(std::fs::File::open(path).map_err(|e| stream_failure(StreamErrorKind::Device, tr!("audio.pipe_open", name = name, e = e)))?)
+    };
+
+    let format = format!("{} {}Hz, {}ch", pcm.encoding.label(), pcm.sample_rate, channels);
+    for profile in &profiles {
+        tx_ui.send(stream_started(profile, name, &format))?;
+    }
+
+    let (routes, workers) = spawn_transcribers(&profiles, &sink, pcm.sample_rate, &tx_ui, &stop_signal, &config, session_start);
+    let result = pump_pcm(reader, pcm.encoding, channels, &routes, &stop_signal);
+    finish_transcribers(routes, workers);
+    if let Ok(false) = result {
+        tx_ui.send(AudioMessage::Status(tr!("audio.pipe_closed", name = name)))?;
+    }
+    result.map(|_| ())
+}
+
+// ── Canal de voz de Discord ───────────────────────────────────────────────
+//
+// El bot entra en el canal (ver `discord`) y recibe el audio de cada
+// participante por separado, a 48 kHz estéreo. Cada uno se transcribe como
+// un interlocutor con su nombre en el servidor; su hilo arranca la primera
+// vez que habla. Mientras calla se le pasa silencio, para que sus tiempos
+// sigan a la par con los de los demás.
+
+const DISCORD_SAMPLE_RATE: u32 = 48_000;
+/// Muestras estéreo de los 20 ms de cada paquete de Discord.
+const DISCORD_TICK_SAMPLES: usize = (DISCORD_SAMPLE_RATE / 50 * 2) as usize;
+
+fn run_capture_discord(
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    // Quién va a hablar no se sabe hasta que habla: no hay pre-grabación
+    if let StreamSink::PreRoll(_) = sink {
+        return Ok(());
+    }
+    let channel = profiles[0].technical_name.clone()
+        .filter(|c| !c.trim().is_empty())
+        .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("discord.no_channel")))?;
+    let channel = channel.trim();
+
+    tx_ui.send(AudioMessage::Status(tr!("discord.joining")))?;
+    let (session, events) = discord::join(&config.discord, channel)
+        .map_err(|e| stream_failure(StreamErrorKind::Device, tr!("discord.join_error", e = e)))?;
+    tx_ui.send(AudioMessage::Status(tr!("discord.joined", channel = channel)))?;
+
+    let device = format!("Discord {}", channel);
+    // La reserva de la sesión cubre un hablante por perfil; los demás
+    // reservan lo suyo al aparecer (ver `extra_speaker_sink`)
+    let mut reservations = Vec::new();
+    let mut overflow = None;
+    let mut refused: HashSet<u32> = HashSet::new();
+    let mut names: HashMap<u32, String> = HashMap::new();
+    let mut speakers: HashMap<u32, (Vec<ChannelRoute>, Vec<thread::JoinHandle<()>>)> = HashMap::new();
+    let mut samples = Vec::with_capacity(DISCORD_TICK_SAMPLES);
+    let mut result = Ok(());
+    while !stop_signal.is_stopped() {
+        let event = select! {
+            recv(events) -> event => event,
+            recv(stop_signal.channel()) -> _ => break,
+        };
+        let audio = match event {
+            Ok(VoiceEvent::Speaker { ssrc, name }) => {
+                names.insert(ssrc, name);
+                continue;
+            }
+            Ok(VoiceEvent::Tick(audio)) => audio,
+            Err(_) => {
+                result = Err(stream_failure(StreamErrorKind::Capture, tr!("discord.disconnected")));
+                break;
+            }
+        };
+        for (ssrc, pcm) in audio {
+            if !speakers.contains_key(&ssrc) {
+                let (Some(name), Some(_)) = (names.get(&ssrc), &pcm) else { continue };
+                if refused.contains(&ssrc) {
+                    continue;
+                }
+                let speaker_sink = if speakers.len() < profiles.len() {
+                    sink.clone()
+                } else {
+                    match extra_speaker_sink(&sink, &config, &mut reservations, &mut overflow) {
+                        Ok(speaker_sink) => speaker_sink,
+                        Err(e) => {
+                            tracing::warn!("Hablante de Discord {} sin transcribir: {:?}", name, e);
+                            tx_ui.send(AudioMessage::Status(tr!("resources.speaker_refused", name = name, e = e)))?;
+                            refused.insert(ssrc);
+                            continue;
+                        }
+                    }
+                };
+                let profile = InterlocutorProfile {
+                    name: name.clone(),
+                    channel: None,
+                    ..profiles[0].clone()
+                };
+                tx_ui.send(stream_started(&profile, &device, "Opus 48kHz, 2ch"))?;
+                let streams = spawn_transcribers(&[profile], &speaker_sink, DISCORD_SAMPLE_RATE, &tx_ui, &stop_signal, &config, session_start);
+                speakers.insert(ssrc, streams);
+            }
+            samples.clear();
+            match pcm {
+                Some(pcm) => dsp::extend_from_s16(&mut samples, &pcm),
+                None => samples.resize(DISCORD_TICK_SAMPLES, 0.0),
+            }
+            route_frames(&samples, 2, &speakers[&ssrc].0);
+        }
+    }
+    session.leave();
+    for (routes, workers) in speakers.into_values() {
+        finish_transcribers(routes, workers);
+    }
+    result
+}
+
+/// Dónde transcribir a un hablante de Discord que no cubre la reserva de la
+/// sesión: con su propio estado Whisper si cabe en memoria y, si no, en un
+/// trabajador compartido por todos los que no quepan (`overflow`, que se
+/// abre la primera vez). Falla si no cabe ni ese.
+fn extra_speaker_sink(
+    sink: &StreamSink,
+    config: &TranscriptionConfig,
+    reservations: &mut Vec<resources::Reservation>,
+    overflow: &mut Option<StreamSink>,
+) -> Result<StreamSink> {
+    let StreamSink::Transcribe { model, compare, pool, .. } = sink else { return Ok(sink.clone()) };
+    // Con trabajadores compartidos el hablante no carga nada
+    if pool.is_some() {
+        return Ok(sink.clone());
+    }
+    if let Some(overflow) = overflow {
+        return Ok(overflow.clone());
+    }
+    let compare_name = compare.as_ref().map(|(name, _)| name.as_str());
+    let own = resources::estimate(&model.0, compare_name, 1, &config.model_source, &config.limits);
+    if let Ok(reservation) = resources::reserve(&own) {
+        reservations.push(reservation);
+        return Ok(sink.clone());
+    }
+    let shared = resources::estimate(&model.0, None, 1, &config.model_source, &config.limits);
+    reservations.push(resources::reserve(&shared)?);
+    tracing::info!("Memoria al límite: los nuevos hablantes de Discord comparten un trabajador Whisper");
+    let shared = sink.with_pool(Arc::new(WhisperPool::start(&model.1, 1, config)?));
+    *overflow = Some(shared.clone());
+    Ok(shared)
+}
+
+fn spawn_ffmpeg_stream(url: &str) -> Result<ChildGuard> {
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(["-nostdin", "-loglevel", "error"]);
+    if url.starts_with("http://") || url.starts_with("https://") {
+        cmd.args(["-reconnect", "1", "-reconnect_streamed", "1", "-reconnect_delay_max", "5",
+                  "-rw_timeout", "15000000"]);
+    }
+    if url.starts_with("rtp://") || url.ends_with(".sdp") {
+        cmd.args(["-protocol_whitelist", "file,udp,rtp"]);
+    }
+    cmd.args(["-i", url, "-vn", "-ac", "1", "-ar", "16000", "-f", "s16le", "pipe:1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    kill_with_parent(&mut cmd);
+    cmd.spawn()
+        .map(|child| ChildGuard { child, program: "ffmpeg" })
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => stream_failure(StreamErrorKind::Device, tr!("audio.network_no_ffmpeg")),
+            _ => stream_failure(StreamErrorKind::Device, tr!("video.ffmpeg_start", e = format!("{:?}", e))),
+        })
+}
+
+/// Canales de una línea de `pactl list sources short`
+/// (`… s16le 2ch 48000Hz …`); 0 si no aparecen.
+pub fn pactl_channels(parts: &[&str]) -> u16 {
+    parts.iter()
+        .find_map(|p| p.strip_suffix("ch")?.parse().ok())
+        .unwrap_or(0)
+}
+
+// ── Captura multiplataforma (cpal / WASAPI / CoreAudio / ALSA) ────────────
+//
+// Windows : WASAPI — micrófonos + Stereo Mix (si habilitado) como inputs
+// macOS   : CoreAudio — micrófonos + BlackHole/Soundflower como inputs
+// Linux   : ALSA directo cuando no hay PulseAudio/PipeWire (si lo hay, los
+//           inputs van por parecord/pw-record/ffmpeg); el loopback es `snd-aloop`
+
+fn run_capture_cpal(
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    // Buscar dispositivo por nombre técnico en la lista de inputs.
+    // En Windows/macOS, tanto micrófonos como dispositivos loopback
+    // (Stereo Mix, BlackHole) aparecen como inputs en cpal.
+    let tech_name = profiles[0].technical_name.clone()
+        .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("audio.no_tech_name_cpal")))?;
+
+    // Los loopback se detectan en el host predeterminado (ver system_audio),
+    // así que si no está en el elegido se busca también allí.
+    let hosts = [select_host(config.audio_host.as_deref()), cpal::default_host()];
+    let (host, device) = hosts.iter()
+        .find_map(|host| {
+            host.input_devices().ok()?
+                .find(|d| d.description().map(|desc| desc.name() == tech_name.as_str()).unwrap_or(false))
+                .map(|d| (host, d))
+        })
+        .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("audio.device_not_found_cpal", name = tech_name)))?;
+
+    let stream_config = device.default_input_config()?;
+    let sample_rate = stream_config.sample_rate();
+    let channels = stream_config.channels() as usize;
+    check_channels(&profiles, &tech_name, channels)?;
+
+    let options = config.streams.get(&tech_name).cloned().unwrap_or_default();
+    if options.exclusive && host.id().name() == "WASAPI" {
+        let endpoint = device.id()
+            .map_err(|e| stream_failure(StreamErrorKind::Device, e.to_string()))?
+            .1;
+        let capture = ExclusiveCapture::start(&endpoint, sample_rate, channels as u16, options.buffer_frames, &tech_name)?;
+        return run_capture_exclusive(capture, profiles, sink, tx_ui, stop_signal, config, session_start);
+    }
+
+    let format = format!("{} {}Hz, {}ch", host.id().name(), sample_rate, channels);
+    for profile in &profiles {
+        tx_ui.send(stream_started(profile, &tech_name, &format))?;
+    }
+
+    let (audio_tx, audio_rx) = crossbeam_channel::bounded::<Vec<f32>>(CALLBACK_QUEUE_BLOCKS);
+    // 100 ms por bloque de entrada; si el dispositivo da bloques mayores,
+    // el búfer crece una vez y se queda así
+    let ring = BufferRing::new(CALLBACK_RING_BLOCKS, sample_rate as usize * channels / 10);
+    let stream = build_input_stream(&device, stream_config, options.buffer_frames, audio_tx, ring.clone(), tech_name.clone())?;
+    stream.play()?;
+
+    let (routes, workers) = spawn_transcribers(&profiles, &sink, sample_rate, &tx_ui, &stop_signal, &config, session_start);
+    loop {
+        if stop_signal.is_stopped() { break; }
+
+        select! {
+            recv(audio_rx) -> buf => match buf {
+                Ok(buf) => {
+                    route_frames(&buf, channels, &routes);
+                    ring.give(buf);
+                }
+                Err(_) => break,
+            },
+            recv(stop_signal.channel()) -> _ => break,
+        }
+    }
+
+    drop(stream);
+    finish_transcribers(routes, workers);
+    Ok(())
+}
+
+/// Dispositivo WASAPI abierto en modo exclusivo (ver `wasapi_exclusive`):
+/// se lee a su ritmo, sin callback de cpal.
+fn run_capture_exclusive(
+    mut capture: ExclusiveCapture,
+    profiles: Vec<InterlocutorProfile>,
+    sink: StreamSink,
+    tx_ui: UiSender,
+    stop_signal: StopSignal,
+    config: TranscriptionConfig,
+    session_start: Instant,
+) -> Result<()> {
+    let tech_name = profiles[0].technical_name.clone().unwrap_or_default();
+    let channels = capture.channels();
+    let format = capture.format();
+    for profile in &profiles {
+        tx_ui.send(stream_started(profile, &tech_name, &format))?;
+    }
+
+    let (routes, workers) = spawn_transcribers(&profiles, &sink, capture.sample_rate(), &tx_ui, &stop_signal, &config, session_start);
+    let mut result = Ok(());
+    let mut samples = Vec::new();
+    while !stop_signal.is_stopped() {
+        match capture.read(&mut samples) {
+            Ok(()) if samples.is_empty() => continue,
+            Ok(()) => route_frames(&samples, channels, &routes),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+    drop(capture);
+    finish_transcribers(routes, workers);
+    result
+}
+
+/// Stream de entrada que entrega bloques en `f32`. WASAPI y CoreAudio dan
+/// `f32`; en ALSA directo lo normal es `i16`. `buffer_frames` > 0 pide ese
+/// tamaño de búfer, dentro de lo que admita el dispositivo. Los bloques se
+/// copian en búferes de `ring`, que el hilo de captura devuelve.
+fn build_input_stream(
+    device: &cpal::Device,
+    stream_config: cpal::SupportedStreamConfig,
+    buffer_frames: u32,
+    audio_tx: Sender<Vec<f32>>,
+    ring: BufferRing,
+    name: String,
+) -> Result<cpal::Stream> {
+    let on_error = move |err| tracing::error!("Error en stream [{}]: {}", name, err);
+    let sample_format = stream_config.sample_format();
+    let buffer_size = match (buffer_frames, stream_config.buffer_size()) {
+        (0, _) => cpal::BufferSize::Default,
+        (frames, cpal::SupportedBufferSize::Range { min, max }) => cpal::BufferSize::Fixed(frames.clamp(*min, (*max).max(*min))),
+        (frames, cpal::SupportedBufferSize::Unknown) => cpal::BufferSize::Fixed(frames),
+    };
+    let mut config: cpal::StreamConfig = stream_config.into();
+    config.buffer_size = buffer_size;
+
+    let stream = match sample_format {
+        cpal::SampleFormat::F32 => device.build_input_stream(
+            &config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                let mut block = ring.take();
+                block.extend_from_slice(data);
+                let _ = audio_tx.try_send(block);
+            },
+            on_error,
+            None,
+        )?,
+        cpal::SampleFormat::I16 => device.build_input_stream(
+            &config,
+            move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                let mut block = ring.take();
+                dsp::extend_from_s16(&mut block, data);
+                let _ = audio_tx.try_send(block);
+            },
+            on_error,
+            None,
+        )?,
+        other => return Err(stream_failure(StreamErrorKind::Device, tr!("audio.sample_format", format = format!("{:?}", other)))),
+    };
+    Ok(stream)
+}
+
+// ── Modelo por stream y control de tiempo real ────────────────────────────
+
+/// Fragmentos seguidos por encima de tiempo real antes de reaccionar.
+const SLOW_CHUNKS_BEFORE_ACTION: u32 = 3;
+
+/// Estado Whisper de un stream. Mide el real-time factor (tiempo de proceso
+/// / audio nuevo) de cada fragmento; si se queda atrás de forma sostenida,
+/// avisa y, si está activado, cambia a un modelo más pequeño.
+struct StreamModel {
+    name: String,
+    state: WhisperState,
+    slow_chunks: u32,
+}
+
+impl StreamModel {
+    fn load(name: &str, path: &str) -> Result<Self> {
+        let ctx = preload::context(path)
+            .map_err(|e| stream_failure(StreamErrorKind::Model, tr!("audio.model_load", e = format!("{:?}", e))))?;
+        let state = ctx.create_state()
+            .map_err(|e| stream_failure(StreamErrorKind::Model, tr!("audio.state_create", e = format!("{:?}", e))))?;
+        Ok(Self { name: name.to_string(), state, slow_chunks: 0 })
+    }
+
+    fn record_chunk(
+        &mut self,
+        elapsed: Duration,
+        audio_secs: f32,
+        config: &TranscriptionConfig,
+        stream_name: &str,
+        tx_ui: &UiSender,
+    ) -> Result<()> {
+        let rtf = elapsed.as_secs_f32() / audio_secs;
+        if rtf <= 1.0 {
+            self.slow_chunks = 0;
+            return Ok(());
+        }
+
+        self.slow_chunks += 1;
+        if self.slow_chunks < SLOW_CHUNKS_BEFORE_ACTION {
+            return Ok(());
+        }
+        self.slow_chunks = 0;
+
+        // Con un archivo propio no hay "modelo más pequeño", y sin conexión
+        // solo sirve si ya está descargado
+        let source = &config.model_source;
+        let smaller = smaller_model(&self.name)
+            .filter(|_| source.custom_path.trim().is_empty())
+            .filter(|smaller| !source.offline || model_is_cached(smaller, source));
+        match smaller {
+            Some(smaller) if config.tuning.auto_downgrade => {
+                tx_ui.send(AudioMessage::Status(tr!(
+                    "perf.downgrading",
+                    name = stream_name, rtf = format!("{:.1}", rtf), from = self.name, to = smaller,
+                )))?;
+                let cached = model_is_cached(smaller, source);
+                let path = Runtime::new()?.block_on(download_whisper_model(smaller, source, |downloaded, total| {
+                    let _ = tx_ui.send(AudioMessage::ModelDownloadProgress { model: smaller.to_string(), downloaded, total });
+                }))?;
+                if !cached {
+                    tx_ui.send(AudioMessage::ModelDownloaded(smaller.to_string()))?;
+                }
+                *self = StreamModel::load(smaller, &path)?;
+            }
+            _ => {
+                tx_ui.send(AudioMessage::Status(tr!(
+                    "perf.behind",
+                    name = stream_name, rtf = format!("{:.1}", rtf), model = self.name,
+                )))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Modelo de un perfil al arrancar su hilo: (nombre, ruta) para cargar su
+/// propio estado, o el pool de la sesión y el nombre de su modelo.
+enum ModelSlot {
+    Own((String, String)),
+    Pool(Arc<WhisperPool>, String),
+}
+
+/// Quién pasa por Whisper los fragmentos de un stream.
+enum ChunkRunner {
+    Own(StreamModel),
+    Pool { pool: Arc<WhisperPool>, name: String, priority: bool },
+}
+
+impl ChunkRunner {
+    fn name(&self) -> &str {
+        match self {
+            ChunkRunner::Own(model) => &model.name,
+            ChunkRunner::Pool { name, .. } => name,
+        }
+    }
+
+    /// Transcribe un fragmento; devuelve también lo que tardó Whisper.
+    #[allow(clippy::too_many_arguments)]
+    fn run(
+        &mut self,
+        audio: &[f32],
+        start_secs: f64,
+        config: &TranscriptionConfig,
+        post: &PostProcessor,
+        hallucinations: &HallucinationFilter,
+        name: &str,
+        voices: Option<&VoiceMatcher>,
+    ) -> (Option<AudioMessage>, Duration) {
+        match self {
+            ChunkRunner::Own(model) => {
+                let started = Instant::now();
+                let segment = transcribe_chunk(audio, start_secs, &mut model.state, config, post, hallucinations, name, voices);
+                (segment, started.elapsed())
+            }
+            ChunkRunner::Pool { pool, priority, .. } => pool.transcribe(ChunkJob {
+                audio: audio.to_vec(),
+                start_secs,
+                name: name.to_string(),
+                identify_voices: voices.is_some(),
+                priority: *priority,
+            }),
+        }
+    }
+
+    /// Con el pool el modelo es de todas las fuentes: no se cambia por una.
+    fn record_chunk(
+        &mut self,
+        elapsed: Duration,
+        audio_secs: f32,
+        config: &TranscriptionConfig,
+        stream_name: &str,
+        tx_ui: &UiSender,
+    ) -> Result<()> {
+        match self {
+            ChunkRunner::Own(model) => model.record_chunk(elapsed, audio_secs, config, stream_name, tx_ui),
+            ChunkRunner::Pool { .. } => Ok(()),
+        }
+    }
+}
+
+/// Siguiente modelo más ligero de la familia Whisper.
+fn smaller_model(name: &str) -> Option<&'static str> {
+    match name {
+        "large-v3" | "large-v2" | "large" => Some("medium"),
+        "medium" => Some("small"),
+        "small" => Some("base"),
+        "base" => Some("tiny"),
+        _ => None,
+    }
+}
+
+// ── Helpers de audio compartidos ──────────────────────────────────────────
+
+/// Comprueba silencio y pasa el fragmento por Whisper; devuelve la
+/// intervención (`AudioMessage::Segment`) o `None` si no hay texto. `audio`
+/// llega ya nivelado (AGC o normalizado).
+/// `chunk_start` es la posición del fragmento en la sesión, en segundos. Con
+/// `voices`, la intervención lleva el nombre de quien se reconozca por la voz.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn transcribe_chunk(
+    audio: &[f32],
+    chunk_start: f64,
+    state: &mut whisper_rs::WhisperState,
+    config: &TranscriptionConfig,
+    post: &PostProcessor,
+    hallucinations: &HallucinationFilter,
+    name: &str,
+    voices: Option<&VoiceMatcher>,
+) -> Option<AudioMessage> {
+    // El umbral de silencio se calibró sobre fragmentos normalizados
+    if dsp::normalized_rms(audio) < SILENCE_THRESHOLD {
+        return None;
+    }
+
+    if let Ok(lang) = run_whisper(state, config, audio) {
+        let n = state.full_n_segments();
+        if n > 0 {
+            let mut text = String::new();
+            let mut words = Vec::new();
+            // Tramo con voz dentro del fragmento (centésimas de segundo)
+            let mut span: Option<(i64, i64)> = None;
+            for i in 0..n {
+                if let Some(seg) = state.get_segment(i) {
+                    let t = seg.to_string().trim().to_string();
+                    if t.len() > 1 && !hallucinations.is_hallucination(&t) {
+                        text.push_str(&t);
+                        text.push(' ');
+                        let (t0, t1) = (seg.start_timestamp(), seg.end_timestamp());
+                        span = Some(span.map_or((t0, t1), |(s, _)| (s, t1)));
+                        collect_words(&seg, chunk_start, &mut words);
+                    }
+                }
+            }
+            let trimmed = post.apply(text.trim());
+            if !trimmed.is_empty() {
+                let (t0, t1) = span.unwrap_or((0, 0));
+                let confidence = mean_probability(&words);
+                // Muestras del tramo con voz (las marcas van en centésimas)
+                let at = |t: i64| (t.max(0) as usize * WHISPER_SAMPLE_RATE as usize / 100).min(audio.len());
+                let speaker = voices
+                    .and_then(|v| v.identify(&audio[at(t0)..at(t1).max(at(t0))]))
+                    .unwrap_or(name);
+                return Some(AudioMessage::Segment {
+                    speaker: speaker.to_string(),
+                    start_secs: chunk_start + t0 as f64 / 100.0,
+                    end_secs: chunk_start + t1 as f64 / 100.0,
+                    text: trimmed,
+                    confidence,
+                    words,
+                    lang: lang.map(str::to_string),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Agrupa los tokens de un segmento en palabras: un token que empieza por
+/// espacio abre palabra nueva y el resto se pega a la anterior. Los tokens
+/// especiales (`[_BEG_]`, `<|es|>`...) se descartan.
+pub fn collect_words(seg: &WhisperSegment, chunk_start: f64, words: &mut Vec<WordTiming>) {
+    let first = words.len();
+    for i in 0..seg.n_tokens() {
+        let Some(token) = seg.get_token(i) else { continue };
+        let Ok(piece) = token.to_str_lossy() else { continue };
+        if piece.starts_with("[_") || piece.starts_with("<|") || piece.trim().is_empty() {
+            continue;
+        }
+        let data = token.token_data();
+        let (start, end) = (chunk_start + data.t0 as f64 / 100.0, chunk_start + data.t1 as f64 / 100.0);
+        let continues = words.len() > first && !piece.starts_with(' ');
+        match words.last_mut() {
+            Some(word) if continues => {
+                word.word.push_str(&piece);
+                word.end_secs = end;
+                word.probability = word.probability.min(data.p);
+            }
+            _ => words.push(WordTiming {
+                word: piece.trim().to_string(),
+                start_secs: start,
+                end_secs: end,
+                probability: data.p,
+            }),
+        }
+    }
+}
+
+/// Decodifica `audio` con Whisper. En modo multilingüe lo hace con detección
+/// de idioma (y, si toca, una segunda pasada traduciendo) y devuelve el
+/// idioma detectado.
+pub fn run_whisper(
+    state: &mut WhisperState,
+    config: &TranscriptionConfig,
+    audio: &[f32],
+) -> std::result::Result<Option<&'static str>, whisper_rs::WhisperError> {
+    let mut params = whisper_params(config);
+    if !config.multilingual.enabled {
+        state.full(params, audio)?;
+        return Ok(None);
+    }
+    params.set_language(None);
+    params.set_translate(false);
+    state.full(params, audio)?;
+    let lang = whisper_rs::get_lang_str(state.full_lang_id_from_state());
+    if let Some(detected) = lang.filter(|l| config.multilingual.should_translate(l, config.lang.source_lang)) {
+        let mut params = whisper_params(config);
+        params.set_language(Some(detected));
+        params.set_translate(true);
+        state.full(params, audio)?;
+    }
+    Ok(lang)
+}
+
+/// Parámetros de decodificación comunes a la captura en vivo y al vídeo.
+pub fn whisper_params(config: &TranscriptionConfig) -> FullParams<'static, 'static> {
+    let tuning = &config.tuning;
+    let strategy = if tuning.beam_size > 1 {
+        SamplingStrategy::BeamSearch { beam_size: tuning.beam_size as i32, patience: -1.0 }
+    } else {
+        SamplingStrategy::Greedy { best_of: tuning.best_of.max(1) as i32 }
+    };
+
+    let mut params = FullParams::new(strategy);
+    if tuning.n_threads > 0 {
+        params.set_n_threads(tuning.n_threads as i32);
+    }
+    params.set_temperature(tuning.temperature);
+    params.set_language(config.lang.source_lang);
+    params.set_translate(config.lang.translate_to_english);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    // Tiempos y probabilidad por token, para las marcas por palabra.
+    params.set_token_timestamps(true);
+    params.set_suppress_blank(true);
+    params.set_suppress_nst(true);
+    params.set_no_speech_thold(tuning.no_speech_thold);
+    let style = config.style.prompt(config.lang.source_lang);
+    let prompt = match (style, config.preset.initial_prompt()) {
+        (Some(style), Some(terms)) => Some(format!("{} {}", style, terms)),
+        (style, terms) => terms.or(style.map(str::to_string)),
+    };
+    if let Some(prompt) = prompt {
+        params.set_initial_prompt(&prompt);
+    }
+    params
+}
+
+pub fn resample(input: &[f32], from: u32, to: u32) -> Vec<f32> {
+    let mut out = Vec::new();
+    resample_into(input, from, to, &mut out);
+    out
+}
+
+/// `resample` en `out`, para reutilizar el búfer de un fragmento al siguiente.
+pub fn resample_into(input: &[f32], from: u32, to: u32, out: &mut Vec<f32>) {
+    let ratio = to as f64 / from as f64;
+    let len = (input.len() as f64 * ratio) as usize;
+    out.clear();
+    out.reserve(len);
+    out.extend((0..len).map(|i| {
+        let src = i as f64 / ratio;
+        let idx = src as usize;
+        let frac = (src - idx as f64) as f32;
+        let a = input.get(idx).copied().unwrap_or(0.0);
+        let b = input.get(idx + 1).copied().unwrap_or(0.0);
+        a + (b - a) * frac
+    }));
+}
+
+/// Remuestreo de un flujo que llega por bloques. `resample` trata cada
+/// llamada por separado y pierde la fracción de muestra del final (un 0,4 %
+/// a 44,1 kHz con bloques de 512); este guarda la posición y la última
+/// muestra de un bloque al siguiente, así que la salida acumulada es la
+/// entrada acumulada por la razón de frecuencias.
+pub struct StreamResampler {
+    /// Muestras de entrada por cada muestra de salida.
+    step: f64,
+    /// Posición de la siguiente muestra de salida respecto al bloque que
+    /// llega; entre -1 y 0 cae entre la última del anterior y la primera.
+    position: f64,
+    last: f32,
+}
+
+impl StreamResampler {
+    pub fn new(from: u32, to: u32) -> Self {
+        Self { step: from as f64 / to as f64, position: 0.0, last: 0.0 }
+    }
+
+    /// Remuestrea el siguiente bloque en `out`.
+    pub fn process_into(&mut self, input: &[f32], out: &mut Vec<f32>) {
+        out.clear();
+        if self.step == 1.0 {
+            out.extend_from_slice(input);
+            return;
+        }
+        let Some(&end) = input.last() else { return };
+        // Para interpolar hace falta la muestra siguiente: la última del
+        // bloque espera al próximo
+        let limit = (input.len() - 1) as f64;
+        out.reserve(((limit - self.position) / self.step).max(0.0) as usize + 1);
+        while self.position < limit {
+            let idx = self.position.floor();
+            let frac = (self.position - idx) as f32;
+            let a = if idx < 0.0 { self.last } else { input[idx as usize] };
+            let b = input[(idx + 1.0) as usize];
+            out.push(a + (b - a) * frac);
+            self.position += self.step;
+        }
+        self.position -= input.len() as f64;
+        self.last = end;
+    }
+}
+
+pub fn normalize_audio(input: &[f32]) -> Vec<f32> {
+    let mut out = input.to_vec();
+    dsp::normalize_in_place(&mut out);
+    out
+}
+
+pub fn calculate_rms(audio: &[f32]) -> f32 {
+    dsp::rms(audio)
+}
+
+// ── Descarga del modelo ────────────────────────────────────────────────────
+
+/// Origen de los modelos si no se configura un espejo.
+pub const DEFAULT_MODEL_MIRROR: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+pub(crate) fn model_file_name(model_name: &str) -> String {
+    format!("ggml-{}.bin", model_name)
+}
+
+/// `true` si el modelo ya está en disco y no hará falta descargarlo.
+pub fn model_is_cached(model_name: &str, source: &ModelSource) -> bool {
+    if !source.custom_path.trim().is_empty() {
+        return true;
+    }
+    Path::new("models").join(model_file_name(model_name)).exists()
+}
+
+/// Línea de estado de una descarga de modelo en curso.
+pub fn download_status(model_name: &str, downloaded: u64, total: u64) -> String {
+    match (downloaded * 100).checked_div(total) {
+        Some(pct) => tr!("model.downloading", model = model_name, pct = pct),
+        None => tr!("model.downloading_mb", model = model_name, mb = downloaded / 1_000_000),
+    }
+}
+
+/// Ruta local del modelo, descargándolo si hace falta. Un archivo propio en
+/// `source` se usa tal cual y en modo sin conexión nunca se descarga nada.
+/// `on_progress` recibe (bytes descargados, total) cada 1 % aproximadamente;
+/// el total es 0 si el servidor no lo indica.
+pub async fn download_whisper_model(
+    model_name: &str,
+    source: &ModelSource,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<String> {
+    let custom = source.custom_path.trim();
+    if !custom.is_empty() {
+        if !Path::new(custom).is_file() {
+            anyhow::bail!(tr!("model.custom_missing", path = custom));
+        }
+        return Ok(custom.to_string());
+    }
+
+    let models_dir = Path::new("models");
+    let model_file = model_file_name(model_name);
+    let model_path = models_dir.join(&model_file);
+
+    if !models_dir.exists() {
+        std::fs::create_dir_all(models_dir)?;
+    }
+
+    if model_path.exists() {
+        return Ok(model_path.to_string_lossy().to_string());
+    }
+    if source.offline {
+        anyhow::bail!(tr!("model.offline_missing", model = model_name, path = model_path.display()));
+    }
+
+    let mirror = match source.mirror_url.trim().trim_end_matches('/') {
+        "" => DEFAULT_MODEL_MIRROR,
+        mirror => mirror,
+    };
+    let url = format!("{}/{}", mirror, model_file);
+
+    tracing::info!("Descargando modelo '{}' desde {}", model_name, url);
+
+    // Sin proxy propio, reqwest ya usa HTTPS_PROXY/HTTP_PROXY/NO_PROXY
+    let mut client = Client::builder();
+    if !source.proxy.trim().is_empty() {
+        let proxy = Proxy::all(source.proxy.trim())
+            .map_err(|e| anyhow::anyhow!(tr!("model.proxy_invalid", e = e)))?;
+        client = client.proxy(proxy);
+    }
+    let client = client.build()?;
+    let response = client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(tr!("audio.download_http", status = response.status()));
+    }
+
+    let total = response.content_length().unwrap_or(0);
+    let mut downloaded: u64 = 0;
+    let mut last_logged_pct: u64 = 0;
+    let mut last_reported: u64 = 0;
+    let report_every = (total / 100).max(1_000_000);
+    let mut file = std::fs::File::create(&model_path)?;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk)?;
+        downloaded += chunk.len() as u64;
+        if downloaded - last_reported >= report_every {
+            last_reported = downloaded;
+            on_progress(downloaded, total);
+        }
+        let pct = (downloaded * 100).checked_div(total).unwrap_or(0);
+        if pct >= last_logged_pct + 10 {
+            last_logged_pct = pct - pct % 10;
+            tracing::debug!("Modelo '{}': {}% ({}/{} MB)", model_name, pct, downloaded / 1_000_000, total / 1_000_000);
+        }
+    }
+
+    tracing::info!("Modelo '{}' descargado en {}", model_name, model_path.display());
+    Ok(model_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_resampler_keeps_the_rate_across_blocks() {
+        for (from, block) in [(44_100, 512), (48_000, 480), (22_050, 333), (8_000, 160), (16_000, 512)] {
+            let mut resampler = StreamResampler::new(from, WHISPER_SAMPLE_RATE);
+            let mut out = Vec::new();
+            let samples = vec![0.5; block];
+            let (mut input_len, mut output_len) = (0usize, 0usize);
+            // Diez minutos de bloques: perdiendo una fracción en cada uno
+            // serían segundos de desfase
+            for _ in 0..(from as usize * 600 / block) {
+                resampler.process_into(&samples, &mut out);
+                assert!(out.iter().all(|s| (s - 0.5).abs() < 1e-6));
+                input_len += block;
+                output_len += out.len();
+            }
+            let ratio = WHISPER_SAMPLE_RATE as f64 / from as f64;
+            let expected = input_len as f64 * ratio;
+            // Solo queda pendiente lo que sale de la última muestra de entrada
+            let missing = expected - output_len as f64;
+            assert!((0.0..=ratio.max(1.0)).contains(&missing), "{} Hz: {} en vez de {}", from, output_len, expected);
+        }
+    }
+
+    #[test]
+    fn stream_resampler_matches_one_pass() {
+        let input: Vec<f32> = (0..44_100).map(|i| (i as f32 * 0.01).sin()).collect();
+        let whole = resample(&input, 44_100, WHISPER_SAMPLE_RATE);
+        let mut resampler = StreamResampler::new(44_100, WHISPER_SAMPLE_RATE);
+        let mut out = Vec::new();
+        let mut blocks = Vec::new();
+        for block in input.chunks(512) {
+            resampler.process_into(block, &mut out);
+            blocks.extend_from_slice(&out);
+        }
+        assert!(whole.len().abs_diff(blocks.len()) <= 1);
+        assert!(whole.iter().zip(&blocks).all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+}
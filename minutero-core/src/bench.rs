@@ -0,0 +1,156 @@
+use anyhow::{Result, anyhow};
+use std::f32::consts::TAU;
+use std::path::Path;
+use std::time::Instant;
+use whisper_rs::WhisperContext;
+use crate::audio::whisper_params;
+use crate::data::{LanguageConfig, TranscriptionConfig, WHISPER_SAMPLE_RATE};
+use crate::settings::AppSettings;
+use crate::video::decode_audio;
+
+// ── Benchmark de modelos ───────────────────────────────────────────────────
+//
+// `transcriptor --bench [archivo]` pasa un clip por cada modelo descargado en
+// `models/` con los mismos fragmentos que la captura en vivo y muestra el
+// real-time factor (tiempo de proceso / audio nuevo; por debajo de 1 el
+// modelo aguanta el ritmo) y la memoria que ocupa. Sin archivo se usa una
+// señal sintética de 30 s con envolvente de habla.
+
+const SYNTHETIC_SECS: u32 = 30;
+
+pub struct BenchResult {
+    pub model: String,
+    pub load_secs: f32,
+    pub rtf: f32,
+    /// Memoria residente añadida al cargar el modelo; solo en Linux.
+    pub memory_mb: Option<u64>,
+}
+
+/// Punto de entrada de `--bench`: imprime la tabla por stdout.
+pub fn run_cli(sample: Option<&str>) -> Result<()> {
+    let audio = match sample {
+        Some(path) => decode_audio(path)?,
+        None => synthetic_sample(),
+    };
+    let settings = AppSettings::load();
+    let mut models = local_models();
+    let custom = settings.model_source.custom_path.trim();
+    if !custom.is_empty() && Path::new(custom).is_file() {
+        let name = Path::new(custom).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        models.push((name, custom.to_string()));
+    }
+    if models.is_empty() {
+        return Err(anyhow!("No hay modelos en models/. Inicia una captura para descargar uno."));
+    }
+
+    let config = TranscriptionConfig {
+        lang: LanguageConfig::default(),
+        preset: settings.active_preset(),
+        tuning: settings.tuning.clone(),
+        style: settings.transcription_style,
+        audio_host: settings.audio_host.clone(),
+        model_source: settings.model_source.clone(),
+        mute: Default::default(),
+        multilingual: Default::default(),
+        voices: Default::default(),
+        streams: Default::default(),
+        capture_tool: settings.capture_tool,
+        compare_model: None,
+        discord: Default::default(),
+        limits: Default::default(),
+    };
+
+    println!(
+        "Audio: {:.1} s ({}), fragmentos de {} s",
+        audio.len() as f32 / WHISPER_SAMPLE_RATE as f32,
+        sample.unwrap_or("sintético"),
+        config.tuning.chunk_secs,
+    );
+    println!("{:<12} {:>10} {:>8} {:>12}", "Modelo", "Carga (s)", "RTF", "Memoria (MB)");
+    for (name, path) in models {
+        match bench_model(&name, &path, &audio, &config) {
+            Ok(r) => println!(
+                "{:<12} {:>10.1} {:>8.2} {:>12}",
+                r.model,
+                r.load_secs,
+                r.rtf,
+                r.memory_mb.map_or("n/d".to_string(), |m| m.to_string()),
+            ),
+            Err(e) => println!("{:<12} error: {:?}", name, e),
+        }
+    }
+    println!("\nRTF < 1: el modelo transcribe en tiempo real en este equipo.");
+    Ok(())
+}
+
+/// (nombre, ruta) de los modelos `ggml-*.bin` ya descargados.
+fn local_models() -> Vec<(String, String)> {
+    let mut models: Vec<(String, String)> = std::fs::read_dir("models")
+        .map(|entries| {
+            entries.flatten()
+                .filter_map(|e| {
+                    let file = e.file_name().to_string_lossy().to_string();
+                    let name = file.strip_prefix("ggml-")?.strip_suffix(".bin")?.to_string();
+                    Some((name, e.path().to_string_lossy().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort_by_key(|(_, path)| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0));
+    models
+}
+
+fn bench_model(name: &str, path: &str, audio: &[f32], config: &TranscriptionConfig) -> Result<BenchResult> {
+    let rss_before = resident_memory_kb();
+    let started = Instant::now();
+    let ctx = WhisperContext::new_with_params(path, Default::default())
+        .map_err(|e| anyhow!("{:?}", e))?;
+    let mut state = ctx.create_state().map_err(|e| anyhow!("{:?}", e))?;
+    let load_secs = started.elapsed().as_secs_f32();
+    let memory_mb = rss_before
+        .zip(resident_memory_kb())
+        .map(|(before, after)| after.saturating_sub(before) / 1024);
+
+    // Igual que la captura en vivo: ventanas y solape de los ajustes.
+    let (target, overlap) = config.tuning.chunk_samples(WHISPER_SAMPLE_RATE);
+    let step = target - overlap;
+    let mut processing = 0.0f32;
+    let mut new_audio = 0.0f32;
+    let mut start = 0;
+    while start + target <= audio.len() {
+        let started = Instant::now();
+        state.full(whisper_params(config), &audio[start..start + target])
+            .map_err(|e| anyhow!("{:?}", e))?;
+        processing += started.elapsed().as_secs_f32();
+        new_audio += step as f32 / WHISPER_SAMPLE_RATE as f32;
+        start += step;
+    }
+    if new_audio == 0.0 {
+        return Err(anyhow!("el audio dura menos de {} s", target / WHISPER_SAMPLE_RATE as usize));
+    }
+
+    Ok(BenchResult { model: name.to_string(), load_secs, rtf: processing / new_audio, memory_mb })
+}
+
+/// Tono con armónicos y sílabas de ~200 ms, para que Whisper no lo descarte
+/// como silencio y decodifique algo parecido a una frase.
+fn synthetic_sample() -> Vec<f32> {
+    let rate = WHISPER_SAMPLE_RATE as f32;
+    (0..WHISPER_SAMPLE_RATE * SYNTHETIC_SECS)
+        .map(|i| {
+            let t = i as f32 / rate;
+            let pitch = 140.0 + 30.0 * (TAU * 0.5 * t).sin();
+            let voice: f32 = (1..=5).map(|h| (TAU * pitch * h as f32 * t).sin() / h as f32).sum();
+            let syllable = (TAU * 2.5 * t).sin().max(0.0);
+            0.3 * voice * syllable
+        })
+        .collect()
+}
+
+/// VmRSS del proceso en KiB.
+fn resident_memory_kb() -> Option<u64> {
+    let status = std::fs::read_to_string(Path::new("/proc/self/status")).ok()?;
+    status.lines()
+        .find_map(|l| l.strip_prefix("VmRSS:"))
+        .and_then(|v| v.trim().trim_end_matches("kB").trim().parse().ok())
+}
@@ -0,0 +1,484 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::Instant;
+use tokio::runtime::Runtime;
+use crate::i18n::tr;
+
+// ── Calendario (solo lectura) ──────────────────────────────────────────────
+//
+// Se lee un feed iCalendar (.ics): la "dirección secreta en formato iCal" de
+// Google Calendar o la exportación de una colección CalDAV (p. ej. Nextcloud:
+// `.../calendars/usuario/personal?export`). Se usa para saber qué reunión
+// está en curso y rellenar título y asistentes.
+//
+// Recurrencias: solo FREQ=DAILY y FREQ=WEEKLY (con INTERVAL, BYDAY, COUNT y
+// UNTIL). Los eventos de día completo se ignoran.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarConfig {
+    pub enabled: bool,
+    pub ics_url: String,
+    /// Usuario y contraseña para CalDAV; vacío = sin autenticación.
+    pub username: String,
+    pub password: String,
+    /// Preguntar si se inicia la captura cuando empieza una videollamada.
+    pub prompt_on_call: bool,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ics_url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            prompt_on_call: true,
+        }
+    }
+}
+
+impl CalendarConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.ics_url.trim().is_empty()
+    }
+}
+
+/// Dominios de videollamada que se buscan en la ubicación y la descripción.
+const CALL_DOMAINS: &[&str] = &[
+    "meet.google.com",
+    "zoom.us/",
+    "teams.microsoft.com",
+    "teams.live.com",
+    "webex.com",
+    "whereby.com",
+    "meet.jit.si",
+];
+
+/// Tiempo durante el que se ofrece iniciar la captura tras empezar un evento.
+const CALL_PROMPT_WINDOW_MINS: i64 = 10;
+
+#[derive(Clone, Debug)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub title: String,
+    pub attendees: Vec<String>,
+    pub call_link: Option<String>,
+    start: NaiveDateTime,
+    zone: Zone,
+    duration: Duration,
+    rule: Option<Recurrence>,
+}
+
+/// Ocurrencia concreta de un evento (las recurrentes tienen varias).
+#[derive(Clone, Debug)]
+pub struct Occurrence {
+    pub event: CalendarEvent,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl Occurrence {
+    /// Identificador estable de esta ocurrencia, para no repetir avisos.
+    pub fn key(&self) -> String {
+        format!("{}@{}", self.event.uid, self.start.timestamp())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Zone {
+    Utc,
+    Named(Tz),
+    Floating,
+}
+
+#[derive(Clone, Debug)]
+enum Frequency {
+    Daily,
+    Weekly,
+}
+
+#[derive(Clone, Debug)]
+struct Recurrence {
+    freq: Frequency,
+    interval: i64,
+    by_day: Vec<Weekday>,
+    count: Option<usize>,
+    until: Option<NaiveDateTime>,
+}
+
+// ── Estado en la interfaz ──────────────────────────────────────────────────
+
+/// Cada cuánto se vuelve a descargar el feed y se comprueba el evento en curso.
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+
+#[derive(Default)]
+pub struct CalendarState {
+    events: Vec<CalendarEvent>,
+    rx: Option<Receiver<Result<Vec<CalendarEvent>, String>>>,
+    last_fetch: Option<Instant>,
+    last_check: Option<Instant>,
+    pub current: Option<Occurrence>,
+    pub status: String,
+    /// Ocurrencias para las que ya se ofreció iniciar la captura.
+    pub prompted: HashSet<String>,
+}
+
+impl CalendarState {
+    /// Recoge descargas terminadas, lanza la siguiente si toca y recalcula el
+    /// evento en curso. Devuelve `true` si este ha cambiado.
+    pub fn poll(&mut self, config: &CalendarConfig) -> bool {
+        if !config.is_enabled() {
+            let changed = self.current.is_some();
+            *self = Self { prompted: std::mem::take(&mut self.prompted), ..Default::default() };
+            return changed;
+        }
+
+        if let Some(rx) = &self.rx {
+            if let Ok(result) = rx.try_recv() {
+                match result {
+                    Ok(events) => {
+                        self.status = tr!("calendar.loaded", n = events.len());
+                        self.events = events;
+                    }
+                    Err(e) => self.status = tr!("common.error", e = e),
+                }
+                self.rx = None;
+                self.last_check = None;
+            }
+        }
+
+        if self.rx.is_none() && self.last_fetch.is_none_or(|t| t.elapsed() >= REFRESH_INTERVAL) {
+            self.refresh(config);
+        }
+
+        if self.last_check.is_some_and(|t| t.elapsed() < CHECK_INTERVAL) {
+            return false;
+        }
+        self.last_check = Some(Instant::now());
+        let previous = self.current.as_ref().map(Occurrence::key);
+        self.current = current_occurrence(&self.events, Local::now());
+        previous != self.current.as_ref().map(Occurrence::key)
+    }
+
+    /// Fuerza una nueva descarga en segundo plano.
+    pub fn refresh(&mut self, config: &CalendarConfig) {
+        let (tx, rx) = channel();
+        let config = config.clone();
+        thread::spawn(move || {
+            let _ = tx.send(fetch_events(&config).map_err(|e| format!("{:?}", e)));
+        });
+        self.rx = Some(rx);
+        self.last_fetch = Some(Instant::now());
+        self.status = tr!("calendar.loading");
+    }
+
+    /// Videollamada que acaba de empezar y para la que aún no se ha preguntado.
+    pub fn pending_call_prompt(&self, config: &CalendarConfig) -> Option<&Occurrence> {
+        let current = self.current.as_ref()?;
+        let just_started = Local::now() - current.start < Duration::minutes(CALL_PROMPT_WINDOW_MINS);
+        (config.prompt_on_call
+            && current.event.call_link.is_some()
+            && just_started
+            && !self.prompted.contains(&current.key()))
+            .then_some(current)
+    }
+}
+
+// ── Descarga ───────────────────────────────────────────────────────────────
+
+/// Descarga y analiza el feed. Bloquea: llamar desde un hilo aparte.
+pub fn fetch_events(config: &CalendarConfig) -> Result<Vec<CalendarEvent>> {
+    let url = config.ics_url.trim().replacen("webcal://", "https://", 1);
+    let body = Runtime::new()?.block_on(async {
+        let mut request = Client::new().get(&url);
+        if !config.username.trim().is_empty() {
+            request = request.basic_auth(config.username.trim(), Some(&config.password));
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("calendario HTTP {}", response.status()));
+        }
+        Ok(response.text().await?)
+    })?;
+    Ok(parse_ics(&body))
+}
+
+/// La ocurrencia en curso en `now`; si se solapan varias, la que empezó más tarde.
+pub fn current_occurrence(events: &[CalendarEvent], now: DateTime<Local>) -> Option<Occurrence> {
+    events.iter()
+        .filter_map(|e| e.occurrence_at(now))
+        .max_by_key(|o| o.start)
+}
+
+// ── Análisis de iCalendar ──────────────────────────────────────────────────
+
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, String)>,
+    value: &'a str,
+}
+
+impl Property<'_> {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+fn parse_ics(body: &str) -> Vec<CalendarEvent> {
+    // Las líneas que empiezan por espacio o tabulador continúan la anterior.
+    let unfolded = body.replace("\r\n", "\n").replace("\n ", "").replace("\n\t", "");
+
+    let mut events = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    for line in unfolded.lines() {
+        let Some(prop) = parse_line(line) else { continue };
+        match (prop.name.as_str(), prop.value) {
+            ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                if let Some(event) = current.take().and_then(|props| build_event(&props)) {
+                    events.push(event);
+                }
+            }
+            _ => {
+                if let Some(props) = current.as_mut() {
+                    props.push(prop);
+                }
+            }
+        }
+    }
+    events
+}
+
+fn parse_line(line: &str) -> Option<Property<'_>> {
+    // El separador nombre/valor es el primer ':' fuera de comillas.
+    let mut in_quotes = false;
+    let colon = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ':' && !in_quotes
+    })?.0;
+
+    let head = &line[..colon];
+    let mut parts = head.split(';');
+    let name = parts.next()?.to_ascii_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.to_ascii_uppercase(), v.trim_matches('"').to_string()))
+        .collect();
+    Some(Property { name, params, value: &line[colon + 1..] })
+}
+
+fn build_event(props: &[Property]) -> Option<CalendarEvent> {
+    let get = |name: &str| props.iter().find(|p| p.name == name);
+
+    if get("STATUS").is_some_and(|p| p.value.eq_ignore_ascii_case("CANCELLED")) {
+        return None;
+    }
+
+    let dtstart = get("DTSTART")?;
+    let (start, zone) = parse_datetime(dtstart)?;
+    let duration = match get("DTEND").and_then(parse_datetime) {
+        Some((end, _)) => end - start,
+        None => get("DURATION").and_then(|p| parse_duration(p.value)).unwrap_or(Duration::hours(1)),
+    };
+
+    let mut text_for_links = String::new();
+    for name in ["URL", "LOCATION", "DESCRIPTION", "X-GOOGLE-CONFERENCE"] {
+        if let Some(p) = get(name) {
+            text_for_links.push_str(&unescape(p.value));
+            text_for_links.push(' ');
+        }
+    }
+
+    Some(CalendarEvent {
+        uid: get("UID").map(|p| p.value.to_string()).unwrap_or_default(),
+        title: get("SUMMARY").map(|p| unescape(p.value)).unwrap_or_default(),
+        attendees: props.iter()
+            .filter(|p| p.name == "ATTENDEE")
+            .filter_map(|p| {
+                p.param("CN")
+                    .map(str::to_string)
+                    .or_else(|| p.value.strip_prefix("mailto:").map(str::to_string))
+            })
+            .collect(),
+        call_link: find_call_link(&text_for_links),
+        start,
+        zone,
+        duration,
+        rule: get("RRULE").and_then(|p| parse_rrule(p.value)),
+    })
+}
+
+/// Fecha y hora de inicio/fin. `None` para eventos de día completo.
+fn parse_datetime(prop: &Property) -> Option<(NaiveDateTime, Zone)> {
+    let value = prop.value.trim();
+    if prop.param("VALUE") == Some("DATE") || value.len() == 8 {
+        return None;
+    }
+    let (raw, utc) = match value.strip_suffix('Z') {
+        Some(raw) => (raw, true),
+        None => (value, false),
+    };
+    let naive = NaiveDateTime::parse_from_str(raw, "%Y%m%dT%H%M%S").ok()?;
+    let zone = if utc {
+        Zone::Utc
+    } else {
+        prop.param("TZID")
+            .and_then(|id| id.parse::<Tz>().ok())
+            .map_or(Zone::Floating, Zone::Named)
+    };
+    Some((naive, zone))
+}
+
+/// Duraciones ISO 8601 sencillas: `PT1H30M`, `P1D`...
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.trim_start_matches(['P', '+']).chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(total)
+}
+
+fn parse_rrule(value: &str) -> Option<Recurrence> {
+    let mut freq = None;
+    let mut rule = Recurrence { freq: Frequency::Daily, interval: 1, by_day: Vec::new(), count: None, until: None };
+    for part in value.split(';') {
+        let (key, val) = part.split_once('=')?;
+        match key {
+            "FREQ" => {
+                freq = match val {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    _ => None,
+                }
+            }
+            "INTERVAL" => rule.interval = val.parse().unwrap_or(1).max(1),
+            "COUNT" => rule.count = val.parse().ok(),
+            "UNTIL" => {
+                let raw = val.trim_end_matches('Z');
+                rule.until = NaiveDateTime::parse_from_str(raw, "%Y%m%dT%H%M%S").ok().or_else(|| {
+                    NaiveDate::parse_from_str(raw, "%Y%m%d").ok().and_then(|d| d.and_hms_opt(23, 59, 59))
+                });
+            }
+            "BYDAY" => {
+                rule.by_day = val.split(',')
+                    .filter_map(|d| match d.trim_start_matches(|c: char| c == '-' || c == '+' || c.is_ascii_digit()) {
+                        "MO" => Some(Weekday::Mon),
+                        "TU" => Some(Weekday::Tue),
+                        "WE" => Some(Weekday::Wed),
+                        "TH" => Some(Weekday::Thu),
+                        "FR" => Some(Weekday::Fri),
+                        "SA" => Some(Weekday::Sat),
+                        "SU" => Some(Weekday::Sun),
+                        _ => None,
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    // Frecuencias no soportadas: se trata como evento único.
+    rule.freq = freq?;
+    Some(rule)
+}
+
+fn unescape(value: &str) -> String {
+    value.replace("\\n", "\n")
+        .replace("\\N", "\n")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+fn find_call_link(text: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '(' | ')'))
+        .find(|token| token.starts_with("http") && CALL_DOMAINS.iter().any(|d| token.contains(d)))
+        .map(str::to_string)
+}
+
+// ── Ocurrencias ────────────────────────────────────────────────────────────
+
+impl CalendarEvent {
+    fn to_local(&self, naive: NaiveDateTime) -> Option<DateTime<Local>> {
+        match self.zone {
+            Zone::Utc => Some(Utc.from_utc_datetime(&naive).with_timezone(&Local)),
+            Zone::Named(tz) => tz.from_local_datetime(&naive).earliest().map(|d| d.with_timezone(&Local)),
+            Zone::Floating => Local.from_local_datetime(&naive).earliest(),
+        }
+    }
+
+    fn occurrence(&self, start: NaiveDateTime) -> Option<Occurrence> {
+        let start = self.to_local(start)?;
+        Some(Occurrence { event: self.clone(), start, end: start + self.duration })
+    }
+
+    /// La ocurrencia que contiene `now`, si la hay.
+    fn occurrence_at(&self, now: DateTime<Local>) -> Option<Occurrence> {
+        let contains = |o: &Occurrence| o.start <= now && now < o.end;
+
+        let Some(rule) = &self.rule else {
+            return self.occurrence(self.start).filter(contains);
+        };
+
+        // Se recorren los días desde el inicio hasta hoy contando ocurrencias,
+        // para respetar COUNT. Basta con hacerlo cada pocos segundos.
+        let first_day = self.start.date();
+        let today = now.date_naive();
+        // Las semanas empiezan en lunes (WKST por defecto).
+        let first_monday = first_day - Duration::days(first_day.weekday().num_days_from_monday() as i64);
+        let week_of = |d: NaiveDate| (d - first_monday).num_days().div_euclid(7);
+        let by_day = if rule.by_day.is_empty() { vec![first_day.weekday()] } else { rule.by_day.clone() };
+
+        let mut found = None;
+        let mut n = 0usize;
+        let mut day = first_day;
+        while day <= today {
+            let matches = match rule.freq {
+                Frequency::Daily => (day - first_day).num_days() % rule.interval == 0,
+                Frequency::Weekly => {
+                    week_of(day) % rule.interval == 0 && by_day.contains(&day.weekday())
+                }
+            };
+            if matches {
+                let start = day.and_time(self.start.time());
+                if rule.until.is_some_and(|u| start > u) || rule.count.is_some_and(|c| n >= c) {
+                    break;
+                }
+                n += 1;
+                // Solo pueden contener `now` las de hoy o ayer (si cruzan la medianoche).
+                if (today - day).num_days() <= 1 {
+                    if let Some(o) = self.occurrence(start).filter(contains) {
+                        found = Some(o);
+                    }
+                }
+            }
+            day += Duration::days(1);
+        }
+        found
+    }
+}
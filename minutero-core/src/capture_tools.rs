@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// ── Programas de captura en Linux ─────────────────────────────────────────
+//
+// Con PulseAudio/PipeWire cada dispositivo se graba con un programa externo
+// que entrega PCM crudo (16 kHz, s16le) por stdout. `parecord` viene con las
+// utilidades de PulseAudio; en sistemas solo PipeWire a menudo está
+// `pw-record` en su lugar, y ffmpeg (`-f pulse`) queda como último recurso.
+// Se prueban en orden, empezando por el nativo del servidor: si uno no
+// arranca o no deja de caerse se pasa al siguiente, y si muere a mitad de la
+// reunión se vuelve a lanzar (ver `audio::run_capture_linux`). En
+// Configuración se puede fijar uno; fijar ffmpeg sin PulseAudio/PipeWire
+// graba los dispositivos de ALSA con `-f alsa` en vez de con cpal.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptureTool {
+    Parecord,
+    PwRecord,
+    Ffmpeg,
+}
+
+impl CaptureTool {
+    pub const ALL: &'static [CaptureTool] = &[CaptureTool::Parecord, CaptureTool::PwRecord, CaptureTool::Ffmpeg];
+
+    pub fn program(self) -> &'static str {
+        match self {
+            CaptureTool::Parecord => "parecord",
+            CaptureTool::PwRecord => "pw-record",
+            CaptureTool::Ffmpeg => "ffmpeg",
+        }
+    }
+
+    /// Está en el `PATH`.
+    pub fn is_installed(self) -> bool {
+        find_in_path(self.program()).is_some()
+    }
+
+    /// Orden que graba `device` con `channels` canales y lo escribe por
+    /// stdout. Con `pulse`, `device` es una fuente de PulseAudio (los
+    /// monitores acaban en `.monitor`); si no, un PCM de ALSA (solo ffmpeg).
+    pub fn command(self, device: &str, channels: usize, pulse: bool) -> Command {
+        let channels = channels.to_string();
+        let mut cmd = Command::new(self.program());
+        match self {
+            CaptureTool::Parecord => {
+                cmd.args(["--device", device, "--rate", "16000",
+                          "--channels", &channels, "--format", "s16le", "--raw"]);
+            }
+            CaptureTool::PwRecord => {
+                // En PipeWire el monitor de una salida se graba pidiendo la
+                // propia salida como destino
+                match device.strip_suffix(".monitor") {
+                    Some(sink) => cmd.args(["--target", sink, "-P", "{ stream.capture.sink = true }"]),
+                    None => cmd.args(["--target", device]),
+                };
+                cmd.args(["--rate", "16000", "--channels", &channels, "--format", "s16", "--raw", "-"]);
+            }
+            CaptureTool::Ffmpeg => {
+                let input = if pulse { "pulse" } else { "alsa" };
+                cmd.args(["-nostdin", "-loglevel", "error", "-f", input, "-i", device,
+                          "-ac", &channels, "-ar", "16000", "-f", "s16le", "pipe:1"]);
+            }
+        }
+        cmd.stdout(Stdio::piped()).stderr(Stdio::null());
+        cmd
+    }
+}
+
+/// Programas que se prueban, en orden: el elegido en Configuración o, en
+/// automático, el nativo de `server` ("PipeWire" o "PulseAudio", ver
+/// `system_audio::linux_sound_server`) primero. Solo los instalados;
+/// `pw-record` no sirve con PulseAudio y sin servidor solo queda ffmpeg.
+pub fn candidates(server: Option<&str>, preferred: Option<CaptureTool>) -> Vec<CaptureTool> {
+    let order = match (preferred, server) {
+        (Some(tool), _) => vec![tool],
+        (None, Some("PipeWire")) => vec![CaptureTool::PwRecord, CaptureTool::Parecord, CaptureTool::Ffmpeg],
+        (None, Some(_)) => vec![CaptureTool::Parecord, CaptureTool::Ffmpeg],
+        (None, None) => Vec::new(),
+    };
+    order.into_iter()
+        .filter(|t| t.is_installed())
+        .filter(|t| server.is_some() || *t == CaptureTool::Ffmpeg)
+        .collect()
+}
+
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
@@ -0,0 +1,107 @@
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::system_audio::detect_os;
+
+// ── Detección de videoconferencias ─────────────────────────────────────────
+//
+// En Linux se miran los flujos de reproducción de PulseAudio/PipeWire
+// (`pactl list sink-inputs`): en cuanto Zoom, Teams, etc. empiezan a sonar
+// aparece uno con su nombre. En otros sistemas no hay equivalente sencillo y
+// la detección queda desactivada.
+
+/// (subcadena a buscar en minúsculas, nombre a mostrar)
+const CONFERENCING_APPS: &[(&str, &str)] = &[
+    ("zoom", "Zoom"),
+    ("teams", "Microsoft Teams"),
+    ("meet", "Google Meet"),
+    ("webex", "Webex"),
+    ("skype", "Skype"),
+    ("discord", "Discord"),
+    ("slack", "Slack"),
+    ("jitsi", "Jitsi"),
+];
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+pub fn is_supported() -> bool {
+    detect_os() == "linux"
+}
+
+/// Aplicaciones de videoconferencia que están reproduciendo audio ahora mismo.
+pub fn detect_conferencing_apps() -> Vec<String> {
+    match detect_os() {
+        "linux" => linux_conferencing_apps(),
+        _ => vec![],
+    }
+}
+
+fn linux_conferencing_apps() -> Vec<String> {
+    let Ok(out) = Command::new("pactl").args(["list", "sink-inputs"]).output() else {
+        return vec![];
+    };
+    let listing = String::from_utf8_lossy(&out.stdout).to_lowercase();
+
+    let mut found: Vec<String> = Vec::new();
+    for line in listing.lines().map(str::trim) {
+        let relevant = ["application.name", "application.process.binary", "media.name"]
+            .iter()
+            .any(|key| line.starts_with(key));
+        if !relevant {
+            continue;
+        }
+        for (needle, label) in CONFERENCING_APPS {
+            if line.contains(needle) && !found.iter().any(|f| f == label) {
+                found.push(label.to_string());
+            }
+        }
+    }
+    found
+}
+
+// ── Estado en la interfaz ──────────────────────────────────────────────────
+
+#[derive(Default)]
+pub struct ConferencingWatcher {
+    rx: Option<Receiver<Vec<String>>>,
+    last_poll: Option<Instant>,
+    /// Aplicaciones sonando en la última comprobación.
+    active: Vec<String>,
+    /// Aplicación recién aparecida para la que se muestra el aviso.
+    pub prompt: Option<String>,
+}
+
+impl ConferencingWatcher {
+    /// Recoge el resultado de la última comprobación y lanza la siguiente.
+    pub fn poll(&mut self, enabled: bool) {
+        if !enabled || !is_supported() {
+            *self = Self::default();
+            return;
+        }
+
+        if let Some(rx) = &self.rx {
+            if let Ok(apps) = rx.try_recv() {
+                // Solo se avisa cuando una aplicación empieza a sonar, no
+                // mientras sigue sonando.
+                if let Some(new) = apps.iter().find(|a| !self.active.contains(a)) {
+                    self.prompt = Some(new.clone());
+                }
+                if self.prompt.as_ref().is_some_and(|p| !apps.contains(p)) {
+                    self.prompt = None;
+                }
+                self.active = apps;
+                self.rx = None;
+            }
+        }
+
+        if self.rx.is_none() && self.last_poll.is_none_or(|t| t.elapsed() >= POLL_INTERVAL) {
+            let (tx, rx) = channel();
+            thread::spawn(move || {
+                let _ = tx.send(detect_conferencing_apps());
+            });
+            self.rx = Some(rx);
+            self.last_poll = Some(Instant::now());
+        }
+    }
+}
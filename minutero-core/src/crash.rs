@@ -0,0 +1,119 @@
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::encryption::{self, EncryptionSettings};
+use crate::export::{minuta_json, MinutaDocument};
+
+// ── Fallos (panics) ────────────────────────────────────────────────────────
+//
+// Un panic en la interfaz o en un hilo de Whisper/audio no debe llevarse la
+// minuta de la reunión. La interfaz deja aquí una copia serializada de la
+// sesión cada vez que cambia; el gancho de panic escribe el informe (mensaje,
+// hilo y backtrace) en `logs/` y la copia en `recovery.json`, que se ofrece
+// restaurar en el siguiente arranque. Con el cifrado activado la copia se
+// cifra con la misma clave que las minutas (y se abre como ellas); si no
+// hay clave no se escribe, antes que dejar la reunión en claro.
+
+const RECOVERY_FILE: &str = "recovery.json";
+const CRASH_DIR: &str = "logs";
+pub const CRASH_PREFIX: &str = "crash_";
+
+/// Última sesión en memoria, ya como JSON de minuta, y el cifrado con que
+/// se escribe en disco.
+static SESSION: Mutex<Option<(String, EncryptionSettings)>> = Mutex::new(None);
+/// Ya hubo un panic: a partir de ahí la copia se mantiene también en disco,
+/// porque la aplicación puede seguir viva si el fallo fue en un hilo de fondo.
+static PANICKED: AtomicBool = AtomicBool::new(false);
+
+/// Instala el gancho de panic. Encadena el anterior, así que el mensaje sigue
+/// saliendo por stderr donde lo haya.
+pub fn install() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        PANICKED.store(true, Ordering::SeqCst);
+        let thread = std::thread::current().name().unwrap_or("<sin nombre>").to_string();
+        let backtrace = Backtrace::force_capture();
+        let report = format!(
+            "Minutero {}\nFecha: {}\nHilo: {}\n{}\n\nBacktrace:\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            Local::now().to_rfc3339(),
+            thread,
+            info,
+            backtrace,
+        );
+        match write_report(&report) {
+            Ok(path) => tracing::error!("Panic en el hilo '{}': {} (informe en {})", thread, info, path.display()),
+            Err(e) => tracing::error!("Panic en el hilo '{}': {} (sin informe: {})", thread, info, e),
+        }
+        // `try_lock`: si el panic ocurrió con el cerrojo tomado, mejor perder
+        // la copia que bloquear el proceso.
+        if let Ok(session) = SESSION.try_lock() {
+            if let Some((json, encryption)) = session.as_ref() {
+                write_recovery(json, encryption);
+            }
+        }
+        previous(info);
+    }));
+}
+
+fn write_report(report: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(CRASH_DIR)?;
+    let stem = format!("{}{}", CRASH_PREFIX, Local::now().format("%Y%m%d_%H%M%S"));
+    let path = Path::new(CRASH_DIR).join(format!("{}.txt", stem));
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Escribe la copia de recuperación, cifrada si lo están las minutas.
+fn write_recovery(json: &str, encryption: &EncryptionSettings) {
+    let data = if encryption.enabled {
+        match encryption::encrypt(json.as_bytes(), encryption) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!("No se guarda la minuta de recuperación sin cifrar: {}", e);
+                return;
+            }
+        }
+    } else {
+        json.as_bytes().to_vec()
+    };
+    if let Err(e) = std::fs::write(RECOVERY_FILE, data) {
+        tracing::error!("No se pudo guardar la minuta de recuperación: {}", e);
+    }
+}
+
+/// Actualiza la copia de la sesión en curso; `None` si no hay nada que
+/// perder.
+pub fn set_session(doc: Option<&MinutaDocument>, encryption: &EncryptionSettings) {
+    let json = doc.and_then(|doc| serde_json::to_string_pretty(&minuta_json(doc, false)).ok());
+    if PANICKED.load(Ordering::SeqCst) {
+        match &json {
+            Some(json) => write_recovery(json, encryption),
+            None => discard_recovery(),
+        }
+    }
+    *SESSION.lock().unwrap() = json.map(|json| (json, encryption.clone()));
+}
+
+/// Minuta sin guardar que dejó un fallo anterior, si la hay. Se abre con
+/// `export::read_minuta_json`, que la descifra si hace falta.
+pub fn pending_recovery() -> Option<PathBuf> {
+    let path = PathBuf::from(RECOVERY_FILE);
+    path.exists().then_some(path)
+}
+
+pub fn discard_recovery() {
+    let _ = std::fs::remove_file(RECOVERY_FILE);
+}
+
+/// Informe del fallo más reciente en `logs/`.
+pub fn last_report() -> Option<PathBuf> {
+    std::fs::read_dir(CRASH_DIR).ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.file_name().is_some_and(|n| n.to_string_lossy().starts_with(CRASH_PREFIX)))
+        .max()
+}
@@ -0,0 +1,715 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use crate::capture_tools::CaptureTool;
+use crate::devices::StreamOptions;
+use crate::discord::DiscordConfig;
+use crate::resources::ResourceLimits;
+use crate::i18n::t;
+use crate::multilingual::MultilingualSettings;
+use crate::speaker_id::VoiceMatcher;
+use crate::storage::UploadSettings;
+pub const WHISPER_SAMPLE_RATE: u32 = 16000;
+/// Duración por defecto de cada ventana, ver `WhisperTuning::chunk_secs`.
+pub const CHUNK_DURATION_SECS: u32 = 5; 
+pub const SILENCE_THRESHOLD: f32 = 0.1; 
+
+// Tipos de fuente de audio
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SourceType {
+    Input,
+    Output,
+    /// Stream de red (HTTP/Icecast/RTP); la URL va en `technical_name`.
+    Network,
+    /// PCM crudo por stdin o una tubería con nombre (ruta en
+    /// `technical_name`, vacía o `-` = stdin).
+    Pipe,
+    /// Canal de voz de Discord (ID en `technical_name`): cada participante
+    /// es un interlocutor, ver `discord`.
+    Discord,
+}
+
+/// Codificación de las muestras de una fuente `Pipe`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PcmEncoding {
+    S16le,
+    F32le,
+}
+
+impl PcmEncoding {
+    pub const ALL: &'static [PcmEncoding] = &[PcmEncoding::S16le, PcmEncoding::F32le];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PcmEncoding::S16le => "s16le",
+            PcmEncoding::F32le => "f32le",
+        }
+    }
+
+    pub fn bytes(self) -> usize {
+        match self {
+            PcmEncoding::S16le => 2,
+            PcmEncoding::F32le => 4,
+        }
+    }
+}
+
+/// Formato del PCM crudo de una fuente `Pipe`, igual que se le pasaría a
+/// ffmpeg (`-f s16le -ar 16000 -ac 1`).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PcmFormat {
+    pub encoding: PcmEncoding,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl Default for PcmFormat {
+    fn default() -> Self {
+        Self { encoding: PcmEncoding::S16le, sample_rate: WHISPER_SAMPLE_RATE, channels: 1 }
+    }
+}
+
+/// Qué hacer cuando falla la captura de una fuente.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestartPolicy {
+    /// Seguir la sesión sin esta fuente.
+    #[default]
+    Continue,
+    /// Volver a abrirla, esperando cada vez el doble (ver
+    /// `InterlocutorProfile::max_restarts`).
+    Retry,
+    /// Parar toda la sesión y guardar lo que haya.
+    FailSession,
+}
+
+impl RestartPolicy {
+    pub const ALL: &'static [RestartPolicy] = &[RestartPolicy::Continue, RestartPolicy::Retry, RestartPolicy::FailSession];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RestartPolicy::Continue => t("restart.continue"),
+            RestartPolicy::Retry => t("restart.retry"),
+            RestartPolicy::FailSession => t("restart.fail_session"),
+        }
+    }
+}
+
+// Estructura para listar dispositivos brutos
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceInfo {
+    pub id: usize,
+    pub name: String,
+    pub technical_name: Option<String>,
+    /// Canales nativos del dispositivo; 0 si no se conocen.
+    pub channels: u16,
+}
+
+// Perfil completo del Interlocutor. Se guarda entre sesiones sin `id` ni
+// `device_id`, que se recalculan al arrancar (ver `devices::reconcile`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InterlocutorProfile {
+    #[serde(skip)]
+    pub id: usize,
+    /// Posición en la lista de dispositivos actual; el que cuenta para
+    /// capturar es `technical_name`.
+    #[serde(skip)]
+    pub device_id: usize,
+    pub source_type: SourceType,
+    pub name: String,
+    pub is_active: bool,
+    pub technical_name: Option<String>,
+    /// Canal del dispositivo que se transcribe como este interlocutor
+    /// (0 = primero). `None` mezcla todos los canales a mono.
+    pub channel: Option<u16>,
+    /// Control automático de ganancia continuo en vez de normalizar cada
+    /// fragmento.
+    pub agc: bool,
+    /// Formato de las muestras si la fuente es una tubería.
+    pub pcm: PcmFormat,
+    /// Varias personas por esta fuente: cada intervención lleva el nombre
+    /// del participante cuya voz reconoce, ver `speaker_id`.
+    pub identify_voices: bool,
+    /// Si la captura falla: seguir sin ella, reintentar o parar la sesión.
+    pub on_error: RestartPolicy,
+    /// Reintentos seguidos con `RestartPolicy::Retry` antes de rendirse.
+    pub max_restarts: u32,
+}
+
+impl Default for InterlocutorProfile {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            device_id: 0,
+            source_type: SourceType::Input,
+            name: String::new(),
+            is_active: true,
+            technical_name: None,
+            channel: None,
+            agc: true,
+            pcm: PcmFormat::default(),
+            identify_voices: false,
+            on_error: RestartPolicy::default(),
+            max_restarts: 3,
+        }
+    }
+}
+
+// Configuración de idioma global para la sesión
+#[derive(Clone, Debug, PartialEq)]
+pub struct LanguageConfig {
+    /// None = autodetección. Some("en"), Some("es"), etc.
+    pub source_lang: Option<&'static str>,
+    /// true = traducir a inglés (único destino que soporta Whisper nativamente)
+    pub translate_to_english: bool,
+}
+
+impl Default for LanguageConfig {
+    fn default() -> Self {
+        Self {
+            source_lang: Some("en"),
+            translate_to_english: false,
+        }
+    }
+}
+
+impl LanguageConfig {
+    pub fn source_label(&self) -> &'static str {
+        match self.source_lang {
+            None => t("lang.auto"),
+            Some("en") => "English",
+            Some("es") => "Español",
+            Some("fr") => "Français",
+            Some("de") => "Deutsch",
+            Some("it") => "Italiano",
+            Some("pt") => "Português",
+            Some("zh") => "中文",
+            Some("ja") => "日本語",
+            Some(other) => other,
+        }
+    }
+
+    pub fn dest_label(&self) -> &'static str {
+        if self.translate_to_english {
+            t("lang.to_english")
+        } else {
+            t("lang.original")
+        }
+    }
+}
+
+pub const SOURCE_LANGUAGES: &[(&str, Option<&'static str>)] = &[
+    ("Auto (detectar)", None),
+    ("English",         Some("en")),
+    ("Español",         Some("es")),
+    ("Français",        Some("fr")),
+    ("Deutsch",         Some("de")),
+    ("Italiano",        Some("it")),
+    ("Português",       Some("pt")),
+    ("中文",            Some("zh")),
+    ("日本語",          Some("ja")),
+];
+
+// Preset de vocabulario: glosario propio de un equipo o tipo de reunión
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preset {
+    pub name: String,
+    /// Nombres de producto, siglas… Un término por línea o separados por comas.
+    pub glossary: String,
+    /// Corrige a posteriori la grafía de los términos del glosario.
+    pub glossary_corrections: bool,
+    /// Grafía fija ("IVA", "PostgreSQL", "José María"): se impone sin mirar
+    /// mayúsculas ni tildes. A diferencia del glosario, no se pasa a Whisper.
+    pub spellings: Vec<String>,
+    /// Sustituciones aplicadas a cada segmento, en orden.
+    pub replacements: Vec<ReplacementRule>,
+    /// Recomponer puntuación y mayúsculas entre fragmentos, ver `punctuation`.
+    pub restore_punctuation: bool,
+    /// Dónde se sube la minuta de las sesiones con esta plantilla, ver `storage`.
+    pub upload: UploadSettings,
+}
+
+// Regla de sustitución por expresión regular ("kubernetis" → "Kubernetes")
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplacementRule {
+    pub enabled: bool,
+    pub pattern: String,
+    /// Admite grupos de captura: `$1`, `${nombre}`.
+    pub replacement: String,
+    pub case_insensitive: bool,
+}
+
+impl Default for ReplacementRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pattern: String::new(),
+            replacement: String::new(),
+            case_insensitive: true,
+        }
+    }
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Self {
+            name: String::from("General"),
+            glossary: String::new(),
+            glossary_corrections: true,
+            spellings: Vec::new(),
+            replacements: Vec::new(),
+            restore_punctuation: false,
+            upload: UploadSettings::default(),
+        }
+    }
+}
+
+impl Preset {
+    pub fn glossary_terms(&self) -> Vec<&str> {
+        self.glossary
+            .split(['\n', ','])
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    /// Texto que se pasa a Whisper como initial prompt. Whisper lo trata como
+    /// "lo que se dijo antes", así que basta con listar los términos.
+    pub fn initial_prompt(&self) -> Option<String> {
+        let terms = self.glossary_terms();
+        if terms.is_empty() {
+            return None;
+        }
+        // CString no admite bytes nulos
+        Some(terms.join(", ").replace('\0', ""))
+    }
+}
+
+// Ajustes avanzados de decodificación: precisión frente a velocidad
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WhisperTuning {
+    /// 0 = automático (lo que decida whisper.cpp según los núcleos).
+    pub n_threads: u32,
+    /// 1 = decodificación greedy; >1 = beam search con ese ancho.
+    pub beam_size: u32,
+    /// Candidatos evaluados en greedy (solo si beam_size == 1).
+    pub best_of: u32,
+    pub temperature: f32,
+    pub no_speech_thold: f32,
+    /// Descartar los fragmentos con frases de `hallucinations`.
+    pub filter_hallucinations: bool,
+    /// Frases que Whisper inventa sobre el silencio, una por línea.
+    pub hallucinations: String,
+    /// Cambiar a un modelo más pequeño si un stream no llega a tiempo real.
+    pub auto_downgrade: bool,
+    /// Duración de cada ventana que se transcribe, en segundos. Más larga =
+    /// más contexto para Whisper y más retraso.
+    pub chunk_secs: f32,
+    /// Parte de cada ventana que se repite al principio de la siguiente, para
+    /// no cortar palabras, en %.
+    pub overlap_percent: u32,
+    /// Retraso máximo de una fuente antes de saltarse audio para alcanzar el
+    /// tiempo real, en segundos; 0 = sin límite.
+    pub max_latency_secs: f32,
+    /// Segundos sin voz tras los que una fuente deja de pasar por Whisper
+    /// hasta que vuelva a oírse algo (ahorro de energía); 0 = desactivado.
+    pub energy_saver_secs: u32,
+    /// Trabajadores Whisper compartidos por todas las fuentes, ver `pool`;
+    /// 0 = cada fuente con su propio estado.
+    pub workers: u32,
+    /// Con trabajadores compartidos, los micrófonos pasan antes.
+    pub mic_priority: bool,
+}
+
+impl Default for WhisperTuning {
+    fn default() -> Self {
+        Self {
+            n_threads: 0,
+            beam_size: 1,
+            best_of: 1,
+            temperature: 0.0,
+            no_speech_thold: 0.6,
+            filter_hallucinations: true,
+            hallucinations: crate::hallucinations::default_list(),
+            auto_downgrade: false,
+            chunk_secs: CHUNK_DURATION_SECS as f32,
+            overlap_percent: 30,
+            max_latency_secs: 30.0,
+            energy_saver_secs: 30,
+            workers: 0,
+            mic_priority: true,
+        }
+    }
+}
+
+/// De dónde sale el modelo ggml de Whisper.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelSource {
+    /// Archivo ggml propio; vacío = `models/ggml-<modelo>.bin`, descargado
+    /// si falta.
+    pub custom_path: String,
+    /// No intentar ninguna descarga (equipos sin acceso a internet).
+    pub offline: bool,
+    /// Carpeta desde la que se descargan los `ggml-*.bin`; vacío = HuggingFace.
+    pub mirror_url: String,
+    /// Proxy HTTP(S) para la descarga; vacío = `HTTPS_PROXY`/`HTTP_PROXY` del
+    /// entorno, si los hay.
+    pub proxy: String,
+}
+
+pub const MIN_CHUNK_SECS: f32 = 2.0;
+pub const MAX_CHUNK_SECS: f32 = 30.0;
+pub const MAX_OVERLAP_PERCENT: u32 = 50;
+pub const MAX_LATENCY_SECS: f32 = 120.0;
+pub const MAX_ENERGY_SAVER_SECS: u32 = 600;
+
+impl WhisperTuning {
+    /// (ventana, solape) en muestras a `sample_rate`, dentro de los límites
+    /// aunque `settings.json` traiga otra cosa.
+    pub fn chunk_samples(&self, sample_rate: u32) -> (usize, usize) {
+        let target = (sample_rate as f32 * self.chunk_secs.clamp(MIN_CHUNK_SECS, MAX_CHUNK_SECS)) as usize;
+        (target, target * self.overlap_percent.min(MAX_OVERLAP_PERCENT) as usize / 100)
+    }
+}
+
+// Lo que los hilos de transcripción necesitan de la configuración
+#[derive(Clone, Debug)]
+pub struct TranscriptionConfig {
+    pub lang: LanguageConfig,
+    pub preset: Preset,
+    pub tuning: WhisperTuning,
+    pub style: TranscriptionStyle,
+    /// Host de cpal con el que se captura; `None` = automático.
+    pub audio_host: Option<String>,
+    pub model_source: ModelSource,
+    /// Silenciar / solo en vivo, compartido con la interfaz.
+    pub mute: MuteControl,
+    /// Detección de idioma por fragmento.
+    pub multilingual: MultilingualSettings,
+    /// Voces registradas de los participantes habituales.
+    pub voices: VoiceMatcher,
+    /// Opciones de apertura por nombre técnico (ver `devices`).
+    pub streams: BTreeMap<String, StreamOptions>,
+    /// Programa de captura en Linux; `None` = automático.
+    pub capture_tool: Option<CaptureTool>,
+    /// Segundo modelo con el que se transcribe también cada fragmento, para
+    /// compararlo con el principal (ver `AudioMessage::Comparison`).
+    pub compare_model: Option<String>,
+    /// Bot con el que se entra en los canales de voz de Discord.
+    pub discord: DiscordConfig,
+    /// Memoria y modelos como mucho, ver `resources`.
+    pub limits: ResourceLimits,
+}
+
+/// Silenciar y "solo" por interlocutor durante la captura. Un stream
+/// silenciado sigue abierto (nivel, grabación) pero no se transcribe; con
+/// algún "solo" activo solo se transcriben esos. Los clones comparten estado.
+#[derive(Clone, Debug, Default)]
+pub struct MuteControl(Arc<Mutex<MuteState>>);
+
+#[derive(Debug, Default)]
+struct MuteState {
+    muted: HashSet<String>,
+    solo: HashSet<String>,
+}
+
+impl MuteControl {
+    pub fn is_muted(&self, profile: &str) -> bool {
+        self.0.lock().unwrap().muted.contains(profile)
+    }
+
+    pub fn is_solo(&self, profile: &str) -> bool {
+        self.0.lock().unwrap().solo.contains(profile)
+    }
+
+    pub fn set_muted(&self, profile: &str, muted: bool) {
+        let mut state = self.0.lock().unwrap();
+        if muted { state.muted.insert(profile.to_string()); } else { state.muted.remove(profile); }
+    }
+
+    pub fn set_solo(&self, profile: &str, solo: bool) {
+        let mut state = self.0.lock().unwrap();
+        if solo { state.solo.insert(profile.to_string()); } else { state.solo.remove(profile); }
+    }
+
+    /// Si el stream de `profile` se transcribe ahora mismo.
+    pub fn is_audible(&self, profile: &str) -> bool {
+        let state = self.0.lock().unwrap();
+        !state.muted.contains(profile) && (state.solo.is_empty() || state.solo.contains(profile))
+    }
+}
+
+/// Literal (actas judiciales: muletillas, repeticiones, frases cortadas) o
+/// limpio para leer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TranscriptionStyle {
+    Verbatim,
+    #[default]
+    Clean,
+}
+
+impl TranscriptionStyle {
+    pub const ALL: &'static [TranscriptionStyle] = &[TranscriptionStyle::Clean, TranscriptionStyle::Verbatim];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            TranscriptionStyle::Verbatim => "session.style_verbatim",
+            TranscriptionStyle::Clean    => "session.style_clean",
+        }
+    }
+
+    /// Comienzo del initial prompt. Whisper imita el estilo de lo que "se dijo
+    /// antes": con muletillas en el prompt deja de omitirlas.
+    pub fn prompt(self, lang: Option<&str>) -> Option<&'static str> {
+        match (self, lang) {
+            (TranscriptionStyle::Clean, _) => None,
+            (TranscriptionStyle::Verbatim, Some("en")) => Some("Umm, so, uh, I- I think, like, hmm... okay."),
+            (TranscriptionStyle::Verbatim, _) => Some("Eh, bueno, o sea, em... yo- yo creo que, mm, a ver."),
+        }
+    }
+}
+
+// Segmento finalizado de la minuta en vivo, con sus tiempos
+#[derive(Clone, Debug)]
+pub struct TranscriptSegment {
+    pub name: String,
+    pub text: String,
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub words: Vec<WordTiming>,
+    /// Alguien la dio por buena en la cola de revisión.
+    pub reviewed: bool,
+    /// Idioma detectado (código ISO), en el modo multilingüe.
+    pub lang: Option<String>,
+    /// Texto tal cual salió de Whisper, si se ha corregido a mano.
+    pub original: Option<String>,
+    /// Correcciones a mano, de la más antigua a la más reciente.
+    pub edits: Vec<SegmentEdit>,
+}
+
+/// Cómo quedó una intervención tras corregirla a mano, y cuándo.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SegmentEdit {
+    /// Fecha y hora local, `dd-mm-aaaa hh:mm:ss`.
+    pub at: String,
+    pub text: String,
+}
+
+impl TranscriptSegment {
+    pub fn confidence(&self) -> f32 {
+        mean_probability(&self.words)
+    }
+
+    /// Marca de pausa al continuar una minuta: una línea sin interlocutor.
+    pub fn pause(at_secs: f64, text: String) -> Self {
+        Self {
+            name: String::new(), text, start_secs: at_secs, end_secs: at_secs,
+            words: Vec::new(), reviewed: true, lang: None, original: None, edits: Vec::new(),
+        }
+    }
+
+    /// Línea sin interlocutor (marca de pausa o texto de un archivo).
+    pub fn is_pause(&self) -> bool {
+        self.name.is_empty()
+    }
+
+    /// Línea tal cual se ve en la minuta: `(Nombre) texto`.
+    pub fn line(&self) -> String {
+        self.line_with(&self.text)
+    }
+
+    /// Como `line`, pero con el texto de Whisper antes de corregirlo.
+    pub fn transcribed_line(&self) -> String {
+        self.line_with(self.transcribed_text())
+    }
+
+    fn line_with(&self, text: &str) -> String {
+        if self.is_pause() { text.to_string() } else { format!("({}) {}", self.name, text) }
+    }
+
+    /// Texto antes de cualquier corrección a mano.
+    pub fn transcribed_text(&self) -> &str {
+        self.original.as_deref().unwrap_or(&self.text)
+    }
+
+    /// Se está corrigiendo a mano y `before` es el texto anterior: la primera
+    /// vez se guarda como el original de Whisper.
+    pub fn begin_edit(&mut self, before: &str) {
+        if self.original.is_none() {
+            self.original = Some(before.to_string());
+        }
+    }
+
+    /// Fin de una corrección: anota en el historial cómo quedó el texto, si
+    /// cambió desde la última.
+    pub fn finish_edit(&mut self) {
+        let last = self.edits.last().map_or(self.transcribed_text(), |e| e.text.as_str());
+        if last != self.text {
+            let at = chrono::Local::now().format("%d-%m-%Y %H:%M:%S").to_string();
+            self.edits.push(SegmentEdit { at, text: self.text.clone() });
+        }
+    }
+
+    /// Desplaza los tiempos `secs` segundos (al continuar una sesión).
+    pub fn shift(&mut self, secs: f64) {
+        self.start_secs += secs;
+        self.end_secs += secs;
+        for word in &mut self.words {
+            word.start_secs += secs;
+            word.end_secs += secs;
+        }
+    }
+}
+
+/// Por debajo de esta probabilidad una palabra se marca como dudosa.
+pub const LOW_CONFIDENCE: f32 = 0.5;
+
+/// Umbral por defecto de la cola de revisión (probabilidad media del segmento).
+pub const DEFAULT_REVIEW_THRESHOLD: f32 = 0.7;
+
+/// Probabilidad media de las palabras; 1.0 si no hay (nada que dudar).
+pub fn mean_probability(words: &[WordTiming]) -> f32 {
+    if words.is_empty() {
+        1.0
+    } else {
+        words.iter().map(|w| w.probability).sum::<f32>() / words.len() as f32
+    }
+}
+
+/// Palabra tal como la reconoció Whisper (antes del post-procesado), con sus
+/// tiempos en segundos de sesión y la probabilidad de su token menos seguro.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub word: String,
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub probability: f32,
+}
+
+impl WordTiming {
+    pub fn is_low_confidence(&self) -> bool {
+        self.probability < LOW_CONFIDENCE
+    }
+}
+
+/// Por qué ha fallado una fuente, para reaccionar sin interpretar el texto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamErrorKind {
+    /// No se encontró o no se pudo abrir el dispositivo, la URL o la tubería.
+    Device,
+    /// La captura se cortó a mitad (el proceso o el dispositivo dejó de
+    /// entregar audio).
+    Capture,
+    /// No se pudo cargar o usar el modelo Whisper.
+    Model,
+    Other,
+}
+
+// Mensajes de comunicación entre el hilo de audio y la UI. Los textos
+// (`Status`, `message`) ya van traducidos; el resto son datos.
+pub enum AudioMessage {
+    /// Progreso sin más estructura, solo para mostrar.
+    Status(String),
+    /// Descarga del modelo en curso, en bytes; `total` es 0 si el servidor
+    /// no lo indica.
+    ModelDownloadProgress { model: String, downloaded: u64, total: u64 },
+    /// Se acaba de descargar el modelo indicado.
+    ModelDownloaded(String),
+    /// Una fuente ha empezado a capturar. `format` describe lo que se abrió
+    /// (host, frecuencia, canales).
+    StreamStarted { profile: String, source_type: SourceType, device: String, format: String },
+    /// Nivel de entrada de una fuente (0.0–1.0), unas pocas veces por segundo.
+    Level { profile: String, rms: f32, peak: f32 },
+    /// Intervención transcrita. Tiempos en segundos de sesión; `confidence`
+    /// es la probabilidad media de sus palabras (1.0 si no hay palabras).
+    Segment {
+        speaker: String,
+        start_secs: f64,
+        end_secs: f64,
+        text: String,
+        confidence: f32,
+        words: Vec<WordTiming>,
+        /// Idioma detectado, solo en el modo multilingüe.
+        lang: Option<String>,
+    },
+    /// Rendimiento de una fuente tras cada fragmento: `rtf` es tiempo de
+    /// proceso / audio nuevo, `backlog_secs` el audio que espera turno y
+    /// `latency_secs` lo que pasó desde que se captó el final del fragmento
+    /// hasta enviar su texto (`None` si era pre-grabación).
+    StreamHealth { profile: String, rtf: f32, backlog_secs: f32, latency_secs: Option<f32> },
+    /// Un fragmento transcrito con el modelo principal y con el de
+    /// comparación, con el RTF de cada uno. Un texto vacío es que ese modelo
+    /// no sacó nada.
+    Comparison {
+        profile: String,
+        start_secs: f64,
+        model: String,
+        text: String,
+        rtf: f32,
+        compared_model: String,
+        compared_text: String,
+        compared_rtf: f32,
+    },
+    /// Una fuente (o grupo de fuentes del mismo dispositivo) ha fallado.
+    StreamError { profile: String, kind: StreamErrorKind, message: String },
+    /// La fuente que falló se vuelve a abrir en `delay_secs`; es el reintento
+    /// `attempt` de `max`.
+    StreamRestarting { profile: String, attempt: u32, max: u32, delay_secs: f32 },
+    /// La fuente volvió a fallar tras `max` reintentos y ya no se reabre.
+    StreamGaveUp { profile: String, max: u32 },
+    /// Error que no es de una fuente concreta.
+    Error(String),
+    /// La captura terminó; `pending` son los streams que no se cerraron a tiempo.
+    Stopped { pending: Vec<String> },
+}
+
+// Mensajes del hilo de transcripción de vídeo
+pub enum VideoMessage {
+    Status(String),
+    Progress(f32),                         // 0.0 – 1.0
+    Segment { timestamp: String, text: String },
+    ModelDownloaded(String),
+    Done,
+    Error(String),
+}
+
+// Mensajes del hilo de re-transcripción de una sesión guardada
+pub enum RetranscribeMessage {
+    Status(String),
+    Progress(f32),                         // 0.0 – 1.0
+    ModelDownloaded(String),
+    Done { model: String, segments: Vec<TranscriptSegment> },
+    Error(String),
+}
+
+/// Tipo de sesión; cambia cómo se exporta la minuta.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionType {
+    #[default]
+    Meeting,
+    /// Pares pregunta/respuesta, ver `interview`.
+    Interview,
+    /// Un solo ponente, con capítulos e índice, ver `lecture`.
+    Lecture,
+}
+
+impl SessionType {
+    pub const ALL: &'static [SessionType] = &[SessionType::Meeting, SessionType::Interview, SessionType::Lecture];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            SessionType::Meeting   => "session.type_meeting",
+            SessionType::Interview => "session.type_interview",
+            SessionType::Lecture   => "session.type_lecture",
+        }
+    }
+}
+
+// Alias para el canal de comunicación de la UI
+pub type UiSender = Sender<AudioMessage>;
\ No newline at end of file
@@ -0,0 +1,208 @@
+use anyhow::{Result, anyhow};
+use hound::WavWriter;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::data::WHISPER_SAMPLE_RATE;
+use crate::export::{sanitize_file_stem, MinutaDocument, MinutaEntry};
+use crate::recording::{has_tracks, read_tracks, read_wav, to_i16, wav_spec};
+use crate::redaction::RedactionSettings;
+
+// ── Corpus para afinar el reconocimiento ───────────────────────────────────
+//
+// Opcional: al guardar una minuta, cada intervención (por defecto solo las
+// corregidas a mano o revisadas) se recorta de la pista de su interlocutor y
+// se añade, con su texto, a una carpeta con el formato de LJSpeech
+// (`wavs/` + `metadata.csv`) o de Common Voice (`clips/` + `train.tsv`).
+// Reunión a reunión sale un corpus propio con el que afinar Whisper. Hace
+// falta guardar las pistas por interlocutor: en la mezcla se oiría a los
+// demás por encima. Las pistas se cierran al terminar la captura, un poco
+// después de guardar, así que se esperan en segundo plano.
+
+/// Espera máxima a que la grabación escriba sus pistas.
+const TRACKS_WAIT: Duration = Duration::from_secs(120);
+/// Margen antes y después de cada intervención, para no cortar palabras.
+const MARGIN_SECS: f64 = 0.2;
+
+/// Las salas guardan en su propio hilo: cada sesión añade sus líneas entera.
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DatasetFormat {
+    LjSpeech,
+    CommonVoice,
+}
+
+impl DatasetFormat {
+    pub const ALL: &'static [DatasetFormat] = &[DatasetFormat::LjSpeech, DatasetFormat::CommonVoice];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DatasetFormat::LjSpeech => "LJSpeech",
+            DatasetFormat::CommonVoice => "Common Voice",
+        }
+    }
+
+    fn clips_dir(self) -> &'static str {
+        match self {
+            DatasetFormat::LjSpeech => "wavs",
+            DatasetFormat::CommonVoice => "clips",
+        }
+    }
+
+    fn metadata_file(self) -> &'static str {
+        match self {
+            DatasetFormat::LjSpeech => "metadata.csv",
+            DatasetFormat::CommonVoice => "train.tsv",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatasetSettings {
+    pub enabled: bool,
+    pub format: DatasetFormat,
+    /// Carpeta del corpus; relativa, junto a `settings.json`.
+    pub dir: String,
+    /// Solo intervenciones corregidas o revisadas: el texto de las demás es
+    /// lo que Whisper ya sabía decir.
+    pub only_corrected: bool,
+    pub min_secs: f32,
+    pub max_secs: f32,
+}
+
+impl Default for DatasetSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: DatasetFormat::LjSpeech,
+            dir: "dataset".into(),
+            only_corrected: true,
+            min_secs: 1.0,
+            // Whisper no ve más de 30 s de una vez
+            max_secs: 25.0,
+        }
+    }
+}
+
+/// Tras guardar la minuta `path`: espera las pistas de su grabación y añade
+/// sus intervenciones al corpus, sin bloquear.
+pub fn export_in_background(settings: &DatasetSettings, redaction: &RedactionSettings, doc: &MinutaDocument, path: &Path) {
+    if !settings.enabled {
+        return;
+    }
+    let (Some(dir), Some(recording)) = (path.parent(), doc.recording.as_deref()) else {
+        tracing::info!("Corpus: '{}' no tiene grabación, no se añade", doc.title);
+        return;
+    };
+    let recording = dir.join(recording);
+    let settings = settings.clone();
+    // Con la anonimización, el texto con la marca ya no es lo que se oye
+    let mask = redaction.enabled.then(|| redaction.mask.clone());
+    let doc = doc.clone();
+    let session = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    std::thread::spawn(move || {
+        let waiting = Instant::now();
+        while !has_tracks(&recording) {
+            if waiting.elapsed() > TRACKS_WAIT {
+                tracing::warn!("Corpus: la grabación de '{}' no tiene pistas por interlocutor", doc.title);
+                return;
+            }
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        match export(&settings, mask.as_deref(), &doc, &recording, &session) {
+            Ok(n) => tracing::info!("Corpus: {} intervenciones de '{}'", n, doc.title),
+            Err(e) => tracing::error!("Corpus: error al añadir '{}': {:?}", doc.title, e),
+        }
+    });
+}
+
+/// Recorta las intervenciones de `doc` de las pistas de `recording` y las
+/// añade al corpus. Devuelve cuántas.
+pub fn export(settings: &DatasetSettings, mask: Option<&str>, doc: &MinutaDocument, recording: &Path, session: &str) -> Result<usize> {
+    let tracks = read_tracks(recording)?;
+    let root = PathBuf::from(&settings.dir);
+    let clips_dir = root.join(settings.format.clips_dir());
+    std::fs::create_dir_all(&clips_dir)?;
+
+    let session = sanitize_file_stem(session);
+    let mut lines = Vec::new();
+    for (track, track_path) in &tracks {
+        let entries: Vec<&MinutaEntry> = doc.entries.iter()
+            .filter(|e| e.speaker.as_deref() == Some(track.speaker.as_str()))
+            .filter(|e| !settings.only_corrected || e.original.is_some() || e.reviewed)
+            .filter(|e| !mask.is_some_and(|m| e.text.contains(m)))
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        let audio = read_wav(track_path)?;
+        for entry in entries {
+            let (Some(start), Some(end)) = (entry.start_secs, entry.end_secs) else { continue };
+            let duration = (end - start) as f32;
+            let text = clean_text(&entry.text);
+            if duration < settings.min_secs || duration > settings.max_secs || text.is_empty() {
+                continue;
+            }
+            let rate = WHISPER_SAMPLE_RATE as f64;
+            let from = (((start - track.offset_secs - MARGIN_SECS).max(0.0) * rate) as usize).min(audio.len());
+            let to = (((end - track.offset_secs + MARGIN_SECS).max(0.0) * rate) as usize).clamp(from, audio.len());
+            if from == to {
+                continue;
+            }
+
+            let id = format!("{}_{:04}", session, lines.len() + 1);
+            let file = format!("{}.wav", id);
+            let mut writer = WavWriter::create(clips_dir.join(&file), wav_spec())?;
+            for &sample in &audio[from..to] {
+                writer.write_sample(to_i16(sample))?;
+            }
+            writer.finalize()?;
+            lines.push(match settings.format {
+                DatasetFormat::LjSpeech => format!("{}|{}|{}", id, text, text),
+                DatasetFormat::CommonVoice => format!(
+                    "{}\t{}\t{}\t0\t0\t\t\t\t{}\t",
+                    speaker_id(&track.speaker), file, text, entry.lang.as_deref().unwrap_or_default(),
+                ),
+            });
+        }
+    }
+    if lines.is_empty() {
+        return Ok(0);
+    }
+    append_metadata(&root, settings.format, &lines)?;
+    Ok(lines.len())
+}
+
+fn append_metadata(root: &Path, format: DatasetFormat, lines: &[String]) -> Result<()> {
+    let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = root.join(format.metadata_file());
+    let new = !path.exists();
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)
+        .map_err(|e| anyhow!("No se pudo abrir {}: {}", path.display(), e))?;
+    // LJSpeech no lleva cabecera
+    if new && format == DatasetFormat::CommonVoice {
+        writeln!(file, "client_id\tpath\tsentence\tup_votes\tdown_votes\tage\tgender\taccents\tlocale\tsegment")?;
+    }
+    for line in lines {
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Texto en una línea, sin los separadores de ninguno de los dos formatos.
+fn clean_text(text: &str) -> String {
+    text.split(|c: char| c.is_whitespace() || c == '|')
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Common Voice identifica a quien habla con un hash, no con su nombre.
+fn speaker_id(name: &str) -> String {
+    Sha256::digest(name.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
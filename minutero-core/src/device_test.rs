@@ -0,0 +1,71 @@
+use anyhow::{anyhow, Result};
+use std::time::{Duration, Instant};
+use crate::data::{InterlocutorProfile, TranscriptionConfig};
+use crate::i18n::tr;
+use crate::preroll::PreRoll;
+
+// ── Probar un dispositivo ──────────────────────────────────────────────────
+//
+// Antes de la reunión: se graban unos segundos del dispositivo de un perfil
+// y se reproducen por la salida predeterminada, para confirmar que es el
+// micrófono (o el monitor) correcto. Usa la misma captura en espera que la
+// pre-grabación, así que el audio pasa por el mismo camino que en la sesión.
+
+/// Segundos que se graban.
+pub const TEST_SECS: u32 = 4;
+
+/// Pico por debajo del cual se avisa de que apenas llega señal.
+const QUIET_PEAK: f32 = 0.01;
+
+pub struct DeviceTest {
+    pub profile: String,
+    id: usize,
+    started: Instant,
+    secs: u32,
+    capture: PreRoll,
+}
+
+impl DeviceTest {
+    pub fn start(profile: &InterlocutorProfile, config: TranscriptionConfig) -> Self {
+        tracing::info!("Probando el dispositivo de '{}'", profile.name);
+        Self::record(profile, config, TEST_SECS)
+    }
+
+    /// Graba `secs` segundos del dispositivo del perfil.
+    pub fn record(profile: &InterlocutorProfile, config: TranscriptionConfig, secs: u32) -> Self {
+        Self {
+            profile: profile.name.clone(),
+            id: profile.id,
+            started: Instant::now(),
+            secs,
+            capture: PreRoll::start(vec![profile.clone()], config, secs),
+        }
+    }
+
+    /// Parte grabada, de 0 a 1.
+    pub fn progress(&self) -> f32 {
+        (self.started.elapsed().as_secs_f32() / self.secs as f32).min(1.0)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.started.elapsed() >= Duration::from_secs(self.secs as u64)
+    }
+
+    /// Cierra el dispositivo y entrega lo grabado (16 kHz mono). Falla si el
+    /// dispositivo no se pudo abrir o no llegó nada.
+    pub fn finish(self) -> Result<Vec<f32>> {
+        if let Some(e) = self.capture.errors().into_iter().next() {
+            return Err(anyhow!(e));
+        }
+        let samples = self.capture.finish().samples(self.id).to_vec();
+        if samples.is_empty() {
+            return Err(anyhow!(tr!("device_test.no_audio")));
+        }
+        Ok(samples)
+    }
+}
+
+/// Lo grabado es prácticamente silencio.
+pub fn is_quiet(samples: &[f32]) -> bool {
+    samples.iter().map(|s| s.abs()).fold(0.0f32, f32::max) < QUIET_PEAK
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::data::{DeviceInfo, InterlocutorProfile, SourceType};
+
+// ── Identificación estable de dispositivos ─────────────────────────────────
+//
+// `DeviceInfo::id` es la posición en la enumeración y cambia en cuanto se
+// conecta, desconecta o reordena algo. Los perfiles se guardan y se vuelven
+// a enlazar por `technical_name` (nombre de PulseAudio/PipeWire, de cpal o
+// del endpoint en Windows), que no depende del orden. Un perfil cuyo
+// dispositivo no está queda "desaparecido": conserva su nombre técnico, no
+// se cambia a otro micrófono por su cuenta y no deja empezar la captura.
+//
+// Cada dispositivo puede tener además un alias propio ("Micro de la sala"),
+// que es lo que se muestra en las listas, y opciones de apertura (tamaño de
+// búfer, modo exclusivo de WASAPI) para interfaces que dan cortes con las
+// predeterminadas.
+
+/// Tamaños de búfer que se ofrecen, en tramas; 0 = el del sistema.
+pub const BUFFER_FRAMES: &[u32] = &[0, 128, 256, 512, 1024, 2048, 4096];
+
+/// Cómo se abre un dispositivo que se captura directamente (cpal: WASAPI,
+/// CoreAudio, ALSA). No afecta a parecord ni a las fuentes de red.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamOptions {
+    /// Tramas por bloque; 0 = el tamaño del sistema.
+    pub buffer_frames: u32,
+    /// Modo exclusivo de WASAPI: sin el mezclador de Windows de por medio.
+    /// Solo en Windows; la aplicación no puede compartir el dispositivo.
+    pub exclusive: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeviceSettings {
+    /// Nombre técnico → alias.
+    pub aliases: BTreeMap<String, String>,
+    /// Interlocutores de la última sesión.
+    pub profiles: Vec<InterlocutorProfile>,
+    /// Nombre técnico → opciones de apertura, solo las distintas de las
+    /// predeterminadas.
+    pub streams: BTreeMap<String, StreamOptions>,
+}
+
+impl DeviceSettings {
+    /// Alias del dispositivo o, si no tiene, su nombre.
+    pub fn display_name(&self, device: &DeviceInfo) -> String {
+        device.technical_name.as_ref()
+            .and_then(|t| self.aliases.get(t))
+            .filter(|alias| !alias.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| device.name.clone())
+    }
+}
+
+/// Dispositivo actual del perfil, por nombre técnico. `None` para fuentes de
+/// red o tubería, que no son dispositivos.
+pub fn find<'a>(profile: &InterlocutorProfile, inputs: &'a [DeviceInfo], outputs: &'a [DeviceInfo]) -> Option<&'a DeviceInfo> {
+    let devices = match profile.source_type {
+        SourceType::Input => inputs,
+        SourceType::Output => outputs,
+        SourceType::Network | SourceType::Pipe | SourceType::Discord => return None,
+    };
+    devices.iter().find(|d| d.technical_name.is_some() && d.technical_name == profile.technical_name)
+}
+
+/// El perfil usa un dispositivo que ahora no está.
+pub fn is_missing(profile: &InterlocutorProfile, inputs: &[DeviceInfo], outputs: &[DeviceInfo]) -> bool {
+    matches!(profile.source_type, SourceType::Input | SourceType::Output)
+        && find(profile, inputs, outputs).is_none()
+}
+
+/// Enlaza perfiles guardados con los dispositivos actuales: recalcula `id` y
+/// `device_id`. Devuelve los nombres de los perfiles sin dispositivo.
+pub fn reconcile(profiles: &mut [InterlocutorProfile], inputs: &[DeviceInfo], outputs: &[DeviceInfo]) -> Vec<String> {
+    let mut missing = Vec::new();
+    for (i, profile) in profiles.iter_mut().enumerate() {
+        profile.id = i;
+        match find(profile, inputs, outputs) {
+            Some(device) => profile.device_id = device.id,
+            None if is_missing(profile, inputs, outputs) => {
+                tracing::warn!(
+                    "Dispositivo de '{}' no encontrado: {:?}",
+                    profile.name, profile.technical_name,
+                );
+                missing.push(profile.name.clone());
+            }
+            None => {}
+        }
+    }
+    missing
+}
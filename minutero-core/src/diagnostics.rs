@@ -0,0 +1,148 @@
+use anyhow::Result;
+use chrono::Local;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+use crate::audio::{get_available_devices, select_host};
+use crate::export::{check_output_dir, unique_path};
+use crate::{crash, logging};
+use crate::settings::AppSettings;
+use crate::system_audio::{check_loopback_status, detect_os};
+
+// ── Informe de diagnóstico ─────────────────────────────────────────────────
+//
+// Zip para adjuntar a una incidencia: sistema, dispositivos tal como los ven
+// pactl y cpal, modelos descargados, logs recientes y preferencias. Los
+// tokens, contraseñas y URLs privadas se sustituyen antes de escribirlas.
+
+const REDACTED: &str = "<oculto>";
+
+/// Genera el informe en `output_dir` y devuelve su ruta.
+pub fn write_report(settings: &AppSettings, model_name: &str, output_dir: &str) -> Result<PathBuf> {
+    let dir = check_output_dir(output_dir)?;
+    let stem = format!("diagnostico_{}", Local::now().format("%Y%m%d_%H%M%S"));
+    let path = unique_path(&dir, &stem, "zip");
+
+    let mut zip = ZipWriter::new(File::create(&path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("sistema.txt", options)?;
+    zip.write_all(system_info(model_name).as_bytes())?;
+    zip.start_file("dispositivos.txt", options)?;
+    zip.write_all(device_info(settings).as_bytes())?;
+    zip.start_file("settings.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(&redacted(settings))?.as_bytes())?;
+
+    for log in log_files() {
+        if let (Some(name), Ok(data)) = (log.file_name(), std::fs::read(&log)) {
+            zip.start_file(format!("logs/{}", name.to_string_lossy()), options)?;
+            zip.write_all(&data)?;
+        }
+    }
+
+    zip.finish()?;
+    tracing::info!("Informe de diagnóstico generado en {}", path.display());
+    Ok(path)
+}
+
+fn system_info(model_name: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("Minutero {}\n", env!("CARGO_PKG_VERSION")));
+    out.push_str(&format!("Fecha: {}\n", Local::now().to_rfc3339()));
+    out.push_str(&format!("SO: {} ({})\n", detect_os(), std::env::consts::ARCH));
+    if detect_os() == "linux" {
+        out.push_str(&command_section("uname -a", "uname", &["-a"]));
+        out.push_str(&command_section("pactl info", "pactl", &["info"]));
+    }
+    out.push_str(&command_section("ffmpeg -version", "ffmpeg", &["-version"]));
+
+    out.push_str(&format!("\n== Modelos ==\nSeleccionado: {}\n", model_name));
+    match std::fs::read_dir("models") {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                out.push_str(&format!("{}  {} MB\n", entry.file_name().to_string_lossy(), size / 1_000_000));
+            }
+        }
+        Err(e) => out.push_str(&format!("models/: {}\n", e)),
+    }
+    out
+}
+
+fn device_info(settings: &AppSettings) -> String {
+    let mut out = String::new();
+    if detect_os() == "linux" {
+        out.push_str(&command_section("pactl list sources short", "pactl", &["list", "sources", "short"]));
+        out.push_str(&command_section("pactl list sinks short", "pactl", &["list", "sinks", "short"]));
+    }
+
+    let host_name = settings.audio_host.as_deref();
+    let host = select_host(host_name);
+    for (title, is_input) in [("Entradas", true), ("Salidas", false)] {
+        out.push_str(&format!("\n== {} ({}) ==\n", title, host.id().name()));
+        for device in get_available_devices(host_name, is_input) {
+            out.push_str(&format!("{}  [{}]\n", device.name, device.technical_name.unwrap_or_default()));
+        }
+    }
+
+    out.push_str("\n== Loopback ==\n");
+    match check_loopback_status() {
+        Ok(info) => {
+            out.push_str(&format!("{:?}: {}\n", info.status, info.message));
+            for device in info.loopback_devices {
+                out.push_str(&format!("{}  [{}]\n", device.name, device.technical_name.unwrap_or_default()));
+            }
+        }
+        Err(e) => out.push_str(&format!("{:?}\n", e)),
+    }
+    out
+}
+
+/// Salida de un comando con su título; si falla se anota el error.
+fn command_section(title: &str, program: &str, args: &[&str]) -> String {
+    let body = match Command::new(program).args(args).output() {
+        Ok(o) => format!("{}{}", String::from_utf8_lossy(&o.stdout), String::from_utf8_lossy(&o.stderr)),
+        Err(e) => format!("no disponible: {}\n", e),
+    };
+    format!("\n== {} ==\n{}", title, body)
+}
+
+/// Log actual, copias rotadas e informes de fallos que existan.
+fn log_files() -> Vec<PathBuf> {
+    let current = logging::log_path();
+    let dir = current.parent().map(Path::to_path_buf).unwrap_or_default();
+    let prefix = current.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.file_name().is_some_and(|n| {
+                    let n = n.to_string_lossy();
+                    n.starts_with(&prefix) || n.starts_with(crash::CRASH_PREFIX)
+                }))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn redacted(settings: &AppSettings) -> AppSettings {
+    let mut s = settings.clone();
+    let hide = |value: &mut String| {
+        if !value.trim().is_empty() {
+            *value = REDACTED.into();
+        }
+    };
+    let integrations = &mut s.integrations;
+    hide(&mut integrations.slack.webhook_url);
+    hide(&mut integrations.slack.bot_token);
+    hide(&mut integrations.notion.token);
+    // Las URLs de iCal privadas llevan el token en la propia ruta.
+    hide(&mut integrations.calendar.ics_url);
+    hide(&mut integrations.calendar.password);
+    s
+}
@@ -0,0 +1,277 @@
+use anyhow::{Result, anyhow};
+use crossbeam_channel::{Receiver, Sender};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serenity::all::{ChannelId, Context, EventHandler, GatewayIntents, GuildId, Http, Ready, ShardManager, UserId};
+use serenity::{async_trait, Client as GatewayClient};
+use songbird::driver::DecodeMode;
+use songbird::model::payload::Speaking;
+use songbird::{CoreEvent, Event, EventContext, EventHandler as VoiceEventHandler, SerenityInit, Songbird};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use tokio::sync::oneshot;
+use crate::data::TranscriptSegment;
+use crate::redaction::{RedactionSettings, Redactor};
+
+// ── Discord ────────────────────────────────────────────────────────────────
+//
+// Un bot (token del portal de desarrolladores, con permiso para conectar y
+// escribir) entra en un canal de voz como fuente de audio: el ID del canal va
+// en el perfil, como la URL de una fuente de red. Discord entrega el audio de
+// cada participante por separado y cada uno se transcribe como un
+// interlocutor con su nombre en el servidor (ver `audio::run_capture_discord`).
+// Aparte, las intervenciones pueden ir a un canal de texto según se
+// transcriben, como en Telegram.
+
+const API: &str = "https://discord.com/api/v10";
+/// Límite de caracteres de un mensaje.
+const MAX_MESSAGE: usize = 2000;
+/// Discord permite cinco mensajes cada cinco segundos en un canal.
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+/// Espera a que el bot se conecte antes de dar el token por malo.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscordConfig {
+    pub bot_token: String,
+    /// ID del servidor en el que están los canales.
+    pub guild_id: String,
+    /// ID del canal de texto al que va la transcripción en directo.
+    pub text_channel_id: String,
+    pub post_live: bool,
+}
+
+impl DiscordConfig {
+    /// Hay datos para publicar en el canal de texto.
+    pub fn posts_live(&self) -> bool {
+        self.post_live && !self.bot_token.trim().is_empty() && !self.text_channel_id.trim().is_empty()
+    }
+}
+
+fn parse_id(id: &str, what: &str) -> Result<u64> {
+    id.trim().parse::<u64>().ok()
+        .filter(|&id| id != 0)
+        .ok_or_else(|| anyhow!("ID de {} no válido: '{}'", what, id.trim()))
+}
+
+// ── Canal de voz ───────────────────────────────────────────────────────────
+
+/// Lo que llega del canal de voz.
+pub(crate) enum VoiceEvent {
+    /// El participante `name` habla con este SSRC.
+    Speaker { ssrc: u32, name: String },
+    /// 20 ms de cada participante conocido: PCM estéreo a 48 kHz, o `None`
+    /// si calla.
+    Tick(Vec<(u32, Option<Vec<i16>>)>),
+}
+
+/// Bot conectado a un canal de voz. `leave` sale del canal y cierra la
+/// conexión.
+pub(crate) struct VoiceSession {
+    rt: Runtime,
+    manager: Arc<Songbird>,
+    shards: Arc<ShardManager>,
+    guild: GuildId,
+}
+
+/// Conecta el bot de `config` y entra en el canal de voz `channel`.
+pub(crate) fn join(config: &DiscordConfig, channel: &str) -> Result<(VoiceSession, Receiver<VoiceEvent>)> {
+    if config.bot_token.trim().is_empty() {
+        return Err(anyhow!("Falta el token del bot"));
+    }
+    let guild = GuildId::new(parse_id(&config.guild_id, "servidor")?);
+    let channel = ChannelId::new(parse_id(channel, "canal")?);
+    let rt = Runtime::new()?;
+    let (tx, rx) = crossbeam_channel::unbounded();
+
+    let (manager, shards) = rt.block_on(async {
+        let manager = Songbird::serenity_from_config(songbird::Config::default().decode_mode(DecodeMode::Decode));
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let mut client = GatewayClient::builder(config.bot_token.trim(), GatewayIntents::GUILDS | GatewayIntents::GUILD_VOICE_STATES)
+            .event_handler(ReadySignal(Mutex::new(Some(ready_tx))))
+            .register_songbird_with(manager.clone())
+            .await?;
+        let shards = client.shard_manager.clone();
+        let http = client.http.clone();
+        // Si el cliente falla (token no válido) se suelta el aviso y la espera acaba
+        tokio::spawn(async move {
+            if let Err(e) = client.start().await {
+                tracing::error!("Discord: {:?}", e);
+            }
+        });
+        match tokio::time::timeout(CONNECT_TIMEOUT, ready_rx).await {
+            Ok(Ok(())) => {}
+            _ => {
+                shards.shutdown_all().await;
+                return Err(anyhow!("El bot no se pudo conectar a Discord"));
+            }
+        }
+
+        let call = match manager.join(guild, channel).await {
+            Ok(call) => call,
+            Err(e) => {
+                shards.shutdown_all().await;
+                return Err(anyhow!("No se pudo entrar en el canal de voz: {}", e));
+            }
+        };
+        let receiver = VoiceReceiver { tx, http, guild };
+        let mut call = call.lock().await;
+        call.add_global_event(CoreEvent::SpeakingStateUpdate.into(), receiver.clone());
+        call.add_global_event(CoreEvent::VoiceTick.into(), receiver);
+        Ok::<_, anyhow::Error>((manager, shards))
+    })?;
+
+    Ok((VoiceSession { rt, manager, shards, guild }, rx))
+}
+
+impl VoiceSession {
+    pub(crate) fn leave(self) {
+        self.rt.block_on(async {
+            if let Err(e) = self.manager.remove(self.guild).await {
+                tracing::warn!("Discord: no se pudo salir del canal: {}", e);
+            }
+            self.shards.shutdown_all().await;
+        });
+    }
+}
+
+/// Avisa una vez de que el bot está conectado.
+struct ReadySignal(Mutex<Option<oneshot::Sender<()>>>);
+
+#[async_trait]
+impl EventHandler for ReadySignal {
+    async fn ready(&self, _: Context, _: Ready) {
+        if let Some(tx) = self.0.lock().unwrap().take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+#[derive(Clone)]
+struct VoiceReceiver {
+    tx: Sender<VoiceEvent>,
+    http: Arc<Http>,
+    guild: GuildId,
+}
+
+#[async_trait]
+impl VoiceEventHandler for VoiceReceiver {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        match ctx {
+            EventContext::SpeakingStateUpdate(Speaking { ssrc, user_id: Some(user), .. }) => {
+                let name = display_name(&self.http, self.guild, UserId::new(user.0)).await;
+                let _ = self.tx.send(VoiceEvent::Speaker { ssrc: *ssrc, name });
+            }
+            EventContext::VoiceTick(tick) => {
+                let mut audio: Vec<(u32, Option<Vec<i16>>)> = tick.speaking.iter()
+                    .map(|(ssrc, data)| (*ssrc, data.decoded_voice.clone()))
+                    .collect();
+                audio.extend(tick.silent.iter().map(|ssrc| (*ssrc, None)));
+                let _ = self.tx.send(VoiceEvent::Tick(audio));
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Apodo en el servidor, o el nombre de usuario si no se puede leer.
+async fn display_name(http: &Http, guild: GuildId, user: UserId) -> String {
+    match guild.member(http, user).await {
+        Ok(member) => member.display_name().to_string(),
+        Err(e) => {
+            tracing::debug!("Discord: sin datos del miembro {}: {}", user, e);
+            match user.to_user(http).await {
+                Ok(user) => user.global_name.unwrap_or(user.name),
+                Err(_) => format!("Discord {}", user),
+            }
+        }
+    }
+}
+
+// ── Canal de texto ─────────────────────────────────────────────────────────
+
+async fn send_message(client: &Client, config: &DiscordConfig, text: &str) -> Result<()> {
+    let response = client
+        .post(format!("{}/channels/{}/messages", API, config.text_channel_id.trim()))
+        .header("Authorization", format!("Bot {}", config.bot_token.trim()))
+        .header("Content-Type", "application/json")
+        .body(json!({ "content": truncate(text, MAX_MESSAGE) }).to_string())
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(anyhow!("API de Discord {}: {}", status, response.text().await.unwrap_or_default()));
+    }
+    Ok(())
+}
+
+fn truncate(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// Envío en directo al canal de texto. Al soltarlo se vacía la cola y se
+/// cierra.
+pub struct DiscordLive {
+    tx: mpsc::Sender<String>,
+    redactor: Option<Redactor>,
+}
+
+impl DiscordLive {
+    pub fn start(config: &DiscordConfig, redaction: &RedactionSettings) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let config = config.clone();
+        std::thread::spawn(move || match Runtime::new() {
+            Ok(rt) => rt.block_on(send_live(&config, rx)),
+            Err(e) => tracing::error!("Discord: no se pudo crear el runtime: {:?}", e),
+        });
+        Self { tx, redactor: redaction.enabled.then(|| Redactor::new(redaction)) }
+    }
+
+    /// Encola una intervención sin bloquear.
+    pub fn publish(&self, segment: &TranscriptSegment) {
+        let text = match &self.redactor {
+            Some(redactor) => redactor.redact(&segment.text),
+            None => segment.text.clone(),
+        };
+        let _ = self.tx.send(format!("**{}**: {}", segment.name, text));
+    }
+}
+
+async fn send_live(config: &DiscordConfig, rx: mpsc::Receiver<String>) {
+    let client = Client::new();
+    let mut last_sent = Instant::now() - MIN_INTERVAL;
+    while let Ok(first) = rx.recv() {
+        tokio::time::sleep(MIN_INTERVAL.saturating_sub(last_sent.elapsed())).await;
+        let mut lines = vec![first];
+        lines.extend(rx.try_iter());
+        for text in batches(&lines) {
+            tokio::time::sleep(MIN_INTERVAL.saturating_sub(last_sent.elapsed())).await;
+            if let Err(e) = send_message(&client, config, &text).await {
+                tracing::warn!("Discord: intervención no enviada: {}", e);
+            }
+            last_sent = Instant::now();
+        }
+    }
+}
+
+/// Junta las líneas en mensajes que caben en el límite de la API.
+fn batches(lines: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for line in lines {
+        match out.last_mut() {
+            Some(current) if current.chars().count() + line.chars().count() < MAX_MESSAGE => {
+                current.push('\n');
+                current.push_str(line);
+            }
+            _ => out.push(line.clone()),
+        }
+    }
+    out
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use std::io::{Seek, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+use crate::export::{MinutaDocument, MinutaEntry};
+use crate::i18n::tr;
+use crate::stats::format_duration;
+
+// ── Exportación a Word (.docx) ─────────────────────────────────────────────
+//
+// Un .docx es un zip con unos pocos XML. Generamos el mínimo que Word y
+// LibreOffice aceptan: portada, lista de asistentes, párrafos con hora e
+// interlocutor y, si hay datos, la tabla de estadísticas.
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+pub fn write_docx(doc: &MinutaDocument, out: impl Write + Seek) -> Result<()> {
+    let mut zip = ZipWriter::new(out);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("[Content_Types].xml", options)?;
+    zip.write_all(CONTENT_TYPES.as_bytes())?;
+    zip.start_file("_rels/.rels", options)?;
+    zip.write_all(ROOT_RELS.as_bytes())?;
+    zip.start_file("word/document.xml", options)?;
+    zip.write_all(document_xml(doc).as_bytes())?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn document_xml(doc: &MinutaDocument) -> String {
+    let mut body = String::new();
+
+    // Portada
+    for _ in 0..6 {
+        body.push_str("<w:p/>");
+    }
+    body.push_str(&paragraph(&[run(&doc.title, Style::Title)], Some("center")));
+    body.push_str(&paragraph(
+        &[run(&format!("{}: {}", tr!("export.date"), doc.date), Style::Muted)],
+        Some("center"),
+    ));
+    if !doc.attendees.is_empty() {
+        body.push_str("<w:p/>");
+        body.push_str(&paragraph(&[run(&tr!("export.attendees"), Style::Heading)], Some("center")));
+        for name in &doc.attendees {
+            body.push_str(&paragraph(&[run(name, Style::Normal)], Some("center")));
+        }
+    }
+    if let Some(recording) = &doc.recording {
+        body.push_str("<w:p/>");
+        body.push_str(&paragraph(
+            &[run(&format!("{}: ", tr!("export.recording")), Style::Heading), run(recording, Style::Normal)],
+            Some("center"),
+        ));
+    }
+    body.push_str(r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#);
+
+    // Transcripción
+    body.push_str(&paragraph(&[run(&tr!("export.transcript"), Style::Heading)], None));
+    for entry in &doc.entries {
+        if let Some(chapter) = &entry.chapter {
+            body.push_str(&paragraph(&[run(chapter, Style::Heading)], None));
+        }
+        body.push_str(&entry_paragraph(entry));
+    }
+
+    // Estadísticas
+    if !doc.stats.is_empty() {
+        body.push_str("<w:p/>");
+        body.push_str(&paragraph(&[run(&tr!("stats.heading"), Style::Heading)], None));
+        let mut rows = vec![vec![
+            tr!("stats.speaker"),
+            tr!("stats.speaking_time"),
+            tr!("stats.words"),
+            tr!("stats.longest"),
+            tr!("stats.interruptions"),
+        ]];
+        rows.extend(doc.stats.iter().map(|s| vec![
+            s.name.clone(),
+            format_duration(s.speaking_secs),
+            s.words.to_string(),
+            format_duration(s.longest_monologue_secs),
+            s.interruptions.to_string(),
+        ]));
+        body.push_str(&table(&rows));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1417" w:right="1417" w:bottom="1417" w:left="1417" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        body
+    )
+}
+
+fn entry_paragraph(entry: &MinutaEntry) -> String {
+    let mut runs = Vec::new();
+    if let Some(ts) = &entry.timestamp {
+        runs.push(run(&format!("[{}] ", ts), Style::Muted));
+    }
+    if let Some(speaker) = &entry.speaker {
+        runs.push(run(&format!("{}: ", speaker), Style::Bold));
+    }
+    runs.push(run(&entry.text, Style::Normal));
+    paragraph(&runs, None)
+}
+
+// ── Helpers de WordprocessingML ────────────────────────────────────────────
+
+#[derive(Clone, Copy)]
+enum Style {
+    Normal,
+    Bold,
+    Muted,
+    Heading,
+    Title,
+}
+
+fn run(text: &str, style: Style) -> String {
+    let props = match style {
+        Style::Normal  => "",
+        Style::Bold    => "<w:rPr><w:b/></w:rPr>",
+        Style::Muted   => r#"<w:rPr><w:color w:val="808080"/></w:rPr>"#,
+        Style::Heading => r#"<w:rPr><w:b/><w:sz w:val="32"/></w:rPr>"#,
+        Style::Title   => r#"<w:rPr><w:b/><w:sz w:val="56"/></w:rPr>"#,
+    };
+    format!(r#"<w:r>{}<w:t xml:space="preserve">{}</w:t></w:r>"#, props, xml_escape(text))
+}
+
+fn paragraph(runs: &[String], align: Option<&str>) -> String {
+    let props = align
+        .map(|a| format!(r#"<w:pPr><w:jc w:val="{}"/></w:pPr>"#, a))
+        .unwrap_or_default();
+    format!("<w:p>{}{}</w:p>", props, runs.concat())
+}
+
+fn table(rows: &[Vec<String>]) -> String {
+    let border = r#"w:val="single" w:sz="4" w:space="0" w:color="999999""#;
+    let mut xml = format!(
+        r#"<w:tbl><w:tblPr><w:tblW w:w="0" w:type="auto"/><w:tblBorders><w:top {b}/><w:left {b}/><w:bottom {b}/><w:right {b}/><w:insideH {b}/><w:insideV {b}/></w:tblBorders></w:tblPr>"#,
+        b = border
+    );
+    for (i, row) in rows.iter().enumerate() {
+        xml.push_str("<w:tr>");
+        for cell in row {
+            let style = if i == 0 { Style::Bold } else { Style::Normal };
+            xml.push_str(&format!("<w:tc>{}</w:tc>", paragraph(&[run(cell, style)], None)));
+        }
+        xml.push_str("</w:tr>");
+    }
+    xml.push_str("</w:tbl>");
+    xml
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,90 @@
+// ── Proceso de muestras ────────────────────────────────────────────────────
+//
+// Nivelar, medir, pasar a mono y convertir de 16 bits se hace con cada
+// bloque de cada fuente: con muchas fuentes es buena parte de la CPU que no
+// se lleva Whisper. En vez de ir muestra a muestra arrastrando un único
+// acumulador (que obliga a sumar en orden y el compilador no puede
+// vectorizar), se recorre el audio en grupos de `LANES` con un acumulador
+// por carril, y el compilador lo convierte en instrucciones SIMD (SSE/AVX,
+// NEON) sin código `unsafe` ni `std::simd`, que aún no es estable. Las
+// variantes `_into` escriben en un búfer del llamador para reutilizarlo de
+// un bloque al siguiente.
+
+/// Muestras por grupo: 8 × f32 llenan un registro AVX (dos de SSE o NEON).
+const LANES: usize = 8;
+/// Pico por debajo del cual un fragmento es silencio y no se nivela.
+const MIN_PEAK: f32 = 0.0001;
+/// Pico al que se lleva un fragmento al nivelarlo.
+const TARGET_PEAK: f32 = 0.95;
+/// 1 / 32768, para multiplicar en vez de dividir (es exacto: potencia de 2).
+const S16_SCALE: f32 = 1.0 / 32768.0;
+
+/// Valor absoluto máximo.
+pub fn peak(audio: &[f32]) -> f32 {
+    let chunks = audio.chunks_exact(LANES);
+    let rest = chunks.remainder();
+    let mut lanes = [0.0f32; LANES];
+    for chunk in chunks {
+        for (lane, &s) in lanes.iter_mut().zip(chunk) {
+            *lane = lane.max(s.abs());
+        }
+    }
+    rest.iter().fold(lanes.into_iter().fold(0.0, f32::max), |peak, s| peak.max(s.abs()))
+}
+
+/// Suma de los cuadrados.
+pub fn sum_squares(audio: &[f32]) -> f32 {
+    let chunks = audio.chunks_exact(LANES);
+    let rest = chunks.remainder();
+    let mut lanes = [0.0f32; LANES];
+    for chunk in chunks {
+        for (lane, &s) in lanes.iter_mut().zip(chunk) {
+            *lane += s * s;
+        }
+    }
+    lanes.iter().sum::<f32>() + rest.iter().map(|&s| s * s).sum::<f32>()
+}
+
+pub fn rms(audio: &[f32]) -> f32 {
+    (sum_squares(audio) / audio.len() as f32).sqrt()
+}
+
+/// Ganancia que lleva el pico de `audio` a `TARGET_PEAK`; 1 si es silencio.
+fn normalize_gain(audio: &[f32]) -> f32 {
+    let peak = peak(audio);
+    if peak < MIN_PEAK { 1.0 } else { TARGET_PEAK / peak }
+}
+
+/// Nivela `audio` sin copiarlo.
+pub fn normalize_in_place(audio: &mut [f32]) {
+    let gain = normalize_gain(audio);
+    if gain != 1.0 {
+        audio.iter_mut().for_each(|s| *s *= gain);
+    }
+}
+
+/// RMS que tendría `audio` nivelado, sin nivelarlo: el RMS escala con la
+/// ganancia.
+pub fn normalized_rms(audio: &[f32]) -> f32 {
+    rms(audio) * normalize_gain(audio)
+}
+
+/// Media de los canales de cada trama de `interleaved`, en `out`.
+pub fn to_mono_into(interleaved: &[f32], channels: usize, out: &mut Vec<f32>) {
+    out.clear();
+    match channels {
+        0 | 1 => out.extend_from_slice(interleaved),
+        // El caso habitual, sin bucle interno
+        2 => out.extend(interleaved.chunks_exact(2).map(|f| (f[0] + f[1]) * 0.5)),
+        _ => {
+            let scale = 1.0 / channels as f32;
+            out.extend(interleaved.chunks_exact(channels).map(|f| f.iter().sum::<f32>() * scale));
+        }
+    }
+}
+
+/// Añade `pcm` (16 bits con signo) a `out` en [-1, 1).
+pub fn extend_from_s16(out: &mut Vec<f32>, pcm: &[i16]) {
+    out.reserve(pcm.len());
+    out.extend(pcm.iter().map(|&s| s as f32 * S16_SCALE));
+}
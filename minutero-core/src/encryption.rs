@@ -0,0 +1,204 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use crate::i18n::tr;
+
+// ── Cifrado en reposo ──────────────────────────────────────────────────────
+//
+// Las minutas exportadas (y con ellas las sesiones JSON que se vuelven a
+// abrir) se pueden guardar cifradas con AES-256-GCM. La clave sale de una
+// frase de paso (PBKDF2-SHA256) o de un archivo de clave cualquiera
+// (SHA-256 de su contenido). El archivo cifrado es `<minuta>.enc`:
+//
+//     MAGIC | sal (16) | nonce (12) | texto cifrado + etiqueta
+//
+// La frase de paso no se guarda en `settings.json`: se pide en cada arranque.
+// Al leer se descifra solo si el archivo empieza por `MAGIC`, así que las
+// minutas antiguas en claro se siguen abriendo igual.
+
+const MAGIC: &[u8] = b"MINUTERO-AES256GCM\x01";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+/// Recomendación actual de OWASP para PBKDF2-HMAC-SHA256.
+const PBKDF2_ROUNDS: u32 = 600_000;
+
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeySource {
+    Passphrase,
+    Keyfile,
+}
+
+impl KeySource {
+    pub const ALL: &'static [KeySource] = &[KeySource::Passphrase, KeySource::Keyfile];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            KeySource::Passphrase => "encryption.passphrase",
+            KeySource::Keyfile    => "encryption.keyfile",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncryptionSettings {
+    /// Cifrar las minutas al exportarlas.
+    pub enabled: bool,
+    pub key_source: KeySource,
+    pub keyfile: String,
+    /// Solo en memoria, nunca en disco.
+    #[serde(skip)]
+    pub passphrase: String,
+}
+
+impl Default for EncryptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            key_source: KeySource::Passphrase,
+            keyfile: String::new(),
+            passphrase: String::new(),
+        }
+    }
+}
+
+/// Frase de paso o contenido del archivo de clave.
+fn secret(settings: &EncryptionSettings) -> Result<Vec<u8>> {
+    match settings.key_source {
+        KeySource::Passphrase if settings.passphrase.is_empty() => Err(anyhow!(tr!("encryption.no_passphrase"))),
+        KeySource::Passphrase => Ok(settings.passphrase.as_bytes().to_vec()),
+        KeySource::Keyfile if settings.keyfile.trim().is_empty() => Err(anyhow!(tr!("encryption.no_keyfile"))),
+        KeySource::Keyfile => std::fs::read(settings.keyfile.trim())
+            .map_err(|e| anyhow!(tr!("encryption.keyfile_error", e = e))),
+    }
+}
+
+fn derive_key(settings: &EncryptionSettings, salt: &[u8]) -> Result<Key<Aes256Gcm>> {
+    let secret = secret(settings)?;
+    let mut key = Key::<Aes256Gcm>::default();
+    match settings.key_source {
+        KeySource::Passphrase => pbkdf2::pbkdf2_hmac::<Sha256>(&secret, salt, PBKDF2_ROUNDS, &mut key),
+        // Un archivo de clave ya tiene entropía de sobra
+        KeySource::Keyfile => key.copy_from_slice(&Sha256::new().chain_update(salt).chain_update(&secret).finalize()),
+    }
+    Ok(key)
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn encrypt(data: &[u8], settings: &EncryptionSettings) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let cipher = Aes256Gcm::new(&derive_key(settings, &salt)?);
+    let ciphertext = cipher.encrypt(Nonce::from_slice(&nonce), data)
+        .map_err(|_| anyhow!(tr!("encryption.failed")))?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+pub fn decrypt(data: &[u8], settings: &EncryptionSettings) -> Result<Vec<u8>> {
+    let body = data.strip_prefix(MAGIC)
+        .filter(|body| body.len() > SALT_LEN + NONCE_LEN)
+        .ok_or_else(|| anyhow!(tr!("encryption.not_encrypted")))?;
+    let (salt, rest) = body.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(&derive_key(settings, salt)?);
+    // GCM no distingue clave equivocada de archivo alterado
+    cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!(tr!("encryption.wrong_key")))
+}
+
+/// Contenido de `path`, descifrado si hace falta.
+pub fn read(path: &Path, settings: &EncryptionSettings) -> Result<Vec<u8>> {
+    let data = std::fs::read(path)?;
+    if is_encrypted(&data) { decrypt(&data, settings) } else { Ok(data) }
+}
+
+/// Comprueba que hay clave antes de escribir nada, para no dejar en claro
+/// una minuta que debía cifrarse.
+pub fn check_key(settings: &EncryptionSettings) -> Result<()> {
+    secret(settings).map(|_| ())
+}
+
+/// Ruta sin el `.enc`, para saber el formato de un archivo cifrado.
+pub fn plain_path(path: &Path) -> PathBuf {
+    match path.extension() {
+        Some(ext) if ext == ENCRYPTED_EXTENSION => path.with_extension(""),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passphrase(passphrase: &str) -> EncryptionSettings {
+        EncryptionSettings { enabled: true, passphrase: passphrase.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn round_trip_and_wrong_key() {
+        let data = "# Minuta\n[00:01] (Ana) Presupuesto: 3.000 €".as_bytes();
+        let encrypted = encrypt(data, &passphrase("correcto caballo pila grapa")).unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(data));
+        assert!(!encrypted.windows(data.len()).any(|w| w == data));
+
+        assert_eq!(decrypt(&encrypted, &passphrase("correcto caballo pila grapa")).unwrap(), data);
+        assert!(decrypt(&encrypted, &passphrase("otra frase")).is_err());
+    }
+
+    #[test]
+    fn keyfile_and_tampering() {
+        let keyfile = std::env::temp_dir().join(format!("minutero-test-{}.key", std::process::id()));
+        std::fs::write(&keyfile, b"clave de prueba").unwrap();
+        let settings = EncryptionSettings {
+            enabled: true,
+            key_source: KeySource::Keyfile,
+            keyfile: keyfile.to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+
+        let encrypted = encrypt(b"texto", &settings).unwrap();
+        // Misma entrada, sal y nonce nuevos
+        assert_ne!(encrypt(b"texto", &settings).unwrap(), encrypted);
+        assert_eq!(decrypt(&encrypted, &settings).unwrap(), b"texto");
+
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&tampered, &settings).is_err());
+        assert!(decrypt(&encrypted[..MAGIC.len() + SALT_LEN], &settings).is_err());
+        assert!(decrypt(b"texto", &settings).is_err());
+        std::fs::remove_file(&keyfile).unwrap();
+    }
+
+    #[test]
+    fn needs_a_key() {
+        assert!(check_key(&passphrase("")).is_err());
+        assert!(encrypt(b"texto", &passphrase("")).is_err());
+        let keyfile = EncryptionSettings { key_source: KeySource::Keyfile, ..Default::default() };
+        assert!(check_key(&keyfile).is_err());
+        assert!(check_key(&passphrase("frase")).is_ok());
+    }
+
+    #[test]
+    fn plain_path_strips_enc() {
+        assert_eq!(plain_path(Path::new("a/minuta.docx.enc")), Path::new("a/minuta.docx"));
+        assert_eq!(plain_path(Path::new("a/minuta.docx")), Path::new("a/minuta.docx"));
+    }
+}
@@ -0,0 +1,522 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use crate::data::{SegmentEdit, TranscriptSegment, WordTiming};
+use crate::docx::write_docx;
+use crate::encryption::{self, EncryptionSettings, ENCRYPTED_EXTENSION};
+use crate::i18n::tr;
+use crate::integrity;
+use crate::pdf::write_pdf;
+use crate::settings::AppSettings;
+use crate::subtitles::write_vtt;
+use crate::stats::{format_duration, SpeakerStats};
+use crate::template::{self, DEFAULT_HTML, DEFAULT_MARKDOWN};
+
+// ── Exportación de minutas ─────────────────────────────────────────────────
+//
+// La UI arma un `MinutaDocument` y cada formato lo vuelca a su manera.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Docx,
+    Pdf,
+    /// Segmentos con tiempos por palabra, para herramientas de alineación.
+    Json,
+    Vtt,
+}
+
+impl ExportFormat {
+    pub const ALL: &'static [ExportFormat] = &[
+        ExportFormat::Markdown, ExportFormat::Html, ExportFormat::Docx, ExportFormat::Pdf,
+        ExportFormat::Json, ExportFormat::Vtt,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown (.md)",
+            ExportFormat::Html     => "HTML (.html)",
+            ExportFormat::Docx     => "Word (.docx)",
+            ExportFormat::Pdf      => "PDF (.pdf)",
+            ExportFormat::Json     => "JSON (.json)",
+            ExportFormat::Vtt      => "WebVTT (.vtt)",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html     => "html",
+            ExportFormat::Docx     => "docx",
+            ExportFormat::Pdf      => "pdf",
+            ExportFormat::Json     => "json",
+            ExportFormat::Vtt      => "vtt",
+        }
+    }
+}
+
+/// Plantillas Tera propias; una ruta vacía usa la integrada.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportTemplates {
+    pub markdown: String,
+    pub html: String,
+    /// Orden del día con las tareas abiertas, ver `template::render_agenda`.
+    pub agenda: String,
+}
+
+/// Cabecera y pie de las páginas del PDF.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PdfBranding {
+    pub company: String,
+    /// Ruta a un JPEG; vacío = sin logo.
+    pub logo_path: String,
+    pub footer: String,
+}
+
+/// Párrafo de la minuta: `[MM:SS] (Interlocutor) texto`.
+#[derive(Clone, Debug)]
+pub struct MinutaEntry {
+    pub timestamp: Option<String>,
+    pub speaker: Option<String>,
+    pub text: String,
+    /// Tiempos exactos en segundos; solo en la captura en vivo.
+    pub start_secs: Option<f64>,
+    pub end_secs: Option<f64>,
+    pub words: Vec<WordTiming>,
+    /// Revisado a mano en la cola de baja confianza.
+    pub reviewed: bool,
+    /// Pregunta, en el modo entrevista.
+    pub question: bool,
+    /// Título del capítulo que empieza aquí, en el modo clase.
+    pub chapter: Option<String>,
+    /// Idioma detectado, en el modo multilingüe.
+    pub lang: Option<String>,
+    /// Texto de Whisper si se corrigió a mano, y el historial de correcciones.
+    pub original: Option<String>,
+    pub edits: Vec<SegmentEdit>,
+}
+
+/// Formato del campo `date` de la minuta.
+const DATE_FORMAT: &str = "%d-%m-%Y %H:%M:%S";
+
+/// Documento a exportar, independiente del formato.
+#[derive(Clone, Debug)]
+pub struct MinutaDocument {
+    pub title: String,
+    pub date: String,
+    pub attendees: Vec<String>,
+    /// Texto tal cual se ve en pantalla; Markdown lo vuelca sin cambios.
+    pub body: String,
+    pub entries: Vec<MinutaEntry>,
+    pub stats: Vec<SpeakerStats>,
+    /// Archivo de la grabación de la reunión, junto a la minuta.
+    pub recording: Option<String>,
+    /// Temas de la sesión (ver `topics`).
+    pub tags: Vec<String>,
+}
+
+impl MinutaDocument {
+    pub fn new(title: String, attendees: Vec<String>, body: String) -> Self {
+        let entries = entries_from_text(&body);
+        Self {
+            title,
+            date: Local::now().format(DATE_FORMAT).to_string(),
+            attendees,
+            body,
+            entries,
+            stats: Vec::new(),
+            recording: None,
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn with_recording(mut self, recording: Option<String>) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// Usa los segmentos en vivo (con sus tiempos) en vez de reinterpretar el texto.
+    pub fn with_segments(mut self, segments: &[TranscriptSegment]) -> Self {
+        if !segments.is_empty() {
+            self.entries = segments.iter().map(|s| MinutaEntry {
+                timestamp: Some(format_duration(s.start_secs)),
+                speaker: Some(s.name.clone()).filter(|_| !s.is_pause()),
+                text: s.text.clone(),
+                start_secs: Some(s.start_secs),
+                end_secs: Some(s.end_secs),
+                words: s.words.clone(),
+                reviewed: s.reviewed,
+                question: false,
+                chapter: None,
+                lang: s.lang.clone(),
+                original: s.original.clone(),
+                edits: s.edits.clone(),
+            }).collect();
+        }
+        self
+    }
+
+    pub fn with_stats(mut self, stats: Vec<SpeakerStats>) -> Self {
+        self.stats = stats;
+        self
+    }
+}
+
+/// Reconstruye los párrafos a partir de líneas `[MM:SS] texto` o `(Nombre) texto`.
+fn entries_from_text(body: &str) -> Vec<MinutaEntry> {
+    body.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let (timestamp, rest) = split_prefix(line, '[', ']');
+            let (speaker, text) = split_prefix(rest, '(', ')');
+            MinutaEntry {
+                timestamp,
+                speaker,
+                text: text.to_string(),
+                start_secs: None,
+                end_secs: None,
+                words: Vec::new(),
+                reviewed: false,
+                question: false,
+                chapter: None,
+                lang: None,
+                original: None,
+                edits: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+fn split_prefix(line: &str, open: char, close: char) -> (Option<String>, &str) {
+    if let Some(rest) = line.strip_prefix(open) {
+        if let Some(end) = rest.find(close) {
+            return (Some(rest[..end].to_string()), rest[end + close.len_utf8()..].trim_start());
+        }
+    }
+    (None, line)
+}
+
+/// Párrafos en Markdown (`[MM:SS] **Nombre:** texto`), sin cabecera; para
+/// copiar fragmentos al portapapeles.
+pub fn entries_markdown(entries: &[MinutaEntry]) -> String {
+    entries.iter()
+        .map(|e| {
+            let mut line = String::new();
+            if let Some(ts) = &e.timestamp {
+                line.push_str(&format!("[{}] ", ts));
+            }
+            if let Some(speaker) = &e.speaker {
+                line.push_str(&format!("**{}:** ", speaker));
+            }
+            line + &e.text
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// ── Nombre de archivo ──────────────────────────────────────────────────────
+
+pub const DEFAULT_FILENAME_PATTERN: &str = "{name}_{timestamp}";
+
+/// Variables del patrón de nombre, para mostrarlas en Configuración.
+pub const FILENAME_VARIABLES: &str = "{name} {title} {participants} {date} {time} {timestamp}";
+
+/// Expande el patrón y limpia el resultado para que sea un nombre válido en
+/// cualquier sistema. `name` es lo que antes formaba el nombre: interlocutores
+/// activos o el nombre del vídeo.
+pub fn file_stem(pattern: &str, doc: &MinutaDocument, name: &str) -> String {
+    let now = Local::now();
+    let pattern = if pattern.trim().is_empty() { DEFAULT_FILENAME_PATTERN } else { pattern };
+    let expanded = pattern
+        .replace("{name}", name)
+        .replace("{title}", &doc.title)
+        .replace("{participants}", &doc.attendees.join("_"))
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H%M%S").to_string())
+        .replace("{timestamp}", &now.format("%Y%m%d_%H%M%S").to_string());
+    sanitize_file_stem(&expanded)
+}
+
+const MAX_STEM_CHARS: usize = 120;
+
+pub fn sanitize_file_stem(raw: &str) -> String {
+    let mut out = String::new();
+    for c in raw.chars() {
+        let c = if c.is_control() || c.is_whitespace() || r#"/\:*?"<>|"#.contains(c) { '_' } else { c };
+        if c == '_' && out.ends_with('_') {
+            continue;
+        }
+        out.push(c);
+    }
+    let out: String = out
+        .trim_matches(|c| c == '_' || c == '.' || c == '-')
+        .chars()
+        .take(MAX_STEM_CHARS)
+        .collect();
+    if out.is_empty() { "minuta".into() } else { out }
+}
+
+/// Si ya existe un archivo con ese nombre añade `_2`, `_3`...
+pub fn unique_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.{}", stem, extension));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}_{}.{}", stem, n, extension));
+        n += 1;
+    }
+    path
+}
+
+// ── Carpeta de destino ─────────────────────────────────────────────────────
+
+/// Ruta absoluta de la carpeta de minutas. Las relativas se resuelven contra
+/// el directorio desde el que se lanzó la aplicación.
+pub fn absolute_output_dir(dir: &str) -> PathBuf {
+    std::path::absolute(dir).unwrap_or_else(|_| PathBuf::from(dir))
+}
+
+/// Crea la carpeta si hace falta y comprueba que se puede escribir en ella.
+pub fn check_output_dir(dir: &str) -> Result<PathBuf> {
+    let path = absolute_output_dir(dir);
+    std::fs::create_dir_all(&path)?;
+    let probe = path.join(".minutero_write_test");
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(&probe)?;
+    Ok(path)
+}
+
+/// Guarda la minuta en `output_dir` con el nombre que marque el patrón de
+/// Configuración.
+pub fn save_minuta(
+    doc: &MinutaDocument,
+    settings: &AppSettings,
+    output_dir: &str,
+    name: &str,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(output_dir)?;
+    let format = settings.export_format;
+    let stem = file_stem(&settings.filename_pattern, doc, name);
+    let encryption = &settings.encryption;
+    if encryption.enabled {
+        encryption::check_key(encryption)?;
+    }
+
+    // Se genera en memoria: con cifrado, la minuta en claro no llega a disco
+    let templates = &settings.export_templates;
+    let mut data = Vec::new();
+    match format {
+        ExportFormat::Markdown => data = template::render(doc, &templates.markdown, DEFAULT_MARKDOWN, false)?.into_bytes(),
+        ExportFormat::Html     => data = template::render(doc, &templates.html, DEFAULT_HTML, true)?.into_bytes(),
+        ExportFormat::Docx     => write_docx(doc, Cursor::new(&mut data))?,
+        ExportFormat::Pdf      => write_pdf(doc, &settings.pdf_branding, &mut data)?,
+        ExportFormat::Json     => serde_json::to_writer_pretty(&mut data, &minuta_json(doc, settings.integrity.hash_chain))?,
+        ExportFormat::Vtt      => write_vtt(doc, &mut data)?,
+    }
+    // Con cifrado acaba en `<minuta>.<ext>.enc`
+    let path = if encryption.enabled {
+        let data = encryption::encrypt(&data, encryption)?;
+        let path = unique_path(Path::new(output_dir), &stem, &format!("{}.{}", format.extension(), ENCRYPTED_EXTENSION));
+        std::fs::write(&path, data)?;
+        path
+    } else {
+        let path = unique_path(Path::new(output_dir), &stem, format.extension());
+        std::fs::write(&path, data)?;
+        path
+    };
+    if settings.integrity.sign {
+        integrity::sign_file(&path, &settings.integrity.signing_key)
+            .map_err(|e| anyhow!(tr!("integrity.sign_failed", path = path.display(), e = e)))?;
+    }
+    Ok(path)
+}
+
+/// Subcarpeta de la de salida con las sesiones sin anonimizar, cuando lo
+/// exportado va anonimizado (ver `redaction`).
+pub const SESSION_STORE_DIR: &str = "sesiones";
+
+/// Guarda `doc` tal cual, en JSON, en `<output_dir>/sesiones/`, para poder
+/// volver a abrir la sesión original. Solo con la anonimización activada: sin
+/// ella, la minuta exportada ya es el original.
+pub fn save_local_session(
+    doc: &MinutaDocument,
+    settings: &AppSettings,
+    output_dir: &str,
+    name: &str,
+) -> Result<Option<PathBuf>> {
+    if !settings.redaction.enabled {
+        return Ok(None);
+    }
+    let mut local = settings.clone();
+    local.export_format = ExportFormat::Json;
+    let dir = Path::new(output_dir).join(SESSION_STORE_DIR);
+    save_minuta(doc, &local, &dir.to_string_lossy(), name).map(Some)
+}
+
+/// Guarda, junto a la minuta, un archivo por interlocutor con solo sus
+/// intervenciones y su hora (declaraciones de cada ponente de una mesa).
+/// Mismo formato, cifrado y firma que la minuta.
+pub fn save_speaker_minutas(
+    doc: &MinutaDocument,
+    settings: &AppSettings,
+    output_dir: &str,
+    name: &str,
+) -> Result<Vec<PathBuf>> {
+    let mut speakers: Vec<&str> = Vec::new();
+    for speaker in doc.entries.iter().filter_map(|e| e.speaker.as_deref()) {
+        if !speakers.contains(&speaker) {
+            speakers.push(speaker);
+        }
+    }
+    speakers.into_iter()
+        .map(|speaker| save_minuta(&speaker_document(doc, speaker), settings, output_dir, &format!("{}_{}", name, speaker)))
+        .collect()
+}
+
+/// La minuta reducida a las intervenciones de `speaker`, cada una con su hora.
+pub fn speaker_document(doc: &MinutaDocument, speaker: &str) -> MinutaDocument {
+    let entries: Vec<MinutaEntry> = doc.entries.iter()
+        .filter(|e| e.speaker.as_deref() == Some(speaker))
+        .cloned()
+        .collect();
+    let body = entries.iter()
+        .map(|e| match &e.timestamp {
+            Some(ts) => format!("[{}] {}\n", ts, e.text),
+            None => format!("{}\n", e.text),
+        })
+        .collect();
+    MinutaDocument {
+        title: tr!("export.speaker_title", title = doc.title, name = speaker),
+        date: doc.date.clone(),
+        attendees: vec![speaker.to_string()],
+        body,
+        entries,
+        stats: doc.stats.iter().filter(|s| s.name == speaker).cloned().collect(),
+        recording: doc.recording.clone(),
+        tags: doc.tags.clone(),
+    }
+}
+
+/// La minuta como JSON: metadatos y segmentos con sus palabras y, con
+/// `hash_chain`, el hash encadenado de cada uno (ver `integrity`).
+pub(crate) fn minuta_json(doc: &MinutaDocument, hash_chain: bool) -> serde_json::Value {
+    let hashes = if hash_chain { integrity::chain(doc) } else { Vec::new() };
+    let segments: Vec<serde_json::Value> = doc.entries.iter().enumerate()
+        .map(|(i, e)| serde_json::json!({
+            "timestamp": e.timestamp,
+            "speaker": e.speaker,
+            "start_secs": e.start_secs,
+            "end_secs": e.end_secs,
+            "text": e.text,
+            "words": e.words,
+            "reviewed": e.reviewed,
+            "question": e.question,
+            "chapter": e.chapter,
+            "lang": e.lang,
+            "original": e.original,
+            "edits": e.edits,
+            "hash": hashes.get(i),
+        }))
+        .collect();
+    let stats: Vec<serde_json::Value> = doc.stats.iter()
+        .map(|s| serde_json::json!({
+            "name": s.name,
+            "speaking_secs": s.speaking_secs,
+            "words": s.words,
+            "longest_monologue_secs": s.longest_monologue_secs,
+            "interruptions": s.interruptions,
+        }))
+        .collect();
+    serde_json::json!({
+        "title": doc.title,
+        "date": doc.date,
+        "attendees": doc.attendees,
+        "recording": doc.recording,
+        "tags": doc.tags,
+        "chain": hash_chain.then(|| integrity::ChainHeader::new(&hashes)),
+        "segments": segments,
+        "stats": stats,
+    })
+}
+
+// ── Lectura de sesiones guardadas ──────────────────────────────────────────
+
+/// Sesión recuperada de una minuta JSON: segmentos con sus tiempos y la
+/// grabación enlazada, si sigue junto al archivo.
+pub struct StoredSession {
+    pub title: String,
+    /// Cuándo se guardó (el campo `date`), si se puede leer.
+    pub saved_at: Option<DateTime<Local>>,
+    pub attendees: Vec<String>,
+    pub tags: Vec<String>,
+    pub segments: Vec<TranscriptSegment>,
+    pub recording: Option<PathBuf>,
+}
+
+/// Abre también minutas cifradas (`.json.enc`) con la clave de `encryption`.
+pub fn read_minuta_json(path: &Path, encryption: &EncryptionSettings) -> Result<StoredSession> {
+    #[derive(Deserialize)]
+    struct StoredSegment {
+        speaker: Option<String>,
+        text: String,
+        start_secs: Option<f64>,
+        end_secs: Option<f64>,
+        #[serde(default)]
+        words: Vec<WordTiming>,
+        #[serde(default)]
+        reviewed: bool,
+        #[serde(default)]
+        lang: Option<String>,
+        #[serde(default)]
+        original: Option<String>,
+        #[serde(default)]
+        edits: Vec<SegmentEdit>,
+    }
+    #[derive(Deserialize)]
+    struct StoredMinuta {
+        title: String,
+        #[serde(default)]
+        date: String,
+        #[serde(default)]
+        attendees: Vec<String>,
+        recording: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        segments: Vec<StoredSegment>,
+    }
+
+    let minuta: StoredMinuta = serde_json::from_slice(&encryption::read(path, encryption)?)?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let segments = minuta.segments.into_iter()
+        .map(|s| {
+            let start_secs = s.start_secs.unwrap_or(0.0);
+            TranscriptSegment {
+                name: s.speaker.unwrap_or_default(),
+                text: s.text,
+                start_secs,
+                end_secs: s.end_secs.unwrap_or(start_secs),
+                words: s.words,
+                reviewed: s.reviewed,
+                lang: s.lang,
+                original: s.original,
+                edits: s.edits,
+            }
+        })
+        .collect();
+    Ok(StoredSession {
+        title: minuta.title,
+        saved_at: NaiveDateTime::parse_from_str(&minuta.date, DATE_FORMAT).ok()
+            .and_then(|date| date.and_local_timezone(Local).earliest()),
+        attendees: minuta.attendees,
+        tags: minuta.tags,
+        segments,
+        // Las de `sesiones/` tienen la grabación en la carpeta de salida
+        recording: minuta.recording
+            .and_then(|r| [dir.join(&r), dir.parent().unwrap_or(dir).join(&r)].into_iter().find(|p| p.exists())),
+    })
+}
@@ -0,0 +1,111 @@
+use anyhow::{Context, Result, anyhow};
+use git2::{Cred, CredentialType, PushOptions, RemoteCallbacks, Repository, Signature};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use crate::export::MinutaDocument;
+use crate::template;
+
+// ── Archivo de minutas en git ──────────────────────────────────────────────
+//
+// Cada minuta guardada se copia a un repositorio git local y se hace commit
+// (con libgit2, sin depender del `git` instalado), con un mensaje hecho con
+// las variables de las plantillas. Con *push* además se sube al remoto: las
+// credenciales son las del agente SSH o el *credential helper* de git, como
+// en la terminal. El equipo revisa las actas como cualquier otro cambio.
+
+pub const DEFAULT_MESSAGE: &str = "Minuta: {{ title }} ({{ date }})";
+
+/// Las salas guardan en sus propios hilos: un commit cada vez.
+static REPO_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitArchiveConfig {
+    pub enabled: bool,
+    /// Carpeta del repositorio (ya clonado o creado con `git init`).
+    pub repo_path: String,
+    /// Carpeta dentro del repositorio; vacía = la raíz.
+    pub subfolder: String,
+    /// Plantilla Tera del mensaje del commit.
+    pub message: String,
+    pub push: bool,
+    pub remote: String,
+}
+
+impl Default for GitArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            repo_path: String::new(),
+            subfolder: String::new(),
+            message: DEFAULT_MESSAGE.into(),
+            push: false,
+            remote: "origin".into(),
+        }
+    }
+}
+
+impl GitArchiveConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.repo_path.trim().is_empty()
+    }
+}
+
+/// Copia la minuta de `path` al repositorio y hace commit (y push, si se pide).
+pub fn archive(config: &GitArchiveConfig, doc: &MinutaDocument, path: &Path) -> Result<()> {
+    let _lock = REPO_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let repo = Repository::open(config.repo_path.trim())
+        .with_context(|| format!("no es un repositorio git: {}", config.repo_path))?;
+    let workdir = repo.workdir().ok_or_else(|| anyhow!("el repositorio no tiene directorio de trabajo"))?;
+    let name = path.file_name().ok_or_else(|| anyhow!("minuta sin nombre de archivo"))?;
+    let relative = Path::new(config.subfolder.trim().trim_matches('/')).join(name);
+    let target = workdir.join(&relative);
+    if let Some(dir) = target.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::copy(path, &target)?;
+
+    let mut index = repo.index()?;
+    index.add_path(&relative)?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let message = match config.message.trim() {
+        "" => template::render_str(doc, DEFAULT_MESSAGE)?,
+        source => template::render_str(doc, source)?,
+    };
+    // La identidad de la configuración de git; si no hay, una genérica
+    let signature = repo.signature().or_else(|_| Signature::now("Minutero", "minutero@localhost"))?;
+    let parents: Vec<_> = parent.iter().collect();
+    let commit = repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &parents)?;
+    tracing::info!("Minuta archivada en git: {} ({})", relative.display(), commit);
+
+    if config.push {
+        push(&repo, config.remote.trim())?;
+    }
+    Ok(())
+}
+
+fn push(repo: &Repository, remote_name: &str) -> Result<()> {
+    let head = repo.head()?;
+    let branch = head.name().ok_or_else(|| anyhow!("HEAD sin rama"))?;
+    let mut remote = repo.find_remote(remote_name)
+        .with_context(|| format!("no existe el remoto '{}'", remote_name))?;
+    let git_config = repo.config()?;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY) {
+            Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        } else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            Cred::credential_helper(&git_config, url, username)
+        } else {
+            Cred::default()
+        }
+    });
+    let mut options = PushOptions::new();
+    options.remote_callbacks(callbacks);
+    remote.push(&[format!("{}:{}", branch, branch)], Some(&mut options))?;
+    tracing::info!("Minuta subida a {} ({})", remote_name, branch);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::data::WhisperTuning;
+
+// ── Alucinaciones de Whisper ───────────────────────────────────────────────
+//
+// Sobre silencio o ruido Whisper a veces "oye" las frases que más vio al
+// entrenarse: créditos de subtítulos y despedidas de vídeos. Los fragmentos
+// que contienen alguna de las frases de la lista se descartan antes de
+// llegar a la minuta. Se compara sin mayúsculas, signos ni espacios de más.
+
+/// Frases conocidas; el usuario puede ampliar o recortar la lista.
+pub const DEFAULT_HALLUCINATIONS: &[&str] = &[
+    "Subtítulos realizados por la comunidad de Amara.org",
+    "Subtitulado por la comunidad de Amara.org",
+    "Subtítulos por la comunidad de Amara.org",
+    "Subtitles by the Amara.org community",
+    "Sous-titres réalisés par la communauté d'Amara.org",
+    "Untertitel der Amara.org-Community",
+    "Gracias por ver el vídeo",
+    "Suscríbete al canal",
+    "Thanks for watching",
+];
+
+pub fn default_list() -> String {
+    DEFAULT_HALLUCINATIONS.join("\n")
+}
+
+pub struct HallucinationFilter {
+    phrases: Vec<String>,
+}
+
+impl HallucinationFilter {
+    pub fn new(tuning: &WhisperTuning) -> Self {
+        let phrases = if tuning.filter_hallucinations {
+            tuning.hallucinations.lines()
+                .map(normalize)
+                .filter(|p| !p.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self { phrases }
+    }
+
+    /// Si el fragmento contiene una frase de la lista.
+    pub fn is_hallucination(&self, text: &str) -> bool {
+        if self.phrases.is_empty() {
+            return false;
+        }
+        let text = normalize(text);
+        let hit = self.phrases.iter().any(|p| text.contains(p.as_str()));
+        if hit {
+            tracing::debug!("Alucinación descartada: {}", text);
+        }
+        hit
+    }
+}
+
+/// Minúsculas, solo letras y números, separados por un espacio.
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+use crate::encryption::{self, EncryptionSettings};
+use crate::export::{read_minuta_json, SESSION_STORE_DIR};
+use crate::roster::fold;
+
+// ── Historial de sesiones ──────────────────────────────────────────────────
+//
+// Las minutas JSON de la carpeta de salida (también las cifradas, si la clave
+// las abre), con su título, fecha, asistentes y temas, para buscarlas y
+// volver a abrirlas. Las que no se pueden leer no salen. Si hay original sin
+// anonimizar en `sesiones/`, se abre ese en vez de la minuta exportada.
+
+pub struct SessionEntry {
+    pub path: PathBuf,
+    pub title: String,
+    pub saved_at: Option<DateTime<Local>>,
+    pub attendees: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl SessionEntry {
+    /// Todas las palabras de `query` salen en el título, un asistente o un
+    /// tema, sin mirar mayúsculas ni tildes.
+    pub fn matches(&self, query: &str) -> bool {
+        let haystack = fold(&format!("{} {} {}", self.title, self.attendees.join(" "), self.tags.join(" ")));
+        fold(query).split_whitespace().all(|word| haystack.contains(word))
+    }
+}
+
+/// Minutas JSON de `dir`, de la más reciente a la más antigua.
+pub fn list_sessions(dir: &Path, encryption: &EncryptionSettings) -> Vec<SessionEntry> {
+    let files = |dir: &Path| -> Vec<PathBuf> {
+        std::fs::read_dir(dir).map(|d| d.flatten().map(|f| f.path()).collect()).unwrap_or_default()
+    };
+    let originals = files(&dir.join(SESSION_STORE_DIR));
+    let stems: Vec<_> = originals.iter().map(|p| stem(p)).collect();
+    let exported = files(dir).into_iter().filter(|p| !stems.contains(&stem(p)));
+    let mut sessions: Vec<SessionEntry> = originals.into_iter()
+        .chain(exported)
+        .filter(|p| encryption::plain_path(p).extension().is_some_and(|e| e == "json"))
+        .filter_map(|path| {
+            let session = read_minuta_json(&path, encryption)
+                .map_err(|e| tracing::debug!("{} no se lee como minuta: {}", path.display(), e))
+                .ok()?;
+            Some(SessionEntry {
+                path,
+                title: session.title,
+                saved_at: session.saved_at,
+                attendees: session.attendees,
+                tags: session.tags,
+            })
+        })
+        .collect();
+    sessions.sort_by(|a, b| b.saved_at.cmp(&a.saved_at));
+    sessions
+}
+
+/// Nombre sin `.enc` ni extensión, para emparejar original y exportada.
+fn stem(path: &Path) -> Option<std::ffi::OsString> {
+    encryption::plain_path(path).file_stem().map(|s| s.to_os_string())
+}
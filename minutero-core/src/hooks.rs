@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use crate::data::TranscriptSegment;
+
+// ── Hooks de usuario ───────────────────────────────────────────────────────
+//
+// Órdenes de shell que se lanzan en ciertos momentos de la sesión, para
+// montar integraciones propias sin esperar a que existan en la aplicación.
+// Cada orden recibe los datos del evento en variables de entorno
+// `MINUTERO_*` y lo principal (el texto de la intervención o la ruta de la
+// minuta) también por stdin. Se ejecutan en segundo plano: un hook lento o
+// que falla no frena la captura, solo queda en el log.
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HookSettings {
+    /// Al empezar la captura.
+    pub on_capture_start: String,
+    /// Por cada intervención transcrita.
+    pub on_segment: String,
+    /// Al guardar la minuta.
+    pub on_session_saved: String,
+}
+
+/// Evento que dispara un hook.
+pub enum HookEvent<'a> {
+    CaptureStarted { title: &'a str },
+    Segment(&'a TranscriptSegment),
+    SessionSaved { path: &'a Path },
+}
+
+impl HookEvent<'_> {
+    fn name(&self) -> &'static str {
+        match self {
+            HookEvent::CaptureStarted { .. } => "capture_started",
+            HookEvent::Segment(_)            => "segment",
+            HookEvent::SessionSaved { .. }   => "session_saved",
+        }
+    }
+
+    fn command<'s>(&self, settings: &'s HookSettings) -> &'s str {
+        match self {
+            HookEvent::CaptureStarted { .. } => &settings.on_capture_start,
+            HookEvent::Segment(_)            => &settings.on_segment,
+            HookEvent::SessionSaved { .. }   => &settings.on_session_saved,
+        }
+    }
+
+    /// Variables de entorno y texto para stdin.
+    fn payload(&self) -> (Vec<(&'static str, String)>, String) {
+        match self {
+            HookEvent::CaptureStarted { title } => (vec![("MINUTERO_TITLE", title.to_string())], title.to_string()),
+            HookEvent::Segment(s) => (
+                vec![
+                    ("MINUTERO_SPEAKER", s.name.clone()),
+                    ("MINUTERO_TEXT", s.text.clone()),
+                    ("MINUTERO_START_SECS", format!("{:.2}", s.start_secs)),
+                    ("MINUTERO_END_SECS", format!("{:.2}", s.end_secs)),
+                ],
+                s.text.clone(),
+            ),
+            HookEvent::SessionSaved { path } => {
+                let path = path.display().to_string();
+                (vec![("MINUTERO_PATH", path.clone())], path)
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+#[cfg(target_os = "windows")]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+/// Lanza el hook del evento, si hay uno configurado.
+pub fn run(settings: &HookSettings, event: HookEvent) {
+    let command = event.command(settings).trim();
+    if command.is_empty() {
+        return;
+    }
+    let (env, input) = event.payload();
+    let spawned = shell(command)
+        .env("MINUTERO_EVENT", event.name())
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::error!("Hook {}: no se pudo lanzar «{}»: {}", event.name(), command, e);
+            return;
+        }
+    };
+
+    let name = event.name();
+    let command = command.to_string();
+    // Esperar en otro hilo para no dejar procesos zombi
+    thread::spawn(move || {
+        if let Some(mut stdin) = child.stdin.take() {
+            // Si el hook no lee stdin, la tubería rota no es un error
+            let _ = stdin.write_all(input.as_bytes());
+        }
+        match child.wait_with_output() {
+            Ok(output) if !output.status.success() => tracing::warn!(
+                "Hook {} («{}») terminó con {}: {}",
+                name, command, output.status, String::from_utf8_lossy(&output.stderr).trim(),
+            ),
+            Ok(_) => tracing::debug!("Hook {} ejecutado", name),
+            Err(e) => tracing::error!("Hook {}: {}", name, e),
+        }
+    });
+}
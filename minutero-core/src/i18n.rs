@@ -0,0 +1,1032 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// ── Idioma de la interfaz ──────────────────────────────────────────────────
+//
+// Tablas de cadenas embebidas: cada clave devuelve su traducción para el
+// idioma activo. El idioma es global (compartido por la UI y los hilos de
+// audio/vídeo), así que los mensajes de estado salen ya traducidos.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UiLanguage {
+    Es,
+    En,
+}
+
+impl UiLanguage {
+    pub const ALL: &'static [UiLanguage] = &[UiLanguage::Es, UiLanguage::En];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UiLanguage::Es => "Español",
+            UiLanguage::En => "English",
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(lang: UiLanguage) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn current_language() -> UiLanguage {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => UiLanguage::En,
+        _ => UiLanguage::Es,
+    }
+}
+
+/// Devuelve la cadena traducida. Si la clave no existe se devuelve tal cual,
+/// para que una traducción olvidada se vea en pantalla en vez de romper nada.
+pub fn t(key: &'static str) -> &'static str {
+    match lookup(key) {
+        Some((es, en)) => match current_language() {
+            UiLanguage::Es => es,
+            UiLanguage::En => en,
+        },
+        None => key,
+    }
+}
+
+/// `tr!("clave")` o `tr!("clave", n = 3, name = nombre)`: traduce y sustituye
+/// los marcadores `{n}`, `{name}`… por los valores dados.
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::t($key).to_string()
+    };
+    ($key:expr, $($name:ident = $val:expr),+ $(,)?) => {{
+        let mut s = $crate::i18n::t($key).to_string();
+        $( s = s.replace(concat!("{", stringify!($name), "}"), &$val.to_string()); )+
+        s
+    }};
+}
+pub use crate::tr;
+
+// ── Tablas (español, inglés) ───────────────────────────────────────────────
+
+fn lookup(key: &str) -> Option<(&'static str, &'static str)> {
+    Some(match key {
+        // Navegación
+        "nav.transcription" => ("🎙 Transcripción", "🎙 Transcription"),
+        "nav.rooms"         => ("🏢 Salas", "🏢 Rooms"),
+        "nav.rooms_count"   => ("🏢 Salas ({n} en curso)", "🏢 Rooms ({n} running)"),
+        "nav.review"        => ("🔍 Revisión", "🔍 Review"),
+        "nav.review_count"  => ("🔍 Revisión ({n})", "🔍 Review ({n})"),
+        "nav.video"         => ("🎬 Vídeo", "🎬 Video"),
+        "nav.history"       => ("🗂 Historial", "🗂 History"),
+        "nav.actions"       => ("✅ Tareas", "✅ Action items"),
+        "nav.stats"         => ("📊 Estadísticas", "📊 Stats"),
+        "nav.log"           => ("📜 Log", "📜 Log"),
+        "nav.settings"      => ("⚙️ Configuración", "⚙️ Settings"),
+        "nav.model"         => ("Modelo: ggml-{model}.bin", "Model: ggml-{model}.bin"),
+        "nav.model_custom"  => ("Modelo: {file}", "Model: {file}"),
+        "nav.offline"       => ("📴 Sin conexión", "📴 Offline"),
+
+        // Comunes
+        "common.status"     => ("Estado:", "Status:"),
+        "common.clear"      => ("🗑️ Limpiar", "🗑️ Clear"),
+        "common.save"       => ("💾 Guardar", "💾 Save"),
+        "common.close"      => ("Cerrar", "Close"),
+        "common.cancel"     => ("Cancelar", "Cancel"),
+        "common.error"      => ("❌ Error: {e}", "❌ Error: {e}"),
+        "common.save_error" => ("❌ Error al guardar: {e}", "❌ Error while saving: {e}"),
+        "common.saved_to"   => ("✅ Guardado en: {path}", "✅ Saved to: {path}"),
+        "common.nothing_to_save" => ("No hay transcripción para guardar.", "There is no transcript to save."),
+        "common.speaker"    => ("Interlocutor {n}", "Speaker {n}"),
+        "common.model_label" => ("Modelo:", "Model:"),
+
+        // Transcripción en tiempo real
+        "live.heading"         => ("🎙️ Transcripción en Tiempo Real", "🎙️ Real-Time Transcription"),
+        "live.placeholder"     => ("El texto transcrito aparecerá aquí.", "Transcribed text will appear here."),
+        "live.idle"            => ("Presiona 'Iniciar Captura' para comenzar.", "Press 'Start Capture' to begin."),
+        "live.model"           => ("Modelo Whisper:", "Whisper model:"),
+        "live.start"           => ("▶ Iniciar Captura", "▶ Start Capture"),
+        "live.stop"            => ("⏹ Detener Captura", "⏹ Stop Capture"),
+        "live.stopping"        => ("⏳ Deteniendo...", "⏳ Stopping..."),
+        "live.no_sources"      => ("❌ Debe añadir y activar al menos una fuente.", "❌ Add and enable at least one source."),
+        "live.no_active"       => ("❌ Active al menos un interlocutor en Configuración.", "❌ Enable at least one speaker in Settings."),
+        "live.starting"        => ("Iniciando {n} fuentes de audio...", "Starting {n} audio sources..."),
+        "live.streams_running" => ("▶ Capturando {n} fuentes [{source}→{dest}]", "▶ Capturing {n} sources [{source}→{dest}]"),
+        "live.mute_hint"       => ("Silenciar: deja de transcribir esta fuente sin cerrarla (se sigue grabando)", "Mute: stop transcribing this source without closing it (it keeps recording)"),
+        "live.solo_hint"       => ("Solo: transcribir únicamente las fuentes en solo", "Solo: transcribe only the sources in solo"),
+        "live.not_transcribing" => ("sin transcribir", "not transcribing"),
+        "live.waveform_hint"   => ("Nivel de los últimos {secs} s. Si está plana, no llega audio de esta fuente; si hay señal y no aparece texto, mire el umbral de no-voz o si la fuente está silenciada.", "Level over the last {secs} s. Flat means no audio is reaching this source; if there is signal but no text, check the no-speech threshold or whether the source is muted."),
+        "health.heading"       => ("🩺 Estado de las fuentes", "🩺 Source health"),
+        "health.source"        => ("Fuente", "Source"),
+        "health.device"        => ("Dispositivo", "Device"),
+        "health.last_audio"    => ("Último audio", "Last audio"),
+        "health.backlog"       => ("En espera", "Backlog"),
+        "compare.label"        => ("Comparar con:", "Compare with:"),
+        "compare.none"         => ("—", "—"),
+        "compare.hint"         => (
+            "Transcribe el mismo audio también con este modelo y muestra los dos textos lado a lado, para elegir entre precisión y velocidad. Duplica el trabajo de Whisper: úselo para evaluar, no en reuniones largas.",
+            "Also transcribes the same audio with this model and shows both texts side by side, to help choose between accuracy and speed. Doubles Whisper's work: use it for evaluation, not long meetings.",
+        ),
+        "compare.heading"      => ("⚖ Comparación de modelos", "⚖ Model comparison"),
+        "compare.summary"      => ("RTF medio: {model} {rtf} · {compared} {compared_rtf} (menos de 1 = tiempo real)", "Mean RTF: {model} {rtf} · {compared} {compared_rtf} (below 1 = real time)"),
+        "health.latency"       => ("Retraso", "Latency"),
+        "speech.read"          => ("🔊 Leer datos de la sesión", "🔊 Read session details"),
+        "speech.stop"          => ("⏹ Parar lectura", "⏹ Stop reading"),
+        "summary.copy"         => ("🧠 Copiar petición de resumen", "🧠 Copy summary prompt"),
+        "summary.copy_hint"    => ("Copia la plantilla de resumen elegida, rellenada con la transcripción, para pegarla en el modelo de lenguaje que uséis. Es la petición, no un resumen: Minutero no los genera.", "Copies the chosen summary template, filled in with the transcript, to paste into the language model you use. It is the prompt, not a summary: Minutero does not generate them."),
+        "summary.error"        => ("❌ Error en la plantilla de resumen: {e}", "❌ Error in the summary template: {e}"),
+        "summary.heading"      => ("🧠 Plantillas de resumen", "🧠 Summary templates"),
+        "summary.hint"         => ("Una por tipo de reunión (daily, retrospectiva, llamada con cliente). Son plantillas Tera con las mismas variables que las de exportación: {{ title }}, {{ date }}, {{ attendees }}, {{ tags }}, {{ body }}, {{ segments }}, {{ stats_table }}…", "One per kind of meeting (standup, retro, client call). They are Tera templates with the same variables as the export ones: {{ title }}, {{ date }}, {{ attendees }}, {{ tags }}, {{ body }}, {{ segments }}, {{ stats_table }}…"),
+        "summary.new"          => ("➕ Nueva", "➕ New"),
+        "summary.restore"      => ("Restaurar las de serie", "Restore defaults"),
+        "summary.name"         => ("Nombre:", "Name:"),
+        "summary.default_name" => ("Plantilla {n}", "Template {n}"),
+        "summary.meeting"      => ("Reunión", "Meeting"),
+        "summary.general_name" => ("General", "General"),
+        "summary.general_prompt" => (
+            "Resume esta reunión en el idioma de la transcripción: los temas tratados, las decisiones y las tareas con su responsable.",
+            "Summarize this meeting in the language of the transcript: the topics discussed, the decisions and the tasks with their owner.",
+        ),
+        "summary.daily_name"   => ("Daily", "Standup"),
+        "summary.daily_prompt" => (
+            "Resume esta daily en el idioma de la transcripción: para cada persona, qué hizo, qué hará y qué la bloquea. Sé breve.",
+            "Summarize this standup in the language of the transcript: for each person, what they did, what they will do and what blocks them. Be brief.",
+        ),
+        "summary.retro_name"   => ("Retrospectiva", "Retrospective"),
+        "summary.retro_prompt" => (
+            "Resume esta retrospectiva en el idioma de la transcripción: qué fue bien, qué fue mal y las acciones de mejora acordadas.",
+            "Summarize this retrospective in the language of the transcript: what went well, what went wrong and the agreed improvement actions.",
+        ),
+        "summary.client_name"  => ("Cliente", "Client"),
+        "summary.client_prompt" => (
+            "Resume esta llamada con un cliente en el idioma de la transcripción: sus necesidades, las objeciones, lo que se le ha prometido y los próximos pasos.",
+            "Summarize this client call in the language of the transcript: their needs, the objections, what they were promised and the next steps.",
+        ),
+        "speech.read_hint"     => (
+            "Lee en voz alta los datos de la sesión (título, fecha, asistentes y tiempo de palabra de cada uno) con el sintetizador del sistema. No es un resumen del contenido.",
+            "Reads the session details aloud (title, date, attendees and each one's speaking time) with the system speech synthesizer. It is not a summary of the content.",
+        ),
+        "speech.no_engine"     => (
+            "No se encuentra un sintetizador de voz. Instale espeak-ng o speech-dispatcher (spd-say)",
+            "No speech synthesizer found. Install espeak-ng or speech-dispatcher (spd-say)",
+        ),
+        "speech.error"         => ("Error iniciando {program}: {e}", "Failed to start {program}: {e}"),
+        "edits.as_edited"      => ("✏ Corregida", "✏ As edited"),
+        "edits.as_transcribed" => ("🎙 Como se transcribió", "🎙 As transcribed"),
+        "edits.as_transcribed_hint" => (
+            "Muestra el texto tal cual salió de Whisper, sin las correcciones a mano. La minuta JSON guarda los dos, con el historial de correcciones.",
+            "Shows the text exactly as Whisper produced it, without manual corrections. The JSON minutes keep both, with the correction history.",
+        ),
+        "edits.original"       => ("Original: {text}", "Original: {text}"),
+        "edits.entry"          => ("{at}: {text}", "{at}: {text}"),
+        "health.latency_hint"  => (
+            "Tiempo desde que se capta el audio hasta que aparece su texto. Verde: va al día; amarillo: se retrasa; rojo: el equipo no da abasto con este modelo (pruebe uno más pequeño o fragmentos más cortos).",
+            "Time from audio being captured to its text appearing. Green: keeping up; yellow: falling behind; red: this hardware can't keep up with the model (try a smaller model or shorter chunks).",
+        ),
+        "health.rtf"           => ("RTF", "RTF"),
+        "health.error"         => ("Último error", "Last error"),
+        "health.now"           => ("ahora", "now"),
+        "health.seconds_ago"   => ("hace {secs} s", "{secs} s ago"),
+        "health.stream_failed" => ("⚠ Ha fallado una fuente: detalles en «Estado de las fuentes»", "⚠ A source failed: see \"Source health\" for details"),
+        "health.restarts"      => ("Si falla", "On failure"),
+        "health.restart_in"    => ("reintento {n}/{max} en {secs} s", "retry {n}/{max} in {secs} s"),
+        "health.gave_up"       => ("sin más reintentos ({max})", "no retries left ({max})"),
+        "restart.continue"     => ("seguir sin ella", "continue without it"),
+        "restart.retry"        => ("reintentar", "retry"),
+        "restart.fail_session" => ("parar la sesión", "stop the session"),
+        "restart.hint"         => (
+            "Qué hacer si falla la captura de esta fuente: seguir la sesión sin ella, volver a abrirla (esperando 1 s, 2 s, 4 s… entre intentos) o parar toda la sesión y guardar la minuta.",
+            "What to do if capturing this source fails: continue the session without it, reopen it (waiting 1 s, 2 s, 4 s… between attempts) or stop the whole session and save the minutes.",
+        ),
+        "restart.max_hint"     => ("Reintentos seguidos antes de rendirse", "Consecutive retries before giving up"),
+        "restart.retrying"     => ("⟳ {name}: reintento {n}/{max} en {secs} s", "⟳ {name}: retry {n}/{max} in {secs} s"),
+        "restart.gave_up"      => ("⚠ {name}: sin más reintentos tras {n}; la sesión sigue sin esta fuente", "⚠ {name}: no retries left after {n}; the session continues without this source"),
+        "restart.session_failed" => ("⛔ {name} ha fallado y su perfil pide parar la sesión", "⛔ {name} failed and its profile asks to stop the session"),
+        "live.recording_error" => ("⚠ Transcribiendo sin grabación: {e}", "⚠ Transcribing without recording: {e}"),
+        "live.preroll_active"  => (
+            "⏺ Pre-grabación: se guardan en memoria los últimos {secs} s de los dispositivos activos",
+            "⏺ Pre-roll: the last {secs} s of the active devices are kept in memory",
+        ),
+        "preroll.error"        => ("⚠ Pre-grabación: {e}", "⚠ Pre-roll: {e}"),
+        "live.stopped_saving"  => ("Captura detenida. Guardando minuta...", "Capture stopped. Saving minutes..."),
+        "live.transcript_label" => ("📝 Minuta (Interlocutor) Texto:", "📝 Minutes (Speaker) Text:"),
+
+        // Vídeo
+        "video.heading"      => ("🎬 Transcripción de Vídeo / Audio", "🎬 Video / Audio Transcription"),
+        "video.idle"         => ("Selecciona un archivo de vídeo o audio.", "Select a video or audio file."),
+        "video.pick"         => ("📂 Seleccionar archivo", "📂 Select file"),
+        "video.filter"       => ("Vídeo / Audio", "Video / Audio"),
+        "video.selected"     => ("Archivo seleccionado. Listo para transcribir.", "File selected. Ready to transcribe."),
+        "video.no_file"      => ("Sin archivo seleccionado", "No file selected"),
+        "video.cancel"       => ("⏹ Cancelar", "⏹ Cancel"),
+        "video.transcribe"   => ("▶ Transcribir", "▶ Transcribe"),
+        "video.transcript_label" => ("📝 Transcripción [HH:MM:SS] texto:", "📝 Transcript [HH:MM:SS] text:"),
+        "video.starting"     => ("Iniciando...", "Starting..."),
+        "video.done"         => ("✅ Transcripción completada.", "✅ Transcription finished."),
+        "video.checking_model" => ("Verificando modelo...", "Checking model..."),
+        "video.extracting"   => ("Extrayendo audio con ffmpeg...", "Extracting audio with ffmpeg..."),
+        "video.ffmpeg_start" => ("Error iniciando ffmpeg: {e}\n¿Está ffmpeg instalado?", "Failed to start ffmpeg: {e}\nIs ffmpeg installed?"),
+        "video.ffmpeg_stdout" => ("No se pudo obtener stdout de ffmpeg", "Could not read ffmpeg stdout"),
+        "video.ffmpeg_empty" => ("ffmpeg no produjo audio. ¿Es un archivo de vídeo/audio válido?", "ffmpeg produced no audio. Is this a valid video/audio file?"),
+        "video.extracted"    => ("Audio extraído: {duration} ({samples} muestras). Cargando modelo...", "Audio extracted: {duration} ({samples} samples). Loading model..."),
+        "video.cancelled"    => ("Transcripción cancelada.", "Transcription cancelled."),
+        "video.chunk"        => ("Fragmento {i}/{total} [{time}]", "Chunk {i}/{total} [{time}]"),
+
+        // Estadísticas
+        "history.heading"     => ("🗂 Sesiones guardadas", "🗂 Saved sessions"),
+        "history.search"      => ("Buscar:", "Search:"),
+        "history.search_hint" => ("título, asistente o tema", "title, attendee or topic"),
+        "history.refresh"     => ("🔄 Actualizar", "🔄 Refresh"),
+        "history.empty"       => ("No hay minutas JSON en {path}. El historial lista las minutas guardadas en formato JSON.", "No JSON minutes in {path}. The history lists minutes saved as JSON."),
+        "history.date"        => ("Fecha", "Date"),
+        "history.title"       => ("Título", "Title"),
+        "history.tag_hint"    => ("Ver las sesiones con este tema", "Show sessions with this topic"),
+        "history.open"        => ("📂 Abrir", "📂 Open"),
+        "actions.heading"     => ("✅ Tareas de las reuniones", "✅ Meeting action items"),
+        "actions.open_count"  => ("{n} abiertas", "{n} open"),
+        "actions.show_done"   => ("Mostrar las hechas", "Show completed"),
+        "actions.empty"       => ("Todavía no hay tareas. Se apuntan al guardar cada minuta: «me encargo de…», «Ana se ocupa de…», «hay que…», «action item: …».", "No action items yet. They are picked up when each minutes file is saved: \"I'll…\", \"Ana will…\", \"we need to…\", \"action item: …\"."),
+        "actions.task"        => ("Tarea", "Task"),
+        "actions.owner"       => ("Responsable", "Owner"),
+        "actions.due"         => ("Plazo", "Due"),
+        "actions.session"     => ("Reunión", "Meeting"),
+        "actions.remove"      => ("Borrar (no era una tarea)", "Delete (not an action item)"),
+        "actions.agenda"      => ("📋 Orden del día", "📋 Agenda"),
+        "actions.agenda_hint" => ("Guarda en la carpeta de salida un orden del día en Markdown con las tareas abiertas, para la próxima reunión.", "Saves a Markdown agenda with the open action items to the output folder, for the next meeting."),
+        "actions.agenda_template" => ("Plantilla del orden del día:", "Agenda template:"),
+        "actions.agenda_title" => ("Orden del día", "Agenda"),
+        "actions.agenda_file" => ("orden_del_dia", "agenda"),
+        "actions.open_heading" => ("Tareas pendientes", "Open action items"),
+        "actions.track"       => ("Apuntar las tareas de cada minuta", "Track action items from each minutes file"),
+        "actions.track_hint"  => ("Al guardar, las frases con un compromiso («me encargo de…», «hay que…») pasan a la pestaña Tareas con su responsable y plazo, junto con las de reuniones anteriores.", "When saving, sentences with a commitment (\"I'll…\", \"we need to…\") go to the Action items tab with their owner and due date, alongside those from earlier meetings."),
+        "stats.heading"       => ("📊 Estadísticas de participación", "📊 Participation statistics"),
+        "stats.empty"         => ("Aún no hay segmentos transcritos.", "No transcribed segments yet."),
+        "stats.speaker"       => ("Interlocutor", "Speaker"),
+        "stats.speaking_time" => ("Tiempo de palabra", "Speaking time"),
+        "stats.share"         => ("Reparto", "Share"),
+        "stats.words"         => ("Palabras", "Words"),
+        "stats.longest"       => ("Intervención más larga", "Longest monologue"),
+        "stats.interruptions" => ("Interrupciones", "Interruptions"),
+        "stats.hint"          => (
+            "Calculado a partir de los tiempos de cada segmento; los valores son aproximados.",
+            "Computed from segment timestamps; values are approximate.",
+        ),
+
+        // Configuración
+        "settings.heading"       => ("⚙️ Configuración de Interlocutores y Audio", "⚙️ Speaker and Audio Settings"),
+        "settings.ui_language"   => ("🗣 Idioma de la interfaz:", "🗣 Interface language:"),
+        "settings.theme"         => ("🎨 Tema:", "🎨 Theme:"),
+        "settings.theme_system"  => ("Según el sistema", "Follow system"),
+        "settings.theme_light"   => ("Claro", "Light"),
+        "settings.theme_dark"    => ("Oscuro", "Dark"),
+        "settings.font_size"     => ("🔠 Tamaño de letra de la minuta:", "🔠 Transcript font size:"),
+        "settings.tab_general"   => ("General", "General"),
+        "settings.tab_postprocessing" => ("✏️ Post-procesado", "✏️ Post-processing"),
+        "settings.tab_integrations" => ("🔗 Integraciones", "🔗 Integrations"),
+        "settings.vocabulary"    => ("📖 Vocabulario propio", "📖 Custom vocabulary"),
+        "settings.preset"        => ("Preset:", "Preset:"),
+        "settings.preset_new"    => ("➕ Nuevo", "➕ New"),
+        "settings.preset_name"   => ("Nombre:", "Name:"),
+        "settings.preset_default_name" => ("Preset {n}", "Preset {n}"),
+        "settings.glossary"      => ("Glosario (nombres de producto, siglas…), uno por línea:", "Glossary (product names, acronyms…), one per line:"),
+        "settings.glossary_hint" => ("Kubernetes\nOKR\nMinutero", "Kubernetes\nOKR\nMinutero"),
+        "settings.glossary_corrections" => ("Corregir también la grafía de estos términos en el texto", "Also fix the spelling of these terms in the text"),
+        "settings.save_error"    => ("❌ No se pudieron guardar las preferencias: {e}", "❌ Could not save preferences: {e}"),
+        "settings.language"      => ("🌐 Idioma", "🌐 Language"),
+        "settings.source_lang"   => ("Idioma original:", "Source language:"),
+        "settings.dest_lang"     => ("Idioma destino:", "Target language:"),
+        "settings.translate_hint" => ("ℹ Whisper solo puede traducir al inglés de forma nativa.", "ℹ Whisper can only translate into English natively."),
+        "multilingual.enabled"   => ("Reunión multilingüe: detectar el idioma de cada intervención", "Multilingual meeting: detect the language of each utterance"),
+        "multilingual.hint"      => ("Cada fragmento se transcribe en el idioma que detecta Whisper y se etiqueta con él. Ignora el idioma original elegido salvo para la traducción.", "Each chunk is transcribed in the language Whisper detects and tagged with it. The chosen source language is only used for translation."),
+        "multilingual.translate_others" => ("Traducir al inglés lo que no esté en el idioma original", "Translate into English whatever is not in the source language"),
+        "settings.loopback_btn"  => ("📊 Configurar Captura de Salida", "📊 Configure Output Capture"),
+        "settings.loopback_count" => ("✅ {n} dispositivos loopback", "✅ {n} loopback devices"),
+        "settings.no_output"     => ("⚠️ Sin dispositivos de salida", "⚠️ No output devices"),
+        "settings.add_source"    => ("Añadir nueva fuente de audio:", "Add a new audio source:"),
+        "settings.add_input"     => ("➕ Entrada (Micrófono)", "➕ Input (Microphone)"),
+        "settings.add_output"    => ("➕ Salida (Loopback)", "➕ Output (Loopback)"),
+        "settings.add_network"   => ("➕ Red (URL)", "➕ Network (URL)"),
+        "settings.add_pipe"      => ("➕ Tubería (PCM)", "➕ Pipe (PCM)"),
+        "settings.add_discord"   => ("➕ Discord (canal de voz)", "➕ Discord (voice channel)"),
+        "settings.pipe_stdin"    => ("- (stdin)", "- (stdin)"),
+        "settings.pipe_hint"     => (
+            "PCM crudo sin cabecera desde stdin (vacío o -) o desde una tubería con nombre (mkfifo). Indica el formato, la frecuencia y los canales con que escribe el otro programa.",
+            "Raw headerless PCM from stdin (empty or -) or from a named pipe (mkfifo). Set the encoding, rate and channels the other program writes.",
+        ),
+        "settings.network_url_hint" => (
+            "Radio por internet, Icecast, HLS, rtp://… o un archivo .sdp. Se decodifica con ffmpeg, que tiene que estar en el PATH.",
+            "Internet radio, Icecast, HLS, rtp://… or an .sdp file. Decoded with ffmpeg, which must be on the PATH.",
+        ),
+        "settings.loopback_first" => ("⚠️ Configure dispositivos loopback primero", "⚠️ Configure loopback devices first"),
+        "settings.profiles"      => ("Perfiles Activos:", "Active Profiles:"),
+        "settings.stop_to_edit"  => ("⚠️ Detenga la captura para cambiar la configuración.", "⚠️ Stop the capture to change the settings."),
+        "settings.recording"     => ("🎙 Grabar la reunión completa", "🎙 Record the whole meeting"),
+        "settings.recording_hint" => (
+            "Mezcla todas las fuentes activas en un archivo junto a las minutas y lo enlaza en la minuta. OGG necesita ffmpeg.",
+            "Mixes every active source into one file next to the minutes and links it from them. OGG requires ffmpeg.",
+        ),
+        "settings.preroll"      => ("⏺ Pre-grabación", "⏺ Pre-roll"),
+        "settings.preroll_hint" => (
+            "Mientras no hay captura, mantiene abiertos los dispositivos de los perfiles activos y guarda sus últimos segundos en memoria (nunca en disco). Al iniciar la captura ese audio se transcribe primero, así no se pierde el principio de la reunión. Desactívalo si no quieres que se escuche sin haber pulsado Iniciar.",
+            "While not capturing, keeps the devices of the active profiles open and holds their last seconds in memory (never on disk). When capture starts that audio is transcribed first, so the start of the meeting is not lost. Turn it off if you do not want anything listened to before pressing Start.",
+        ),
+        "split.label"           => ("Minuta por bloques:", "Minutes in blocks:"),
+        "split.hint"            => ("En jornadas largas, guarda la minuta y empieza otra sin parar la captura. 0 = desactivado.", "For long days, saves the minutes and starts new ones without stopping capture. 0 = off."),
+        "split.every_hours"     => ("Bloque nuevo cada tantas horas", "New block every this many hours"),
+        "split.after_silence"   => ("Bloque nuevo tras tantos minutos sin intervenciones", "New block after this many minutes without speech"),
+        "split.block_title"     => ("{title} (bloque {n})", "{title} (block {n})"),
+        "split.saved"           => ("Bloque {n} de la minuta guardado", "Minutes block {n} saved"),
+        "split.status_duration" => ("📑 Bloque {n} guardado por duración; sigue la captura en un bloque nuevo", "📑 Block {n} saved (duration); capture continues in a new block"),
+        "split.status_silence"  => ("📑 Bloque {n} guardado tras un silencio largo; sigue la captura en un bloque nuevo", "📑 Block {n} saved after a long silence; capture continues in a new block"),
+        "resume.button"         => ("⏯ Continuar minuta", "⏯ Continue minutes"),
+        "resume.hint"           => ("Sigue capturando en la minuta en pantalla (o en la abierta con «Abrir sesión»): los tiempos siguen contando desde el principio de la sesión y queda marcada la pausa.", "Keep capturing into the minutes on screen (or the ones opened with \"Open session\"): timestamps keep counting from the start of the session and the break is marked."),
+        "resume.marker"         => ("⏸ Pausa de {mins} min — se reanuda a las {time}", "⏸ {mins} min break — resumed at {time}"),
+        "settings.recording_tracks" => ("Pista por interlocutor", "Track per speaker"),
+        "settings.recording_tracks_hint" => (
+            "Guarda además un WAV por fuente en <grabación>_pistas/ para poder re-transcribir la sesión con otro modelo.",
+            "Also saves one WAV per source in <recording>_pistas/ so the session can be re-transcribed with another model.",
+        ),
+        "dataset.enabled"        => ("Corpus para afinar Whisper", "Fine-tuning corpus"),
+        "dataset.hint"           => (
+            "Al guardar, recorta cada intervención de la pista de su interlocutor y la añade con su texto a la carpeta indicada, en formato LJSpeech (wavs/ + metadata.csv) o Common Voice (clips/ + train.tsv).",
+            "On save, cuts every segment out of its speaker's track and appends it with its text to the given folder, in LJSpeech (wavs/ + metadata.csv) or Common Voice (clips/ + train.tsv) layout.",
+        ),
+        "dataset.only_corrected" => ("Solo corregidas o revisadas", "Only corrected or reviewed"),
+        "dataset.needs_tracks"   => ("⚠ Necesita la grabación con pista por interlocutor", "⚠ Needs the recording with one track per speaker"),
+        "settings.output_dir"    => ("Ruta de guardado de minutas:", "Minutes output folder:"),
+        "settings.output_dir_not_writable" => ("⚠ No se puede escribir en la carpeta: {e}", "⚠ Folder is not writable: {e}"),
+        "settings.device_missing" => ("Dispositivo no encontrado", "Device not found"),
+        "settings.device_missing_named" => ("⚠ No conectado: {name}", "⚠ Not connected: {name}"),
+        "devices.aliases"      => ("Alias de dispositivos", "Device aliases"),
+        "roster.heading"       => ("👥 Participantes habituales", "👥 Regular participants"),
+        "roster.hint"          => ("Al escribir el nombre de un interlocutor se proponen estos participantes; al elegir uno se pone también su dispositivo habitual. El cargo aparece junto al nombre en los asistentes de la minuta.", "Typing a speaker's name suggests these participants; picking one also selects their usual device. The role is shown next to the name in the minutes' attendees."),
+        "roster.name"          => ("Nombre", "Name"),
+        "roster.role"          => ("Cargo", "Role"),
+        "roster.device"        => ("Dispositivo habitual", "Usual device"),
+        "roster.voice"         => ("Voz", "Voice"),
+        "speaker_id.profile_hint" => ("Varias personas por esta fuente: poner en cada intervención el nombre del participante habitual cuya voz se reconozca (hacen falta al menos dos voces registradas).", "Several people on this source: label each segment with the regular participant whose voice is recognized (needs at least two enrolled voices)."),
+        "speaker_id.forget"    => ("Voz registrada. Pulsa para borrarla.", "Voice enrolled. Click to delete it."),
+        "speaker_id.threshold" => ("Parecido mínimo de la voz:", "Minimum voice similarity:"),
+        "speaker_id.threshold_hint" => ("Más alto: menos errores, pero más intervenciones se quedan con el nombre de la fuente.", "Higher: fewer mistakes, but more segments keep the source's name."),
+        "roster.add"           => ("➕ Añadir participante", "➕ Add participant"),
+        "roster.remember"      => ("Guardar como participante habitual, con este dispositivo", "Save as a regular participant, with this device"),
+        "roster.remembered"    => ("⭐ {name} guardado en los participantes habituales", "⭐ {name} saved to regular participants"),
+        "enroll.open"          => ("Registrar la voz leyendo un párrafo", "Enroll the voice by reading a paragraph"),
+        "enroll.title"         => ("🎙 Registrar la voz de {name}", "🎙 Enroll {name}'s voice"),
+        "enroll.intro"         => ("Se grabarán {secs} s por el micrófono elegido. Pulsa «Empezar» y lee el párrafo en voz alta, con tu tono habitual y sin prisa.", "{secs} s will be recorded from the chosen microphone. Press «Start» and read the paragraph aloud, in your usual tone and without hurrying."),
+        "enroll.microphone"    => ("Micrófono:", "Microphone:"),
+        "enroll.reading"       => ("🔴 Grabando… lee el párrafo en voz alta", "🔴 Recording… read the paragraph aloud"),
+        "enroll.paragraph"     => ("El jueves por la mañana revisamos el presupuesto del próximo trimestre. Hubo dudas sobre los gastos de viaje y sobre la fecha de entrega del proyecto, así que quedamos en volver a reunirnos la semana que viene con las cifras actualizadas. Mientras tanto, cada equipo enviará un breve resumen de lo que lleva hecho y de lo que le falta.", "On Thursday morning we reviewed the budget for next quarter. There were questions about travel expenses and the project's delivery date, so we agreed to meet again next week with updated figures. In the meantime, each team will send a short summary of what it has done and what is still missing."),
+        "enroll.start"         => ("▶ Empezar", "▶ Start"),
+        "enroll.retry"         => ("🔄 Repetir", "🔄 Record again"),
+        "enroll.done"          => ("✅ Voz de {name} registrada", "✅ {name}'s voice enrolled"),
+        "enroll.error"         => ("❌ No se pudo registrar la voz: {e}", "❌ Could not enroll the voice: {e}"),
+        "enroll.too_little_voice" => ("apenas se oye voz en la grabación", "there is hardly any voice in the recording"),
+        "devices.aliases_hint" => ("Nombre propio para cada dispositivo, que es el que se ve en las listas. Los interlocutores se guardan por el nombre técnico del dispositivo (pasa el ratón por encima para verlo), no por su posición.", "Your own name for each device, shown in the lists. Speakers are saved by the device's technical name (hover to see it), not by its position."),
+        "devices.alias_hint"   => ("Alias (opcional)", "Alias (optional)"),
+        "devices.streams"      => ("Búfer de captura", "Capture buffer"),
+        "devices.streams_hint" => ("Para interfaces que dan cortes (y transcripciones sin sentido): un búfer más grande aguanta mejor los picos de carga. Solo afecta a los dispositivos que se abren directamente (WASAPI, CoreAudio, ALSA), no a PulseAudio/PipeWire.", "For interfaces with dropouts (and garbled transcripts): a larger buffer copes better with load spikes. Only affects devices opened directly (WASAPI, CoreAudio, ALSA), not PulseAudio/PipeWire."),
+        "devices.buffer_default" => ("Del sistema", "System default"),
+        "devices.buffer_frames" => ("{n} tramas ({ms} ms a 48 kHz)", "{n} frames ({ms} ms at 48 kHz)"),
+        "devices.exclusive"    => ("Exclusivo", "Exclusive"),
+        "devices.exclusive_hint" => ("Modo exclusivo de WASAPI: el dispositivo se abre en su formato nativo, sin el mezclador de Windows. Ninguna otra aplicación podrá usarlo mientras se captura.", "WASAPI exclusive mode: the device is opened in its native format, bypassing the Windows mixer. No other application can use it while capturing."),
+        "devices.missing"      => ("⚠ Dispositivo no conectado para: {names}. Conéctelo o elija otro en Configuración.", "⚠ Device not connected for: {names}. Plug it in or pick another one in Settings."),
+        "devices.missing_capture" => ("❌ No se puede empezar: falta el dispositivo de {names}.", "❌ Cannot start: the device for {names} is missing."),
+        "device_test.hint"      => ("Probar dispositivo: graba {secs} s y los reproduce por la salida predeterminada", "Test device: records {secs} s and plays them back through the default output"),
+        "device_test.recording" => ("🎙 Grabando {secs} s de {name}… hable ahora", "🎙 Recording {secs} s from {name}… speak now"),
+        "device_test.playing"   => ("🔊 Reproduciendo lo que llegó de {name}", "🔊 Playing back what {name} picked up"),
+        "device_test.quiet"     => ("⚠ Apenas llega señal de {name}: ¿es el dispositivo correcto o está silenciado?", "⚠ Almost no signal from {name}: is it the right device, or is it muted?"),
+        "device_test.no_audio"  => ("No llegó audio del dispositivo", "No audio arrived from the device"),
+        "device_test.error"     => ("❌ Prueba de {name}: {e}", "❌ Testing {name}: {e}"),
+        "settings.audio_host"    => ("Sistema de audio:", "Audio system:"),
+        "settings.audio_host_auto" => ("Automático", "Automatic"),
+        "settings.audio_host_hint" => (
+            "Host de cpal para los micrófonos. Automático usa PulseAudio/PipeWire en Linux y WASAPI/CoreAudio en Windows/macOS. ASIO y JACK aparecen si cpal se compiló con la feature correspondiente.",
+            "cpal host for microphones. Automatic uses PulseAudio/PipeWire on Linux and WASAPI/CoreAudio on Windows/macOS. ASIO and JACK appear when cpal is built with the matching feature.",
+        ),
+        "settings.capture_tool"  => ("Programa de captura:", "Capture program:"),
+        "settings.capture_tool_auto" => ("Automático", "Automatic"),
+        "settings.capture_tool_hint" => (
+            "Programa con el que se graban las fuentes de PulseAudio/PipeWire. Automático prueba el nativo del servidor (pw-record en PipeWire, parecord en PulseAudio) y, si falla, los demás; ffmpeg queda como último recurso. Con ffmpeg elegido y sin servidor de sonido, los dispositivos de ALSA también se graban con él.",
+            "Program used to record PulseAudio/PipeWire sources. Automatic tries the server's native one first (pw-record on PipeWire, parecord on PulseAudio) and falls back to the others; ffmpeg is the last resort. With ffmpeg selected and no sound server, ALSA devices are recorded with it too.",
+        ),
+        "settings.agc_hint"      => (
+            "Control automático de ganancia: iguala el volumen de forma continua sin subir el ruido en los silencios. Desactívalo si la fuente ya llega nivelada.",
+            "Automatic gain control: evens out the volume continuously without boosting noise during silences. Turn it off if the source is already levelled.",
+        ),
+        "settings.channel_mix"   => ("Mezcla", "Mix"),
+        "settings.channel_n"     => ("Canal {n}", "Channel {n}"),
+        "settings.split_channels" => ("Dividir en {n} interlocutores, uno por canal", "Split into {n} speakers, one per channel"),
+        "settings.channel_speaker" => ("{name} (canal {n})", "{name} (channel {n})"),
+
+        // Alertas
+        "alerts.heading"       => ("🔔 Alertas por palabra clave", "🔔 Keyword alerts"),
+        "alerts.keywords"      => ("Palabras vigiladas (una por línea):", "Watched keywords (one per line):"),
+        "alerts.keywords_hint" => ("presupuesto\naction item", "budget\naction item"),
+        "alerts.notify"        => ("Mostrar notificación de escritorio", "Show a desktop notification"),
+        "alerts.title"         => ("🔔 Mencionado: {keywords}", "🔔 Mentioned: {keywords}"),
+
+        // Rendimiento de Whisper
+        "tuning.heading"     => ("🛠 Avanzado: rendimiento de Whisper", "🛠 Advanced: Whisper performance"),
+        "tuning.threads"     => ("Hilos:", "Threads:"),
+        "tuning.auto"        => ("auto", "auto"),
+        "tuning.beam_size"   => ("Ancho de beam:", "Beam size:"),
+        "tuning.best_of"     => ("Best of (greedy):", "Best of (greedy):"),
+        "tuning.temperature" => ("Temperatura:", "Temperature:"),
+        "tuning.no_speech"   => ("Umbral de no-voz:", "No-speech threshold:"),
+        "tuning.no_speech_hint" => (
+            "Probabilidad de silencio a partir de la cual Whisper descarta un fragmento. Más bajo = menos texto inventado sobre el silencio, pero puede perder voz débil.",
+            "Silence probability above which Whisper drops a chunk. Lower = less made-up text over silence, but quiet speech may be lost.",
+        ),
+        "tuning.filter_hallucinations" => ("Descartar frases que Whisper inventa sobre el silencio", "Drop phrases Whisper makes up over silence"),
+        "tuning.hallucinations_hint" => (
+            "Una frase por línea. Se descarta cualquier fragmento que la contenga, sin distinguir mayúsculas ni signos.",
+            "One phrase per line. Any chunk containing it is dropped, ignoring case and punctuation.",
+        ),
+        "tuning.hallucinations_restore" => ("Lista por defecto", "Default list"),
+        "tuning.chunk"       => ("Duración de fragmento:", "Chunk length:"),
+        "tuning.overlap"     => ("Solape:", "Overlap:"),
+        "tuning.max_latency" => ("Retraso máximo:", "Max latency:"),
+        "tuning.unlimited"   => ("sin límite", "unlimited"),
+        "tuning.energy_saver" => ("Reposo tras silencio:", "Rest after silence:"),
+        "tuning.energy_saver_hint" => (
+            "Tras estos segundos sin voz, la fuente deja de pasar por Whisper y consulta el audio con menos frecuencia hasta que vuelva a oírse algo. Ahorra CPU y batería en portátiles durante los silencios largos.",
+            "After this many seconds without speech, the source stops going through Whisper and polls audio less often until something is heard again. Saves CPU and battery on laptops during long silences.",
+        ),
+        "tuning.off"         => ("desactivado", "off"),
+        "tuning.workers"     => ("Trabajadores compartidos:", "Shared workers:"),
+        "tuning.workers_per_stream" => ("uno por fuente", "one per source"),
+        "tuning.workers_hint" => (
+            "Con muchas fuentes a la vez, unos pocos trabajadores Whisper para todas cargan el modelo una sola vez y reparten mejor la CPU que un estado por fuente.",
+            "With many sources at once, a few Whisper workers shared by all of them load the model only once and share the CPU better than one state per source.",
+        ),
+        "tuning.mic_priority" => (
+            "Con trabajadores compartidos, transcribir antes los micrófonos",
+            "With shared workers, transcribe microphones first",
+        ),
+        "tuning.hint"        => (
+            "Más beam/best_of = más precisión y más lento. Si el texto se retrasa, bájalos. Fragmentos más largos dan más contexto a Whisper pero el texto tarda más en aparecer.",
+            "Higher beam/best_of = more accurate but slower. Lower them if text lags behind. Longer chunks give Whisper more context but text takes longer to appear.",
+        ),
+        "tuning.reset"       => ("Restablecer", "Reset"),
+        "tuning.auto_downgrade" => (
+            "Cambiar a un modelo más pequeño si una fuente no llega a tiempo real",
+            "Switch to a smaller model if a source falls behind real time",
+        ),
+        "perf.skipped"       => (
+            "⚠️ {name} iba demasiado retrasado: se han saltado {secs} s sin transcribir",
+            "⚠️ {name} was too far behind: skipped {secs} s without transcribing",
+        ),
+        "perf.dropped"       => (
+            "⚠️ {name} va retrasado: descartados {secs} s de audio para no agotar la memoria",
+            "⚠️ {name} is falling behind: dropped {secs} s of audio to bound memory",
+        ),
+        "perf.resting"       => (
+            "💤 {name}: sin voz desde hace {secs} s, en reposo hasta que vuelva a oírse algo",
+            "💤 {name}: no speech for {secs} s, resting until something is heard again",
+        ),
+        "perf.behind"        => (
+            "⚠️ {name} va por detrás del tiempo real (RTF {rtf}) con '{model}'",
+            "⚠️ {name} is falling behind real time (RTF {rtf}) with '{model}'",
+        ),
+        "perf.downgrading"   => (
+            "⚠️ {name} va por detrás del tiempo real (RTF {rtf}): cambiando de '{from}' a '{to}'...",
+            "⚠️ {name} is falling behind real time (RTF {rtf}): switching from '{from}' to '{to}'...",
+        ),
+
+        // Post-procesado
+        "post.punctuation"    => ("Recomponer puntuación y mayúsculas entre fragmentos", "Restore punctuation and casing across chunks"),
+        "post.punctuation_hint" => (
+            "Whisper trata cada fragmento de 5 s como una frase. Con esto se quitan los puntos de corte, se une la frase con el fragmento siguiente del mismo interlocutor y se añaden ¿ ¡ en español.",
+            "Whisper treats every 5 s chunk as a sentence. This removes the periods added at cuts, joins the sentence with the same speaker's next chunk and adds ¿ ¡ in Spanish.",
+        ),
+        "post.spellings"      => ("🔠 Grafía fija", "🔠 Fixed spelling"),
+        "post.spellings_hint" => (
+            "Siglas, productos y nombres que deben escribirse siempre así (IVA, PostgreSQL, José María). Se corrigen sin mirar mayúsculas ni tildes y, a diferencia del glosario, no se pasan a Whisper.",
+            "Acronyms, products and names that must always be written this way (VAT, PostgreSQL, José María). They are fixed regardless of case and accents and, unlike the glossary, are not passed to Whisper.",
+        ),
+        "post.add_spelling"   => ("➕ Añadir término", "➕ Add term"),
+        "post.rules"          => ("🔁 Reglas de sustitución", "🔁 Replacement rules"),
+        "post.rules_hint"     => (
+            "Expresiones regulares aplicadas a cada segmento, en orden. Deja el reemplazo vacío para eliminar muletillas.",
+            "Regular expressions applied to every segment, in order. Leave the replacement empty to strip filler words.",
+        ),
+        "post.pattern"        => ("Patrón (regex)", "Pattern (regex)"),
+        "post.replacement"    => ("Reemplazo", "Replacement"),
+        "post.case_sensitive" => ("Distinguir mayúsculas", "Case sensitive"),
+        "post.invalid"        => ("Expresión no válida: {e}", "Invalid expression: {e}"),
+        "post.add_rule"       => ("➕ Añadir regla", "➕ Add rule"),
+
+        // Idiomas de Whisper
+        "lang.auto"        => ("Auto", "Auto"),
+        "lang.auto_detect" => ("Auto (detectar)", "Auto (detect)"),
+        "lang.original"    => ("Original (sin traducción)", "Original (no translation)"),
+        "lang.to_english"  => ("English (traducir)", "English (translate)"),
+
+        // Diálogo de loopback
+        "loopback.title"       => ("📊 Configuración de Captura de Audio de Salida", "📊 Output Audio Capture Setup"),
+        "loopback.ok"          => ("✅ Sistema configurado correctamente", "✅ System configured correctly"),
+        "loopback.instructions" => ("Instrucciones:", "Instructions:"),
+        "loopback.refresh"     => ("🔄 Actualizar Dispositivos", "🔄 Refresh Devices"),
+        "loopback.detected"    => ("✅ {n} dispositivos loopback detectados", "✅ {n} loopback devices detected"),
+        "loopback.none"        => ("⚠️ No se detectaron dispositivos loopback", "⚠️ No loopback devices detected"),
+        "loopback.unsupported" => ("Sistema operativo no soportado", "Unsupported operating system"),
+        "loopback.win_found"   => ("✅ {n} dispositivos de captura de sistema detectados", "✅ {n} system capture devices detected"),
+        "loopback.win_found_help" => (
+            "Dispositivos disponibles para capturar audio del sistema:\n\nÚsalos en Configuración como fuentes de tipo SALIDA.",
+            "Devices available to capture system audio:\n\nUse them in Settings as OUTPUT sources.",
+        ),
+        "loopback.win_missing" => ("⚠️ No se detecta 'Stereo Mix' u otro dispositivo loopback", "⚠️ 'Stereo Mix' or another loopback device was not detected"),
+        "loopback.win_missing_help" => (
+            "Para capturar el audio del sistema en Windows:\n\
+             \n\
+             1. Click derecho en el icono de volumen (barra de tareas)\n\
+             2. Selecciona 'Configuración de sonido' → 'Más opciones de sonido'\n\
+             3. Pestaña 'Grabación' → click derecho → 'Mostrar dispositivos deshabilitados'\n\
+             4. Busca 'Mezcla estéreo' o 'Stereo Mix' → click derecho → 'Habilitar'\n\
+             \n\
+             Si no aparece Stereo Mix, tu tarjeta de sonido no lo soporta.\n\
+             Alternativa: instala 'VB-Audio Virtual Cable' (gratuito):\n  https://vb-audio.com/Cable/",
+            "To capture system audio on Windows:\n\
+             \n\
+             1. Right-click the volume icon (taskbar)\n\
+             2. Choose 'Sound settings' → 'More sound settings'\n\
+             3. 'Recording' tab → right-click → 'Show disabled devices'\n\
+             4. Find 'Stereo Mix' → right-click → 'Enable'\n\
+             \n\
+             If Stereo Mix does not appear, your sound card does not support it.\n\
+             Alternative: install 'VB-Audio Virtual Cable' (free):\n  https://vb-audio.com/Cable/",
+        ),
+        "loopback.linux_found" => ("✅ {system} — {n} dispositivos monitor detectados", "✅ {system} — {n} monitor devices detected"),
+        "loopback.linux_found_help" => (
+            "Los dispositivos '.monitor' capturan el audio de salida del sistema.\nÚsalos en Configuración como fuentes de tipo SALIDA.",
+            "'.monitor' devices capture the system output audio.\nUse them in Settings as OUTPUT sources.",
+        ),
+        "loopback.linux_missing" => ("⚠️ {system}: no se detectan dispositivos monitor", "⚠️ {system}: no monitor devices detected"),
+        "loopback.linux_missing_help" => (
+            "Verifica que tu tarjeta de audio esté activa:\n  pactl list sinks short\n\nLos dispositivos '.monitor' deberían aparecer automáticamente.",
+            "Check that your sound card is active:\n  pactl list sinks short\n\n'.monitor' devices should appear automatically.",
+        ),
+        "loopback.sink_help"   => (
+            "O crea una salida virtual «Minutero»: envía ahí el audio de la reunión (en la app o en pavucontrol) y se seguirá oyendo por tu salida habitual.",
+            "Or create a \"Minutero\" virtual output: send the meeting audio there (in the app or in pavucontrol) and you will still hear it through your usual output.",
+        ),
+        "loopback.sink_create" => ("🔧 Crear salida virtual «Minutero»", "🔧 Create \"Minutero\" virtual output"),
+        "loopback.sink_remove" => ("🗑 Eliminar salida virtual «Minutero»", "🗑 Remove \"Minutero\" virtual output"),
+        "loopback.sink_created" => ("Salida virtual «Minutero» creada", "\"Minutero\" virtual output created"),
+        "loopback.sink_selected" => (
+            "Salida virtual creada; «{name}» seleccionado como fuente de salida",
+            "Virtual output created; \"{name}\" selected as output source",
+        ),
+        "loopback.sink_removed" => ("Salida virtual «Minutero» eliminada", "\"Minutero\" virtual output removed"),
+        "loopback.sink_error"  => ("No se pudo crear la salida virtual: {e}", "Could not create the virtual output: {e}"),
+        "loopback.sink_no_output" => (
+            "No se pudo crear la salida virtual: no hay una salida de audio predeterminada",
+            "Could not create the virtual output: there is no default audio output",
+        ),
+        "loopback.alsa_found"  => (
+            "✅ ALSA directo (sin PulseAudio/PipeWire) — {n} dispositivos Loopback detectados",
+            "✅ Direct ALSA (no PulseAudio/PipeWire) — {n} Loopback devices detected",
+        ),
+        "loopback.alsa_found_help" => (
+            "Lo que se reproduce en un lado del Loopback (hw:Loopback,0) se captura en el otro (hw:Loopback,1).\nEnvía ahí el audio de la reunión y úsalo en Configuración como fuente de tipo SALIDA.",
+            "Whatever plays into one side of the Loopback (hw:Loopback,0) is captured on the other (hw:Loopback,1).\nSend the meeting audio there and use it in Settings as an OUTPUT source.",
+        ),
+        "loopback.alsa_missing" => (
+            "⚠️ ALSA directo (sin PulseAudio/PipeWire): no hay dispositivo Loopback",
+            "⚠️ Direct ALSA (no PulseAudio/PipeWire): no Loopback device",
+        ),
+        "loopback.alsa_missing_help" => (
+            "Los micrófonos funcionan por ALSA. Para capturar el audio del sistema carga el módulo de loopback:\n  sudo modprobe snd-aloop\n\nO instala PipeWire/PulseAudio para tener los dispositivos '.monitor'.",
+            "Microphones work through ALSA. To capture system audio, load the loopback module:\n  sudo modprobe snd-aloop\n\nOr install PipeWire/PulseAudio to get '.monitor' devices.",
+        ),
+        "loopback.mac_found"   => ("✅ {n} dispositivos de audio virtual detectados", "✅ {n} virtual audio devices detected"),
+        "loopback.mac_found_help" => (
+            "Asegúrate de que las aplicaciones envíen el audio a este dispositivo virtual.\n\
+             En la mayoría de casos se configura con un 'Dispositivo Agregado' en Audio MIDI Setup.",
+            "Make sure applications send their audio to this virtual device.\n\
+             Usually this is done with an 'Aggregate Device' in Audio MIDI Setup.",
+        ),
+        "loopback.mac_missing" => ("⚠️ macOS requiere software de audio virtual", "⚠️ macOS requires virtual audio software"),
+        "loopback.mac_missing_help" => (
+            "macOS no tiene captura de salida nativa.\n\
+             \n\
+             Opción recomendada — BlackHole (gratuito):\n  https://github.com/ExistentialAudio/BlackHole\n\
+             \n\
+             Instalación:\n\
+             \x20 1. Descarga e instala BlackHole 2ch\n\
+             \x20 2. Abre 'Audio MIDI Setup' (en /Aplicaciones/Utilidades/)\n\
+             \x20 3. Crea un 'Dispositivo Agregado' con tu salida habitual + BlackHole\n\
+             \x20 4. Úsalo como salida del sistema en Preferencias de Sonido\n\
+             \x20 5. BlackHole aparecerá aquí como dispositivo disponible\n\
+             \n\
+             Alternativa de pago — Loopback (Rogue Amoeba):\n  https://rogueamoeba.com/loopback/",
+            "macOS has no native output capture.\n\
+             \n\
+             Recommended option — BlackHole (free):\n  https://github.com/ExistentialAudio/BlackHole\n\
+             \n\
+             Setup:\n\
+             \x20 1. Download and install BlackHole 2ch\n\
+             \x20 2. Open 'Audio MIDI Setup' (in /Applications/Utilities/)\n\
+             \x20 3. Create an 'Aggregate Device' with your usual output + BlackHole\n\
+             \x20 4. Use it as the system output in Sound Preferences\n\
+             \x20 5. BlackHole will show up here as an available device\n\
+             \n\
+             Paid alternative — Loopback (Rogue Amoeba):\n  https://rogueamoeba.com/loopback/",
+        ),
+
+        // Hilo de audio
+        "audio.checking_model"  => ("Verificando modelo...", "Checking model..."),
+        "audio.stream_error"    => ("Error en {name}: {e}", "Error in {name}: {e}"),
+        "model.downloading"     => ("⬇ Descargando modelo '{model}': {pct} %", "⬇ Downloading model '{model}': {pct}%"),
+        "model.downloading_mb"  => ("⬇ Descargando modelo '{model}': {mb} MB", "⬇ Downloading model '{model}': {mb} MB"),
+        "model.heading"         => ("📦 Modelo Whisper: origen, proxy y modo sin conexión", "📦 Whisper model: source, proxy and offline mode"),
+        "model.custom_path"     => ("Archivo ggml:", "ggml file:"),
+        "model.custom_path_hint" => ("Se usa este archivo en lugar de elegir y descargar el modelo por nombre. Vacío = models/ggml-<modelo>.bin.", "This file is used instead of picking and downloading the model by name. Empty = models/ggml-<model>.bin."),
+        "model.custom_missing"  => ("No existe el modelo {path}", "Model {path} does not exist"),
+        "model.offline"         => ("Modo sin conexión (no descargar nunca)", "Offline mode (never download)"),
+        "model.offline_hint"    => ("Para equipos sin acceso a huggingface.co: copia el modelo a models/ o elige su archivo arriba.", "For machines that cannot reach huggingface.co: copy the model into models/ or pick its file above."),
+        "preload.enabled"       => ("Precargar el modelo al abrir la aplicación", "Preload the model when the app opens"),
+        "resources.max_memory"  => ("Memoria máxima para los modelos:", "Maximum memory for models:"),
+        "resources.max_memory_hint" => ("Entre todas las capturas y salas. Automático = la memoria libre del sistema (solo en Linux; en otros sistemas, sin límite).", "Across all captures and rooms. Automatic = the system's free memory (Linux only; no limit elsewhere)."),
+        "resources.max_models"  => ("Modelos cargados a la vez:", "Models loaded at once:"),
+        "resources.max_models_hint" => ("Cada fuente carga su propio modelo, y otro más si se compara.", "Every source loads its own model, plus another one when comparing."),
+        "resources.auto"        => ("Automático", "Automatic"),
+        "resources.unlimited"   => ("Sin límite", "Unlimited"),
+        "resources.estimate"    => ("{n} fuentes activas: unos {required} de {available} disponibles", "{n} active sources: about {required} of {available} available"),
+        "resources.speaker_refused" => ("⚠ {name} no se transcribe: {e}", "⚠ {name} is not transcribed: {e}"),
+        "resources.tight"       => ("⚠ Los modelos ocuparán unos {required}: queda poca memoria libre", "⚠ The models will take about {required}: little free memory left"),
+        "resources.too_much_memory" => ("❌ Los modelos necesitan unos {required} y el límite deja {limit}. Usa un modelo más pequeño o menos fuentes.", "❌ The models need about {required} and the limit leaves {limit}. Use a smaller model or fewer sources."),
+        "resources.too_many_models" => ("❌ Harían falta {n} modelos y el límite deja cargar {max}. Usa menos fuentes o no compares modelos.", "❌ {n} models would be needed and the limit allows {max}. Use fewer sources or don't compare models."),
+        "preload.hint"          => ("La captura empieza al instante en vez de esperar a que se cargue el modelo, a cambio de tenerlo en memoria desde el principio.", "Capture starts immediately instead of waiting for the model to load, at the cost of keeping it in memory from the start."),
+        "preload.loading"       => ("⏳ Precargando modelo '{model}'...", "⏳ Preloading model '{model}'..."),
+        "preload.ready"         => ("✔ Modelo '{model}' cargado", "✔ Model '{model}' loaded"),
+        "preload.error"         => ("⚠ No se pudo precargar el modelo: {e}", "⚠ Could not preload the model: {e}"),
+        "model.mirror"          => ("Espejo de modelos:", "Model mirror:"),
+        "model.mirror_hint"     => ("URL de la carpeta con los ggml-<modelo>.bin, p. ej. un servidor interno.", "URL of the folder holding the ggml-<model>.bin files, e.g. an internal server."),
+        "model.proxy"           => ("Proxy:", "Proxy:"),
+        "model.proxy_hint"      => ("Vacío = HTTPS_PROXY / HTTP_PROXY del entorno. Admite usuario y contraseña en la URL.", "Empty = HTTPS_PROXY / HTTP_PROXY from the environment. User and password may go in the URL."),
+        "model.proxy_invalid"   => ("Proxy no válido: {e}", "Invalid proxy: {e}"),
+        "model.offline_missing" => ("Modo sin conexión: falta el modelo '{model}' en {path}", "Offline mode: model '{model}' is missing at {path}"),
+        "audio.preroll_used"    => ("⏺ Incluidos {secs} s de pre-grabación", "⏺ Included {secs} s of pre-roll"),
+        "audio.finished"        => ("Captura finalizada.", "Capture finished."),
+        "audio.stopping"        => ("⏳ Cerrando fuentes de audio...", "⏳ Closing audio sources..."),
+        "audio.shutdown_timeout" => ("Captura finalizada, pero no se cerraron a tiempo: {names}", "Capture finished, but these did not close in time: {names}"),
+        "audio.model_load"      => ("Error cargando modelo: {e}", "Failed to load model: {e}"),
+        "audio.state_create"    => ("Error creando estado: {e}", "Failed to create state: {e}"),
+        "audio.no_tech_name"    => ("Dispositivo sin nombre técnico. Recarga la aplicación.", "Device has no technical name. Restart the application."),
+        "audio.no_tech_name_cpal" => ("Dispositivo sin nombre técnico. Reconfigura el perfil en Ajustes.", "Device has no technical name. Reconfigure the profile in Settings."),
+        "audio.device_not_found" => ("Dispositivo '{name}' no encontrado.\n\nDispositivos disponibles:\n{list}", "Device '{name}' not found.\n\nAvailable devices:\n{list}"),
+        "audio.device_not_found_cpal" => (
+            "Dispositivo '{name}' no encontrado.\n\
+             • Windows: comprueba que el dispositivo sigue conectado.\n\
+             • Para captura de sistema: activa 'Stereo Mix' en el panel de sonido.",
+            "Device '{name}' not found.\n\
+             • Windows: check that the device is still connected.\n\
+             • For system capture: enable 'Stereo Mix' in the sound control panel.",
+        ),
+        "audio.alsa_fallback"   => (
+            "🔈 Sin PulseAudio/PipeWire: captura directa por ALSA",
+            "🔈 No PulseAudio/PipeWire: capturing directly through ALSA",
+        ),
+        "audio.sample_format"   => ("Formato de muestra no soportado: {format}", "Unsupported sample format: {format}"),
+        "audio.capture_tool_start" => ("Error iniciando {program}: {e}", "Failed to start {program}: {e}"),
+        "audio.capture_tool_missing" => (
+            "No se encuentra parecord, pw-record ni ffmpeg para grabar de PulseAudio/PipeWire. Instale pulseaudio-utils (Debian/Ubuntu/Fedora) o libpulse (Arch) para parecord, pipewire-bin (Debian/Ubuntu) / pipewire-utils (Fedora) para pw-record, o ffmpeg",
+            "Neither parecord, pw-record nor ffmpeg was found to record from PulseAudio/PipeWire. Install pulseaudio-utils (Debian/Ubuntu/Fedora) or libpulse (Arch) for parecord, pipewire-bin (Debian/Ubuntu) / pipewire-utils (Fedora) for pw-record, or ffmpeg",
+        ),
+        "audio.capture_tool_not_installed" => ("{program} está elegido como programa de captura pero no está instalado (o no sirve con este servidor de sonido)", "{program} is selected as the capture program but it is not installed (or does not work with this sound server)"),
+        "audio.capture_tool_switch" => ("{from} no funciona con {name}; se graba con {to}", "{from} does not work with {name}; recording with {to}"),
+        "audio.capture_restart" => ("⚠ {program} se cerró con {name}; reiniciando ({n}/{max})", "⚠ {program} stopped on {name}; restarting ({n}/{max})"),
+        "audio.process_stdout"  => ("No se pudo obtener stdout de {program}", "Could not read {program} stdout"),
+        "audio.process_died"    => ("{program} terminó inesperadamente ({status})", "{program} exited unexpectedly ({status})"),
+        "audio.process_not_running" => ("{name} no está abierto", "{name} is not running"),
+        "audio.process_loopback_error" => (
+            "No se pudo capturar la aplicación ({what}: {hr}). Requiere Windows 10 2004 o posterior",
+            "Could not capture the application ({what}: {hr}). Requires Windows 10 2004 or later",
+        ),
+        "audio.process_loopback_unsupported" => (
+            "La captura de una sola aplicación ({name}) solo está disponible en Windows",
+            "Capturing a single application ({name}) is only available on Windows",
+        ),
+        "audio.exclusive_unsupported" => (
+            "El modo exclusivo ({name}) solo está disponible en Windows",
+            "Exclusive mode ({name}) is only available on Windows",
+        ),
+        "audio.exclusive_in_use" => (
+            "{name} está en uso por otra aplicación; en modo exclusivo no se puede compartir",
+            "{name} is in use by another application; it cannot be shared in exclusive mode",
+        ),
+        "audio.exclusive_not_allowed" => (
+            "Windows no permite el modo exclusivo en {name}: actívelo en Sonido → Propiedades del dispositivo → Avanzado",
+            "Windows does not allow exclusive mode on {name}: enable it in Sound → Device properties → Advanced",
+        ),
+        "audio.exclusive_format" => (
+            "{name} no acepta {rate} Hz y {channels} canales en modo exclusivo; use el modo compartido",
+            "{name} does not accept {rate} Hz and {channels} channels in exclusive mode; use shared mode",
+        ),
+        "audio.exclusive_error" => ("No se pudo abrir el dispositivo en modo exclusivo ({what}: {hr})", "Could not open the device in exclusive mode ({what}: {hr})"),
+        "audio.no_url"          => ("La fuente de red no tiene URL", "The network source has no URL"),
+        "audio.network_no_ffmpeg" => (
+            "Las fuentes de red se decodifican con ffmpeg y no se encuentra en el PATH. Instálelo (apt install ffmpeg, brew install ffmpeg o ffmpeg.org) y vuelva a iniciar.",
+            "Network sources are decoded with ffmpeg, which was not found on the PATH. Install it (apt install ffmpeg, brew install ffmpeg or ffmpeg.org) and start again.",
+        ),
+        "audio.pipe_waiting"    => ("⏩ Esperando audio en {name}…", "⏩ Waiting for audio on {name}…"),
+        "audio.pipe_open"       => ("No se pudo abrir {name}: {e}", "Could not open {name}: {e}"),
+        "audio.pipe_closed"     => ("⏩ {name} se ha cerrado; la fuente termina", "⏩ {name} was closed; the source has ended"),
+        "audio.read_error"      => ("Error leyendo audio: {e}", "Error reading audio: {e}"),
+        "audio.channel"         => ("canal {n}", "channel {n}"),
+        "audio.recording_error" => ("No se pudo guardar la grabación de la reunión: {e}", "Could not save the meeting recording: {e}"),
+        "audio.channel_missing" => ("'{name}' tiene {channels} canales; no existe el canal {n}", "'{name}' has {channels} channels; channel {n} does not exist"),
+        "audio.download_http"   => ("Error al descargar: HTTP {status}", "Download failed: HTTP {status}"),
+
+        // Salas
+        "rooms.heading"      => ("Sesiones simultáneas", "Simultaneous sessions"),
+        "rooms.hint"         => ("Cada sala captura a la vez que la ventana, con sus propias fuentes, modelo y carpeta, y guarda su minuta al detenerse. También se manejan desde la API de la página web (/api/sessions).", "Each room captures alongside the window, with its own sources, model and folder, and saves its minutes when stopped. They can also be driven from the web page API (/api/sessions)."),
+        "rooms.main"         => ("Sesión de la ventana", "Window session"),
+        "rooms.segments"     => ("{n} intervenciones", "{n} segments"),
+        "rooms.add"          => ("➕ Nueva sala", "➕ New room"),
+        "rooms.add_hint"     => ("Copia los interlocutores activos, el modelo y la carpeta de la ventana", "Copies the window's active speakers, model and folder"),
+        "rooms.name"         => ("Nombre:", "Name:"),
+        "rooms.default_name" => ("Sala {n}", "Room {n}"),
+
+        // Sesión y calendario
+        "session.title"          => ("Reunión:", "Meeting:"),
+        "session.attendees"      => ("Asistentes:", "Attendees:"),
+        "session.attendees_hint" => ("Separados por comas (vacío = interlocutores)", "Comma-separated (empty = speakers)"),
+        "session.type"          => ("Tipo de sesión:", "Session type:"),
+        "session.type_meeting"  => ("Reunión", "Meeting"),
+        "session.type_interview" => ("Modo entrevista", "Interview mode"),
+        "session.style"         => ("Transcripción:", "Transcription:"),
+        "session.style_clean"   => ("Limpia", "Clean read"),
+        "session.style_verbatim" => ("Literal (muletillas y repeticiones)", "Verbatim (fillers and repetitions)"),
+        "session.type_lecture"  => ("Modo clase (capítulos)", "Lecture mode (chapters)"),
+        "lecture.contents"      => ("Índice", "Contents"),
+        "lecture.chapter"       => ("Capítulo {n}: {title}", "Chapter {n}: {title}"),
+        "interview.question"    => ("P{n}:", "Q{n}:"),
+        "interview.answer"      => ("R:", "A:"),
+        "calendar.heading"       => ("📅 Calendario (iCal / CalDAV)", "📅 Calendar (iCal / CalDAV)"),
+        "calendar.url"           => ("URL del calendario (.ics):", "Calendar URL (.ics):"),
+        "calendar.username"      => ("Usuario (CalDAV):", "Username (CalDAV):"),
+        "calendar.password"      => ("Contraseña (CalDAV):", "Password (CalDAV):"),
+        "calendar.prompt_on_call" => ("Preguntar si empezar a capturar cuando comienza una videollamada", "Offer to start capture when a video call begins"),
+        "calendar.refresh"       => ("🔄 Actualizar", "🔄 Refresh"),
+        "calendar.loading"       => ("Cargando calendario...", "Loading calendar..."),
+        "calendar.loaded"        => ("{n} eventos cargados", "{n} events loaded"),
+        "calendar.current"       => ("📅 En curso: {title} ({start}–{end})", "📅 Now: {title} ({start}–{end})"),
+        "calendar.use_event"     => ("Usar evento", "Use event"),
+        "calendar.prompt_title"  => ("📅 Reunión en curso", "📅 Meeting started"),
+        "calendar.prompt_body"   => ("Ha empezado «{title}». ¿Iniciar la captura?", "\"{title}\" has started. Start capture?"),
+        "calendar.not_now"       => ("Ahora no", "Not now"),
+
+        // Salida
+        "exit.title"            => ("⚠ Captura en curso", "⚠ Capture in progress"),
+        "exit.body"             => ("Si sales ahora se detendrá la captura. ¿Guardar la minuta antes de salir?", "Quitting will stop the capture. Save the minutes before quitting?"),
+        "exit.save_and_quit"    => ("💾 Guardar y salir", "💾 Save and quit"),
+        "exit.quit_without_saving" => ("Salir sin guardar", "Quit without saving"),
+        "review.heading"        => ("🔍 Revisión de segmentos dudosos", "🔍 Low-confidence review"),
+        "review.threshold"      => ("Confianza media por debajo de:", "Average confidence below:"),
+        "review.hint"           => ("Del segmento menos fiable al más. Corrige el texto aquí antes de exportar la minuta oficial; ✔ lo saca de la cola.", "Least reliable first. Fix the text here before exporting the official minutes; ✔ removes it from the queue."),
+        "review.empty"          => ("No hay segmentos pendientes de revisar.", "No segments left to review."),
+        "review.play"           => ("Escuchar en la grabación", "Play from the recording"),
+        "review.accept"         => ("✔ Revisado", "✔ Reviewed"),
+        "crash.title"           => ("⚠ Minutero se cerró inesperadamente", "⚠ Minutero closed unexpectedly"),
+        "crash.body"            => ("Se guardó la minuta que estaba en pantalla. ¿Restaurarla?", "The minutes on screen were saved. Restore them?"),
+        "crash.encrypted"       => ("Está cifrada: si no abre, escriba la frase de paso en Configuración › Cifrado y vuelva a restaurar.", "It is encrypted: if it does not open, enter the passphrase in Settings › Encryption and restore again."),
+        "crash.report"          => ("Informe del fallo: {path}", "Crash report: {path}"),
+        "crash.restore"         => ("↺ Restaurar", "↺ Restore"),
+        "crash.discard"         => ("Descartar", "Discard"),
+
+        // Integridad
+        "integrity.heading"     => ("🔏 Integridad (uso legal)", "🔏 Integrity (legal use)"),
+        "integrity.hash_chain"  => ("Encadenar las intervenciones con SHA-256 (minuta JSON)", "Hash-chain the segments with SHA-256 (JSON minutes)"),
+        "integrity.hash_chain_hint" => ("Cada intervención guarda el hash de la anterior más su contenido: cualquier cambio posterior se detecta.", "Each segment stores the hash of the previous one plus its content: any later change is detectable."),
+        "integrity.sign"        => ("Firmar cada minuta exportada (ssh-keygen, archivo .sig)", "Sign every exported minutes file (ssh-keygen, .sig file)"),
+        "integrity.key"         => ("Clave SSH:", "SSH key:"),
+        "integrity.key_hint"    => ("Clave privada sin contraseña, o la pública si la privada está en ssh-agent.", "Private key without a passphrase, or the public key if the private one is in ssh-agent."),
+        "integrity.verify"      => ("🔍 Verificar una minuta...", "🔍 Verify minutes..."),
+        "integrity.no_key"      => ("No hay clave de firma configurada", "No signing key configured"),
+        "integrity.ssh_keygen_missing" => ("No se pudo ejecutar ssh-keygen: {e}", "Could not run ssh-keygen: {e}"),
+        "integrity.sign_failed" => ("Minuta guardada en {path} pero sin firmar: {e}", "Minutes saved to {path} but not signed: {e}"),
+        "integrity.chain_ok"    => ("✔ Cadena íntegra ({n} intervenciones)", "✔ Chain intact ({n} segments)"),
+        "integrity.chain_broken" => ("✖ Cadena rota en la intervención {n}: la minuta se ha modificado", "✖ Chain broken at segment {n}: the minutes were modified"),
+        "integrity.chain_truncated" => ("✖ La minuta tenía {expected} intervenciones y ahora tiene {found}: se han quitado o añadido al final", "✖ The minutes had {expected} segments and now have {found}: some were removed or added at the end"),
+        "integrity.chain_missing" => ("Sin cadena de hashes", "No hash chain"),
+        "integrity.signature_ok" => ("✔ Firma válida: {msg}", "✔ Valid signature: {msg}"),
+        "integrity.signature_bad" => ("✖ Firma no válida: {msg}", "✖ Invalid signature: {msg}"),
+        "integrity.signature_missing" => ("Sin firma (.sig)", "No signature (.sig)"),
+
+        // Cifrado
+        "encryption.heading"    => ("🔒 Cifrado", "🔒 Encryption"),
+        "encryption.enabled"    => ("Cifrar las minutas exportadas (AES-256-GCM, archivo .enc)", "Encrypt exported minutes (AES-256-GCM, .enc file)"),
+        "encryption.hint"       => ("Las sesiones cifradas se abren igual desde «Abrir sesión» con la misma clave. La grabación de audio no se cifra.", "Encrypted sessions open the same way from \"Open session\" with the same key. The audio recording is not encrypted."),
+        "encryption.recovery"   => (
+            "La copia de recuperación tras un fallo (recovery.json) se cifra con la misma clave; sin clave no se guarda.",
+            "The crash recovery copy (recovery.json) is encrypted with the same key; without a key it is not saved.",
+        ),
+        "encryption.recording_plain" => (
+            "⚠ La grabación y las pistas por interlocutor (WAV/OGG) se guardan sin cifrar",
+            "⚠ The recording and per-speaker tracks (WAV/OGG) are saved unencrypted",
+        ),
+        "encryption.passphrase" => ("Frase de paso", "Passphrase"),
+        "encryption.passphrase_hint" => ("No se guarda: hay que escribirla de nuevo en cada arranque.", "Not stored: it must be typed again on every start."),
+        "encryption.keyfile"    => ("Archivo de clave", "Key file"),
+        "encryption.no_passphrase" => ("Falta la frase de paso del cifrado", "The encryption passphrase is missing"),
+        "encryption.no_keyfile" => ("No hay archivo de clave configurado", "No key file configured"),
+        "encryption.keyfile_error" => ("No se pudo leer el archivo de clave: {e}", "Could not read the key file: {e}"),
+        "encryption.failed"     => ("No se pudo cifrar la minuta", "Could not encrypt the minutes"),
+        "encryption.not_encrypted" => ("El archivo no está cifrado por Minutero", "The file was not encrypted by Minutero"),
+        "encryption.wrong_key"  => ("Clave incorrecta o archivo alterado", "Wrong key or tampered file"),
+
+        // Anonimización
+        "redaction.heading"  => ("🕶 Anonimización", "🕶 Redaction"),
+        "redaction.enabled"  => ("Anonimizar las minutas exportadas", "Redact exported minutes"),
+        "redaction.hint"     => (
+            "Se aplica a los archivos guardados y a lo que se envía a Slack o Notion. La minuta en pantalla se conserva completa.",
+            "Applies to saved files and to what is sent to Slack or Notion. The on-screen minutes stay complete.",
+        ),
+        "redaction.profanity" => ("Palabrotas", "Profanity"),
+        "redaction.emails"   => ("Correos", "Emails"),
+        "redaction.phones"   => ("Teléfonos", "Phone numbers"),
+        "redaction.ids"      => ("DNI/NIE y tarjetas", "ID and card numbers"),
+        "redaction.mask"     => ("Sustituir por:", "Replace with:"),
+        "redaction.custom"   => ("Patrones propios (expresiones regulares, uno por línea):", "Custom patterns (regular expressions, one per line):"),
+
+        // Log
+        "log.heading"       => ("📜 Registro de la aplicación", "📜 Application log"),
+        "log.copy"          => ("📋 Copiar log", "📋 Copy log"),
+        "log.file"          => ("Archivo: {path}", "File: {path}"),
+        "log.empty"         => ("Todavía no hay entradas.", "No entries yet."),
+        "log.level"         => ("Nivel de log:", "Log level:"),
+        "diagnostics.generate" => ("🩺 Generar informe de diagnóstico", "🩺 Generate diagnostics report"),
+        "diagnostics.hint"  => (
+            "Crea un .zip en la carpeta de minutas con datos del sistema, dispositivos, modelos, logs y configuración (sin tokens ni contraseñas) para adjuntar a una incidencia.",
+            "Creates a .zip in the minutes folder with system info, devices, models, logs and settings (without tokens or passwords) to attach to an issue.",
+        ),
+
+        // Notificaciones
+        "notify.heading"         => ("💬 Notificaciones de escritorio", "💬 Desktop notifications"),
+        "notify.on_capture"      => ("Al iniciar y detener la captura", "When capture starts and stops"),
+        "notify.on_errors"       => ("Si falla una fuente de audio o el guardado", "When an audio source or saving fails"),
+        "notify.on_downloads"    => ("Cuando termina la descarga de un modelo", "When a model finishes downloading"),
+        "notify.capture_started" => ("🎙️ Captura iniciada", "🎙️ Capture started"),
+        "notify.capture_stopped" => ("⏹ Captura detenida", "⏹ Capture stopped"),
+        "notify.retranscribed"   => ("🔁 Sesión re-transcrita", "🔁 Session re-transcribed"),
+        "notify.stream_error"    => ("❌ Error en la captura", "❌ Capture error"),
+        "notify.save_failed"     => ("❌ No se pudo guardar la minuta", "❌ Could not save the minutes"),
+        "notify.upload_failed"   => ("❌ No se pudo subir la minuta", "❌ Could not upload the minutes"),
+        "notify.model_ready"     => ("✓ Modelo descargado", "✓ Model downloaded"),
+        "notify.model_ready_body" => ("El modelo '{model}' está listo.", "Model '{model}' is ready."),
+
+        // Videoconferencias
+        "conferencing.detect"       => ("🎧 Ofrecer iniciar la transcripción cuando una videollamada empieza a sonar", "🎧 Offer to start transcribing when a video call starts playing audio"),
+        "conferencing.unsupported"  => ("Solo disponible en Linux (PulseAudio/PipeWire)", "Only available on Linux (PulseAudio/PipeWire)"),
+        "conferencing.prompt_title" => ("🎧 Videollamada detectada", "🎧 Video call detected"),
+        "conferencing.prompt_body"  => ("{app} está reproduciendo audio. ¿Iniciar transcripción?", "{app} is playing audio. Start transcribing?"),
+
+        // Portapapeles
+        "copy.plain"     => ("Texto", "Plain text"),
+        "copy.all"       => ("📋 Copiar minuta", "📋 Copy minutes"),
+        "copy.selection" => ("📋 Copiar selección", "📋 Copy selection"),
+        "copy.done"      => ("✓ Copiado al portapapeles", "✓ Copied to clipboard"),
+        "copy.empty"     => ("No hay transcripción para copiar.", "There is no transcript to copy."),
+
+        // Reproducción
+        "playback.open"          => ("📂 Abrir sesión…", "📂 Open session…"),
+        "playback.open_hint"     => ("Carga una minuta exportada en JSON y su grabación", "Load minutes exported as JSON and their recording"),
+        "playback.play"          => ("▶ Escuchar intervención", "▶ Play segment"),
+        "playback.play_hint"     => ("Reproduce la intervención marcada (o haz doble clic en ella)", "Plays the selected segment (or double-click it)"),
+        "playback.stop"          => ("⏹ Parar", "⏹ Stop"),
+        "playback.no_segment"    => ("Esa línea no está en la grabación.", "That line is not in the recording."),
+        "playback.error"         => ("⚠ No se pudo reproducir: {e}", "⚠ Could not play: {e}"),
+        "playback.out_of_range"  => ("el tramo está fuera de la grabación", "the segment is outside the recording"),
+        "playback.no_output"     => ("no hay salida de audio", "there is no audio output"),
+        "playback.open_error"    => ("⚠ No se pudo abrir la sesión: {e}", "⚠ Could not open the session: {e}"),
+        "playback.session_loaded" => ("Sesión cargada: {path}", "Session loaded: {path}"),
+        "playback.session_no_recording" => ("Sesión cargada sin grabación: {path}", "Session loaded without a recording: {path}"),
+
+        // Re-transcripción
+        "retranscribe.button"    => ("🔁 Re-transcribir sesión", "🔁 Re-transcribe session"),
+        "retranscribe.hint"      => ("Vuelve a transcribir las pistas guardadas con el modelo seleccionado ({model}) y guarda una versión nueva de la minuta", "Re-transcribes the stored tracks with the selected model ({model}) and saves a new version of the minutes"),
+        "retranscribe.no_tracks_hint" => ("Necesita una sesión grabada con pista por interlocutor", "Needs a session recorded with one track per speaker"),
+        "retranscribe.no_tracks" => ("no hay pistas por interlocutor para esta grabación: {e}", "there are no per-speaker tracks for this recording: {e}"),
+        "retranscribe.started"   => ("Re-transcribiendo la sesión con {model}...", "Re-transcribing the session with {model}..."),
+        "retranscribe.chunk"     => ("Re-transcribiendo {name}: fragmento {i}/{total}", "Re-transcribing {name}: chunk {i}/{total}"),
+        "retranscribe.done"      => ("✅ Sesión re-transcrita con {model}; guardando la nueva versión...", "✅ Session re-transcribed with {model}; saving the new version..."),
+        "retranscribe.version_title" => ("{title} (re-transcrita con {model})", "{title} (re-transcribed with {model})"),
+
+        // Integraciones
+        "integrations.hint"       => (
+            "Al detener la captura, la minuta guardada se envía a los servicios activos. Los tokens se guardan en settings.json sin cifrar.",
+            "When capture stops, the saved minutes are sent to every enabled service. Tokens are stored unencrypted in settings.json.",
+        ),
+        "integrations.enabled"    => ("Activado", "Enabled"),
+        "integrations.saved_at"   => ("Minuta guardada en: {path}", "Minutes saved to: {path}"),
+        "integrations.slack_webhook" => ("Webhook entrante:", "Incoming webhook:"),
+        "integrations.slack_token"   => ("Token de bot (opcional):", "Bot token (optional):"),
+        "integrations.slack_channel" => ("ID del canal:", "Channel ID:"),
+        "integrations.slack_attach"  => ("Adjuntar el archivo (requiere token de bot)", "Attach the file (requires bot token)"),
+        "integrations.telegram_token" => ("Token del bot:", "Bot token:"),
+        "integrations.telegram_chat" => ("ID del chat:", "Chat ID:"),
+        "integrations.telegram_attach" => ("Adjuntar el archivo de la minuta", "Attach the minutes file"),
+        "integrations.telegram_live" => ("Enviar también la transcripción en directo", "Also send the transcript live"),
+        "integrations.telegram_live_hint" => ("Durante la captura, cada intervención llega al chat según se transcribe (agrupadas, como mucho un mensaje por segundo). Mejor un chat privado con el bot.", "During capture, every segment reaches the chat as it is transcribed (batched, at most one message per second). A private chat with the bot is best."),
+        "integrations.matrix_homeserver" => ("Servidor:", "Homeserver:"),
+        "integrations.matrix_token" => ("Token de acceso:", "Access token:"),
+        "integrations.matrix_room" => ("ID de la sala:", "Room ID:"),
+        "integrations.matrix_attach" => ("Enviar también la minuta como archivo", "Also send the minutes as a file"),
+        "git.hint"             => ("Copia cada minuta guardada a un repositorio git local y hace commit; con push, la sube al remoto con las credenciales de git (agente SSH o credential helper).", "Copies every saved minutes file into a local git repository and commits it; with push, uploads it to the remote using git's credentials (SSH agent or credential helper)."),
+        "git.repo"             => ("Repositorio:", "Repository:"),
+        "git.subfolder"        => ("Carpeta:", "Folder:"),
+        "git.message"          => ("Mensaje del commit:", "Commit message:"),
+        "git.push"             => ("Hacer push a", "Push to"),
+        "discord.hint"         => ("Un bot con permiso para conectar y escribir. Añade un perfil Discord con el ID del canal de voz: cada participante se transcribe como un interlocutor con su nombre en el servidor.", "A bot allowed to connect and send messages. Add a Discord profile with the voice channel ID: every participant is transcribed as a separate speaker with their server name."),
+        "discord.token"        => ("Token del bot:", "Bot token:"),
+        "discord.guild"        => ("ID del servidor:", "Server ID:"),
+        "discord.text_channel" => ("ID del canal de texto:", "Text channel ID:"),
+        "discord.post_live"    => ("Publicar la transcripción en directo en el canal de texto", "Post the live transcript to the text channel"),
+        "discord.voice_channel" => ("ID del canal de voz", "Voice channel ID"),
+        "discord.voice_channel_hint" => ("Con el modo desarrollador de Discord: clic derecho en el canal → Copiar ID. El token y el servidor se configuran en Integraciones.", "With Discord developer mode: right-click the channel → Copy ID. Token and server are set under Integrations."),
+        "discord.no_channel"   => ("Falta el ID del canal de voz de Discord", "Missing the Discord voice channel ID"),
+        "discord.joining"      => ("Conectando el bot de Discord...", "Connecting the Discord bot..."),
+        "discord.joined"       => ("Bot en el canal de voz {channel}", "Bot in voice channel {channel}"),
+        "discord.join_error"   => ("No se pudo entrar en el canal de Discord: {e}", "Could not join the Discord channel: {e}"),
+        "discord.disconnected" => ("Se perdió la conexión con el canal de Discord", "Lost the connection to the Discord channel"),
+        "ndi.hint"             => ("Durante la captura, la aplicación aparece como fuente NDI y envía la última línea como metadata XML (<minutero_caption speaker=\"…\">texto</minutero_caption>) para superponerla en vMix, TriCaster u OBS. Necesita el runtime de NDI (NDI Tools).", "During capture, the app shows up as an NDI source and sends the latest line as XML metadata (<minutero_caption speaker=\"…\">text</minutero_caption>) to overlay it in vMix, TriCaster or OBS. Requires the NDI runtime (NDI Tools)."),
+        "ndi.source_name"      => ("Nombre de la fuente:", "Source name:"),
+        "ndi.not_installed"    => ("No se encuentra el runtime de NDI; instala NDI Tools o el SDK", "NDI runtime not found; install NDI Tools or the SDK"),
+        "ndi.error"            => ("⚠ Salida NDI desactivada: {e}", "⚠ NDI output disabled: {e}"),
+        "storage.heading"      => ("Copia en la nube", "Cloud copy"),
+        "storage.enabled"      => ("Subir la minuta al guardar", "Upload the minutes when saving"),
+        "storage.hint"         => ("Cada plantilla tiene su destino. La subida va en segundo plano y se reintenta varias veces si el servidor no responde; la minuta local se guarda igual.", "Each preset has its own destination. The upload runs in the background and is retried several times if the server does not respond; the local minutes are saved either way."),
+        "storage.kind"         => ("Destino:", "Destination:"),
+        "storage.endpoint"     => ("Endpoint:", "Endpoint:"),
+        "storage.webdav_url"   => ("URL de la carpeta:", "Folder URL:"),
+        "storage.server"       => ("Servidor:", "Server:"),
+        "storage.bucket"       => ("Bucket:", "Bucket:"),
+        "storage.region"       => ("Región:", "Region:"),
+        "storage.folder"       => ("Carpeta:", "Folder:"),
+        "storage.access_key"   => ("Clave de acceso:", "Access key:"),
+        "storage.secret_key"   => ("Clave secreta:", "Secret key:"),
+        "storage.username"     => ("Usuario:", "Username:"),
+        "storage.password"     => ("Contraseña de aplicación:", "App password:"),
+        "storage.include_audio" => ("Subir también la grabación", "Also upload the recording"),
+        "storage.uploaded"     => ("Subido {file} a {url}", "Uploaded {file} to {url}"),
+        "storage.failed"       => ("No se pudo subir {file} tras varios intentos: {e}", "Could not upload {file} after several attempts: {e}"),
+        "integrations.notion_token"      => ("Token de integración:", "Integration token:"),
+        "integrations.notion_database"   => ("ID de la base de datos:", "Database ID:"),
+        "integrations.notion_title_prop" => ("Columna de título:", "Title property:"),
+        "integrations.notion_date_prop"  => ("Columna de fecha (opcional):", "Date property (optional):"),
+        "integrations.notion_attendees_prop" => ("Columna de asistentes (multi-select, opcional):", "Attendees property (multi-select, optional):"),
+        "hooks.heading"            => ("Hooks (órdenes propias)", "Hooks (custom commands)"),
+        "hooks.hint"               => ("Se ejecutan con la shell del sistema. Reciben MINUTERO_EVENT y los datos del evento en variables MINUTERO_* (TITLE; SPEAKER, TEXT, START_SECS, END_SECS; PATH), y el texto o la ruta también por stdin. Vacío = nada.", "Run with the system shell. They get MINUTERO_EVENT and the event data in MINUTERO_* variables (TITLE; SPEAKER, TEXT, START_SECS, END_SECS; PATH), and the text or path on stdin too. Empty = nothing."),
+        "hooks.on_capture_start"   => ("Al empezar la captura:", "When capture starts:"),
+        "hooks.on_segment"         => ("Por cada intervención:", "On every segment:"),
+        "hooks.on_session_saved"   => ("Al guardar la minuta:", "When the minutes are saved:"),
+        "integrations.mqtt_hint"   => ("Publica cada intervención en JSON (session, speaker, start_secs, end_secs, text) en cuanto se transcribe, durante la captura.", "Publishes every segment as JSON (session, speaker, start_secs, end_secs, text) as soon as it is transcribed, during capture."),
+        "integrations.mqtt_broker" => ("Broker (host y puerto):", "Broker (host and port):"),
+        "integrations.mqtt_topic"  => ("Tema:", "Topic:"),
+        "integrations.mqtt_username" => ("Usuario (opcional):", "Username (optional):"),
+        "integrations.mqtt_password" => ("Contraseña:", "Password:"),
+        "web.heading"              => ("Página web en vivo", "Live web page"),
+        "web.hint"                 => ("Sirve una página con la minuta en vivo y un botón de iniciar/detener, para seguirla desde el móvil u otro equipo.", "Serves a page with the live transcript and a start/stop button, to follow it from a phone or another machine."),
+        "web.port"                 => ("Puerto:", "Port:"),
+        "web.lan"                  => ("Accesible desde la red local", "Reachable from the local network"),
+        "web.lan_hint"             => ("Desde otro equipo, cambia 127.0.0.1 por la IP de este. Sin token, desde la red la página solo se puede ver: iniciar y detener lo piden.", "From another machine, replace 127.0.0.1 with this one's IP. Without a token the page is view-only from the network: start and stop require it."),
+        "web.token"                => ("Token (opcional):", "Token (optional):"),
+        "web.error"                => ("No se pudo abrir la página web: {e}", "Could not serve the web page: {e}"),
+        "web.captions"             => ("Subtítulos en directo:", "Live captions:"),
+        "web.captions_hint"        => ("Para OBS, vMix o un reproductor: playlist HLS de subtítulos WebVTT, WebVTT con los últimos 30 s o solo la última línea en texto. Las salas los tienen en /api/sessions/<id>/captions.*. Un reproductor web en otro dominio necesita token.", "For OBS, vMix or a player: HLS WebVTT subtitle playlist, WebVTT with the last 30 s, or just the last line as text. Rooms have them under /api/sessions/<id>/captions.*. A web player on another domain needs a token."),
+
+        // Exportación
+        "export.minutes_title" => ("Minuta de Transcripción", "Transcription Minutes"),
+        "export.video_title"   => ("Transcripción: {name}", "Transcript: {name}"),
+        "export.speaker_title" => ("{title} — {name}", "{title} — {name}"),
+        "export.per_speaker"   => ("Guardar también un archivo por interlocutor", "Also save one file per speaker"),
+        "export.per_speaker_hint" => ("Junto a la minuta, un archivo con solo las intervenciones de cada interlocutor y su hora, por ejemplo para las declaraciones de cada ponente de una mesa redonda.", "Next to the minutes, one file per speaker with only their segments and timestamps, e.g. for each panelist's statements."),
+        "export.normalize_numbers" => ("Números y fechas en cifras", "Numbers and dates as digits"),
+        "export.normalize_numbers_hint" => ("Al exportar, «veinticinco de marzo de dos mil veinticuatro» pasa a «25 de marzo de 2024», «veinte por ciento» a «20 %». Los números sueltos menores que diez se quedan con letras. La minuta en pantalla no cambia.", "When exporting, \"March twenty-fifth, twenty twenty-four\" becomes \"March 25, 2024\" and \"twenty percent\" becomes \"20%\". Single numbers below ten stay as words. The on-screen minutes are unchanged."),
+        "topics.enabled"       => ("Etiquetar con los temas, hasta", "Tag with topics, up to"),
+        "topics.enabled_hint"  => ("Al guardar, la minuta se etiqueta con las palabras que más se repiten en distintas intervenciones («presupuesto», «contratación»). Salen en la minuta y el historial busca por ellas.", "When saving, the minutes are tagged with the words repeated most across different segments (\"budget\", \"hiring\"). They appear in the minutes and the history can search by them."),
+        "topics.ignore"        => ("Ignorar:", "Ignore:"),
+        "topics.ignore_hint"   => ("palabras separadas por comas", "comma-separated words"),
+        "export.date"          => ("Fecha", "Date"),
+        "export.attendees"     => ("Asistentes", "Attendees"),
+        "export.tags"          => ("Temas", "Topics"),
+        "export.recording"     => ("Grabación", "Recording"),
+        "export.transcript"    => ("Transcripción", "Transcript"),
+        "export.format"        => ("Formato de exportación:", "Export format:"),
+        "export.filename"      => ("Nombre de archivo:", "File name:"),
+        "export.filename_preview" => ("Ejemplo: {file}", "Example: {file}"),
+        "export.filename_hint" => ("Variables disponibles: {vars}", "Available variables: {vars}"),
+        "export.page"          => ("Página {n} de {total}", "Page {n} of {total}"),
+        "export.pdf_company"   => ("Empresa (cabecera):", "Company (header):"),
+        "export.pdf_logo"      => ("Logo JPEG:", "JPEG logo:"),
+        "export.pdf_footer"    => ("Pie de página:", "Footer:"),
+        "export.browse"        => ("Examinar...", "Browse..."),
+        "export.template"      => ("Plantilla (vacío = integrada):", "Template (empty = built-in):"),
+        "export.template_hint" => (
+            "Variables disponibles: title, date, attendees, body, segments, stats, stats_table, labels",
+            "Available variables: title, date, attendees, body, segments, stats, stats_table, labels",
+        ),
+
+        _ => return None,
+    })
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokio::runtime::Runtime;
+use crate::calendar::CalendarConfig;
+use crate::discord::DiscordConfig;
+use crate::export::MinutaDocument;
+use crate::git_archive::{self, GitArchiveConfig};
+use crate::i18n::tr;
+use crate::matrix::{self, MatrixConfig};
+use crate::mqtt::MqttConfig;
+use crate::ndi::NdiConfig;
+use crate::notion::{self, NotionConfig};
+use crate::slack::{self, SlackConfig};
+use crate::stats::format_duration;
+use crate::telegram::{self, TelegramConfig};
+
+// ── Integraciones ──────────────────────────────────────────────────────────
+//
+// Envío de la minuta a servicios externos al terminar una sesión. Se ejecuta
+// en el mismo hilo que guarda el archivo, así que puede bloquear sin afectar
+// a la interfaz. Un fallo en un servicio no impide los demás.
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntegrationSettings {
+    pub slack: SlackConfig,
+    pub notion: NotionConfig,
+    /// Al guardar y, si se quiere, también en directo (ver `telegram`).
+    pub telegram: TelegramConfig,
+    pub matrix: MatrixConfig,
+    /// Bot para los canales de voz y la transcripción en directo (ver `discord`).
+    pub discord: DiscordConfig,
+    /// Commit de cada minuta en un repositorio local (ver `git_archive`).
+    pub git: GitArchiveConfig,
+    /// Se publica en directo, intervención a intervención (ver `mqtt`).
+    pub mqtt: MqttConfig,
+    /// Última línea como fuente NDI, en directo (ver `ndi`).
+    pub ndi: NdiConfig,
+    /// No envía nada: solo se lee para rellenar título y asistentes.
+    pub calendar: CalendarConfig,
+}
+
+impl IntegrationSettings {
+    fn any_enabled(&self) -> bool {
+        self.slack.is_enabled()
+            || self.notion.is_enabled()
+            || self.telegram.is_enabled()
+            || self.matrix.is_enabled()
+            || self.git.is_enabled()
+    }
+}
+
+/// Envía la minuta guardada en `path` a cada integración activa.
+pub fn deliver(doc: &MinutaDocument, path: &Path, settings: &IntegrationSettings) {
+    if !settings.any_enabled() {
+        return;
+    }
+
+    // libgit2 es síncrono: no necesita el runtime
+    if settings.git.is_enabled() {
+        if let Err(e) = git_archive::archive(&settings.git, doc, path) {
+            tracing::error!("Git: {:?}", e);
+        }
+    }
+
+    let rt = match Runtime::new() {
+        Ok(rt) => rt,
+        Err(e) => {
+            tracing::error!("Integraciones: no se pudo crear el runtime: {:?}", e);
+            return;
+        }
+    };
+
+    if settings.slack.is_enabled() {
+        if let Err(e) = rt.block_on(slack::post_minuta(&settings.slack, doc, path)) {
+            tracing::error!("Slack: {:?}", e);
+        }
+    }
+    if settings.notion.is_enabled() {
+        if let Err(e) = rt.block_on(notion::create_page(&settings.notion, doc)) {
+            tracing::error!("Notion: {:?}", e);
+        }
+    }
+    if settings.telegram.is_enabled() {
+        if let Err(e) = rt.block_on(telegram::post_minuta(&settings.telegram, doc, path)) {
+            tracing::error!("Telegram: {:?}", e);
+        }
+    }
+    if settings.matrix.is_enabled() {
+        if let Err(e) = rt.block_on(matrix::post_minuta(&settings.matrix, doc, path)) {
+            tracing::error!("Matrix: {:?}", e);
+        }
+    }
+}
+
+/// Resumen breve de la sesión: título, fecha, asistentes y tiempo de palabra.
+pub fn overview(doc: &MinutaDocument) -> String {
+    let mut out = format!("{}\n{}: {}", doc.title, tr!("export.date"), doc.date);
+    if !doc.attendees.is_empty() {
+        out.push_str(&format!("\n{}: {}", tr!("export.attendees"), doc.attendees.join(", ")));
+    }
+    if !doc.stats.is_empty() {
+        out.push('\n');
+        for s in &doc.stats {
+            out.push_str(&format!(
+                "\n• {}: {} ({} {})",
+                s.name,
+                format_duration(s.speaking_secs),
+                s.words,
+                tr!("stats.words").to_lowercase(),
+            ));
+        }
+    }
+    out
+}
@@ -0,0 +1,264 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use crate::encryption::{self, EncryptionSettings};
+use crate::export::{MinutaDocument, MinutaEntry};
+use crate::i18n::tr;
+
+// ── Minutas a prueba de manipulación ───────────────────────────────────────
+//
+// Para actas con valor legal. Cada intervención se encadena con SHA-256
+// (hash de la anterior + su contenido) y la cadena se guarda en la minuta
+// JSON: cambiar, quitar o reordenar una intervención rompe la cadena desde
+// ese punto. El origen de la cadena incluye cuántas intervenciones tiene, y
+// la cabecera `chain` guarda ese número y el último hash, así que cortar las
+// últimas también se detecta. Además el archivo exportado, en cualquier formato, se puede
+// firmar con una clave SSH local (`ssh-keygen -Y sign`), que deja la firma
+// junto a él en `<archivo>.sig`.
+
+/// Espacio de nombres de las firmas; evita reutilizarlas para otra cosa.
+const SIGNATURE_NAMESPACE: &str = "minutero";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntegritySettings {
+    /// Guardar la cadena de hashes en la minuta JSON.
+    pub hash_chain: bool,
+    /// Firmar cada minuta exportada con `signing_key`.
+    pub sign: bool,
+    /// Clave privada SSH (sin contraseña, o la pública si la privada está en
+    /// ssh-agent).
+    pub signing_key: String,
+}
+
+fn sha256_hex(data: &str) -> String {
+    Sha256::digest(data.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Origen de la cadena: liga la primera intervención a la sesión y al
+/// número de intervenciones. Sin número, el de las minutas anteriores a
+/// la cabecera `chain`.
+fn genesis(title: &str, date: &str, segments: Option<usize>) -> String {
+    match segments {
+        Some(n) => sha256_hex(&format!("{}\n{}\n{}\n{}", SIGNATURE_NAMESPACE, title, date, n)),
+        None => sha256_hex(&format!("{}\n{}\n{}", SIGNATURE_NAMESPACE, title, date)),
+    }
+}
+
+fn link(previous: &str, speaker: Option<&str>, start: Option<f64>, end: Option<f64>, text: &str) -> String {
+    let secs = |t: Option<f64>| t.map(|t| t.to_string()).unwrap_or_default();
+    sha256_hex(&format!(
+        "{}\n{}\n{}\n{}\n{}",
+        previous, speaker.unwrap_or_default(), secs(start), secs(end), text,
+    ))
+}
+
+/// Hash de cada intervención, en orden.
+pub fn chain(doc: &MinutaDocument) -> Vec<String> {
+    let mut previous = genesis(&doc.title, &doc.date, Some(doc.entries.len()));
+    doc.entries.iter()
+        .map(|MinutaEntry { speaker, start_secs, end_secs, text, .. }| {
+            previous = link(&previous, speaker.as_deref(), *start_secs, *end_secs, text);
+            previous.clone()
+        })
+        .collect()
+}
+
+/// Resultado de comprobar la cadena de una minuta JSON.
+#[derive(Debug, PartialEq)]
+pub enum ChainCheck {
+    Intact { segments: usize },
+    /// Primera intervención (desde 0) cuyo hash no cuadra.
+    Broken { index: usize },
+    /// Faltan (o sobran) intervenciones al final: se guardaron `expected`.
+    Truncated { expected: usize, found: usize },
+    /// La minuta se guardó sin cadena.
+    Missing,
+}
+
+pub fn verify_chain(path: &Path, encryption: &EncryptionSettings) -> Result<ChainCheck> {
+    #[derive(Deserialize)]
+    struct StoredSegment {
+        speaker: Option<String>,
+        start_secs: Option<f64>,
+        end_secs: Option<f64>,
+        text: String,
+        hash: Option<String>,
+    }
+    #[derive(Deserialize)]
+    struct StoredMinuta {
+        title: String,
+        date: String,
+        segments: Vec<StoredSegment>,
+        chain: Option<ChainHeader>,
+    }
+
+    let minuta: StoredMinuta = serde_json::from_slice(&encryption::read(path, encryption)?)?;
+    if minuta.chain.is_none() && minuta.segments.iter().all(|s| s.hash.is_none()) {
+        return Ok(ChainCheck::Missing);
+    }
+    let found = minuta.segments.len();
+    let expected = minuta.chain.as_ref().map(|c| c.segments);
+    if let Some(expected) = expected.filter(|&expected| expected != found) {
+        return Ok(ChainCheck::Truncated { expected, found });
+    }
+    let mut previous = genesis(&minuta.title, &minuta.date, expected);
+    for (index, s) in minuta.segments.iter().enumerate() {
+        previous = link(&previous, s.speaker.as_deref(), s.start_secs, s.end_secs, &s.text);
+        if s.hash.as_deref() != Some(previous.as_str()) {
+            return Ok(ChainCheck::Broken { index });
+        }
+    }
+    if minuta.chain.is_some_and(|c| found > 0 && c.head != previous) {
+        return Ok(ChainCheck::Broken { index: found - 1 });
+    }
+    Ok(ChainCheck::Intact { segments: found })
+}
+
+/// Cabecera `chain` de la minuta JSON: cuántas intervenciones hay y el hash
+/// de la última.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChainHeader {
+    pub segments: usize,
+    pub head: String,
+}
+
+impl ChainHeader {
+    pub fn new(hashes: &[String]) -> Self {
+        Self { segments: hashes.len(), head: hashes.last().cloned().unwrap_or_default() }
+    }
+}
+
+fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+/// Firma `path` con la clave SSH y devuelve la ruta de la firma.
+pub fn sign_file(path: &Path, key: &str) -> Result<PathBuf> {
+    if key.trim().is_empty() {
+        return Err(anyhow!(tr!("integrity.no_key")));
+    }
+    // ssh-keygen no sobrescribe una firma anterior
+    let signature = signature_path(path);
+    let _ = std::fs::remove_file(&signature);
+    let output = Command::new("ssh-keygen")
+        .args(["-Y", "sign", "-f", key.trim(), "-n", SIGNATURE_NAMESPACE])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| anyhow!(tr!("integrity.ssh_keygen_missing", e = e)))?;
+    if !output.status.success() {
+        return Err(anyhow!(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(signature)
+}
+
+/// Comprueba la firma `<path>.sig`, si existe. Devuelve `None` sin firma y
+/// si no, si es válida y el mensaje de ssh-keygen (incluye la huella de la
+/// clave, que es lo que hay que comparar con la del firmante).
+pub fn check_signature(path: &Path) -> Result<Option<(bool, String)>> {
+    let signature = signature_path(path);
+    if !signature.exists() {
+        return Ok(None);
+    }
+    let output = Command::new("ssh-keygen")
+        .args(["-Y", "check-novalidate", "-n", SIGNATURE_NAMESPACE, "-s"])
+        .arg(&signature)
+        .stdin(std::fs::File::open(path)?)
+        .output()
+        .map_err(|e| anyhow!(tr!("integrity.ssh_keygen_missing", e = e)))?;
+    let message = [output.stdout, output.stderr].iter()
+        .map(|out| String::from_utf8_lossy(out).trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(Some((output.status.success(), message)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::minuta_json;
+
+    fn document() -> MinutaDocument {
+        MinutaDocument::new(
+            "Junta de socios".into(),
+            Vec::new(),
+            "[00:01] (Ana) Abrimos la sesión\n[00:20] (Luis) Aprobado el presupuesto\n[00:45] (Ana) Se levanta la sesión".into(),
+        )
+    }
+
+    /// Guarda `json` en un archivo temporal y comprueba su cadena.
+    fn check(name: &str, json: &serde_json::Value) -> ChainCheck {
+        let path = std::env::temp_dir().join(format!("minutero-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, serde_json::to_vec(json).unwrap()).unwrap();
+        let check = verify_chain(&path, &EncryptionSettings::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        check
+    }
+
+    #[test]
+    fn chain_follows_the_content() {
+        let doc = document();
+        let hashes = chain(&doc);
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(chain(&doc), hashes);
+
+        let mut edited = doc.clone();
+        edited.entries[1].text = "Rechazado el presupuesto".into();
+        let edited = chain(&edited);
+        assert_eq!(edited[0], hashes[0]);
+        assert_ne!(edited[1], hashes[1]);
+        assert_ne!(edited[2], hashes[2]);
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let json = minuta_json(&document(), true);
+        assert_eq!(check("intact", &json), ChainCheck::Intact { segments: 3 });
+        assert_eq!(check("missing", &minuta_json(&document(), false)), ChainCheck::Missing);
+
+        let mut edited = json.clone();
+        edited["segments"][1]["text"] = "Rechazado el presupuesto".into();
+        assert_eq!(check("edited", &edited), ChainCheck::Broken { index: 1 });
+
+        let mut reordered = json.clone();
+        reordered["segments"].as_array_mut().unwrap().swap(0, 1);
+        assert_eq!(check("reordered", &reordered), ChainCheck::Broken { index: 0 });
+    }
+
+    #[test]
+    fn detects_truncation() {
+        let json = minuta_json(&document(), true);
+
+        let mut truncated = json.clone();
+        truncated["segments"].as_array_mut().unwrap().pop();
+        assert_eq!(check("truncated", &truncated), ChainCheck::Truncated { expected: 3, found: 2 });
+
+        // Ajustar la cabecera no basta: el origen de la cadena lleva el número
+        let mut header = truncated.clone();
+        header["chain"]["segments"] = 2.into();
+        assert_eq!(check("header", &header), ChainCheck::Broken { index: 0 });
+
+        // Ni quitarla para que parezca del formato anterior
+        let mut legacy = truncated.clone();
+        legacy.as_object_mut().unwrap().remove("chain");
+        assert_eq!(check("headless", &legacy), ChainCheck::Broken { index: 0 });
+    }
+
+    #[test]
+    fn reads_legacy_chains() {
+        let doc = document();
+        let mut json = minuta_json(&doc, false);
+        let mut previous = genesis(&doc.title, &doc.date, None);
+        for (segment, entry) in json["segments"].as_array_mut().unwrap().iter_mut().zip(&doc.entries) {
+            previous = link(&previous, entry.speaker.as_deref(), entry.start_secs, entry.end_secs, &entry.text);
+            segment["hash"] = previous.clone().into();
+        }
+        assert_eq!(check("legacy", &json), ChainCheck::Intact { segments: 3 });
+    }
+}
@@ -0,0 +1,90 @@
+use crate::export::{MinutaDocument, MinutaEntry};
+use crate::i18n::tr;
+
+// ── Modo entrevista ────────────────────────────────────────────────────────
+//
+// En una entrevista la minuta se lee mejor como pares pregunta/respuesta. Se
+// marca cada intervención que parece una pregunta (signos de interrogación o,
+// si Whisper no los puso, una partícula interrogativa al principio) y se
+// agrupan las intervenciones seguidas: una pregunta numerada y, debajo, la
+// respuesta de cada interlocutor.
+
+/// Palabras que abren una pregunta aunque falte el signo.
+const QUESTION_WORDS_ES: &[&str] = &[
+    "qué", "cómo", "cuándo", "dónde", "adónde", "quién", "quiénes", "cuál", "cuáles",
+    "cuánto", "cuánta", "cuántos", "cuántas", "por qué",
+];
+const QUESTION_WORDS_EN: &[&str] = &[
+    "what", "how", "when", "where", "who", "whom", "whose", "which", "why",
+    "do", "does", "did", "is", "are", "was", "were", "can", "could", "would", "will",
+    "should", "have", "has",
+];
+
+/// ¿Parece una pregunta? `lang` como en `punctuation` (`None` = ambas listas).
+pub fn is_question(text: &str, lang: Option<&str>) -> bool {
+    let text = text.trim();
+    if text.starts_with('¿') || text.trim_end_matches(['"', '»', ')']).ends_with('?') {
+        return true;
+    }
+    // Con punto final Whisper ya decidió que no es pregunta
+    if text.ends_with(['.', '!', '…']) {
+        return false;
+    }
+    let lower = text.to_lowercase();
+    let words: &[&[&str]] = match lang {
+        Some("es") => &[QUESTION_WORDS_ES],
+        Some("en") => &[QUESTION_WORDS_EN],
+        _ => &[QUESTION_WORDS_ES, QUESTION_WORDS_EN],
+    };
+    words.iter().flat_map(|list| list.iter()).any(|w| {
+        lower.strip_prefix(w).is_some_and(|rest| !rest.starts_with(char::is_alphanumeric))
+    })
+}
+
+impl MinutaDocument {
+    /// Reagrupa la minuta en pares pregunta/respuesta: los párrafos llevan
+    /// delante «P1:», «R:»… y `body` pasa a ser ese mismo texto en Markdown.
+    pub fn into_interview(mut self, lang: Option<&str>) -> Self {
+        let mut grouped: Vec<MinutaEntry> = Vec::new();
+        for mut entry in std::mem::take(&mut self.entries) {
+            entry.question = is_question(&entry.text, lang);
+            match grouped.last_mut() {
+                Some(last) if last.question == entry.question && last.speaker == entry.speaker => {
+                    last.text = format!("{} {}", last.text, entry.text);
+                    last.end_secs = entry.end_secs.or(last.end_secs);
+                    last.words.extend(entry.words);
+                    last.reviewed &= entry.reviewed;
+                }
+                _ => grouped.push(entry),
+            }
+        }
+
+        let mut number = 0;
+        let mut body = String::new();
+        for entry in &mut grouped {
+            let label = if entry.question {
+                number += 1;
+                tr!("interview.question", n = number)
+            } else {
+                tr!("interview.answer")
+            };
+            if entry.question && number > 1 {
+                body.push_str("---\n\n");
+            }
+            body.push_str(&format!("**{}** ", label));
+            if let Some(ts) = &entry.timestamp {
+                body.push_str(&format!("[{}] ", ts));
+            }
+            if let Some(speaker) = &entry.speaker {
+                body.push_str(&format!("({}) ", speaker));
+            }
+            body.push_str(&entry.text);
+            body.push_str("\n\n");
+            entry.text = format!("{} {}", label, entry.text);
+        }
+
+        self.entries = grouped;
+        self.body = body;
+        self
+    }
+}
@@ -0,0 +1,452 @@
+use crate::export::MinutaDocument;
+
+// ── Números y fechas en cifras (normalización inversa) ────────────────────
+//
+// Whisper escribe los números con letras: "veinticinco de marzo de dos mil
+// veinticuatro". Al exportar, las cantidades pasan a cifras ("25 de marzo de
+// 2024", "3,5", "20 %"). Los números sueltos por debajo de diez se quedan
+// con letras ("dos personas"), salvo en fechas y porcentajes. La minuta en
+// pantalla no cambia.
+
+#[derive(Clone, Copy, PartialEq)]
+enum Lang {
+    Es,
+    En,
+}
+
+/// Papel de una palabra dentro de un número.
+#[derive(Clone, Copy)]
+enum Word {
+    /// Se suma al grupo. Rango: 1 unidades, 2 decenas, 3 centenas; dentro de
+    /// un grupo cada palabra ha de tener un rango menor que la anterior.
+    Add(u64, u8),
+    /// "hundred": multiplica el grupo.
+    Hundred,
+    /// mil, millón, thousand…
+    Mult(u64),
+    /// "y" (treinta y dos), "and" (one hundred and five).
+    And,
+    /// Solo cuenta en fechas: "primero de mayo", "March fifth".
+    Ordinal(u64),
+}
+
+const ES_UNITS: &[(&str, u64)] = &[
+    ("cero", 0), ("un", 1), ("uno", 1), ("una", 1), ("dos", 2), ("tres", 3), ("cuatro", 4),
+    ("cinco", 5), ("seis", 6), ("siete", 7), ("ocho", 8), ("nueve", 9), ("diez", 10),
+    ("once", 11), ("doce", 12), ("trece", 13), ("catorce", 14), ("quince", 15),
+    ("dieciséis", 16), ("dieciseis", 16), ("diecisiete", 17), ("dieciocho", 18),
+    ("diecinueve", 19), ("veintiuno", 21), ("veintiún", 21), ("veintiun", 21),
+    ("veintiuna", 21), ("veintidós", 22), ("veintidos", 22), ("veintitrés", 23),
+    ("veintitres", 23), ("veinticuatro", 24), ("veinticinco", 25), ("veintiséis", 26),
+    ("veintiseis", 26), ("veintisiete", 27), ("veintiocho", 28), ("veintinueve", 29),
+];
+const ES_TENS: &[(&str, u64)] = &[
+    ("veinte", 20), ("treinta", 30), ("cuarenta", 40), ("cincuenta", 50), ("sesenta", 60),
+    ("setenta", 70), ("ochenta", 80), ("noventa", 90),
+];
+const ES_HUNDREDS: &[(&str, u64)] = &[
+    ("cien", 100), ("ciento", 100), ("doscientos", 200), ("doscientas", 200),
+    ("trescientos", 300), ("trescientas", 300), ("cuatrocientos", 400),
+    ("cuatrocientas", 400), ("quinientos", 500), ("quinientas", 500), ("seiscientos", 600),
+    ("seiscientas", 600), ("setecientos", 700), ("setecientas", 700), ("ochocientos", 800),
+    ("ochocientas", 800), ("novecientos", 900), ("novecientas", 900),
+];
+const ES_MONTHS: &[&str] = &[
+    "enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre",
+    "setiembre", "octubre", "noviembre", "diciembre",
+];
+
+const EN_UNITS: &[(&str, u64)] = &[
+    ("zero", 0), ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5), ("six", 6),
+    ("seven", 7), ("eight", 8), ("nine", 9), ("ten", 10), ("eleven", 11), ("twelve", 12),
+    ("thirteen", 13), ("fourteen", 14), ("fifteen", 15), ("sixteen", 16), ("seventeen", 17),
+    ("eighteen", 18), ("nineteen", 19),
+];
+const EN_TENS: &[(&str, u64)] = &[
+    ("twenty", 20), ("thirty", 30), ("forty", 40), ("fifty", 50), ("sixty", 60),
+    ("seventy", 70), ("eighty", 80), ("ninety", 90),
+];
+const EN_ORDINALS: &[(&str, u64)] = &[
+    ("first", 1), ("second", 2), ("third", 3), ("fourth", 4), ("fifth", 5), ("sixth", 6),
+    ("seventh", 7), ("eighth", 8), ("ninth", 9), ("tenth", 10), ("eleventh", 11),
+    ("twelfth", 12), ("thirteenth", 13), ("fourteenth", 14), ("fifteenth", 15),
+    ("sixteenth", 16), ("seventeenth", 17), ("eighteenth", 18), ("nineteenth", 19),
+    ("twentieth", 20), ("thirtieth", 30),
+];
+const EN_MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august", "september",
+    "october", "november", "december",
+];
+
+fn find(table: &[(&str, u64)], word: &str) -> Option<u64> {
+    table.iter().find(|(w, _)| *w == word).map(|(_, v)| *v)
+}
+
+fn lookup(lang: Lang, word: &str) -> Option<Word> {
+    match lang {
+        Lang::Es => find(ES_UNITS, word).map(|v| Word::Add(v, 1))
+            .or_else(|| find(ES_TENS, word).map(|v| Word::Add(v, 2)))
+            .or_else(|| find(ES_HUNDREDS, word).map(|v| Word::Add(v, 3)))
+            .or(match word {
+                "mil" => Some(Word::Mult(1_000)),
+                "millón" | "millon" | "millones" => Some(Word::Mult(1_000_000)),
+                "y" => Some(Word::And),
+                "primero" => Some(Word::Ordinal(1)),
+                _ => None,
+            }),
+        Lang::En => {
+            // "twenty-five", "thirty-first"
+            if let Some((tens, unit)) = word.split_once('-') {
+                let tens = find(EN_TENS, tens)?;
+                return match find(EN_UNITS, unit) {
+                    Some(v) if (1..10).contains(&v) => Some(Word::Add(tens + v, 2)),
+                    _ => find(EN_ORDINALS, unit).filter(|v| *v < 10).map(|v| Word::Ordinal(tens + v)),
+                };
+            }
+            find(EN_UNITS, word).map(|v| Word::Add(v, 1))
+                .or_else(|| find(EN_TENS, word).map(|v| Word::Add(v, 2)))
+                .or_else(|| find(EN_ORDINALS, word).map(Word::Ordinal))
+                .or(match word {
+                    "hundred" => Some(Word::Hundred),
+                    "thousand" => Some(Word::Mult(1_000)),
+                    "million" | "millions" => Some(Word::Mult(1_000_000)),
+                    "and" => Some(Word::And),
+                    _ => None,
+                })
+        }
+    }
+}
+
+/// Palabra del texto con la puntuación que lleva pegada.
+struct Token<'a> {
+    lead: &'a str,
+    core: &'a str,
+    trail: &'a str,
+    word: String,
+}
+
+impl<'a> Token<'a> {
+    fn new(raw: &'a str) -> Self {
+        let start = raw.find(char::is_alphanumeric).unwrap_or(raw.len());
+        let end = raw.rfind(char::is_alphanumeric)
+            .map_or(start, |i| i + raw[i..].chars().next().map_or(1, char::len_utf8));
+        let core = &raw[start..end.max(start)];
+        Self { lead: &raw[..start], core, trail: &raw[end.max(start)..], word: core.to_lowercase() }
+    }
+}
+
+/// Número reconocido a partir de `start`; `end` es la primera palabra que
+/// ya no forma parte de él.
+struct Parsed {
+    value: u64,
+    end: usize,
+    words: usize,
+    ordinal: bool,
+    /// Solo "mil" o "thousand": "mil gracias" no es una cantidad.
+    bare_multiplier: bool,
+}
+
+/// Sin `multipliers` se para antes de "mil", "million"…
+fn parse(lang: Lang, tokens: &[Token], start: usize, multipliers: bool) -> Option<Parsed> {
+    let (mut total, mut group) = (0u64, 0u64);
+    let mut last_rank = 4u8;
+    let mut last_mult = u64::MAX;
+    let (mut end, mut words, mut ordinal) = (start, 0, false);
+    let mut i = start;
+    while let Some(token) = tokens.get(i) {
+        if i > start && !token.lead.is_empty() {
+            break;
+        }
+        match lookup(lang, &token.word) {
+            Some(Word::Add(v, rank)) if rank < last_rank => {
+                group += v;
+                // "twenty-five" ya lleva las unidades
+                last_rank = if rank == 2 && v % 10 != 0 { 1 } else { rank };
+            }
+            Some(Word::Ordinal(v)) if last_rank > 1 => {
+                group += v;
+                ordinal = true;
+                words += 1;
+                end = i + 1;
+                break;
+            }
+            Some(Word::Hundred) if (1..100).contains(&group) => {
+                group *= 100;
+                last_rank = 3;
+            }
+            Some(Word::Mult(m)) if multipliers && m < last_mult => {
+                total += group.max(1) * m;
+                group = 0;
+                last_rank = 4;
+                last_mult = m;
+            }
+            // "mil millones"
+            Some(Word::Mult(m)) if multipliers && group == 0 && total < m => {
+                total *= m;
+                last_mult = m;
+            }
+            Some(Word::And) if token.trail.is_empty() && words > 0 => {
+                let next = tokens.get(i + 1)
+                    .filter(|t| t.lead.is_empty())
+                    .and_then(|t| lookup(lang, &t.word));
+                let joins = match (lang, next) {
+                    (Lang::Es, Some(Word::Add(v, 1))) => last_rank == 2 && v < 10,
+                    (Lang::En, Some(Word::Add(_, _))) => last_rank == 3 || (last_rank == 4 && total > 0),
+                    _ => false,
+                };
+                if !joins {
+                    break;
+                }
+                i += 1;
+                continue;
+            }
+            _ => break,
+        }
+        words += 1;
+        end = i + 1;
+        i += 1;
+        if !token.trail.is_empty() {
+            break;
+        }
+    }
+    let bare_multiplier = words == 1 && matches!(lookup(lang, &tokens[start].word), Some(Word::Mult(_)));
+    (words > 0).then_some(Parsed { value: total + group, end, words, ordinal, bare_multiplier })
+}
+
+/// Parte decimal tras "coma" / "point": "tres coma cinco", "three point one
+/// four". El multiplicador que siga se queda con letras: "3,5 millones".
+fn parse_decimals(lang: Lang, tokens: &[Token], at: usize) -> Option<(String, usize)> {
+    let separator = tokens.get(at).filter(|t| t.lead.is_empty() && t.trail.is_empty())?;
+    match lang {
+        Lang::Es if separator.word == "coma" => {
+            let p = parse(lang, tokens, at + 1, false).filter(|p| !p.ordinal)?;
+            Some((p.value.to_string(), p.end))
+        }
+        Lang::En if separator.word == "point" => {
+            let mut digits = String::new();
+            let mut i = at + 1;
+            while let Some(token) = tokens.get(i).filter(|t| t.lead.is_empty()) {
+                match lookup(lang, &token.word) {
+                    Some(Word::Add(v, 1)) if v < 10 => digits.push_str(&v.to_string()),
+                    _ => break,
+                }
+                i += 1;
+                if !token.trail.is_empty() {
+                    break;
+                }
+            }
+            (!digits.is_empty()).then_some((digits, i))
+        }
+        _ => None,
+    }
+}
+
+/// "por ciento", "percent", "per cent" justo después del número.
+fn parse_percent(lang: Lang, tokens: &[Token], at: usize) -> Option<usize> {
+    let word = |i: usize| tokens.get(i).filter(|t| t.lead.is_empty()).map(|t| t.word.as_str());
+    let joined = |i: usize| tokens.get(i).is_some_and(|t| t.trail.is_empty());
+    match (lang, word(at)) {
+        (Lang::Es, Some("por")) if joined(at) && matches!(word(at + 1), Some("ciento" | "cien")) => Some(at + 2),
+        (Lang::En, Some("percent")) => Some(at + 1),
+        (Lang::En, Some("per")) if joined(at) && word(at + 1) == Some("cent") => Some(at + 2),
+        _ => None,
+    }
+}
+
+/// En inglés los meses van con mayúscula, y así "may" no cuenta.
+fn is_month(lang: Lang, token: Option<&Token>) -> bool {
+    let Some(token) = token else { return false };
+    match lang {
+        Lang::Es => ES_MONTHS.contains(&token.word.as_str()),
+        Lang::En => EN_MONTHS.contains(&token.word.as_str()) && token.core.starts_with(char::is_uppercase),
+    }
+}
+
+/// Cifras con separador de miles a partir de 10 000 (la RAE no lo pone en
+/// números de cuatro cifras, y así los años quedan como "2024").
+fn format_integer(value: u64, lang: Lang) -> String {
+    let digits = value.to_string();
+    if value < 10_000 {
+        return digits;
+    }
+    let separator = if lang == Lang::Es { '.' } else { ',' };
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn english_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Intenta convertir el número que empieza en `start`. Devuelve el texto en
+/// cifras y la primera palabra que queda después.
+fn convert(lang: Lang, tokens: &[Token], start: usize, ambiguous_once: bool) -> Option<(String, usize)> {
+    let mut p = parse(lang, tokens, start, true)?;
+    let closed = |end: usize| tokens[end - 1].trail.is_empty();
+
+    // "twenty twenty-four", "nineteen ninety": años dichos en dos mitades
+    if lang == Lang::En && p.words == 1 && (19..=20).contains(&p.value) && closed(p.end) {
+        if let Some(second) = parse(lang, tokens, p.end, false).filter(|s| !s.ordinal && (10..100).contains(&s.value)) {
+            p.value = p.value * 100 + second.value;
+            p.words += second.words;
+            p.end = second.end;
+        }
+    }
+
+    let word_at = |i: usize| tokens.get(i).map(|t| t.word.as_str()).unwrap_or_default();
+    // "veinticinco de marzo", "March fifth", "the fifth of March"
+    let before_month = closed(p.end) && matches!((lang, word_at(p.end)), (Lang::Es, "de") | (Lang::En, "of"))
+        && is_month(lang, tokens.get(p.end + 1));
+    let after_month = lang == Lang::En && start > 0
+        && tokens[start - 1].trail.is_empty() && is_month(lang, tokens.get(start - 1));
+    let date = before_month || after_month;
+
+    let mut text = format_integer(p.value, lang);
+    let mut end = p.end;
+    let mut sign = "";
+    let mut decimal = false;
+    if !p.ordinal && closed(end) {
+        if let Some((digits, after)) = parse_decimals(lang, tokens, end) {
+            text = format!("{}{}{}", p.value, if lang == Lang::Es { ',' } else { '.' }, digits);
+            end = after;
+            decimal = true;
+        }
+    }
+    if !p.ordinal && closed(end) {
+        if let Some(after) = parse_percent(lang, tokens, end) {
+            end = after;
+            sign = if lang == Lang::Es { " %" } else { "%" };
+        }
+    }
+
+    if (p.ordinal && !date) || p.bare_multiplier || (ambiguous_once && p.words == 1 && tokens[start].word == "once") {
+        return None;
+    }
+    if p.value < 10 && p.words == 1 && !decimal && sign.is_empty() && !date {
+        return None;
+    }
+    if p.ordinal && lang == Lang::En && before_month {
+        text.push_str(english_suffix(p.value));
+    }
+    Some((format!("{}{}{}{}", tokens[start].lead, text, sign, tokens[end - 1].trail), end))
+}
+
+/// Pasa a cifras los números de `text`. `lang` es el idioma del texto; sin
+/// idioma se prueban español e inglés.
+pub fn normalize_text(text: &str, lang: Option<&str>) -> String {
+    let langs: &[Lang] = match lang {
+        Some("es") => &[Lang::Es],
+        Some("en") => &[Lang::En],
+        Some(_) => return text.to_string(),
+        None => &[Lang::Es, Lang::En],
+    };
+    let tokens: Vec<Token> = text.split_whitespace().map(Token::new).collect();
+    if !tokens.iter().any(|t| langs.iter().any(|l| lookup(*l, &t.word).is_some())) {
+        return text.to_string();
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    let mut changed = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        // Sin idioma, "once" es más a menudo el inglés "una vez" que un 11
+        match langs.iter().find_map(|l| convert(*l, &tokens, i, lang.is_none())) {
+            Some((number, end)) => {
+                out.push(number);
+                changed = true;
+                i = end;
+            }
+            None => {
+                let t = &tokens[i];
+                out.push(format!("{}{}{}", t.lead, t.core, t.trail));
+                i += 1;
+            }
+        }
+    }
+    if changed { out.join(" ") } else { text.to_string() }
+}
+
+impl MinutaDocument {
+    /// Números y fechas en cifras. Cada intervención usa su idioma detectado
+    /// si lo tiene, y si no `lang`.
+    pub fn normalize_numbers(mut self, lang: Option<&str>) -> Self {
+        self.body = self.body.split('\n')
+            .map(|line| normalize_text(line, lang))
+            .collect::<Vec<_>>()
+            .join("\n");
+        for entry in &mut self.entries {
+            let text = normalize_text(&entry.text, entry.lang.as_deref().or(lang));
+            if text != entry.text {
+                // Las palabras sueltas con sus tiempos ya no casan con el texto.
+                entry.words.clear();
+                entry.text = text;
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(lang: Option<&str>, cases: &[(&str, &str)]) {
+        for (text, expected) in cases {
+            assert_eq!(normalize_text(text, lang), *expected, "{:?} ({:?})", text, lang);
+        }
+    }
+
+    #[test]
+    fn spanish() {
+        check(Some("es"), &[
+            ("veinticinco de marzo de dos mil veinticuatro", "25 de marzo de 2024"),
+            ("el primero de mayo", "el 1 de mayo"),
+            ("treinta y dos", "32"),
+            ("ciento veintitrés mil cuatrocientos", "123.400"),
+            ("tres mil millones", "3.000.000.000"),
+            ("tres coma cinco millones de euros", "3,5 millones de euros"),
+            ("subió un veinte por ciento", "subió un 20 %"),
+            ("doce, trece y catorce", "12, 13 y 14"),
+            // Se quedan con letras
+            ("vinieron dos personas", "vinieron dos personas"),
+            ("mil gracias", "mil gracias"),
+        ]);
+    }
+
+    #[test]
+    fn english() {
+        check(Some("en"), &[
+            ("on March fifth", "on March 5"),
+            ("the fifth of March", "the 5th of March"),
+            ("in twenty twenty-four", "in 2024"),
+            ("three point one four", "3.14"),
+            ("one hundred and five", "105"),
+            ("fifty percent", "50%"),
+            ("the first item", "the first item"),
+            ("may I ask", "may I ask"),
+        ]);
+    }
+
+    #[test]
+    fn language() {
+        check(None, &[
+            ("twenty-five people", "25 people"),
+            // Sin idioma, "once" es el inglés
+            ("once upon a time", "once upon a time"),
+        ]);
+        check(Some("es"), &[("once", "11")]);
+        check(Some("fr"), &[("veinte", "veinte")]);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use crate::export::{MinutaDocument, MinutaEntry};
+use crate::i18n::tr;
+
+// ── Modo clase: capítulos ──────────────────────────────────────────────────
+//
+// En una clase o conferencia larga con un solo ponente la minuta es un muro
+// de texto. Se parte en capítulos donde cambia el tema: una pausa larga, o
+// una pausa más corta cuando el vocabulario de antes y de después apenas se
+// parece. Cada capítulo se titula con sus palabras más repetidas y la minuta
+// exportada empieza con un índice.
+
+/// Duración mínima de un capítulo: evita partir en cada titubeo.
+const MIN_CHAPTER_SECS: f64 = 180.0;
+/// Pausa que por sí sola abre capítulo.
+const LONG_PAUSE_SECS: f64 = 6.0;
+/// Pausa que abre capítulo si además cambia el vocabulario.
+const SHORT_PAUSE_SECS: f64 = 1.5;
+/// Audio a cada lado del corte con el que se compara el vocabulario.
+const CONTEXT_SECS: f64 = 90.0;
+/// Similitud (coseno) por debajo de la cual se considera otro tema.
+const TOPIC_SHIFT_SIMILARITY: f64 = 0.15;
+/// Palabras del título de cada capítulo.
+const TITLE_WORDS: usize = 3;
+
+const STOPWORDS_ES: &[&str] = &[
+    "para", "pero", "como", "este", "esta", "esto", "estos", "estas", "porque", "cuando", "donde",
+    "entonces", "también", "tiene", "tienen", "hace", "hacer", "puede", "pueden", "desde", "hasta",
+    "sobre", "entre", "todo", "todos", "toda", "todas", "otro", "otra", "otros", "otras", "cada",
+    "bueno", "pues", "vale", "muy", "más", "menos", "aquí", "ahora", "bien", "algo", "nada", "ese",
+    "esa", "eso", "esos", "esas", "aquel", "cual", "sido", "está", "están", "hemos", "vamos",
+    "tenemos", "ellos", "ellas", "nosotros", "vosotros", "usted", "ustedes", "decir", "digamos",
+];
+const STOPWORDS_EN: &[&str] = &[
+    "that", "this", "these", "those", "with", "from", "have", "been", "were", "what", "when",
+    "where", "which", "there", "their", "they", "them", "then", "than", "will", "would", "could",
+    "should", "about", "into", "just", "like", "also", "some", "very", "more", "much", "here",
+    "because", "okay", "well", "going", "really", "thing", "things", "know", "think", "right",
+];
+
+/// Índices de las intervenciones que abren capítulo (siempre la primera).
+/// Sin tiempos no hay pausas que medir y todo queda en un capítulo.
+pub fn chapter_starts(entries: &[MinutaEntry], lang: Option<&str>) -> Vec<usize> {
+    if entries.is_empty() {
+        return Vec::new();
+    }
+    let mut starts = vec![0];
+    let mut chapter_start = entries[0].start_secs;
+    for i in 1..entries.len() {
+        let (Some(start), Some(previous_end), Some(opened)) =
+            (entries[i].start_secs, entries[i - 1].end_secs, chapter_start)
+        else {
+            continue;
+        };
+        if start - opened < MIN_CHAPTER_SECS {
+            continue;
+        }
+        let pause = start - previous_end;
+        let shift = pause >= LONG_PAUSE_SECS || (pause >= SHORT_PAUSE_SECS && {
+            let before = term_counts(entries[..i].iter().rev()
+                .take_while(|e| e.end_secs.is_some_and(|t| start - t <= CONTEXT_SECS)), lang);
+            let after = term_counts(entries[i..].iter()
+                .take_while(|e| e.start_secs.is_some_and(|t| t - start <= CONTEXT_SECS)), lang);
+            similarity(&before, &after) < TOPIC_SHIFT_SIMILARITY
+        });
+        if shift {
+            starts.push(i);
+            chapter_start = Some(start);
+        }
+    }
+    starts
+}
+
+/// Frecuencia de las palabras con contenido (4+ letras y no vacías).
+pub(crate) fn term_counts<'a>(entries: impl Iterator<Item = &'a MinutaEntry>, lang: Option<&str>) -> HashMap<String, usize> {
+    let stopwords: &[&[&str]] = match lang {
+        Some("es") => &[STOPWORDS_ES],
+        Some("en") => &[STOPWORDS_EN],
+        _ => &[STOPWORDS_ES, STOPWORDS_EN],
+    };
+    let mut counts = HashMap::new();
+    for entry in entries {
+        for word in entry.text.split(|c: char| !c.is_alphanumeric()) {
+            let word = word.to_lowercase();
+            if word.chars().count() >= 4 && !stopwords.iter().any(|list| list.contains(&word.as_str())) {
+                *counts.entry(word).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+fn similarity(a: &HashMap<String, usize>, b: &HashMap<String, usize>) -> f64 {
+    let dot: f64 = a.iter()
+        .filter_map(|(w, &n)| b.get(w).map(|&m| (n * m) as f64))
+        .sum();
+    let norm = |v: &HashMap<String, usize>| v.values().map(|&n| (n * n) as f64).sum::<f64>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 { 1.0 } else { dot / denominator }
+}
+
+/// Palabras más repetidas del capítulo; empate por orden de aparición.
+fn chapter_title(entries: &[MinutaEntry], lang: Option<&str>) -> String {
+    let counts = term_counts(entries.iter(), lang);
+    let mut seen = Vec::new();
+    for entry in entries {
+        for word in entry.text.split(|c: char| !c.is_alphanumeric()) {
+            let word = word.to_lowercase();
+            if counts.contains_key(&word) && !seen.contains(&word) {
+                seen.push(word);
+            }
+        }
+    }
+    // `sort_by` es estable: los empates conservan el orden de aparición
+    seen.sort_by(|a, b| counts[b].cmp(&counts[a]));
+    let title: Vec<String> = seen.into_iter()
+        .take(TITLE_WORDS)
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect();
+    title.join(", ")
+}
+
+impl MinutaDocument {
+    /// Marca el inicio de cada capítulo en `entries` y rehace `body` en
+    /// Markdown con el índice delante y un encabezado por capítulo.
+    pub fn into_lecture(mut self, lang: Option<&str>) -> Self {
+        let starts = chapter_starts(&self.entries, lang);
+        let mut contents = String::new();
+        let mut sections = String::new();
+        for (n, &start) in starts.iter().enumerate() {
+            let end = starts.get(n + 1).copied().unwrap_or(self.entries.len());
+            let heading = tr!("lecture.chapter", n = n + 1, title = chapter_title(&self.entries[start..end], lang));
+            let timestamp = self.entries[start].timestamp.as_ref()
+                .map(|ts| format!("[{}] ", ts))
+                .unwrap_or_default();
+            contents.push_str(&format!("{}. {}{}\n", n + 1, timestamp, heading));
+            sections.push_str(&format!("## {}\n\n", heading));
+            for entry in &self.entries[start..end] {
+                if let Some(ts) = &entry.timestamp {
+                    sections.push_str(&format!("[{}] ", ts));
+                }
+                if let Some(speaker) = &entry.speaker {
+                    sections.push_str(&format!("({}) ", speaker));
+                }
+                sections.push_str(&entry.text);
+                sections.push_str("\n\n");
+            }
+            self.entries[start].chapter = Some(heading);
+        }
+        if !starts.is_empty() {
+            self.body = format!("## {}\n\n{}\n{}", tr!("lecture.contents"), contents, sections);
+        }
+        self
+    }
+}
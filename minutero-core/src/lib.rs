@@ -0,0 +1,183 @@
+//! # minutero-core
+//!
+//! Todo lo que hace Minutero salvo la ventana: enumerar dispositivos,
+//! capturar varias fuentes a la vez, transcribirlas con Whisper, llevar la
+//! sesión (grabación, estadísticas, re-transcripción) y exportar la minuta.
+//! La aplicación de escritorio (`minutero-gui`) es una capa fina encima; otras
+//! herramientas pueden usar el mismo pipeline sin `egui`.
+//!
+//! ## Transcribir en vivo
+//!
+//! [`audio_thread_main`] abre un hilo por fuente y entrega por un canal
+//! [`AudioMessage`]s: streams abiertos, niveles de entrada, intervenciones
+//! transcritas con sus tiempos y confianza, errores por fuente y, al terminar,
+//! [`AudioMessage::Stopped`]. Se para con [`StopSignal::stop`], que
+//! despierta en el acto a todos sus hilos.
+//!
+//! ```no_run
+//! use std::sync::mpsc;
+//! use minutero_core::{
+//!     audio_thread_main, get_available_devices, AudioMessage, InterlocutorProfile, LanguageConfig,
+//!     PreRollAudio, SourceType, StopSignal, TranscriptionConfig,
+//! };
+//!
+//! let mic = get_available_devices(None, true).into_iter().next().expect("sin micrófono");
+//! let profile = InterlocutorProfile {
+//!     id: 1,
+//!     device_id: mic.id,
+//!     source_type: SourceType::Input,
+//!     name: "Ana".into(),
+//!     is_active: true,
+//!     technical_name: mic.technical_name,
+//!     channel: None,
+//!     agc: true,
+//!     pcm: Default::default(),
+//!     identify_voices: false,
+//!     on_error: Default::default(),
+//!     max_restarts: 3,
+//! };
+//! let config = TranscriptionConfig {
+//!     lang: LanguageConfig::default(),
+//!     preset: Default::default(),
+//!     tuning: Default::default(),
+//!     style: Default::default(),
+//!     audio_host: None,
+//!     model_source: Default::default(),
+//!     mute: Default::default(),
+//!     multilingual: Default::default(),
+//!     voices: Default::default(),
+//!     streams: Default::default(),
+//!     capture_tool: None,
+//!     compare_model: None,
+//!     discord: Default::default(),
+//!     limits: Default::default(),
+//! };
+//!
+//! let (tx, rx) = mpsc::channel();
+//! let stop = StopSignal::new();
+//! let worker = {
+//!     let stop = stop.clone();
+//!     std::thread::spawn(move || {
+//!         audio_thread_main("base".into(), tx, stop, vec![profile], config, None, PreRollAudio::default())
+//!     })
+//! };
+//!
+//! for msg in rx {
+//!     match msg {
+//!         AudioMessage::Segment { speaker, text, start_secs, .. } => {
+//!             println!("[{:.1}] {}: {}", start_secs, speaker, text);
+//!             if text.contains("fin de la reunión") {
+//!                 stop.stop();
+//!             }
+//!         }
+//!         AudioMessage::Stopped { .. } => break,
+//!         _ => {}
+//!     }
+//! }
+//! worker.join().unwrap()?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! ## Otras piezas
+//!
+//! - Archivos de audio o vídeo: [`video::video_transcription_thread`].
+//! - Modelo cargado antes de empezar: [`preload::preload_thread`].
+//! - Varias sesiones a la vez, cada una con sus fuentes: [`rooms::Room`].
+//! - Minuta: [`export::MinutaDocument`] y [`export::save_minuta`] (Markdown,
+//!   HTML, Word, PDF, WebVTT…), con [`redaction`] para anonimizarla y
+//!   [`interview`] / [`lecture`] para exportarla como preguntas y respuestas o
+//!   por capítulos, [`itn`] para pasar números y fechas a cifras,
+//!   [`integrity`] para encadenarla y firmarla y [`encryption`] para cifrarla.
+//! - Sesión: [`recording::MixRecorder`] graba la mezcla y las pistas, que
+//!   [`retranscribe::retranscribe_thread`] vuelve a pasar por otro modelo,
+//!   y [`split`] la parte en varias minutas en jornadas largas.
+//! - Minuta en vivo desde el móvil u otro equipo: [`web::WebServer`].
+//! - Dispositivos por nombre estable, con alias y opciones de búfer:
+//!   [`devices`] (en Windows, también [`wasapi_exclusive`]), y una prueba
+//!   rápida de cada uno: [`device_test::DeviceTest`].
+//! - Participantes habituales con su cargo y dispositivo: [`roster`], y
+//!   quién habla en una fuente compartida, por la voz: [`speaker_id`].
+//! - Frases que Whisper inventa sobre el silencio: [`hallucinations`].
+//! - Reuniones que cambian de idioma sobre la marcha: [`multilingual`].
+//! - Preferencias persistentes: [`settings::AppSettings`].
+//! - Fallos: [`crash::install`] guarda el backtrace y la minuta en curso si
+//!   algo hace panic.
+//!
+//! Los textos para el usuario (estados, errores) salen en el idioma elegido
+//! con [`i18n::set_language`].
+
+pub mod i18n;
+pub mod data;
+pub mod audio;
+pub mod devices;
+pub mod roster;
+pub mod speaker_id;
+pub mod device_test;
+pub mod agc;
+pub mod dsp;
+pub mod video;
+pub mod system_audio;
+pub mod capture_tools;
+pub mod virtual_sink;
+pub mod process_loopback;
+pub mod wasapi_exclusive;
+pub mod settings;
+pub mod postprocess;
+pub mod hallucinations;
+pub mod punctuation;
+pub mod multilingual;
+pub mod interview;
+pub mod lecture;
+pub mod topics;
+pub mod actions;
+pub mod itn;
+pub mod redaction;
+pub mod integrity;
+pub mod encryption;
+pub mod recording;
+pub mod dataset;
+pub mod split;
+pub mod preroll;
+pub mod preload;
+pub mod stop;
+pub mod pool;
+pub mod resources;
+pub mod rooms;
+pub mod playback;
+pub mod speech;
+pub mod retranscribe;
+pub mod alerts;
+pub mod notify;
+pub mod stats;
+pub mod export;
+pub mod history;
+pub mod docx;
+pub mod pdf;
+pub mod subtitles;
+pub mod template;
+pub mod integrations;
+pub mod slack;
+pub mod notion;
+pub mod telegram;
+pub mod matrix;
+pub mod discord;
+pub mod storage;
+pub mod git_archive;
+pub mod mqtt;
+pub mod ndi;
+pub mod hooks;
+pub mod web;
+pub mod calendar;
+pub mod conferencing;
+pub mod logging;
+pub mod crash;
+pub mod diagnostics;
+pub mod bench;
+
+pub use audio::{audio_thread_main, get_available_devices, host_names};
+pub use data::{
+    AudioMessage, DeviceInfo, InterlocutorProfile, LanguageConfig, ModelSource, Preset, SessionType,
+    SourceType, StreamErrorKind, TranscriptSegment, TranscriptionConfig, TranscriptionStyle, WhisperTuning,
+};
+pub use preroll::PreRollAudio;
+pub use stop::StopSignal;
@@ -0,0 +1,221 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::{Interest, Subscriber};
+use tracing::{Event, Level, Metadata};
+
+// ── Registro (logging) ─────────────────────────────────────────────────────
+//
+// Suscriptor mínimo de `tracing`: cada evento va a `logs/minutero.log`
+// (rotado por tamaño) y a un búfer en memoria que muestra la vista "Log".
+// En Linux stderr se redirige a /dev/null, así que este es el único sitio
+// donde quedan los errores de los hilos de fondo.
+
+const LOG_DIR: &str = "logs";
+const LOG_FILE: &str = "minutero.log";
+/// Tamaño a partir del cual se rota el archivo, y copias antiguas que se guardan.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+const KEEP_ROTATED: usize = 3;
+/// Líneas que conserva la vista "Log".
+const MAX_MEMORY_LINES: usize = 2000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: &'static [LogLevel] = &[
+        LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn  => "WARN",
+            LogLevel::Info  => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        }
+    }
+
+    fn from_level(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN  => LogLevel::Warn,
+            Level::INFO  => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            _            => LogLevel::Trace,
+        }
+    }
+}
+
+/// Línea ya formateada para la vista "Log".
+#[derive(Clone)]
+pub struct LogLine {
+    pub level: LogLevel,
+    pub text: String,
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+static LINES: Mutex<VecDeque<LogLine>> = Mutex::new(VecDeque::new());
+static FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// Instala el suscriptor global. Si no se puede abrir el archivo se sigue
+/// registrando en memoria.
+pub fn init(level: LogLevel) {
+    set_level(level);
+    match LogFile::open() {
+        Ok(file) => *FILE.lock().unwrap() = Some(file),
+        Err(e) => record_line(LogLevel::Warn, format!("No se pudo abrir el log: {:?}", e)),
+    }
+    let _ = tracing::subscriber::set_global_default(LogSubscriber { next_id: AtomicU64::new(1) });
+}
+
+/// Cambia la verbosidad en caliente (desde Configuración).
+pub fn set_level(level: LogLevel) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn recent_lines() -> Vec<LogLine> {
+    LINES.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear_recent() {
+    LINES.lock().unwrap().clear();
+}
+
+/// Ruta absoluta del log actual (relativa si no se conoce el directorio de trabajo).
+pub fn log_path() -> PathBuf {
+    let relative = Path::new(LOG_DIR).join(LOG_FILE);
+    std::env::current_dir().map(|dir| dir.join(&relative)).unwrap_or(relative)
+}
+
+fn record_line(level: LogLevel, text: String) {
+    if let Some(file) = FILE.lock().unwrap().as_mut() {
+        file.write_line(&text);
+    }
+    let mut lines = LINES.lock().unwrap();
+    if lines.len() >= MAX_MEMORY_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(LogLine { level, text });
+}
+
+// ── Archivo con rotación ───────────────────────────────────────────────────
+
+struct LogFile {
+    file: File,
+    size: u64,
+}
+
+impl LogFile {
+    fn open() -> std::io::Result<Self> {
+        std::fs::create_dir_all(LOG_DIR)?;
+        let file = OpenOptions::new().create(true).append(true).open(log_path())?;
+        let size = file.metadata()?.len();
+        Ok(Self { file, size })
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.size >= MAX_LOG_BYTES {
+            if let Ok(fresh) = Self::rotate() {
+                *self = fresh;
+            }
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.size += line.len() as u64 + 1;
+        }
+    }
+
+    /// minutero.log → minutero.log.1 → … → minutero.log.N (se descarta).
+    fn rotate() -> std::io::Result<Self> {
+        let rotated = |n: usize| Path::new(LOG_DIR).join(format!("{}.{}", LOG_FILE, n));
+        let _ = std::fs::remove_file(rotated(KEEP_ROTATED));
+        for n in (1..KEEP_ROTATED).rev() {
+            let _ = std::fs::rename(rotated(n), rotated(n + 1));
+        }
+        std::fs::rename(log_path(), rotated(1))?;
+        Self::open()
+    }
+}
+
+// ── Suscriptor ─────────────────────────────────────────────────────────────
+
+struct LogSubscriber {
+    next_id: AtomicU64,
+}
+
+impl Subscriber for LogSubscriber {
+    // El nivel cambia en caliente: no se puede cachear el interés por callsite.
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        LogLevel::from_level(metadata.level()) as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let level = LogLevel::from_level(metadata.level());
+        let mut fields = FieldFormatter::default();
+        event.record(&mut fields);
+        record_line(level, format!(
+            "{} {:<5} {}: {}{}",
+            Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            level.label(),
+            metadata.target(),
+            fields.message,
+            fields.rest,
+        ));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[derive(Default)]
+struct FieldFormatter {
+    message: String,
+    rest: String,
+}
+
+impl Visit for FieldFormatter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.rest, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.rest, " {}={:?}", field.name(), value);
+        }
+    }
+}
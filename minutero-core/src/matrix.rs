@@ -0,0 +1,121 @@
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use crate::export::MinutaDocument;
+use crate::i18n::tr;
+use crate::integrations::overview;
+
+// ── Matrix ─────────────────────────────────────────────────────────────────
+//
+// Publica el resumen en una sala de Matrix/Element con la API cliente-servidor
+// y, opcionalmente, sube la minuta al repositorio de medios del servidor y la
+// envía como archivo. Basta un token de acceso de una cuenta (mejor una de
+// bot) que ya esté en la sala; las salas cifradas de extremo a extremo no
+// están soportadas.
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatrixConfig {
+    pub enabled: bool,
+    /// `https://matrix.example.org`
+    pub homeserver: String,
+    pub access_token: String,
+    /// ID de la sala (`!abc123:example.org`), no su alias.
+    pub room_id: String,
+    pub attach_file: bool,
+}
+
+impl MatrixConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+            && !self.homeserver.trim().is_empty()
+            && !self.access_token.trim().is_empty()
+            && !self.room_id.trim().is_empty()
+    }
+
+    /// `segments` van codificados (el ID de la sala lleva `!` y `:`).
+    fn url(&self, segments: &[&str]) -> Result<Url> {
+        let mut url = Url::parse(self.homeserver.trim())?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("URL del servidor no válida"))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+}
+
+pub async fn post_minuta(config: &MatrixConfig, doc: &MinutaDocument, path: &Path) -> Result<()> {
+    let client = Client::new();
+    let text = overview(doc);
+    if !config.attach_file {
+        let text = format!("{}\n\n{}", text, tr!("integrations.saved_at", path = path.display()));
+        return send_event(&client, config, json!({ "msgtype": "m.text", "body": text })).await;
+    }
+
+    send_event(&client, config, json!({ "msgtype": "m.text", "body": text })).await?;
+    let data = std::fs::read(path)?;
+    let filename = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mimetype = mimetype(path);
+    let size = data.len();
+    let mut url = config.url(&["_matrix", "media", "v3", "upload"])?;
+    url.query_pairs_mut().append_pair("filename", &filename);
+    let response = client
+        .post(url)
+        .bearer_auth(config.access_token.trim())
+        .header("Content-Type", mimetype)
+        .body(data)
+        .send()
+        .await?;
+    let uploaded = check(response).await?;
+    let content_uri = uploaded["content_uri"].as_str().ok_or_else(|| anyhow!("respuesta sin content_uri"))?;
+    send_event(&client, config, json!({
+        "msgtype": "m.file",
+        "body": filename,
+        "url": content_uri,
+        "info": { "mimetype": mimetype, "size": size },
+    })).await
+}
+
+async fn send_event(client: &Client, config: &MatrixConfig, content: Value) -> Result<()> {
+    // El ID de transacción evita duplicados si el servidor recibe la petición dos veces
+    let txn = format!("minutero-{}", Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    let url = config.url(&["_matrix", "client", "v3", "rooms", config.room_id.trim(), "send", "m.room.message", &txn])?;
+    let response = client
+        .put(url)
+        .bearer_auth(config.access_token.trim())
+        .header("Content-Type", "application/json")
+        .body(content.to_string())
+        .send()
+        .await?;
+    check(response).await.map(|_| ())
+}
+
+/// Los errores llegan como `{"errcode": ..., "error": ...}` con un estado HTTP de error.
+async fn check(response: reqwest::Response) -> Result<Value> {
+    let status = response.status();
+    let value: Value = serde_json::from_str(&response.text().await?).unwrap_or_default();
+    if status.is_success() {
+        Ok(value)
+    } else {
+        Err(anyhow!(
+            "API de Matrix: HTTP {} {}",
+            status,
+            value["error"].as_str().or(value["errcode"].as_str()).unwrap_or(""),
+        ))
+    }
+}
+
+fn mimetype(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("md") => "text/markdown",
+        Some("html") => "text/html",
+        Some("json") => "application/json",
+        Some("vtt") => "text/vtt",
+        Some("pdf") => "application/pdf",
+        Some("docx") => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,116 @@
+use rumqttc::{Client, ConnectionError, MqttOptions, QoS};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use crate::data::TranscriptSegment;
+use crate::redaction::{RedactionSettings, Redactor};
+
+// ── MQTT ───────────────────────────────────────────────────────────────────
+//
+// A diferencia de Slack o Notion, no espera al final: cada intervención se
+// publica en cuanto se da por buena, como un JSON, para domótica y paneles
+// que quieran seguir la reunión en directo. La conexión vive en su propio
+// hilo y se reintenta sola; si el broker no está, las intervenciones se
+// pierden para MQTT pero la captura sigue.
+
+/// Intervenciones pendientes de enviar antes de descartar.
+const QUEUE_CAPACITY: usize = 100;
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub topic: String,
+    /// Vacío = sin autenticación.
+    pub username: String,
+    pub password: String,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: 1883,
+            topic: "minutero/segments".into(),
+            username: String::new(),
+            password: String::new(),
+        }
+    }
+}
+
+impl MqttConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.host.trim().is_empty() && !self.topic.trim().is_empty()
+    }
+}
+
+/// Publicador de una sesión. Al soltarlo se desconecta.
+pub struct MqttPublisher {
+    client: Client,
+    topic: String,
+    session: String,
+    redactor: Option<Redactor>,
+}
+
+impl MqttPublisher {
+    /// Conecta en segundo plano; no falla aunque el broker no responda.
+    pub fn connect(config: &MqttConfig, redaction: &RedactionSettings, session: &str) -> Self {
+        let client_id = format!("minutero-{}", std::process::id());
+        let mut options = MqttOptions::new(client_id, config.host.trim(), config.port);
+        options.set_keep_alive(KEEP_ALIVE);
+        if !config.username.trim().is_empty() {
+            options.set_credentials(config.username.trim(), &config.password);
+        }
+        let (client, mut connection) = Client::new(options, QUEUE_CAPACITY);
+
+        let host = format!("{}:{}", config.host.trim(), config.port);
+        std::thread::spawn(move || {
+            for event in connection.iter() {
+                match event {
+                    Ok(_) => {}
+                    // Se soltó el publicador: fin de la sesión
+                    Err(ConnectionError::RequestsDone) => break,
+                    Err(e) => {
+                        tracing::warn!("MQTT {}: {}", host, e);
+                        std::thread::sleep(RETRY_DELAY);
+                    }
+                }
+            }
+        });
+
+        Self {
+            client,
+            topic: config.topic.trim().to_string(),
+            session: session.to_string(),
+            redactor: redaction.enabled.then(|| Redactor::new(redaction)),
+        }
+    }
+
+    /// Publica una intervención sin bloquear; si la cola está llena se pierde.
+    pub fn publish(&self, segment: &TranscriptSegment) {
+        let text = match &self.redactor {
+            Some(redactor) => redactor.redact(&segment.text),
+            None => segment.text.clone(),
+        };
+        let payload = serde_json::json!({
+            "session": self.session,
+            "speaker": segment.name,
+            "start_secs": segment.start_secs,
+            "end_secs": segment.end_secs,
+            "text": text,
+        });
+        if let Err(e) = self.client.try_publish(&self.topic, QoS::AtLeastOnce, false, payload.to_string()) {
+            tracing::warn!("MQTT: intervención no publicada: {}", e);
+        }
+    }
+}
+
+impl Drop for MqttPublisher {
+    fn drop(&mut self) {
+        let _ = self.client.try_disconnect();
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+// ── Reuniones multilingües ─────────────────────────────────────────────────
+//
+// Con un idioma fijo, Whisper "traduce" a ese idioma lo que se dice en otro,
+// o lo transcribe mal. En este modo cada fragmento se decodifica con
+// detección de idioma y la intervención lleva el idioma detectado. Como
+// Whisper solo traduce al inglés, la traducción opcional pasa al inglés lo
+// que no está en el idioma principal (el de origen elegido).
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MultilingualSettings {
+    pub enabled: bool,
+    /// Traducir al inglés los fragmentos que no están en el idioma principal.
+    pub translate_others: bool,
+}
+
+impl MultilingualSettings {
+    /// Si el fragmento detectado en `detected` se vuelve a decodificar
+    /// traducido. Sin idioma principal (autodetección) no hay "otros".
+    pub fn should_translate(&self, detected: &str, primary: Option<&str>) -> bool {
+        self.enabled
+            && self.translate_others
+            && detected != "en"
+            && primary.is_some_and(|p| p != detected)
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::{Result, anyhow};
+use libloading::Library;
+use serde::{Deserialize, Serialize};
+use std::ffi::{c_char, c_int, c_void, CString};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+use crate::data::TranscriptSegment;
+use crate::i18n::tr;
+use crate::redaction::{RedactionSettings, Redactor};
+
+// ── Subtítulos por NDI ─────────────────────────────────────────────────────
+//
+// Para mesas de realización (vMix, TriCaster, OBS con el plugin de NDI): la
+// aplicación aparece en la red como una fuente NDI más y envía la última
+// línea de subtítulo como *metadata frame*, un XML que el mezclador puede
+// leer para superponerlo:
+//
+//   <minutero_caption speaker="Ana" start="12.34">texto</minutero_caption>
+//
+// El SDK de NDI no es libre, así que no se enlaza: se carga en tiempo de
+// ejecución el runtime que instala NDI Tools (o el SDK). Sin él, la salida
+// avisa y la captura sigue. La línea se reenvía cada poco para que los
+// receptores que se conectan a mitad la vean sin esperar a la siguiente.
+
+/// Cada cuánto se repite la última línea.
+const RESEND_INTERVAL: Duration = Duration::from_secs(2);
+/// `NDIlib_send_timecode_synthesize`: que NDI ponga el timecode.
+const TIMECODE_SYNTHESIZE: i64 = i64::MAX;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NdiConfig {
+    pub enabled: bool,
+    /// Nombre de la fuente tal como aparece en el mezclador.
+    pub source_name: String,
+}
+
+impl Default for NdiConfig {
+    fn default() -> Self {
+        Self { enabled: false, source_name: "Minutero".into() }
+    }
+}
+
+impl NdiConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.source_name.trim().is_empty()
+    }
+}
+
+#[repr(C)]
+struct SendCreate {
+    ndi_name: *const c_char,
+    groups: *const c_char,
+    clock_video: bool,
+    clock_audio: bool,
+}
+
+#[repr(C)]
+struct MetadataFrame {
+    length: c_int,
+    timecode: i64,
+    data: *mut c_char,
+}
+
+type Initialize = unsafe extern "C" fn() -> bool;
+type SendCreateFn = unsafe extern "C" fn(*const SendCreate) -> *mut c_void;
+type SendMetadata = unsafe extern "C" fn(*mut c_void, *const MetadataFrame);
+type SendDestroy = unsafe extern "C" fn(*mut c_void);
+
+/// Salida NDI de una sesión. Al soltarla la fuente desaparece de la red.
+pub struct NdiOutput {
+    tx: Sender<String>,
+    redactor: Option<Redactor>,
+}
+
+impl NdiOutput {
+    /// Carga el runtime y crea la fuente. Falla si NDI no está instalado.
+    pub fn start(config: &NdiConfig, redaction: &RedactionSettings) -> Result<Self> {
+        let library = load_runtime()?;
+        let name = CString::new(config.source_name.trim())?;
+        let (tx, rx) = channel();
+        let (ready_tx, ready_rx) = channel();
+        // La instancia de envío se usa siempre desde el mismo hilo
+        std::thread::spawn(move || {
+            match NdiSender::create(&library, &name) {
+                Ok(sender) => {
+                    let _ = ready_tx.send(Ok(()));
+                    sender.run(rx);
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                }
+            }
+        });
+        ready_rx.recv().map_err(|_| anyhow!("el hilo de NDI terminó sin crear la fuente"))??;
+        tracing::info!("Fuente NDI '{}' creada", config.source_name.trim());
+        Ok(Self { tx, redactor: redaction.enabled.then(|| Redactor::new(redaction)) })
+    }
+
+    /// Envía una intervención como la línea actual, sin bloquear.
+    pub fn publish(&self, segment: &TranscriptSegment) {
+        let text = match &self.redactor {
+            Some(redactor) => redactor.redact(&segment.text),
+            None => segment.text.clone(),
+        };
+        let xml = format!(
+            "<minutero_caption speaker=\"{}\" start=\"{:.2}\">{}</minutero_caption>",
+            escape_xml(&segment.name),
+            segment.start_secs,
+            escape_xml(&text),
+        );
+        let _ = self.tx.send(xml);
+    }
+}
+
+/// Instancia de envío de NDI con las funciones del runtime.
+struct NdiSender<'a> {
+    instance: *mut c_void,
+    send_metadata: libloading::Symbol<'a, SendMetadata>,
+    destroy: libloading::Symbol<'a, SendDestroy>,
+}
+
+impl<'a> NdiSender<'a> {
+    fn create(library: &'a Library, name: &CString) -> Result<Self> {
+        // SAFETY: firmas de la API C de NDI (Processing.NDI.Lib.h, v5/v6)
+        unsafe {
+            let initialize: libloading::Symbol<Initialize> = library.get(b"NDIlib_initialize\0")?;
+            if !initialize() {
+                return Err(anyhow!("NDIlib_initialize: la CPU no es compatible con NDI"));
+            }
+            let create: libloading::Symbol<SendCreateFn> = library.get(b"NDIlib_send_create\0")?;
+            let settings = SendCreate { ndi_name: name.as_ptr(), groups: std::ptr::null(), clock_video: false, clock_audio: false };
+            let instance = create(&settings);
+            if instance.is_null() {
+                return Err(anyhow!("NDIlib_send_create no creó la fuente"));
+            }
+            Ok(Self {
+                instance,
+                send_metadata: library.get(b"NDIlib_send_send_metadata\0")?,
+                destroy: library.get(b"NDIlib_send_destroy\0")?,
+            })
+        }
+    }
+
+    /// Envía cada línea que llega y repite la última; termina al soltar el `NdiOutput`.
+    fn run(self, rx: Receiver<String>) {
+        let mut current: Option<CString> = None;
+        loop {
+            match rx.recv_timeout(RESEND_INTERVAL) {
+                Ok(xml) => current = CString::new(xml).ok(),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if let Some(xml) = &current {
+                let frame = MetadataFrame {
+                    length: xml.as_bytes().len() as c_int,
+                    timecode: TIMECODE_SYNTHESIZE,
+                    data: xml.as_ptr() as *mut c_char,
+                };
+                // SAFETY: NDI copia el frame antes de volver
+                unsafe { (self.send_metadata)(self.instance, &frame) };
+            }
+        }
+    }
+}
+
+impl Drop for NdiSender<'_> {
+    fn drop(&mut self) {
+        // SAFETY: la instancia se creó con NDIlib_send_create y no se usa más
+        unsafe { (self.destroy)(self.instance) };
+    }
+}
+
+/// El runtime de NDI: primero donde indican las variables de NDI Tools,
+/// luego las rutas del sistema.
+fn load_runtime() -> Result<Library> {
+    #[cfg(target_os = "windows")]
+    const NAMES: &[&str] = &["Processing.NDI.Lib.x64.dll"];
+    #[cfg(target_os = "macos")]
+    const NAMES: &[&str] = &["libndi.dylib", "/usr/local/lib/libndi.dylib", "/Library/NDI SDK for Apple/lib/macOS/libndi.dylib"];
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    const NAMES: &[&str] = &["libndi.so.6", "libndi.so.5", "libndi.so"];
+
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    for var in ["NDI_RUNTIME_DIR_V6", "NDI_RUNTIME_DIR_V5"] {
+        if let Some(dir) = std::env::var_os(var) {
+            candidates.extend(NAMES.iter().map(|name| PathBuf::from(&dir).join(name)));
+        }
+    }
+    candidates.extend(NAMES.iter().map(PathBuf::from));
+    for candidate in &candidates {
+        // SAFETY: el runtime de NDI no ejecuta nada al cargarse
+        if let Ok(library) = unsafe { Library::new(candidate) } {
+            return Ok(library);
+        }
+    }
+    Err(anyhow!(tr!("ndi.not_installed")))
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
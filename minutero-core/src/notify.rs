@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+
+// ── Notificaciones de escritorio ───────────────────────────────────────────
+//
+// Igual que con pactl/ffmpeg, delegamos en la herramienta nativa de cada
+// sistema en vez de enlazar una librería. Si falla, se ignora: perder una
+// notificación no debe interrumpir la captura.
+
+/// Qué eventos de la sesión generan notificación. Las alertas por palabra
+/// clave tienen su propio interruptor junto a la lista de vigilancia.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub capture: bool,
+    pub errors: bool,
+    pub downloads: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self { capture: true, errors: true, downloads: true }
+    }
+}
+
+pub fn desktop_notification(title: &str, body: &str) {
+    if let Ok(mut child) = spawn_notifier(title, body) {
+        // Esperar en otro hilo para no dejar procesos zombi
+        thread::spawn(move || { let _ = child.wait(); });
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn spawn_notifier(title: &str, body: &str) -> std::io::Result<Child> {
+    Command::new("notify-send")
+        .args(["--app-name=Minutero", title, body])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+#[cfg(target_os = "macos")]
+fn spawn_notifier(title: &str, body: &str) -> std::io::Result<Child> {
+    let script = format!(
+        "display notification \"{}\" with title \"{}\"",
+        applescript_escape(body), applescript_escape(title),
+    );
+    Command::new("osascript")
+        .args(["-e", &script])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+#[cfg(target_os = "macos")]
+fn applescript_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(target_os = "windows")]
+fn spawn_notifier(title: &str, body: &str) -> std::io::Result<Child> {
+    // Globo de la bandeja del sistema vía Windows Forms; no requiere módulos extra
+    let script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $n = New-Object System.Windows.Forms.NotifyIcon; \
+         $n.Icon = [System.Drawing.SystemIcons]::Information; \
+         $n.Visible = $true; \
+         $n.ShowBalloonTip(5000, '{}', '{}', 'Info'); \
+         Start-Sleep -Seconds 6; $n.Dispose()",
+        title.replace('\'', "''"), body.replace('\'', "''"),
+    );
+    Command::new("powershell")
+        .args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", &script])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn spawn_notifier(_title: &str, _body: &str) -> std::io::Result<Child> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "sin notificaciones"))
+}
@@ -0,0 +1,179 @@
+use anyhow::{Result, anyhow};
+use chrono::Local;
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use crate::export::{MinutaDocument, MinutaEntry};
+use crate::i18n::tr;
+use crate::stats::format_duration;
+
+// ── Notion ─────────────────────────────────────────────────────────────────
+//
+// Crea una página en una base de datos de Notion con la fecha y los
+// asistentes como propiedades y la transcripción como contenido. La
+// integración debe estar compartida con la base de datos.
+
+const API: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+/// Límites de la API: bloques por petición y caracteres por tramo de texto.
+const MAX_BLOCKS: usize = 100;
+const MAX_TEXT: usize = 2000;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotionConfig {
+    pub enabled: bool,
+    pub token: String,
+    pub database_id: String,
+    /// Nombres de las columnas de la base de datos. Vacío = no se rellena.
+    pub title_property: String,
+    pub date_property: String,
+    /// Columna de tipo *multi-select*.
+    pub attendees_property: String,
+}
+
+impl Default for NotionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            token: String::new(),
+            database_id: String::new(),
+            title_property: "Name".into(),
+            date_property: String::new(),
+            attendees_property: String::new(),
+        }
+    }
+}
+
+impl NotionConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.token.trim().is_empty() && !self.database_id.trim().is_empty()
+    }
+}
+
+pub async fn create_page(config: &NotionConfig, doc: &MinutaDocument) -> Result<()> {
+    let client = Client::new();
+    let blocks = content_blocks(doc);
+    let mut chunks = blocks.chunks(MAX_BLOCKS);
+
+    let page = request(config, client.post(format!("{}/pages", API)), json!({
+        "parent": { "database_id": config.database_id.trim() },
+        "properties": properties(config, doc),
+        "children": chunks.next().unwrap_or_default(),
+    })).await?;
+    let page_id = page["id"].as_str().ok_or_else(|| anyhow!("respuesta sin id de página"))?;
+
+    // El resto del contenido se añade por lotes.
+    for chunk in chunks {
+        request(
+            config,
+            client.patch(format!("{}/blocks/{}/children", API, page_id)),
+            json!({ "children": chunk }),
+        ).await?;
+    }
+    Ok(())
+}
+
+async fn request(config: &NotionConfig, builder: RequestBuilder, body: Value) -> Result<Value> {
+    let response = builder
+        .bearer_auth(config.token.trim())
+        .header("Notion-Version", NOTION_VERSION)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await?;
+    let status = response.status();
+    let value: Value = serde_json::from_str(&response.text().await?)?;
+    if !status.is_success() {
+        return Err(anyhow!(
+            "API de Notion HTTP {}: {}",
+            status,
+            value["message"].as_str().unwrap_or("error desconocido")
+        ));
+    }
+    Ok(value)
+}
+
+fn properties(config: &NotionConfig, doc: &MinutaDocument) -> Value {
+    let mut props = Map::new();
+    let title = config.title_property.trim();
+    props.insert(
+        if title.is_empty() { "Name".into() } else { title.into() },
+        json!({ "title": rich_text(&doc.title, json!({})) }),
+    );
+    let date = config.date_property.trim();
+    if !date.is_empty() {
+        props.insert(date.into(), json!({ "date": { "start": Local::now().to_rfc3339() } }));
+    }
+    let attendees = config.attendees_property.trim();
+    if !attendees.is_empty() {
+        // Notion no admite comas en las opciones de multi-select.
+        let options: Vec<Value> = doc.attendees.iter()
+            .map(|name| json!({ "name": name.replace(',', " ") }))
+            .collect();
+        props.insert(attendees.into(), json!({ "multi_select": options }));
+    }
+    Value::Object(props)
+}
+
+fn content_blocks(doc: &MinutaDocument) -> Vec<Value> {
+    let mut blocks = vec![heading(&tr!("export.transcript"))];
+    blocks.extend(doc.entries.iter().map(entry_block));
+
+    if !doc.stats.is_empty() {
+        blocks.push(heading(&tr!("stats.heading")));
+        blocks.extend(doc.stats.iter().map(|s| {
+            let line = format!(
+                "{}: {} · {} {} · {} {}",
+                s.name,
+                format_duration(s.speaking_secs),
+                s.words,
+                tr!("stats.words").to_lowercase(),
+                s.interruptions,
+                tr!("stats.interruptions").to_lowercase(),
+            );
+            json!({
+                "object": "block",
+                "type": "bulleted_list_item",
+                "bulleted_list_item": { "rich_text": rich_text(&line, json!({})) },
+            })
+        }));
+    }
+    blocks
+}
+
+fn entry_block(entry: &MinutaEntry) -> Value {
+    let mut spans = Vec::new();
+    if let Some(ts) = &entry.timestamp {
+        spans.extend(rich_text(&format!("[{}] ", ts), json!({ "color": "gray" })));
+    }
+    if let Some(speaker) = &entry.speaker {
+        spans.extend(rich_text(&format!("{}: ", speaker), json!({ "bold": true })));
+    }
+    spans.extend(rich_text(&entry.text, json!({})));
+    json!({
+        "object": "block",
+        "type": "paragraph",
+        "paragraph": { "rich_text": spans },
+    })
+}
+
+fn heading(text: &str) -> Value {
+    json!({
+        "object": "block",
+        "type": "heading_2",
+        "heading_2": { "rich_text": rich_text(text, json!({})) },
+    })
+}
+
+/// Trozos de texto de como mucho `MAX_TEXT` caracteres con las anotaciones dadas.
+fn rich_text(text: &str, annotations: Value) -> Vec<Value> {
+    let chars: Vec<char> = text.chars().collect();
+    chars.chunks(MAX_TEXT)
+        .map(|chunk| json!({
+            "type": "text",
+            "text": { "content": chunk.iter().collect::<String>() },
+            "annotations": annotations,
+        }))
+        .collect()
+}
@@ -0,0 +1,446 @@
+use anyhow::{Result, anyhow};
+use std::io::Write;
+use std::path::Path;
+use crate::export::{MinutaDocument, MinutaEntry, PdfBranding};
+use crate::i18n::tr;
+use crate::stats::format_duration;
+
+// ── Exportación a PDF ──────────────────────────────────────────────────────
+//
+// PDF mínimo escrito a mano: fuentes estándar Helvetica (no hay que
+// incrustar nada) con codificación WinAnsi, que cubre acentos y eñes.
+// El logo, si se configura, debe ser JPEG: se incrusta tal cual (DCTDecode).
+
+const PAGE_W: f32 = 595.0; // A4 en puntos
+const PAGE_H: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const HEADER_H: f32 = 40.0;
+const FOOTER_H: f32 = 30.0;
+const BODY_SIZE: f32 = 10.5;
+
+const GREY: f32 = 0.5;
+const BLACK: f32 = 0.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold    => "F2",
+        }
+    }
+}
+
+pub fn write_pdf(doc: &MinutaDocument, branding: &PdfBranding, mut out: impl Write) -> Result<()> {
+    let logo = if branding.logo_path.trim().is_empty() {
+        None
+    } else {
+        match JpegLogo::load(Path::new(branding.logo_path.trim())) {
+            Ok(logo) => Some(logo),
+            Err(e) => {
+                // Un logo roto no debe impedir archivar la minuta.
+                tracing::warn!("Logo ignorado: {:?}", e);
+                None
+            }
+        }
+    };
+
+    let pages = layout(doc);
+    let total = pages.len();
+
+    let mut writer = PdfWriter::default();
+    // 1: catálogo, 2: árbol de páginas, 3-4: fuentes, 5: logo (opcional)
+    writer.object(1, b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    writer.object(3, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec());
+    writer.object(4, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_vec());
+    if let Some(logo) = &logo {
+        let mut obj = format!(
+            "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /{} /BitsPerComponent 8 /Filter /DCTDecode /Length {} >>\nstream\n",
+            logo.width, logo.height, logo.color_space, logo.data.len()
+        ).into_bytes();
+        obj.extend_from_slice(&logo.data);
+        obj.extend_from_slice(b"\nendstream");
+        writer.object(5, obj);
+    }
+
+    let xobject = if logo.is_some() { " /XObject << /Im1 5 0 R >>" } else { "" };
+    let mut kids = Vec::new();
+    for (i, body) in pages.iter().enumerate() {
+        let page_id = 6 + i * 2;
+        let content_id = page_id + 1;
+        kids.push(format!("{} 0 R", page_id));
+
+        let mut content = page_header(branding, logo.as_ref());
+        content.push_str(body);
+        content.push_str(&page_footer(branding, i + 1, total));
+
+        writer.object(page_id, format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >>{} >> /Contents {} 0 R >>",
+            PAGE_W, PAGE_H, xobject, content_id
+        ).into_bytes());
+        writer.object(content_id, stream(content.as_bytes()));
+    }
+    writer.object(2, format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), total).into_bytes());
+
+    out.write_all(&writer.finish())?;
+    Ok(())
+}
+
+// ── Cabecera y pie ─────────────────────────────────────────────────────────
+
+fn page_header(branding: &PdfBranding, logo: Option<&JpegLogo>) -> String {
+    let mut out = String::new();
+    let top = PAGE_H - MARGIN + 10.0;
+    let mut x = MARGIN;
+
+    if let Some(logo) = logo {
+        let h = HEADER_H - 12.0;
+        let w = h * logo.width as f32 / logo.height.max(1) as f32;
+        out.push_str(&format!("q {:.2} 0 0 {:.2} {:.2} {:.2} cm /Im1 Do Q\n", w, h, x, top - h));
+        x += w + 10.0;
+    }
+    let company = branding.company.trim();
+    if !company.is_empty() {
+        out.push_str(&text_op(x, top - 20.0, Font::Bold, 12.0, BLACK, company));
+    }
+    if logo.is_some() || !company.is_empty() {
+        let y = top - HEADER_H + 4.0;
+        out.push_str(&format!("0.7 G 0.5 w {} {:.2} m {} {:.2} l S\n", MARGIN, y, PAGE_W - MARGIN, y));
+    }
+    out
+}
+
+fn page_footer(branding: &PdfBranding, page: usize, total: usize) -> String {
+    let y = MARGIN - FOOTER_H + 10.0;
+    let mut out = String::new();
+    let footer = branding.footer.trim();
+    if !footer.is_empty() {
+        out.push_str(&text_op(MARGIN, y, Font::Regular, 8.0, GREY, footer));
+    }
+    let number = tr!("export.page", n = page, total = total);
+    let w = text_width(&number, 8.0);
+    out.push_str(&text_op(PAGE_W - MARGIN - w, y, Font::Regular, 8.0, GREY, &number));
+    out
+}
+
+// ── Maquetación ────────────────────────────────────────────────────────────
+//
+// Misma estructura que la minuta en Markdown: título, fecha, asistentes,
+// separador, párrafos y tabla de estadísticas.
+
+struct Layout {
+    pages: Vec<String>,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self { pages: vec![String::new()], y: Self::top() }
+    }
+
+    fn top() -> f32 {
+        PAGE_H - MARGIN - HEADER_H
+    }
+
+    fn bottom() -> f32 {
+        MARGIN + FOOTER_H
+    }
+
+    /// Salta de página si no caben `height` puntos más.
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < Self::bottom() {
+            self.pages.push(String::new());
+            self.y = Self::top();
+        }
+    }
+
+    fn current(&mut self) -> &mut String {
+        self.pages.last_mut().expect("siempre hay al menos una página")
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn rule(&mut self) {
+        self.reserve(12.0);
+        self.y -= 6.0;
+        let y = self.y;
+        self.current().push_str(&format!("0.7 G 0.5 w {} {:.2} m {} {:.2} l S\n", MARGIN, y, PAGE_W - MARGIN, y));
+        self.y -= 6.0;
+    }
+
+    /// Párrafo con tramos de distinto estilo, partido en líneas por palabras.
+    fn paragraph(&mut self, runs: &[(Font, f32, &str)], size: f32) {
+        let leading = size * 1.35;
+        let max_x = PAGE_W - MARGIN;
+        let mut x = MARGIN;
+        let mut line = String::new();
+        let mut first = true;
+
+        self.reserve(leading);
+        self.y -= leading;
+        for (font, gray, text) in runs {
+            for word in text.split_whitespace() {
+                let w = text_width(word, size);
+                let space = if first { 0.0 } else { text_width(" ", size) };
+                if !first && x + space + w > max_x {
+                    self.current().push_str(&line);
+                    line.clear();
+                    self.reserve(leading);
+                    self.y -= leading;
+                    x = MARGIN;
+                } else {
+                    x += space;
+                }
+                line.push_str(&text_op(x, self.y, *font, size, *gray, word));
+                x += w;
+                first = false;
+            }
+        }
+        self.current().push_str(&line);
+    }
+
+    fn table(&mut self, rows: &[Vec<String>], size: f32) {
+        let leading = size * 1.5;
+        let col_w = (PAGE_W - 2.0 * MARGIN) / rows.first().map_or(1, Vec::len).max(1) as f32;
+        for (i, row) in rows.iter().enumerate() {
+            self.reserve(leading);
+            self.y -= leading;
+            let font = if i == 0 { Font::Bold } else { Font::Regular };
+            let y = self.y;
+            let mut ops = String::new();
+            for (c, cell) in row.iter().enumerate() {
+                let cell = fit(cell, col_w - 6.0, size);
+                ops.push_str(&text_op(MARGIN + c as f32 * col_w, y, font, size, BLACK, &cell));
+            }
+            self.current().push_str(&ops);
+        }
+    }
+}
+
+fn layout(doc: &MinutaDocument) -> Vec<String> {
+    let mut l = Layout::new();
+
+    l.paragraph(&[(Font::Bold, BLACK, doc.title.as_str())], 18.0);
+    l.gap(4.0);
+    let date = format!("{}: {}", tr!("export.date"), doc.date);
+    l.paragraph(&[(Font::Regular, GREY, date.as_str())], BODY_SIZE);
+    if !doc.attendees.is_empty() {
+        let label = format!("{}:", tr!("export.attendees"));
+        let names = doc.attendees.join(", ");
+        l.paragraph(&[(Font::Bold, BLACK, label.as_str()), (Font::Regular, BLACK, names.as_str())], BODY_SIZE);
+    }
+    if let Some(recording) = &doc.recording {
+        let label = format!("{}:", tr!("export.recording"));
+        l.paragraph(&[(Font::Bold, BLACK, label.as_str()), (Font::Regular, BLACK, recording.as_str())], BODY_SIZE);
+    }
+    l.rule();
+
+    for entry in &doc.entries {
+        if let Some(chapter) = &entry.chapter {
+            l.gap(8.0);
+            l.paragraph(&[(Font::Bold, BLACK, chapter.as_str())], 13.0);
+            l.gap(4.0);
+        }
+        entry_paragraph(&mut l, entry);
+        l.gap(3.0);
+    }
+
+    if !doc.stats.is_empty() {
+        l.gap(12.0);
+        l.paragraph(&[(Font::Bold, BLACK, tr!("stats.heading").as_str())], 13.0);
+        l.gap(4.0);
+        let mut rows = vec![vec![
+            tr!("stats.speaker"),
+            tr!("stats.speaking_time"),
+            tr!("stats.words"),
+            tr!("stats.longest"),
+            tr!("stats.interruptions"),
+        ]];
+        rows.extend(doc.stats.iter().map(|s| vec![
+            s.name.clone(),
+            format_duration(s.speaking_secs),
+            s.words.to_string(),
+            format_duration(s.longest_monologue_secs),
+            s.interruptions.to_string(),
+        ]));
+        l.table(&rows, 9.0);
+    }
+
+    l.pages
+}
+
+fn entry_paragraph(l: &mut Layout, entry: &MinutaEntry) {
+    let ts = entry.timestamp.as_ref().map(|t| format!("[{}]", t));
+    let speaker = entry.speaker.as_ref().map(|s| format!("{}:", s));
+    let mut runs = Vec::new();
+    if let Some(ts) = &ts {
+        runs.push((Font::Regular, GREY, ts.as_str()));
+    }
+    if let Some(speaker) = &speaker {
+        runs.push((Font::Bold, BLACK, speaker.as_str()));
+    }
+    runs.push((Font::Regular, BLACK, entry.text.as_str()));
+    l.paragraph(&runs, BODY_SIZE);
+}
+
+/// Recorta el texto con "..." para que quepa en una celda.
+fn fit(text: &str, max_w: f32, size: f32) -> String {
+    if text_width(text, size) <= max_w {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        if text_width(&format!("{}{}...", out, c), size) > max_w {
+            break;
+        }
+        out.push(c);
+    }
+    out + "..."
+}
+
+// ── Texto y métricas ───────────────────────────────────────────────────────
+
+fn text_op(x: f32, y: f32, font: Font, size: f32, gray: f32, text: &str) -> String {
+    format!(
+        "BT {} g /{} {} Tf {:.2} {:.2} Td <{}> Tj ET\n",
+        gray, font.resource(), size, x, y, hex(&win_ansi(text))
+    )
+}
+
+/// Anchos de Helvetica (1/1000 em) para ASCII 32..=126. La negrita es algo
+/// más ancha; se compensa con margen en `text_width`.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = win_ansi(text).iter().map(|&b| match b {
+        32..=126 => HELVETICA_WIDTHS[(b - 32) as usize] as u32,
+        _ => 600,
+    }).sum();
+    units as f32 * size / 1000.0 * 1.06
+}
+
+/// Convierte a WinAnsi (cp1252). Lo que no se puede representar (emojis,
+/// alfabetos no latinos) se omite.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars().filter_map(|c| match c {
+        ' '..='~' => Some(c as u8),
+        '\u{A0}'..='\u{FF}' => Some(c as u32 as u8),
+        '€' => Some(0x80),
+        '…' => Some(0x85),
+        '‘' => Some(0x91),
+        '’' => Some(0x92),
+        '“' => Some(0x93),
+        '”' => Some(0x94),
+        '•' => Some(0x95),
+        '–' => Some(0x96),
+        '—' => Some(0x97),
+        _ => None,
+    }).collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+// ── Logo JPEG ──────────────────────────────────────────────────────────────
+
+struct JpegLogo {
+    width: u16,
+    height: u16,
+    color_space: &'static str,
+    data: Vec<u8>,
+}
+
+impl JpegLogo {
+    fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        if !data.starts_with(&[0xFF, 0xD8]) {
+            return Err(anyhow!("el logo debe ser JPEG: {}", path.display()));
+        }
+
+        // Buscar el marcador SOF para sacar dimensiones y componentes.
+        let mut i = 2;
+        while i + 9 < data.len() {
+            if data[i] != 0xFF {
+                i += 1;
+                continue;
+            }
+            let marker = data[i + 1];
+            let len = u16::from_be_bytes([data[i + 2], data[i + 3]]) as usize;
+            if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                let height = u16::from_be_bytes([data[i + 5], data[i + 6]]);
+                let width = u16::from_be_bytes([data[i + 7], data[i + 8]]);
+                let color_space = match data[i + 9] {
+                    1 => "DeviceGray",
+                    4 => "DeviceCMYK",
+                    _ => "DeviceRGB",
+                };
+                return Ok(Self { width, height, color_space, data });
+            }
+            i += 2 + len;
+        }
+        Err(anyhow!("JPEG sin cabecera de imagen: {}", path.display()))
+    }
+}
+
+// ── Serialización ──────────────────────────────────────────────────────────
+
+fn stream(content: &[u8]) -> Vec<u8> {
+    let mut obj = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+    obj.extend_from_slice(content);
+    obj.extend_from_slice(b"\nendstream");
+    obj
+}
+
+#[derive(Default)]
+struct PdfWriter {
+    objects: Vec<(usize, Vec<u8>)>,
+}
+
+impl PdfWriter {
+    fn object(&mut self, id: usize, body: Vec<u8>) {
+        self.objects.push((id, body));
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.objects.sort_by_key(|(id, _)| *id);
+        let size = self.objects.last().map_or(0, |(id, _)| *id) + 1;
+
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = vec![None; size];
+        for (id, body) in &self.objects {
+            offsets[*id] = Some(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", size).as_bytes());
+        for offset in &offsets[1..] {
+            let entry = match offset {
+                Some(o) => format!("{:010} 00000 n \n", o),
+                None => "0000000000 65535 f \n".to_string(),
+            };
+            out.extend_from_slice(entry.as_bytes());
+        }
+        out.extend_from_slice(
+            format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", size, xref).as_bytes(),
+        );
+        out
+    }
+}
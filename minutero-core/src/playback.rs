@@ -0,0 +1,95 @@
+use anyhow::{Result, anyhow};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use crate::audio::resample;
+use crate::data::WHISPER_SAMPLE_RATE;
+use crate::i18n::tr;
+use crate::recording::read_wav_range;
+use crate::video::decode_audio_range;
+
+// ── Reproducción de la grabación ───────────────────────────────────────────
+//
+// Para comprobar qué se dijo de verdad: se reproduce el tramo de la
+// grabación de la reunión que corresponde a una intervención. Solo se lee
+// ese tramo (WAV con hound saltando al inicio, OGG con ffmpeg buscando en
+// la entrada): una sesión de horas no cabe entera en memoria como f32.
+
+/// Margen antes y después de la intervención, para no cortar la primera
+/// palabra ni la última.
+const MARGIN_SECS: f64 = 0.5;
+
+#[derive(Default)]
+pub struct Player {
+    stream: Option<cpal::Stream>,
+    finished: Arc<AtomicBool>,
+}
+
+impl Player {
+    /// Reproduce `path` entre `start_secs` y `end_secs` (segundos de sesión)
+    /// por la salida de audio predeterminada. Corta lo que estuviera sonando.
+    pub fn play(&mut self, path: &Path, start_secs: f64, end_secs: f64) -> Result<()> {
+        self.stop();
+        let from = (start_secs - MARGIN_SECS).max(0.0);
+        let to = (end_secs + MARGIN_SECS).max(from);
+        let audio = read_segment(path, from, to)?;
+        if audio.is_empty() {
+            return Err(anyhow!(tr!("playback.out_of_range")));
+        }
+        self.play_samples(&audio)
+    }
+
+    /// Reproduce `samples` (16 kHz mono) por la salida predeterminada.
+    pub fn play_samples(&mut self, samples: &[f32]) -> Result<()> {
+        self.stop();
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| anyhow!(tr!("playback.no_output")))?;
+        let config = device.default_output_config()?;
+        let out_rate = config.sample_rate();
+        let channels = config.channels() as usize;
+        let clip = resample(samples, WHISPER_SAMPLE_RATE, out_rate);
+
+        let finished = Arc::new(AtomicBool::new(false));
+        let done = finished.clone();
+        let mut position = 0;
+        let stream = device.build_output_stream(
+            &config.into(),
+            move |out: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for frame in out.chunks_mut(channels) {
+                    let sample = clip.get(position).copied().unwrap_or(0.0);
+                    frame.fill(sample);
+                    position += 1;
+                }
+                if position >= clip.len() {
+                    done.store(true, Ordering::SeqCst);
+                }
+            },
+            |err| tracing::error!("Error en la reproducción: {}", err),
+            None,
+        )?;
+        stream.play()?;
+
+        self.stream = Some(stream);
+        self.finished = finished;
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        self.stream = None;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.stream.is_some() && !self.finished.load(Ordering::SeqCst)
+    }
+}
+
+/// Tramo de la grabación entre `from_secs` y `to_secs`, a 16 kHz mono.
+fn read_segment(path: &Path, from_secs: f64, to_secs: f64) -> Result<Vec<f32>> {
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav")) {
+        let rate = WHISPER_SAMPLE_RATE as f64;
+        return read_wav_range(path, (from_secs * rate) as usize, (to_secs * rate) as usize);
+    }
+    decode_audio_range(&path.to_string_lossy(), from_secs, to_secs - from_secs)
+}
@@ -0,0 +1,153 @@
+use anyhow::{Result, anyhow};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::audio::transcribe_chunk;
+use crate::data::{AudioMessage, TranscriptionConfig};
+use crate::hallucinations::HallucinationFilter;
+use crate::i18n::tr;
+use crate::postprocess::PostProcessor;
+use crate::preload;
+
+// ── Trabajadores Whisper compartidos ───────────────────────────────────────
+//
+// Sin pool, cada fuente tiene su propio estado Whisper en su hilo y, con
+// muchas fuentes, todas compiten por los mismos núcleos y van igual de
+// retrasadas. Con `WhisperTuning::workers` > 0 la sesión arranca ese número
+// de trabajadores sobre un único contexto (el modelo se carga una vez) y
+// las fuentes les pasan sus fragmentos. Se atienden por prioridad: primero
+// los micrófonos, que es lo que se lee mientras se habla, y después el resto
+// por orden de llegada. Cada fuente espera a que salga su fragmento antes de
+// preparar el siguiente, así que nunca tiene más de uno en cola.
+
+/// Fragmento de una fuente, listo para Whisper.
+pub(crate) struct ChunkJob {
+    /// 16 kHz, ya nivelado.
+    pub audio: Vec<f32>,
+    pub start_secs: f64,
+    pub name: String,
+    pub identify_voices: bool,
+    /// Se atiende antes que los que no la tienen.
+    pub priority: bool,
+}
+
+struct Job {
+    chunk: ChunkJob,
+    /// Orden de llegada, para desempatar.
+    seq: u64,
+    reply: Sender<(Option<AudioMessage>, Duration)>,
+}
+
+impl Ord for Job {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap saca el mayor: prioridad primero y, dentro, el más antiguo
+        self.chunk.priority.cmp(&other.chunk.priority).then(other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Job {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+
+impl Eq for Job {}
+
+#[derive(Default)]
+struct Queue {
+    jobs: BinaryHeap<Job>,
+    next_seq: u64,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+}
+
+impl Shared {
+    /// Siguiente fragmento; `None` cuando se cierra el pool.
+    fn next(&self) -> Option<Job> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if let Some(job) = queue.jobs.pop() {
+                return Some(job);
+            }
+            if queue.closed {
+                return None;
+            }
+            queue = self.ready.wait(queue).unwrap();
+        }
+    }
+}
+
+/// Trabajadores de una sesión. Al soltarlo terminan cuando acaban lo que
+/// tengan entre manos.
+pub(crate) struct WhisperPool {
+    shared: Arc<Shared>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl WhisperPool {
+    pub(crate) fn start(model_path: &str, workers: usize, config: &TranscriptionConfig) -> Result<Self> {
+        let ctx = preload::context(model_path)
+            .map_err(|e| anyhow!(tr!("audio.model_load", e = format!("{:?}", e))))?;
+        let shared = Arc::new(Shared::default());
+        let mut handles = Vec::new();
+        for _ in 0..workers.max(1) {
+            let mut state = ctx.create_state()
+                .map_err(|e| anyhow!(tr!("audio.state_create", e = format!("{:?}", e))))?;
+            let shared = shared.clone();
+            let config = config.clone();
+            handles.push(thread::spawn(move || {
+                let post = PostProcessor::new(&config.preset, config.style);
+                let hallucinations = HallucinationFilter::new(&config.tuning);
+                while let Some(job) = shared.next() {
+                    let chunk = &job.chunk;
+                    let voices = (chunk.identify_voices && !config.voices.is_empty()).then_some(&config.voices);
+                    let started = Instant::now();
+                    let segment = transcribe_chunk(
+                        &chunk.audio, chunk.start_secs, &mut state, &config, &post, &hallucinations, &chunk.name, voices,
+                    );
+                    let _ = job.reply.send((segment, started.elapsed()));
+                }
+            }));
+        }
+        tracing::info!("{} trabajadores Whisper compartidos", handles.len());
+        Ok(Self { shared, workers: handles })
+    }
+
+    /// Transcribe `chunk` en cuanto le toque. Devuelve la intervención y lo
+    /// que tardó Whisper, sin contar la espera.
+    pub(crate) fn transcribe(&self, chunk: ChunkJob) -> (Option<AudioMessage>, Duration) {
+        let (reply, rx) = channel();
+        {
+            let mut queue = self.shared.queue.lock().unwrap();
+            let seq = queue.next_seq;
+            queue.next_seq += 1;
+            queue.jobs.push(Job { chunk, seq, reply });
+        }
+        self.shared.ready.notify_one();
+        rx.recv().unwrap_or((None, Duration::ZERO))
+    }
+}
+
+impl Drop for WhisperPool {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().closed = true;
+        self.shared.ready.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use regex::{Regex, RegexBuilder};
+use crate::data::{Preset, ReplacementRule, TranscriptionStyle};
+
+// ── Post-procesado del texto transcrito ───────────────────────────────────
+//
+// Se aplica a cada segmento antes de enviarlo a la UI, así que tanto la
+// minuta en pantalla como los archivos exportados salen ya corregidos.
+
+pub struct PostProcessor {
+    /// (patrón, grafía correcta) para cada término del glosario.
+    glossary: Vec<(Regex, String)>,
+    /// Lo mismo para el diccionario de grafías, sin mirar tildes.
+    spellings: Vec<(Regex, String)>,
+    /// Reglas del usuario ya compiladas; las inválidas se descartan.
+    replacements: Vec<(Regex, String)>,
+    /// Quitar muletillas, repeticiones y frases cortadas (transcripción limpia).
+    clean: bool,
+}
+
+impl PostProcessor {
+    pub fn new(preset: &Preset, style: TranscriptionStyle) -> Self {
+        let glossary = if preset.glossary_corrections {
+            preset.glossary_terms().into_iter().filter_map(|t| term_rule(t, false)).collect()
+        } else {
+            Vec::new()
+        };
+        let spellings = preset.spellings.iter()
+            .filter_map(|t| term_rule(t, true))
+            .collect();
+        let replacements = preset.replacements.iter()
+            .filter(|r| r.enabled && !r.pattern.is_empty())
+            .filter_map(|r| compile_rule(r).ok().map(|re| (re, r.replacement.clone())))
+            .collect();
+        Self { glossary, spellings, replacements, clean: style == TranscriptionStyle::Clean }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut out = if self.clean { remove_disfluencies(text) } else { text.to_string() };
+        for (re, replacement) in self.glossary.iter().chain(&self.spellings) {
+            out = re.replace_all(&out, regex::NoExpand(replacement)).into_owned();
+        }
+        if self.replacements.is_empty() {
+            return out;
+        }
+        for (re, replacement) in &self.replacements {
+            out = re.replace_all(&out, replacement.as_str()).into_owned();
+        }
+        // Quitar muletillas deja dobles espacios y comas huérfanas
+        out.split_whitespace().collect::<Vec<_>>().join(" ")
+            .replace(" ,", ",")
+            .replace(" .", ".")
+    }
+}
+
+// ── Transcripción limpia ───────────────────────────────────────────────────
+
+/// Muletillas sin contenido. "este", "bueno", "well"… también significan
+/// algo y se quedan.
+const FILLERS: &[&str] = &[
+    "eh", "ehm", "em", "emm", "mm", "mmm", "hmm", "hm", "ah", "uh", "um", "umm", "uhm", "er", "erm",
+];
+
+/// Quita muletillas, palabras repetidas seguidas ("el el") y arranques en
+/// falso ("vamos- vamos a"), conservando la puntuación final.
+fn remove_disfluencies(text: &str) -> String {
+    let bare = |token: &str| token.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut kept: Vec<String> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let word = bare(token);
+        let next = tokens.get(i + 1).map(|t| bare(t));
+        let filler = FILLERS.contains(&word.as_str());
+        let repeated = !word.is_empty() && next.as_deref() == Some(word.as_str());
+        let false_start = token.ends_with('-') && !word.is_empty()
+            && next.as_deref().is_some_and(|n| n.starts_with(&word));
+        if filler || repeated || false_start {
+            // El punto de una muletilla final pasa a la palabra anterior
+            if let (Some(end), Some(last)) = (token.chars().last().filter(|c| ".?!…".contains(*c)), kept.last_mut()) {
+                if !last.ends_with(['.', '?', '!', '…']) {
+                    last.push(end);
+                }
+            }
+            continue;
+        }
+        kept.push(token.to_string());
+    }
+
+    let mut out = kept.join(" ");
+    // "Eh, bueno" → "Bueno": la mayúscula era de la muletilla
+    if text.starts_with(char::is_uppercase) {
+        let mut chars = out.chars();
+        if let Some(first) = chars.next() {
+            out = first.to_uppercase().chain(chars).collect();
+        }
+    }
+    out
+}
+
+/// Compila una regla del usuario. La UI lo usa también para validar.
+pub fn compile_rule(rule: &ReplacementRule) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(&rule.pattern)
+        .case_insensitive(rule.case_insensitive)
+        .build()
+}
+
+/// Letras que se confunden al quitar o poner tildes.
+const ACCENT_GROUPS: &[&str] = &["aáàâä", "eéèêë", "iíìîï", "oóòôö", "uúùûü", "nñ", "cç"];
+
+/// Regla que corrige mayúsculas y separadores de un término: "Kubernetes"
+/// encaja con "kubernetes", y "GitLab CI" con "gitlab-ci" o "gitlabci". Con
+/// `fold_accents`, "José María" encaja también con "jose maria".
+fn term_rule(term: &str, fold_accents: bool) -> Option<(Regex, String)> {
+    let words: Vec<String> = term
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !w.is_empty())
+        .map(|w| if fold_accents { accent_pattern(w) } else { regex::escape(w) })
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    // \b solo tiene sentido junto a caracteres de palabra ("C++" no lo lleva al final)
+    let boundary = |c: Option<char>| match c {
+        Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+        _ => "",
+    };
+    let pattern = format!(
+        r"(?i){}{}{}",
+        boundary(term.trim().chars().next()),
+        words.join(r"[\s-]?"),
+        boundary(term.trim().chars().last()),
+    );
+    Regex::new(&pattern).ok().map(|re| (re, term.trim().to_string()))
+}
+
+/// Patrón de una palabra en el que cada vocal (y la ñ, la ç) admite sus
+/// variantes con y sin tilde.
+fn accent_pattern(word: &str) -> String {
+    word.chars()
+        .map(|c| {
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            match ACCENT_GROUPS.iter().find(|g| g.contains(lower)) {
+                Some(group) => format!("[{}]", group),
+                None => regex::escape(&c.to_string()),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,83 @@
+use anyhow::{anyhow, Result};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext};
+use crate::audio::{download_whisper_model, model_is_cached};
+use crate::data::{AudioMessage, ModelSource, WHISPER_SAMPLE_RATE};
+use crate::i18n::tr;
+
+// ── Precarga del modelo al arrancar ────────────────────────────────────────
+//
+// Cargar large-v3 tarda más de 20 s, y sin precarga eso pasa después de
+// pulsar "Iniciar Captura". Con la opción activada el modelo se descarga,
+// se carga y se calienta (una pasada con un segundo de silencio) en segundo
+// plano al abrir la aplicación, y las capturas crean su estado Whisper a
+// partir de ese mismo contexto.
+//
+// Solo se guarda un modelo: precargar otro libera el anterior. Mientras se
+// precarga se mantiene el candado, así que una captura que empiece a la vez
+// espera a que termine en vez de cargar (o descargar) el modelo por su
+// cuenta.
+
+struct Preloaded {
+    path: String,
+    ctx: Arc<WhisperContext>,
+}
+
+static PRELOADED: Mutex<Option<Preloaded>> = Mutex::new(None);
+
+/// Descarga, carga y calienta `model_name`. Informa por `tx` como el hilo de
+/// audio (estado, progreso de descarga, errores).
+pub fn preload_thread(model_name: String, source: ModelSource, tx: Sender<AudioMessage>) -> Result<()> {
+    let mut slot = PRELOADED.lock().unwrap();
+    *slot = None;
+
+    let _ = tx.send(AudioMessage::Status(tr!("preload.loading", model = model_name)));
+    let cached = model_is_cached(&model_name, &source);
+    let path = Runtime::new()?
+        .block_on(download_whisper_model(&model_name, &source, |downloaded, total| {
+            let _ = tx.send(AudioMessage::ModelDownloadProgress { model: model_name.clone(), downloaded, total });
+        }))?;
+    if !cached {
+        let _ = tx.send(AudioMessage::ModelDownloaded(model_name.clone()));
+    }
+
+    let ctx = WhisperContext::new_with_params(&path, Default::default())
+        .map_err(|e| anyhow!(tr!("audio.model_load", e = format!("{:?}", e))))?;
+    let mut state = ctx.create_state()
+        .map_err(|e| anyhow!(tr!("audio.state_create", e = format!("{:?}", e))))?;
+    // La primera inferencia reserva búferes y compila kernels de GPU
+    let silence = vec![0.0f32; WHISPER_SAMPLE_RATE as usize];
+    if let Err(e) = state.full(FullParams::new(SamplingStrategy::Greedy { best_of: 1 }), &silence) {
+        tracing::warn!("Calentamiento del modelo '{}' fallido: {:?}", model_name, e);
+    }
+
+    tracing::info!("Modelo '{}' precargado desde {}", model_name, path);
+    *slot = Some(Preloaded { path, ctx: Arc::new(ctx) });
+    let _ = tx.send(AudioMessage::Status(tr!("preload.ready", model = model_name)));
+    Ok(())
+}
+
+/// Contexto Whisper de `path`: el precargado si es ese archivo o uno nuevo.
+/// Si hay una precarga en curso, espera a que termine.
+pub(crate) fn context(path: &str) -> Result<Arc<WhisperContext>, whisper_rs::WhisperError> {
+    if let Some(preloaded) = PRELOADED.lock().unwrap().as_ref().filter(|p| p.path == path) {
+        return Ok(preloaded.ctx.clone());
+    }
+    WhisperContext::new_with_params(path, Default::default()).map(Arc::new)
+}
+
+/// Espera a que termine una precarga en curso, para no descargar el mismo
+/// modelo dos veces a la vez.
+pub(crate) fn wait() {
+    drop(PRELOADED.lock());
+}
+
+/// Libera el modelo precargado (al desactivar la opción). No bloquea: si hay
+/// una precarga en curso, el modelo se queda hasta la siguiente.
+pub fn release() {
+    if let Ok(mut slot) = PRELOADED.try_lock() {
+        *slot = None;
+    }
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::audio::preroll_thread_main;
+use crate::stop::StopSignal;
+use crate::data::{AudioMessage, InterlocutorProfile, SourceType, TranscriptionConfig, WHISPER_SAMPLE_RATE};
+
+// ── Pre-grabación (búfer circular antes de empezar) ───────────────────────
+//
+// Con la opción activada, mientras no hay captura los dispositivos de los
+// perfiles activos se mantienen abiertos y se guardan sus últimos segundos
+// en memoria (nunca en disco). Al pulsar "Iniciar Captura" ese audio entra
+// en la sesión por delante del vivo, así que la presentación de la reunión
+// no se pierde. Está desactivada por defecto: escuchar sin estar grabando
+// es justo lo que un usuario no espera de la aplicación.
+//
+// Solo se aplica a dispositivos: una tubería no se puede volver a abrir y
+// una URL se escucha igual de bien al empezar.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreRollSettings {
+    pub enabled: bool,
+    pub seconds: u32,
+}
+
+impl Default for PreRollSettings {
+    fn default() -> Self {
+        Self { enabled: false, seconds: 30 }
+    }
+}
+
+pub const MAX_PREROLL_SECS: u32 = 120;
+
+/// Perfiles cuyo dispositivo puede quedarse escuchando.
+pub fn preroll_profiles(profiles: &[InterlocutorProfile]) -> Vec<InterlocutorProfile> {
+    profiles.iter()
+        .filter(|p| matches!(p.source_type, SourceType::Input | SourceType::Output))
+        .cloned()
+        .collect()
+}
+
+/// Últimos segundos de un perfil, a 16 kHz mono.
+struct Ring {
+    samples: VecDeque<f32>,
+    /// Cuándo llegó la última muestra.
+    ended: Instant,
+}
+
+/// Búferes de todos los perfiles, por id de perfil. Los llenan los hilos de
+/// captura en espera (ver `audio::preroll_thread_main`).
+#[derive(Clone)]
+pub struct PreRollBuffers {
+    rings: Arc<Mutex<HashMap<usize, Ring>>>,
+    capacity: usize,
+}
+
+impl PreRollBuffers {
+    fn new(seconds: u32) -> Self {
+        Self {
+            rings: Arc::new(Mutex::new(HashMap::new())),
+            capacity: (WHISPER_SAMPLE_RATE * seconds) as usize,
+        }
+    }
+
+    /// Añade `samples` (16 kHz mono) y descarta lo que pase de la capacidad.
+    pub fn push(&self, profile: usize, samples: &[f32], arrived: Instant) {
+        let mut rings = self.rings.lock().unwrap();
+        let ring = rings.entry(profile)
+            .or_insert_with(|| Ring { samples: VecDeque::with_capacity(self.capacity), ended: arrived });
+        ring.samples.extend(samples);
+        let excess = ring.samples.len().saturating_sub(self.capacity);
+        ring.samples.drain(..excess);
+        ring.ended = arrived;
+    }
+}
+
+/// Audio de pre-grabación entregado a una sesión.
+#[derive(Clone, Default)]
+pub struct PreRollAudio {
+    tracks: HashMap<usize, (Vec<f32>, Instant)>,
+}
+
+impl PreRollAudio {
+    /// Muestras (16 kHz mono) del perfil `profile`; vacío si no hay.
+    pub fn samples(&self, profile: usize) -> &[f32] {
+        self.tracks.get(&profile).map(|(samples, _)| samples.as_slice()).unwrap_or_default()
+    }
+
+    /// Instante en que sonó la primera muestra guardada de todos los
+    /// perfiles: el origen de la sesión cuando hay pre-grabación.
+    pub fn origin(&self) -> Option<Instant> {
+        self.tracks.values()
+            .map(|(samples, ended)| {
+                let length = Duration::from_secs_f64(samples.len() as f64 / WHISPER_SAMPLE_RATE as f64);
+                ended.checked_sub(length).unwrap_or(*ended)
+            })
+            .min()
+    }
+
+    /// Segundos de audio guardados del perfil más largo.
+    pub fn longest_secs(&self) -> f64 {
+        self.tracks.values()
+            .map(|(samples, _)| samples.len() as f64 / WHISPER_SAMPLE_RATE as f64)
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Tiempo máximo esperando a que los dispositivos en espera se liberen
+/// antes de abrirlos para la captura.
+const RELEASE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Captura en espera de los perfiles activos.
+pub struct PreRoll {
+    profiles: Vec<InterlocutorProfile>,
+    audio_host: Option<String>,
+    buffers: PreRollBuffers,
+    stop: StopSignal,
+    handle: Option<thread::JoinHandle<()>>,
+    rx: Receiver<AudioMessage>,
+}
+
+impl PreRoll {
+    pub fn start(profiles: Vec<InterlocutorProfile>, config: TranscriptionConfig, seconds: u32) -> Self {
+        let buffers = PreRollBuffers::new(seconds);
+        let stop = StopSignal::new();
+        let (tx, rx) = channel::<AudioMessage>();
+        let handle = {
+            let (profiles, config, buffers, stop) = (profiles.clone(), config.clone(), buffers.clone(), stop.clone());
+            thread::spawn(move || preroll_thread_main(profiles, config, buffers, stop, tx))
+        };
+        tracing::info!("Pre-grabación activa ({} s) para {} perfiles", seconds, profiles.len());
+        Self { profiles, audio_host: config.audio_host, buffers, stop, handle: Some(handle), rx }
+    }
+
+    /// Sigue sirviendo para estos perfiles, este host y esta duración. El
+    /// nombre de un perfil puede cambiar sin volver a abrir el dispositivo.
+    pub fn matches(&self, profiles: &[InterlocutorProfile], audio_host: &Option<String>, seconds: u32) -> bool {
+        let device = |p: &InterlocutorProfile| (p.id, p.source_type.clone(), p.technical_name.clone(), p.channel);
+        self.profiles.iter().map(device).eq(profiles.iter().map(device))
+            && &self.audio_host == audio_host
+            && self.buffers.capacity == (WHISPER_SAMPLE_RATE * seconds) as usize
+    }
+
+    /// Errores de los dispositivos en espera desde la última llamada.
+    pub fn errors(&self) -> Vec<String> {
+        self.rx.try_iter()
+            .filter_map(|msg| match msg {
+                AudioMessage::StreamError { message, .. } | AudioMessage::Error(message) => Some(message),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Deja de escuchar, espera a que se liberen los dispositivos y entrega
+    /// lo guardado.
+    pub fn finish(mut self) -> PreRollAudio {
+        self.stop.stop();
+        if let Some(handle) = self.handle.take() {
+            let deadline = Instant::now() + RELEASE_TIMEOUT;
+            while Instant::now() < deadline && !handle.is_finished() {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+        let rings = std::mem::take(&mut *self.buffers.rings.lock().unwrap());
+        PreRollAudio {
+            tracks: rings.into_iter()
+                .map(|(id, ring)| (id, (Vec::from(ring.samples), ring.ended)))
+                .collect(),
+        }
+    }
+}
+
+impl Drop for PreRoll {
+    fn drop(&mut self) {
+        self.stop.stop();
+    }
+}
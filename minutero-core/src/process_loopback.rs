@@ -0,0 +1,515 @@
+use anyhow::Result;
+use crate::data::DeviceInfo;
+#[cfg(not(target_os = "windows"))]
+use crate::{audio::stream_failure, data::StreamErrorKind, i18n::tr};
+
+// ── Audio de una sola aplicación (Windows Process Loopback) ───────────────
+//
+// Desde Windows 10 2004 / Windows 11, WASAPI puede capturar solo lo que
+// reproduce un proceso y sus hijos (`VAD\Process_Loopback`). Así se
+// transcribe Teams o Chrome sin habilitar la Mezcla estéreo y sin que se
+// cuelen otras aplicaciones. Los procesos se ofrecen como fuentes de SALIDA
+// con `technical_name` = `process:<ejecutable>`; el PID se busca al empezar
+// la captura, porque cambia en cada arranque de la aplicación.
+//
+// cpal no expone esta API, así que se llama directamente a mmdevapi/COM.
+
+pub const PROCESS_PREFIX: &str = "process:";
+
+/// (subcadena del ejecutable en minúsculas, nombre a mostrar)
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const PROCESS_APPS: &[(&str, &str)] = &[
+    ("teams", "Microsoft Teams"),
+    ("zoom", "Zoom"),
+    ("webex", "Webex"),
+    ("skype", "Skype"),
+    ("discord", "Discord"),
+    ("slack", "Slack"),
+    ("chrome", "Google Chrome"),
+    ("msedge", "Microsoft Edge"),
+    ("firefox", "Firefox"),
+];
+
+pub fn is_process_source(technical_name: Option<&str>) -> bool {
+    technical_name.is_some_and(|n| n.starts_with(PROCESS_PREFIX))
+}
+
+/// Aplicaciones conocidas que están abiertas, como fuentes de salida.
+pub fn list_processes() -> Vec<DeviceInfo> {
+    #[cfg(target_os = "windows")]
+    {
+        let mut devices: Vec<DeviceInfo> = Vec::new();
+        for process in win::snapshot() {
+            let lower = process.exe.to_lowercase();
+            let Some((_, label)) = PROCESS_APPS.iter().find(|(needle, _)| lower.contains(needle)) else { continue };
+            let technical_name = format!("{}{}", PROCESS_PREFIX, process.exe);
+            if devices.iter().any(|d| d.technical_name.as_deref() == Some(technical_name.as_str())) {
+                continue;
+            }
+            devices.push(DeviceInfo {
+                id: devices.len(),
+                name: format!("🪟 {} ({})", label, process.exe),
+                technical_name: Some(technical_name),
+                channels: win::CHANNELS,
+            });
+        }
+        devices
+    }
+    #[cfg(not(target_os = "windows"))]
+    Vec::new()
+}
+
+/// Captura en curso del audio de un proceso. Se usa desde un solo hilo.
+pub struct ProcessCapture {
+    #[cfg(target_os = "windows")]
+    inner: win::Capture,
+}
+
+impl ProcessCapture {
+    /// Empieza a capturar el proceso principal de `exe` y sus hijos.
+    pub fn start(exe: &str) -> Result<Self> {
+        #[cfg(target_os = "windows")]
+        return Ok(Self { inner: win::Capture::start(exe)? });
+
+        #[cfg(not(target_os = "windows"))]
+        Err(stream_failure(StreamErrorKind::Device, tr!("audio.process_loopback_unsupported", name = exe)))
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        #[cfg(target_os = "windows")]
+        return win::SAMPLE_RATE;
+        #[cfg(not(target_os = "windows"))]
+        0
+    }
+
+    pub fn channels(&self) -> usize {
+        #[cfg(target_os = "windows")]
+        return win::CHANNELS as usize;
+        #[cfg(not(target_os = "windows"))]
+        0
+    }
+
+    /// Deja en `samples` las muestras entrelazadas disponibles ahora (puede
+    /// no haber ninguna). El búfer se reutiliza.
+    /// Mientras la aplicación no suena Windows no entrega nada; se rellena
+    /// con silencio para que el tiempo de sesión siga avanzando.
+    pub fn read(&mut self, samples: &mut Vec<f32>) -> Result<()> {
+        samples.clear();
+        #[cfg(target_os = "windows")]
+        return self.inner.read(samples);
+        #[cfg(not(target_os = "windows"))]
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win {
+    use anyhow::Result;
+    use std::ffi::c_void;
+    use std::ptr;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::mpsc::{channel, Sender};
+    use std::time::{Duration, Instant};
+    use crate::audio::stream_failure;
+    use crate::dsp;
+    use crate::data::StreamErrorKind;
+    use crate::i18n::tr;
+
+    pub const SAMPLE_RATE: u32 = 44_100;
+    pub const CHANNELS: u16 = 2;
+
+    type Hresult = i32;
+    const S_OK: Hresult = 0;
+    const E_NOINTERFACE: Hresult = 0x8000_4002_u32 as i32;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    struct Guid(u32, u16, u16, [u8; 8]);
+
+    const IID_IUNKNOWN: Guid = Guid(0x0000_0000, 0x0000, 0x0000, [0xC0, 0, 0, 0, 0, 0, 0, 0x46]);
+    const IID_IAGILE_OBJECT: Guid = Guid(0x94EA_2B94, 0xE9CC, 0x49E0, [0xC0, 0xFF, 0xEE, 0x64, 0xCA, 0x8F, 0x5B, 0x90]);
+    const IID_ICOMPLETION_HANDLER: Guid = Guid(0x41D9_49AB, 0x9862, 0x444A, [0x80, 0xF6, 0xC2, 0x61, 0x33, 0x4D, 0xA5, 0xEB]);
+    const IID_IAUDIO_CLIENT: Guid = Guid(0x1CB9_AD4C, 0xDBFA, 0x4C32, [0xB1, 0x78, 0xC2, 0xF5, 0x68, 0xA7, 0x03, 0xB2]);
+    const IID_IAUDIO_CAPTURE_CLIENT: Guid = Guid(0xC8AD_BD64, 0xE71E, 0x48A0, [0xA4, 0xDE, 0x18, 0x5C, 0x39, 0x5C, 0xD3, 0x17]);
+
+    const COINIT_MULTITHREADED: u32 = 0;
+    const AUDCLNT_SHAREMODE_SHARED: i32 = 0;
+    const AUDCLNT_STREAMFLAGS_LOOPBACK: u32 = 0x0002_0000;
+    const AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM: u32 = 0x8000_0000;
+    const AUDCLNT_BUFFERFLAGS_SILENT: u32 = 0x2;
+    const AUDIOCLIENT_ACTIVATION_TYPE_PROCESS_LOOPBACK: u32 = 1;
+    const PROCESS_LOOPBACK_MODE_INCLUDE_TARGET_PROCESS_TREE: u32 = 0;
+    const VT_BLOB: u16 = 65;
+    const WAVE_FORMAT_PCM: u16 = 1;
+    /// Búfer de WASAPI en unidades de 100 ns (200 ms); se vacía cada 10 ms.
+    const BUFFER_DURATION: i64 = 2_000_000;
+    const TH32CS_SNAPPROCESS: u32 = 0x2;
+    const MAX_PATH: usize = 260;
+
+    #[repr(C)]
+    struct UnknownVtbl {
+        query_interface: unsafe extern "system" fn(*mut c_void, *const Guid, *mut *mut c_void) -> Hresult,
+        add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+        release: unsafe extern "system" fn(*mut c_void) -> u32,
+    }
+
+    #[repr(C)]
+    struct AudioClientVtbl {
+        base: UnknownVtbl,
+        initialize: unsafe extern "system" fn(*mut c_void, i32, u32, i64, i64, *const WaveFormatEx, *const Guid) -> Hresult,
+        get_buffer_size: usize,
+        get_stream_latency: usize,
+        get_current_padding: usize,
+        is_format_supported: usize,
+        get_mix_format: usize,
+        get_device_period: usize,
+        start: unsafe extern "system" fn(*mut c_void) -> Hresult,
+        stop: unsafe extern "system" fn(*mut c_void) -> Hresult,
+        reset: usize,
+        set_event_handle: usize,
+        get_service: unsafe extern "system" fn(*mut c_void, *const Guid, *mut *mut c_void) -> Hresult,
+    }
+
+    #[repr(C)]
+    struct CaptureClientVtbl {
+        base: UnknownVtbl,
+        get_buffer: unsafe extern "system" fn(*mut c_void, *mut *mut u8, *mut u32, *mut u32, *mut u64, *mut u64) -> Hresult,
+        release_buffer: unsafe extern "system" fn(*mut c_void, u32) -> Hresult,
+        get_next_packet_size: unsafe extern "system" fn(*mut c_void, *mut u32) -> Hresult,
+    }
+
+    #[repr(C)]
+    struct AsyncOperationVtbl {
+        base: UnknownVtbl,
+        get_activate_result: unsafe extern "system" fn(*mut c_void, *mut Hresult, *mut *mut c_void) -> Hresult,
+    }
+
+    #[repr(C)]
+    struct CompletionHandlerVtbl {
+        base: UnknownVtbl,
+        activate_completed: unsafe extern "system" fn(*mut c_void, *mut c_void) -> Hresult,
+    }
+
+    #[repr(C, packed(1))]
+    struct WaveFormatEx {
+        format_tag: u16,
+        channels: u16,
+        samples_per_sec: u32,
+        avg_bytes_per_sec: u32,
+        block_align: u16,
+        bits_per_sample: u16,
+        size: u16,
+    }
+
+    /// `AUDIOCLIENT_ACTIVATION_PARAMS` con `AUDIOCLIENT_PROCESS_LOOPBACK_PARAMS`.
+    #[repr(C)]
+    struct ActivationParams {
+        activation_type: u32,
+        target_process_id: u32,
+        loopback_mode: u32,
+    }
+
+    /// `PROPVARIANT` con un `VT_BLOB`.
+    #[repr(C)]
+    struct PropVariantBlob {
+        vt: u16,
+        reserved: [u16; 3],
+        size: u32,
+        data: *const u8,
+    }
+
+    #[repr(C)]
+    struct ProcessEntry32W {
+        size: u32,
+        usage: u32,
+        process_id: u32,
+        default_heap_id: usize,
+        module_id: u32,
+        threads: u32,
+        parent_process_id: u32,
+        pri_class_base: i32,
+        flags: u32,
+        exe_file: [u16; MAX_PATH],
+    }
+
+    #[link(name = "ole32")]
+    extern "system" {
+        fn CoInitializeEx(reserved: *mut c_void, co_init: u32) -> Hresult;
+        fn CoUninitialize();
+    }
+
+    #[link(name = "mmdevapi")]
+    extern "system" {
+        fn ActivateAudioInterfaceAsync(
+            device_interface_path: *const u16,
+            riid: *const Guid,
+            activation_params: *const PropVariantBlob,
+            completion_handler: *mut c_void,
+            activation_operation: *mut *mut c_void,
+        ) -> Hresult;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateToolhelp32Snapshot(flags: u32, process_id: u32) -> *mut c_void;
+        fn Process32FirstW(snapshot: *mut c_void, entry: *mut ProcessEntry32W) -> i32;
+        fn Process32NextW(snapshot: *mut c_void, entry: *mut ProcessEntry32W) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    unsafe fn vtbl<T>(object: *mut c_void) -> &'static T {
+        &**(object as *mut *const T)
+    }
+
+    unsafe fn release(object: *mut c_void) {
+        if !object.is_null() {
+            (vtbl::<UnknownVtbl>(object).release)(object);
+        }
+    }
+
+    fn check(hr: Hresult, what: &str) -> Result<()> {
+        if hr < 0 {
+            return Err(stream_failure(StreamErrorKind::Device, tr!("audio.process_loopback_error", what = what, hr = format!("0x{:08X}", hr as u32))));
+        }
+        Ok(())
+    }
+
+    // ── Procesos ──────────────────────────────────────────────────────────
+
+    pub struct ProcessEntry {
+        pub pid: u32,
+        pub parent: u32,
+        pub exe: String,
+    }
+
+    pub fn snapshot() -> Vec<ProcessEntry> {
+        let mut processes = Vec::new();
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+            if snapshot.is_null() || snapshot as isize == -1 {
+                return processes;
+            }
+            let mut entry: ProcessEntry32W = std::mem::zeroed();
+            entry.size = std::mem::size_of::<ProcessEntry32W>() as u32;
+            let mut ok = Process32FirstW(snapshot, &mut entry);
+            while ok != 0 {
+                let len = entry.exe_file.iter().position(|&c| c == 0).unwrap_or(MAX_PATH);
+                processes.push(ProcessEntry {
+                    pid: entry.process_id,
+                    parent: entry.parent_process_id,
+                    exe: String::from_utf16_lossy(&entry.exe_file[..len]),
+                });
+                ok = Process32NextW(snapshot, &mut entry);
+            }
+            CloseHandle(snapshot);
+        }
+        processes
+    }
+
+    /// Proceso principal de `exe`: el que no tiene como padre otro proceso
+    /// del mismo ejecutable (Chrome y Teams abren decenas de hijos).
+    fn root_process(exe: &str) -> Option<u32> {
+        let processes = snapshot();
+        let same = |p: &ProcessEntry| p.exe.eq_ignore_ascii_case(exe);
+        processes.iter()
+            .filter(|p| same(p))
+            .find(|p| !processes.iter().any(|parent| parent.pid == p.parent && same(parent)))
+            .map(|p| p.pid)
+    }
+
+    // ── Activación asíncrona ──────────────────────────────────────────────
+    //
+    // `ActivateAudioInterfaceAsync` avisa a un objeto COM propio cuando
+    // termina. Tiene que ser ágil (responder a IAgileObject) o Windows se
+    // niega a llamarlo desde su hilo.
+
+    #[repr(C)]
+    struct CompletionHandler {
+        vtbl: *const CompletionHandlerVtbl,
+        refs: AtomicU32,
+        done: Sender<()>,
+    }
+
+    static HANDLER_VTBL: CompletionHandlerVtbl = CompletionHandlerVtbl {
+        base: UnknownVtbl {
+            query_interface: handler_query_interface,
+            add_ref: handler_add_ref,
+            release: handler_release,
+        },
+        activate_completed: handler_activate_completed,
+    };
+
+    unsafe extern "system" fn handler_query_interface(this: *mut c_void, riid: *const Guid, out: *mut *mut c_void) -> Hresult {
+        let riid = *riid;
+        if riid == IID_IUNKNOWN || riid == IID_IAGILE_OBJECT || riid == IID_ICOMPLETION_HANDLER {
+            handler_add_ref(this);
+            *out = this;
+            S_OK
+        } else {
+            *out = ptr::null_mut();
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe extern "system" fn handler_add_ref(this: *mut c_void) -> u32 {
+        (*(this as *mut CompletionHandler)).refs.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    unsafe extern "system" fn handler_release(this: *mut c_void) -> u32 {
+        let left = (*(this as *mut CompletionHandler)).refs.fetch_sub(1, Ordering::SeqCst) - 1;
+        if left == 0 {
+            drop(Box::from_raw(this as *mut CompletionHandler));
+        }
+        left
+    }
+
+    unsafe extern "system" fn handler_activate_completed(this: *mut c_void, _operation: *mut c_void) -> Hresult {
+        let _ = (*(this as *mut CompletionHandler)).done.send(());
+        S_OK
+    }
+
+    /// `IAudioClient` del árbol de procesos de `pid`.
+    unsafe fn activate_process_client(pid: u32) -> Result<*mut c_void> {
+        let params = ActivationParams {
+            activation_type: AUDIOCLIENT_ACTIVATION_TYPE_PROCESS_LOOPBACK,
+            target_process_id: pid,
+            loopback_mode: PROCESS_LOOPBACK_MODE_INCLUDE_TARGET_PROCESS_TREE,
+        };
+        let variant = PropVariantBlob {
+            vt: VT_BLOB,
+            reserved: [0; 3],
+            size: std::mem::size_of::<ActivationParams>() as u32,
+            data: &params as *const ActivationParams as *const u8,
+        };
+        let path: Vec<u16> = "VAD\\Process_Loopback".encode_utf16().chain(Some(0)).collect();
+
+        let (done_tx, done_rx) = channel();
+        let handler = Box::into_raw(Box::new(CompletionHandler {
+            vtbl: &HANDLER_VTBL,
+            refs: AtomicU32::new(1),
+            done: done_tx,
+        })) as *mut c_void;
+
+        let mut operation: *mut c_void = ptr::null_mut();
+        let hr = ActivateAudioInterfaceAsync(path.as_ptr(), &IID_IAUDIO_CLIENT, &variant, handler, &mut operation);
+        let waited = if hr >= 0 { done_rx.recv_timeout(Duration::from_secs(5)).is_ok() } else { false };
+        handler_release(handler);
+        check(hr, "ActivateAudioInterfaceAsync")?;
+        if !waited {
+            release(operation);
+            return Err(stream_failure(StreamErrorKind::Device, tr!("audio.process_loopback_error", what = "ActivateAudioInterfaceAsync", hr = "timeout")));
+        }
+
+        let mut activate_hr: Hresult = 0;
+        let mut unknown: *mut c_void = ptr::null_mut();
+        let hr = (vtbl::<AsyncOperationVtbl>(operation).get_activate_result)(operation, &mut activate_hr, &mut unknown);
+        release(operation);
+        check(hr, "GetActivateResult")?;
+        check(activate_hr, "ActivateCompleted")?;
+
+        let mut client: *mut c_void = ptr::null_mut();
+        let hr = (vtbl::<UnknownVtbl>(unknown).query_interface)(unknown, &IID_IAUDIO_CLIENT, &mut client);
+        release(unknown);
+        check(hr, "IAudioClient")?;
+        Ok(client)
+    }
+
+    // ── Captura ───────────────────────────────────────────────────────────
+
+    pub struct Capture {
+        client: *mut c_void,
+        capture: *mut c_void,
+        started: Instant,
+        /// Tramas entregadas (reales o de relleno) desde `started`.
+        frames: u64,
+    }
+
+    impl Capture {
+        pub fn start(exe: &str) -> Result<Self> {
+            let pid = root_process(exe)
+                .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("audio.process_not_running", name = exe)))?;
+
+            unsafe {
+                check(CoInitializeEx(ptr::null_mut(), COINIT_MULTITHREADED), "CoInitializeEx")?;
+                // A partir de aquí Drop deshace CoInitializeEx aunque falle algo
+                let mut this = Self { client: ptr::null_mut(), capture: ptr::null_mut(), started: Instant::now(), frames: 0 };
+                this.client = activate_process_client(pid)?;
+
+                let block_align = CHANNELS * 2;
+                let format = WaveFormatEx {
+                    format_tag: WAVE_FORMAT_PCM,
+                    channels: CHANNELS,
+                    samples_per_sec: SAMPLE_RATE,
+                    avg_bytes_per_sec: SAMPLE_RATE * block_align as u32,
+                    block_align,
+                    bits_per_sample: 16,
+                    size: 0,
+                };
+                let client = vtbl::<AudioClientVtbl>(this.client);
+                check((client.initialize)(
+                    this.client,
+                    AUDCLNT_SHAREMODE_SHARED,
+                    AUDCLNT_STREAMFLAGS_LOOPBACK | AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM,
+                    BUFFER_DURATION,
+                    0,
+                    &format,
+                    ptr::null(),
+                ), "IAudioClient::Initialize")?;
+                check((client.get_service)(this.client, &IID_IAUDIO_CAPTURE_CLIENT, &mut this.capture), "GetService")?;
+                check((client.start)(this.client), "IAudioClient::Start")?;
+                this.started = Instant::now();
+                tracing::info!("Process Loopback de {} (PID {})", exe, pid);
+                Ok(this)
+            }
+        }
+
+        pub fn read(&mut self, samples: &mut Vec<f32>) -> Result<()> {
+            unsafe {
+                let capture = vtbl::<CaptureClientVtbl>(self.capture);
+                loop {
+                    let mut packet = 0u32;
+                    check((capture.get_next_packet_size)(self.capture, &mut packet), "GetNextPacketSize")?;
+                    if packet == 0 {
+                        break;
+                    }
+                    let mut data: *mut u8 = ptr::null_mut();
+                    let mut frames = 0u32;
+                    let mut flags = 0u32;
+                    check((capture.get_buffer)(self.capture, &mut data, &mut frames, &mut flags, ptr::null_mut(), ptr::null_mut()), "GetBuffer")?;
+                    let count = frames as usize * CHANNELS as usize;
+                    if flags & AUDCLNT_BUFFERFLAGS_SILENT != 0 || data.is_null() {
+                        samples.resize(samples.len() + count, 0.0);
+                    } else {
+                        let pcm = std::slice::from_raw_parts(data as *const i16, count);
+                        dsp::extend_from_s16(samples, pcm);
+                    }
+                    check((capture.release_buffer)(self.capture, frames), "ReleaseBuffer")?;
+                }
+            }
+            self.frames += (samples.len() / CHANNELS as usize) as u64;
+
+            // Sin audio de la aplicación no llegan paquetes: silencio hasta
+            // ponerse al día, con 100 ms de margen para no trocear de más.
+            let expected = (self.started.elapsed().as_secs_f64() * SAMPLE_RATE as f64) as u64;
+            let behind = expected.saturating_sub(self.frames);
+            if behind > SAMPLE_RATE as u64 / 10 {
+                samples.resize(samples.len() + behind as usize * CHANNELS as usize, 0.0);
+                self.frames += behind;
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for Capture {
+        fn drop(&mut self) {
+            unsafe {
+                if !self.client.is_null() {
+                    (vtbl::<AudioClientVtbl>(self.client).stop)(self.client);
+                }
+                release(self.capture);
+                release(self.client);
+                CoUninitialize();
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::data::TranscriptSegment;
+
+// ── Puntuación y mayúsculas entre fragmentos ──────────────────────────────
+//
+// Con fragmentos de 5 s Whisper trata cada trozo como una frase completa:
+// mayúscula al principio y punto al final aunque la frase siga en el
+// siguiente. Estas reglas, por interlocutor, deciden si un segmento continúa
+// el anterior (pausa corta y empieza por una palabra funcional) o abre frase
+// nueva, y arreglan el final del anterior y el principio del nuevo.
+
+/// Pausa a partir de la cual se considera que empieza una frase nueva.
+const SENTENCE_PAUSE_SECS: f64 = 1.0;
+
+/// Palabras que casi nunca abren frase pero sí continúan una.
+const CONTINUATION_WORDS_ES: &[&str] = &[
+    "y", "e", "o", "u", "pero", "que", "de", "del", "a", "al", "en", "con", "por", "para",
+    "sin", "porque", "pues", "aunque", "sino", "la", "las", "el", "los", "lo", "un", "una",
+    "se", "como", "cuando", "donde", "si", "ni",
+];
+const CONTINUATION_WORDS_EN: &[&str] = &[
+    "and", "or", "but", "that", "which", "of", "to", "in", "on", "with", "for", "by",
+    "because", "so", "the", "a", "an", "as", "when", "where", "if", "than", "nor",
+];
+
+const SENTENCE_END: &[char] = &['.', '?', '!', '…'];
+
+/// Ajusta `next` (y el segmento anterior del mismo interlocutor, si lo hay)
+/// para que la puntuación tenga sentido a través del corte. `lang` es el
+/// idioma del texto (`None` = desconocido: se usan las listas de ambos).
+/// Devuelve `true` si ha cambiado el segmento anterior.
+pub fn join_segments(previous: Option<&mut TranscriptSegment>, next: &mut TranscriptSegment, lang: Option<&str>) -> bool {
+    next.text = tidy_sentence(&next.text, lang);
+
+    let Some(previous) = previous else {
+        next.text = capitalize_first(&next.text);
+        return false;
+    };
+
+    let pause = next.start_secs - previous.end_secs;
+    let continues = pause < SENTENCE_PAUSE_SECS
+        && !previous.text.ends_with(['?', '!'])
+        && first_word(&next.text).is_some_and(|w| is_continuation_word(&w, lang));
+
+    let before = previous.text.clone();
+    if continues {
+        // El punto lo puso Whisper al cortar, no el hablante.
+        if previous.text.ends_with('.') && !previous.text.ends_with("...") {
+            previous.text.pop();
+        }
+        next.text = lowercase_first(&next.text);
+    } else {
+        if !previous.text.ends_with(SENTENCE_END) && !previous.text.is_empty() {
+            previous.text.push('.');
+        }
+        next.text = capitalize_first(&next.text);
+    }
+    previous.text != before
+}
+
+/// Arreglos dentro de un segmento: espacio tras comas y signos, mayúscula
+/// después de fin de frase y, en español, signos de apertura `¿` / `¡`.
+fn tidy_sentence(text: &str, lang: Option<&str>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut capitalize = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if capitalize && c.is_alphabetic() {
+            out.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            out.push(c);
+            if !c.is_whitespace() {
+                capitalize = false;
+            }
+        }
+        // "hola,qué tal" → "hola, qué tal". El punto se deja: "p.ej.", "3.5".
+        if matches!(c, '?' | '!' | ',' | ';' | ':') && chars.peek().is_some_and(|n| n.is_alphabetic()) {
+            out.push(' ');
+        }
+        if SENTENCE_END.contains(&c) {
+            capitalize = chars.peek().is_none_or(|n| n.is_whitespace() || matches!(c, '?' | '!'));
+        }
+    }
+
+    if lang == Some("es") {
+        out = add_inverted_marks(&out);
+    }
+    out
+}
+
+/// "Qué hora es?" → "¿Qué hora es?". Solo frases que terminan en `?` / `!`
+/// sin su signo de apertura.
+fn add_inverted_marks(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 4);
+    let mut sentence = String::new();
+    for c in text.chars() {
+        sentence.push(c);
+        if SENTENCE_END.contains(&c) {
+            out.push_str(&open_sentence(&sentence, c));
+            sentence.clear();
+        }
+    }
+    out.push_str(&sentence);
+    out
+}
+
+fn open_sentence(sentence: &str, end: char) -> String {
+    let opening = match end {
+        '?' => '¿',
+        '!' => '¡',
+        _ => return sentence.to_string(),
+    };
+    if sentence.contains(opening) {
+        return sentence.to_string();
+    }
+    let trimmed = sentence.trim_start();
+    let indent = &sentence[..sentence.len() - trimmed.len()];
+    format!("{}{}{}", indent, opening, trimmed)
+}
+
+fn first_word(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .next()
+        .map(|w| w.trim_start_matches(['¿', '¡']).trim_end_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+}
+
+fn is_continuation_word(word: &str, lang: Option<&str>) -> bool {
+    match lang {
+        Some("es") => CONTINUATION_WORDS_ES.contains(&word),
+        Some("en") => CONTINUATION_WORDS_EN.contains(&word),
+        _ => CONTINUATION_WORDS_ES.contains(&word) || CONTINUATION_WORDS_EN.contains(&word),
+    }
+}
+
+/// Primera letra en mayúscula (saltando `¿`, `¡`, comillas...).
+fn capitalize_first(text: &str) -> String {
+    map_first_letter(text, |c| c.to_uppercase().collect())
+}
+
+fn lowercase_first(text: &str) -> String {
+    map_first_letter(text, |c| c.to_lowercase().collect())
+}
+
+fn map_first_letter(text: &str, f: impl Fn(char) -> String) -> String {
+    match text.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((i, c)) => format!("{}{}{}", &text[..i], f(c), &text[i + c.len_utf8()..]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_secs: f64, end_secs: f64) -> TranscriptSegment {
+        TranscriptSegment {
+            name: "Ana".into(),
+            text: text.into(),
+            start_secs,
+            end_secs,
+            words: Vec::new(),
+            reviewed: false,
+            lang: None,
+            original: None,
+            edits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn joins_across_the_cut() {
+        // (anterior, siguiente, pausa, idioma) → (anterior, siguiente, ¿cambia el anterior?)
+        let cases = [
+            ("Vamos a revisar el presupuesto.", "Y luego las cuentas.", 0.3, Some("es"),
+             ("Vamos a revisar el presupuesto", "y luego las cuentas.", true)),
+            ("Vamos a revisar el presupuesto.", "Y luego las cuentas.", 2.0, Some("es"),
+             ("Vamos a revisar el presupuesto.", "Y luego las cuentas.", false)),
+            ("Qué opinas?", "Y luego las cuentas.", 0.3, Some("es"),
+             ("Qué opinas?", "Y luego las cuentas.", false)),
+            ("We need to check the budget.", "And then the accounts.", 0.3, Some("en"),
+             ("We need to check the budget", "and then the accounts.", true)),
+            ("Espera...", "Pero bueno.", 0.3, Some("es"),
+             ("Espera...", "pero bueno.", false)),
+            ("sin punto", "Luego seguimos.", 2.0, Some("es"),
+             ("sin punto.", "Luego seguimos.", true)),
+            ("Vamos.", "Que sí.", 0.3, None,
+             ("Vamos", "que sí.", true)),
+        ];
+        for (previous, next, pause, lang, expected) in cases {
+            let mut a = segment(previous, 0.0, 5.0);
+            let mut b = segment(next, 5.0 + pause, 10.0);
+            let changed = join_segments(Some(&mut a), &mut b, lang);
+            assert_eq!((a.text.as_str(), b.text.as_str(), changed), expected, "{:?} + {:?}", previous, next);
+        }
+    }
+
+    #[test]
+    fn tidies_a_single_segment() {
+        let cases = [
+            ("hola,qué tal", Some("es"), "Hola, qué tal"),
+            ("qué hora es? son las tres.", Some("es"), "¿Qué hora es? Son las tres."),
+            ("¿Vienes? sí", Some("es"), "¿Vienes? Sí"),
+            ("Bien. vale.", Some("es"), "Bien. Vale."),
+            ("p.ej. 3.5 euros", Some("es"), "P.ej. 3.5 euros"),
+            ("what time is it? three.", Some("en"), "What time is it? Three."),
+        ];
+        for (text, lang, expected) in cases {
+            let mut next = segment(text, 0.0, 1.0);
+            assert!(!join_segments(None, &mut next, lang));
+            assert_eq!(next.text, expected, "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::{Result, anyhow};
+use hound::{SampleFormat, WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use crate::data::WHISPER_SAMPLE_RATE;
+use crate::export::sanitize_file_stem;
+
+// ── Grabación de la reunión (mezcla) ───────────────────────────────────────
+//
+// Además de transcribir, cada fuente activa aporta su audio (ya a 16 kHz
+// mono) a una única pista. La posición de cada fuente en la pista se cuenta
+// desde que empezó la grabación, así que micrófono y loopback quedan
+// alineados aunque se abran con unos milisegundos de diferencia. La pista se
+// escribe a disco a medida que todas las fuentes han aportado su parte.
+//
+// Opcionalmente cada fuente se guarda también por separado en
+// `<grabación>_pistas/`, con un `pistas.json` que dice de quién es cada
+// archivo y en qué segundo de la sesión empieza. Con ellas se puede volver a
+// transcribir la reunión con otro modelo (ver `retranscribe.rs`).
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordingFormat {
+    Wav,
+    /// Opus en contenedor Ogg, convertido con ffmpeg al terminar.
+    Ogg,
+}
+
+impl RecordingFormat {
+    pub const ALL: &'static [RecordingFormat] = &[RecordingFormat::Wav, RecordingFormat::Ogg];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RecordingFormat::Wav => "WAV",
+            RecordingFormat::Ogg => "OGG (Opus)",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            RecordingFormat::Wav => "wav",
+            RecordingFormat::Ogg => "ogg",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingSettings {
+    pub enabled: bool,
+    pub format: RecordingFormat,
+    /// Guardar además una pista WAV por interlocutor.
+    pub keep_tracks: bool,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self { enabled: false, format: RecordingFormat::Ogg, keep_tracks: true }
+    }
+}
+
+/// Pista de un interlocutor dentro de `pistas.json`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrackInfo {
+    pub speaker: String,
+    /// Nombre del WAV dentro de la carpeta de pistas.
+    pub file: String,
+    /// Segundo de la sesión en el que empieza la pista.
+    pub offset_secs: f64,
+}
+
+const TRACKS_MANIFEST: &str = "pistas.json";
+
+/// Carpeta de pistas por interlocutor de una grabación.
+pub fn tracks_dir(recording: &Path) -> PathBuf {
+    let stem = recording.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    recording.with_file_name(format!("{}_pistas", stem))
+}
+
+pub fn has_tracks(recording: &Path) -> bool {
+    tracks_dir(recording).join(TRACKS_MANIFEST).exists()
+}
+
+/// Pistas de una grabación, con su ruta completa. Vacío si no se guardaron.
+pub fn read_tracks(recording: &Path) -> Result<Vec<(TrackInfo, PathBuf)>> {
+    let dir = tracks_dir(recording);
+    let tracks: Vec<TrackInfo> = serde_json::from_str(&std::fs::read_to_string(dir.join(TRACKS_MANIFEST))?)?;
+    Ok(tracks.into_iter().map(|t| { let path = dir.join(&t.file); (t, path) }).collect())
+}
+
+/// WAV de 16 kHz mono como los que escribe este módulo.
+pub fn read_wav(path: &Path) -> Result<Vec<f32>> {
+    let mut reader = hound::WavReader::open(path)?;
+    reader.samples::<i16>()
+        .map(|s| Ok(s? as f32 / 32768.0))
+        .collect()
+}
+
+/// Muestras `from..to` de una grabación (16 kHz mono), sin leer el resto
+/// del archivo.
+pub fn read_wav_range(path: &Path, from: usize, to: usize) -> Result<Vec<f32>> {
+    let mut reader = hound::WavReader::open(path)?;
+    let end = u32::try_from(to).unwrap_or(u32::MAX).min(reader.duration());
+    let start = u32::try_from(from).unwrap_or(u32::MAX).min(end);
+    reader.seek(start)?;
+    reader.samples::<i16>()
+        .take((end - start) as usize)
+        .map(|s| Ok(s? as f32 / 32768.0))
+        .collect()
+}
+
+pub(crate) fn wav_spec() -> WavSpec {
+    WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    }
+}
+
+pub(crate) fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+/// Retraso máximo de una fuente antes de escribir sin esperarla, en muestras.
+/// Una fuente atascada no debe hacer crecer la mezcla en memoria sin límite.
+const MAX_LAG_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize * 60;
+
+struct Mixer {
+    writer: Option<WavWriter<BufWriter<File>>>,
+    started: Instant,
+    /// Muestras aún sin escribir; `pending[0]` es la muestra `flushed`.
+    pending: Vec<f32>,
+    flushed: usize,
+    /// Siguiente muestra de cada fuente; `None` cuando ha terminado.
+    positions: Vec<Option<usize>>,
+    tracks: Vec<TrackInfo>,
+}
+
+impl Mixer {
+    fn push(&mut self, source: usize, samples: &[f32]) -> Result<()> {
+        let Some(position) = self.positions[source] else { return Ok(()) };
+        self.positions[source] = Some(position + samples.len());
+
+        // Lo que llega más tarde de lo ya escrito se pierde.
+        let skip = self.flushed.saturating_sub(position).min(samples.len());
+        let offset = (position + skip).saturating_sub(self.flushed);
+        let samples = &samples[skip..];
+        if self.pending.len() < offset + samples.len() {
+            self.pending.resize(offset + samples.len(), 0.0);
+        }
+        for (mixed, s) in self.pending[offset..].iter_mut().zip(samples) {
+            *mixed += s;
+        }
+        self.flush_ready()
+    }
+
+    /// Escribe lo que ya han aportado todas las fuentes activas.
+    fn flush_ready(&mut self) -> Result<()> {
+        let furthest = self.flushed + self.pending.len();
+        let ready = self.positions.iter()
+            .flatten()
+            .copied()
+            .min()
+            .unwrap_or(furthest)
+            .max(furthest.saturating_sub(MAX_LAG_SAMPLES));
+        self.write(ready.saturating_sub(self.flushed).min(self.pending.len()))
+    }
+
+    fn write(&mut self, count: usize) -> Result<()> {
+        if let Some(writer) = &mut self.writer {
+            for s in &self.pending[..count] {
+                writer.write_sample(to_i16(*s))?;
+            }
+        }
+        self.pending.drain(..count);
+        self.flushed += count;
+        Ok(())
+    }
+}
+
+/// Pista compartida por todos los hilos de transcripción de la sesión.
+#[derive(Clone)]
+pub struct MixRecorder {
+    mixer: Arc<Mutex<Mixer>>,
+    path: PathBuf,
+    format: RecordingFormat,
+    keep_tracks: bool,
+}
+
+impl MixRecorder {
+    /// Empieza la grabación en `path` (WAV). Si el formato es OGG se
+    /// convierte en `finish`. `started` es el origen de la pista: ahora, o
+    /// antes si la sesión empieza con pre-grabación.
+    pub fn create(path: &Path, settings: &RecordingSettings, started: Instant) -> Result<Self> {
+        let writer = WavWriter::create(path, wav_spec())?;
+        let mixer = Mixer {
+            writer: Some(writer),
+            started,
+            pending: Vec::new(),
+            flushed: 0,
+            positions: Vec::new(),
+            tracks: Vec::new(),
+        };
+        Ok(Self {
+            mixer: Arc::new(Mutex::new(mixer)),
+            path: path.to_path_buf(),
+            format: settings.format,
+            keep_tracks: settings.keep_tracks,
+        })
+    }
+
+    /// Registra una fuente cuya primera muestra sonó en `since`.
+    pub fn source(&self, speaker: &str, since: Instant) -> MixSource {
+        let mut mixer = self.mixer.lock().unwrap();
+        let now = (since.saturating_duration_since(mixer.started).as_secs_f64() * WHISPER_SAMPLE_RATE as f64) as usize;
+        mixer.positions.push(Some(now));
+        let index = mixer.positions.len() - 1;
+
+        let track = if self.keep_tracks {
+            let file = format!("{:02}_{}.wav", index + 1, sanitize_file_stem(speaker));
+            let dir = tracks_dir(&self.path);
+            match std::fs::create_dir_all(&dir).map_err(anyhow::Error::from)
+                .and_then(|_| Ok(WavWriter::create(dir.join(&file), wav_spec())?))
+            {
+                Ok(writer) => {
+                    mixer.tracks.push(TrackInfo {
+                        speaker: speaker.to_string(),
+                        file,
+                        offset_secs: now as f64 / WHISPER_SAMPLE_RATE as f64,
+                    });
+                    Some(writer)
+                }
+                Err(e) => {
+                    tracing::error!("No se pudo crear la pista de {}: {:?}", speaker, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        MixSource { recorder: self.clone(), index, track }
+    }
+
+    /// Cierra la pista con lo que haya y, si se pidió, la convierte a OGG.
+    /// Devuelve la ruta del archivo final.
+    pub fn finish(&self) -> Result<PathBuf> {
+        {
+            let mut mixer = self.mixer.lock().unwrap();
+            let remaining = mixer.pending.len();
+            mixer.write(remaining)?;
+            if let Some(writer) = mixer.writer.take() {
+                writer.finalize()?;
+            }
+            if !mixer.tracks.is_empty() {
+                let manifest = serde_json::to_string_pretty(&mixer.tracks)?;
+                std::fs::write(tracks_dir(&self.path).join(TRACKS_MANIFEST), manifest)?;
+            }
+        }
+        match self.format {
+            RecordingFormat::Wav => Ok(self.path.clone()),
+            RecordingFormat::Ogg => {
+                let ogg = self.path.with_extension(self.format.extension());
+                convert_to_ogg(&self.path, &ogg)?;
+                std::fs::remove_file(&self.path)?;
+                Ok(ogg)
+            }
+        }
+    }
+}
+
+/// Aportación de una fuente a la mezcla. Al soltarla deja de contar para
+/// decidir qué parte de la pista está completa.
+pub struct MixSource {
+    recorder: MixRecorder,
+    index: usize,
+    /// Pista propia, si se guardan por separado.
+    track: Option<WavWriter<BufWriter<File>>>,
+}
+
+impl MixSource {
+    /// `samples` a 16 kHz mono, a continuación de lo último enviado.
+    pub fn push(&mut self, samples: &[f32]) {
+        if let Some(track) = &mut self.track {
+            if let Err(e) = samples.iter().try_for_each(|s| track.write_sample(to_i16(*s))) {
+                tracing::error!("Error escribiendo la pista: {:?}", e);
+                self.track = None;
+            }
+        }
+        let mut mixer = self.recorder.mixer.lock().unwrap();
+        if let Err(e) = mixer.push(self.index, samples) {
+            tracing::error!("Error escribiendo la grabación: {:?}", e);
+            mixer.writer = None;
+        }
+    }
+}
+
+impl Drop for MixSource {
+    fn drop(&mut self) {
+        if let Some(track) = self.track.take() {
+            if let Err(e) = track.finalize() {
+                tracing::error!("Error cerrando la pista: {:?}", e);
+            }
+        }
+        let mut mixer = self.recorder.mixer.lock().unwrap();
+        mixer.positions[self.index] = None;
+        let _ = mixer.flush_ready();
+    }
+}
+
+fn convert_to_ogg(wav: &Path, ogg: &Path) -> Result<()> {
+    let output = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(wav)
+        .args(["-c:a", "libopus", "-b:a", "32k"])
+        .arg(ogg)
+        .output()
+        .map_err(|e| anyhow!("ffmpeg: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!("ffmpeg: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
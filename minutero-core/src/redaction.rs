@@ -0,0 +1,202 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use crate::export::MinutaDocument;
+
+// ── Anonimización de minutas exportadas ───────────────────────────────────
+//
+// Para minutas que salen del equipo: tacos, correos, teléfonos, DNI/NIE y
+// patrones propios se sustituyen por una marca en los archivos y en las
+// integraciones. La minuta en pantalla se queda como estaba, y el original
+// se guarda aparte en la carpeta local de sesiones (ver
+// `export::save_local_session`).
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionSettings {
+    pub enabled: bool,
+    pub profanity: bool,
+    pub emails: bool,
+    pub phones: bool,
+    /// DNI/NIE y números de tarjeta.
+    pub ids: bool,
+    /// Expresiones regulares propias, una por línea.
+    pub custom_patterns: String,
+    pub mask: String,
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            profanity: true,
+            emails: true,
+            phones: true,
+            ids: true,
+            custom_patterns: String::new(),
+            mask: "[REDACTADO]".into(),
+        }
+    }
+}
+
+const PROFANITY: &[&str] = &[
+    // es
+    "joder", "jodido", "jodida", "mierda", "coño", "cabrón", "cabrona", "gilipollas", "hostia",
+    "hostias", "puta", "puto", "putada", "capullo", "imbécil", "idiota", "subnormal", "carajo",
+    "pendejo", "cojones", "cojonudo",
+    // en
+    "fuck", "fucking", "fucked", "shit", "bullshit", "bitch", "asshole", "bastard", "damn",
+    "crap", "dick",
+];
+
+const EMAIL: &str = r"[\w.+-]+@[\w-]+(?:\.[\w-]+)+";
+/// Nueve o más cifras con espacios o guiones, con prefijo internacional opcional.
+const PHONE: &str = r"\+?\d(?:[\s-]?\d){8,}";
+const DNI_NIE: &str = r"\b[XYZxyz]?\d{7,8}[\s-]?[A-Za-z]\b";
+const CARD: &str = r"\b\d{4}(?:[\s-]?\d{4}){3}\b";
+
+/// Reglas compiladas a partir de la configuración.
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    mask: String,
+}
+
+impl Redactor {
+    pub fn new(settings: &RedactionSettings) -> Self {
+        let mut sources: Vec<String> = Vec::new();
+        if settings.profanity {
+            let words: Vec<String> = PROFANITY.iter().map(|w| regex::escape(w)).collect();
+            sources.push(format!(r"(?i)\b(?:{})\b", words.join("|")));
+        }
+        if settings.emails {
+            sources.push(EMAIL.into());
+        }
+        if settings.ids {
+            sources.push(DNI_NIE.into());
+            sources.push(CARD.into());
+        }
+        if settings.phones {
+            sources.push(PHONE.into());
+        }
+        sources.extend(custom_patterns(&settings.custom_patterns).map(String::from));
+
+        let patterns = sources.iter()
+            .filter_map(|p| RegexBuilder::new(p).build().ok())
+            .collect();
+        Self { patterns, mask: settings.mask.clone() }
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for re in &self.patterns {
+            out = re.replace_all(&out, regex::NoExpand(&self.mask)).into_owned();
+        }
+        out
+    }
+}
+
+/// Patrones propios no vacíos, para compilarlos o validarlos en la UI.
+pub fn custom_patterns(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().map(str::trim).filter(|l| !l.is_empty())
+}
+
+/// Copia del documento para exportar. Sin cambios si la anonimización está
+/// desactivada.
+pub fn redact_document(doc: &MinutaDocument, settings: &RedactionSettings) -> MinutaDocument {
+    let mut doc = doc.clone();
+    if !settings.enabled {
+        return doc;
+    }
+    let redactor = Redactor::new(settings);
+    doc.title = redactor.redact(&doc.title);
+    doc.body = redactor.redact(&doc.body);
+    // Un tema que se taparía también delata lo tapado
+    doc.tags.retain(|tag| redactor.redact(tag) == *tag);
+    for entry in &mut doc.entries {
+        let redacted = redactor.redact(&entry.text);
+        if redacted != entry.text {
+            // Las palabras sueltas con sus tiempos delatarían lo que se tapa.
+            entry.words.clear();
+            entry.text = redacted;
+        }
+        // El texto de Whisper y el historial de correcciones también salen
+        // en el JSON: se tapan igual, cambie o no el texto final
+        entry.original = entry.original.as_deref().map(|o| redactor.redact(o));
+        for edit in &mut entry.edits {
+            edit.text = redactor.redact(&edit.text);
+        }
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::SegmentEdit;
+
+    fn settings() -> RedactionSettings {
+        RedactionSettings { enabled: true, ..Default::default() }
+    }
+
+    #[test]
+    fn masks_each_kind() {
+        let redactor = Redactor::new(&RedactionSettings {
+            custom_patterns: "(?i)proyecto fénix\n\n".into(),
+            ..settings()
+        });
+        let cases = [
+            ("escríbeme a ana.garcia+minutas@example.com mañana", "escríbeme a [REDACTADO] mañana"),
+            ("llama al +34 612 345 678", "llama al [REDACTADO]"),
+            ("mi DNI es 12345678Z", "mi DNI es [REDACTADO]"),
+            ("NIE X1234567L", "NIE [REDACTADO]"),
+            ("tarjeta 4111 1111 1111 1111", "tarjeta [REDACTADO]"),
+            ("¡Joder, qué lío!", "¡[REDACTADO], qué lío!"),
+            ("what the fuck", "what the [REDACTADO]"),
+            ("el proyecto Fénix va tarde", "el [REDACTADO] va tarde"),
+            // Cifras sueltas y palabras que solo empiezan como un taco se quedan
+            ("son 25 personas y 2024 euros", "son 25 personas y 2024 euros"),
+            ("Hostiapp no es un taco", "Hostiapp no es un taco"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(redactor.redact(text), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn honours_switches_and_mask() {
+        let redactor = Redactor::new(&RedactionSettings {
+            profanity: false,
+            emails: false,
+            mask: "***".into(),
+            ..settings()
+        });
+        assert_eq!(redactor.redact("joder, ana@example.com 612345678"), "joder, ana@example.com ***");
+
+        // Un patrón propio que no compila se ignora sin tumbar el resto
+        let redactor = Redactor::new(&RedactionSettings { custom_patterns: "(sin cerrar".into(), ..settings() });
+        assert_eq!(redactor.redact("(sin cerrar joder"), "(sin cerrar [REDACTADO]");
+    }
+
+    #[test]
+    fn document_covers_history() {
+        let mut doc = MinutaDocument::new(
+            "Reunión con ana@example.com".into(),
+            Vec::new(),
+            "[00:01] (Ana) Escríbeme a ana@example.com\n[00:09] (Luis) De acuerdo".into(),
+        );
+        doc.tags = vec!["presupuesto".into(), "ana@example.com".into()];
+        doc.entries[0].original = Some("escríbeme a ana@example.com".into());
+        doc.entries[0].edits = vec![SegmentEdit { at: "01-01-2025 10:00:00".into(), text: "Escríbeme a ana@example.com".into() }];
+
+        assert_eq!(redact_document(&doc, &RedactionSettings::default()).body, doc.body);
+
+        let redacted = redact_document(&doc, &settings());
+        assert_eq!(redacted.title, "Reunión con [REDACTADO]");
+        assert!(!redacted.body.contains("ana@example.com"));
+        assert_eq!(redacted.tags, ["presupuesto"]);
+        let entry = &redacted.entries[0];
+        assert_eq!(entry.text, "Escríbeme a [REDACTADO]");
+        assert_eq!(entry.original.as_deref(), Some("escríbeme a [REDACTADO]"));
+        assert_eq!(entry.edits[0].text, "Escríbeme a [REDACTADO]");
+        assert_eq!(redacted.entries[1].text, "De acuerdo");
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use crate::audio::model_file_name;
+use crate::data::ModelSource;
+use crate::i18n::tr;
+
+// ── Límite de memoria de los modelos ───────────────────────────────────────
+//
+// Cada fuente carga su propio modelo Whisper (dos si se compara con otro),
+// así que tres micrófonos con large-v3 piden unos 12 GB y en un portátil de
+// 8 GB acaba actuando el OOM killer. Antes de empezar se estima lo que hará
+// falta (pesos del modelo más los búferes de inferencia, con las cifras de
+// whisper.cpp) y se compara con el límite configurado o, sin límite, con la
+// memoria libre del sistema. Si no cabe, la captura no empieza y se dice por
+// qué. Las salas cuentan juntas: cada captura reserva lo suyo hasta que
+// termina. En Discord cada hablante nuevo reserva al empezar a hablar y,
+// si ya no cabe, comparte un único trabajador con los demás que no quepan.
+
+/// Por encima de esta fracción del límite se avisa, aunque quepa.
+const TIGHT_RATIO: f64 = 0.8;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    /// Memoria máxima para los modelos de todas las capturas, en MB; 0 = la
+    /// libre del sistema (solo se puede leer en Linux).
+    pub max_memory_mb: u64,
+    /// Modelos cargados a la vez como mucho; 0 = sin límite.
+    pub max_models: usize,
+}
+
+/// Lo que hay reservado por las capturas en marcha: (MB, modelos).
+static RESERVED: Mutex<(u64, usize)> = Mutex::new((0, 0));
+
+/// (pesos, búferes de inferencia) de cada familia, en MB.
+fn family_mb(name: &str) -> (u64, u64) {
+    let family = name.split(['-', '.']).next().unwrap_or_default();
+    match family {
+        "tiny" => (75, 200),
+        "base" => (142, 250),
+        "small" => (466, 390),
+        "medium" => (1500, 600),
+        "large" if name.contains("turbo") => (1600, 700),
+        "large" => (2900, 1000),
+        _ => (1500, 600),
+    }
+}
+
+/// Memoria estimada de una instancia de `model`, en MB. Si el archivo ya
+/// está en disco cuenta su tamaño real (los cuantizados ocupan mucho menos).
+pub fn model_memory_mb(model: &str, source: &ModelSource) -> u64 {
+    let (weights, buffers) = family_mb(model);
+    let path = if source.custom_path.trim().is_empty() {
+        Path::new("models").join(model_file_name(model))
+    } else {
+        Path::new(source.custom_path.trim()).to_path_buf()
+    };
+    let weights = std::fs::metadata(path).map_or(weights, |m| m.len() / 1_000_000);
+    weights + buffers
+}
+
+/// Memoria disponible del sistema en MB, si se puede saber.
+pub fn available_memory_mb() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        meminfo.lines()
+            .find_map(|l| l.strip_prefix("MemAvailable:"))
+            .and_then(|v| v.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+            .map(|kb| kb / 1024)
+    }
+    #[cfg(not(target_os = "linux"))]
+    None
+}
+
+/// Lo que pediría una captura, comparado con lo que queda.
+#[derive(Clone, Debug)]
+pub struct MemoryEstimate {
+    pub models: usize,
+    pub required_mb: u64,
+    /// Memoria que queda para esta captura; `None` si no hay límite ni se
+    /// puede leer la del sistema.
+    pub limit_mb: Option<u64>,
+    /// Modelos que quedan por cargar; `None` sin límite.
+    pub models_left: Option<usize>,
+}
+
+impl MemoryEstimate {
+    pub fn fits(&self) -> bool {
+        self.error().is_none()
+    }
+
+    /// Cabe, pero casi no queda margen.
+    pub fn is_tight(&self) -> bool {
+        self.limit_mb.is_some_and(|limit| self.required_mb as f64 > limit as f64 * TIGHT_RATIO)
+    }
+
+    /// Por qué no cabe, para el usuario.
+    pub fn error(&self) -> Option<String> {
+        if let Some(left) = self.models_left.filter(|&left| self.models > left) {
+            return Some(tr!("resources.too_many_models", n = self.models, max = left));
+        }
+        let limit = self.limit_mb.filter(|&limit| self.required_mb > limit)?;
+        Some(tr!("resources.too_much_memory", required = format_mb(self.required_mb), limit = format_mb(limit)))
+    }
+}
+
+/// Estima una captura de `streams` fuentes con `model` (y `compare`, si se
+/// compara) frente a los límites y lo que ya tienen reservado otras capturas.
+pub fn estimate(model: &str, compare: Option<&str>, streams: usize, source: &ModelSource, limits: &ResourceLimits) -> MemoryEstimate {
+    let per_stream = model_memory_mb(model, source) + compare.map_or(0, |c| model_memory_mb(c, source));
+    let models = streams * if compare.is_some() { 2 } else { 1 };
+    let (reserved_mb, reserved_models) = *RESERVED.lock().unwrap();
+    let limit_mb = if limits.max_memory_mb > 0 {
+        Some(limits.max_memory_mb.saturating_sub(reserved_mb))
+    } else {
+        // Lo ya cargado por otras capturas ya no está libre
+        available_memory_mb()
+    };
+    MemoryEstimate {
+        models,
+        required_mb: per_stream * streams as u64,
+        limit_mb,
+        models_left: (limits.max_models > 0).then(|| limits.max_models.saturating_sub(reserved_models)),
+    }
+}
+
+/// Memoria y modelos apartados para una captura. Se liberan al soltarla.
+pub struct Reservation {
+    mb: u64,
+    models: usize,
+}
+
+/// Aparta lo que pide `estimate`, o falla con el motivo si no cabe.
+pub fn reserve(estimate: &MemoryEstimate) -> Result<Reservation> {
+    if let Some(error) = estimate.error() {
+        return Err(anyhow!(error));
+    }
+    let mut reserved = RESERVED.lock().unwrap();
+    reserved.0 += estimate.required_mb;
+    reserved.1 += estimate.models;
+    Ok(Reservation { mb: estimate.required_mb, models: estimate.models })
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        let mut reserved = RESERVED.lock().unwrap();
+        reserved.0 = reserved.0.saturating_sub(self.mb);
+        reserved.1 = reserved.1.saturating_sub(self.models);
+    }
+}
+
+pub fn format_mb(mb: u64) -> String {
+    if mb >= 1024 {
+        format!("{:.1} GB", mb as f64 / 1024.0)
+    } else {
+        format!("{} MB", mb)
+    }
+}
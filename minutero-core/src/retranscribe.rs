@@ -0,0 +1,120 @@
+use anyhow::{Result, anyhow};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use tokio::runtime::Runtime;
+use whisper_rs::WhisperContext;
+
+use crate::audio::{
+    collect_words, download_status, download_whisper_model, model_is_cached, run_whisper,
+};
+use crate::data::{RetranscribeMessage, TranscriptSegment, TranscriptionConfig, SILENCE_THRESHOLD, WHISPER_SAMPLE_RATE};
+use crate::dsp;
+use crate::i18n::tr;
+use crate::stop::StopSignal;
+use crate::hallucinations::HallucinationFilter;
+use crate::postprocess::PostProcessor;
+use crate::recording::{read_tracks, read_wav};
+
+// ── Re-transcripción de una sesión guardada ────────────────────────────────
+//
+// Las pistas por interlocutor de la grabación se vuelven a pasar por Whisper
+// con otro modelo (típicamente uno grande, sin prisa). Salen segmentos con
+// los mismos nombres y tiempos de sesión que en vivo; la UI los guarda como
+// una versión nueva de la minuta.
+
+/// Ventana nativa de Whisper: sin tiempo real que cumplir, más contexto.
+const WINDOW_SECS: u32 = 30;
+
+pub fn retranscribe_thread(
+    recording: PathBuf,
+    model_name: String,
+    config: TranscriptionConfig,
+    tx: Sender<RetranscribeMessage>,
+    stop_signal: StopSignal,
+) -> Result<()> {
+    let tracks = read_tracks(&recording)
+        .map_err(|e| anyhow!(tr!("retranscribe.no_tracks", e = e)))?;
+
+    let _ = tx.send(RetranscribeMessage::Status(tr!("video.checking_model")));
+    let cached = model_is_cached(&model_name, &config.model_source);
+    let model_path = Runtime::new()?
+        .block_on(download_whisper_model(&model_name, &config.model_source, |downloaded, total| {
+            let _ = tx.send(RetranscribeMessage::Status(download_status(&model_name, downloaded, total)));
+        }))?;
+    if !cached {
+        let _ = tx.send(RetranscribeMessage::ModelDownloaded(model_name.clone()));
+    }
+
+    let ctx = WhisperContext::new_with_params(&model_path, Default::default())
+        .map_err(|e| anyhow!(tr!("audio.model_load", e = format!("{:?}", e))))?;
+    let mut state = ctx.create_state()
+        .map_err(|e| anyhow!(tr!("audio.state_create", e = format!("{:?}", e))))?;
+    let post = PostProcessor::new(&config.preset, config.style);
+    let hallucinations = HallucinationFilter::new(&config.tuning);
+
+    let tracks = tracks.into_iter()
+        .map(|(info, path)| Ok((info, read_wav(&path)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let window = (WHISPER_SAMPLE_RATE * WINDOW_SECS) as usize;
+    let total: usize = tracks.iter().map(|(_, audio)| audio.len().div_ceil(window)).sum();
+    let mut processed = 0;
+    let mut segments = Vec::new();
+    let mut normalized = Vec::with_capacity(window);
+
+    for (track, audio) in &tracks {
+        for (i, chunk) in audio.chunks(window).enumerate() {
+            if stop_signal.is_stopped() {
+                let _ = tx.send(RetranscribeMessage::Status(tr!("video.cancelled")));
+                return Ok(());
+            }
+            processed += 1;
+            let _ = tx.send(RetranscribeMessage::Progress(processed as f32 / total.max(1) as f32));
+            let _ = tx.send(RetranscribeMessage::Status(tr!(
+                "retranscribe.chunk", name = track.speaker, i = processed, total = total,
+            )));
+
+            if dsp::normalized_rms(chunk) < SILENCE_THRESHOLD {
+                continue;
+            }
+            normalized.clear();
+            normalized.extend_from_slice(chunk);
+            dsp::normalize_in_place(&mut normalized);
+            let chunk_start = track.offset_secs + (i * window) as f64 / WHISPER_SAMPLE_RATE as f64;
+
+            let lang = match run_whisper(&mut state, &config, &normalized) {
+                Ok(lang) => lang,
+                Err(e) => {
+                    tracing::error!("Error re-transcribiendo {} en {:.0} s: {:?}", track.speaker, chunk_start, e);
+                    continue;
+                }
+            };
+            for n in 0..state.full_n_segments() {
+                let Some(seg) = state.get_segment(n) else { continue };
+                let text = seg.to_string().trim().to_string();
+                if text.len() <= 1 || hallucinations.is_hallucination(&text) {
+                    continue;
+                }
+                let mut words = Vec::new();
+                collect_words(&seg, chunk_start, &mut words);
+                segments.push(TranscriptSegment {
+                    name: track.speaker.clone(),
+                    text: post.apply(&text),
+                    start_secs: chunk_start + seg.start_timestamp() as f64 / 100.0,
+                    end_secs: chunk_start + seg.end_timestamp() as f64 / 100.0,
+                    words,
+                    reviewed: false,
+                    lang: lang.map(str::to_string),
+                    original: None,
+                    edits: Vec::new(),
+                });
+            }
+        }
+    }
+
+    // Las intervenciones de todos, en el orden en que ocurrieron
+    segments.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+    let _ = tx.send(RetranscribeMessage::Progress(1.0));
+    let _ = tx.send(RetranscribeMessage::Done { model: model_name, segments });
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use anyhow::{anyhow, Result};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use crate::audio::audio_thread_main;
+use crate::data::{AudioMessage, InterlocutorProfile, TranscriptSegment, TranscriptionConfig};
+use crate::export::{save_local_session, save_minuta, save_speaker_minutas, MinutaDocument};
+use crate::actions;
+use crate::dataset;
+use crate::hooks::{self, HookEvent};
+use crate::i18n::tr;
+use crate::integrations;
+use crate::preroll::PreRollAudio;
+use crate::redaction::redact_document;
+use crate::settings::AppSettings;
+use crate::stats::compute_stats;
+use crate::stop::StopSignal;
+use crate::storage;
+
+// ── Salas ──────────────────────────────────────────────────────────────────
+//
+// Además de la sesión de la ventana se pueden llevar otras a la vez, por
+// ejemplo las de dos salas de reuniones en un mismo servidor. Cada sala tiene
+// sus perfiles, su modelo y su carpeta de salida, y su propio hilo de
+// captura; no tiene minuta editable: acumula las intervenciones y guarda la
+// minuta al detenerse, igual que la sesión principal.
+//
+// Nada impide que dos salas (o una sala y la ventana) abran el mismo
+// dispositivo; según el sistema eso funciona o da un error de la fuente.
+
+pub struct Room {
+    pub id: usize,
+    pub name: String,
+    pub model: String,
+    pub output_dir: String,
+    pub profiles: Vec<InterlocutorProfile>,
+    pub segments: Vec<TranscriptSegment>,
+    pub status: String,
+    rx: Option<Receiver<AudioMessage>>,
+    stop: Option<StopSignal>,
+    worker: Option<thread::JoinHandle<()>>,
+    /// Detenida, a la espera de que se cierren las fuentes para guardar.
+    saving_pending: bool,
+}
+
+impl Room {
+    pub fn new(id: usize, name: String, model: String, output_dir: String, profiles: Vec<InterlocutorProfile>) -> Self {
+        Self {
+            id,
+            name,
+            model,
+            output_dir,
+            profiles,
+            segments: Vec::new(),
+            status: tr!("live.idle"),
+            rx: None,
+            stop: None,
+            worker: None,
+            saving_pending: false,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.stop.is_some()
+    }
+
+    /// Detenida pero con fuentes aún cerrándose o la minuta sin guardar.
+    pub fn is_stopping(&self) -> bool {
+        self.stop.is_none() && (self.saving_pending || self.worker.as_ref().is_some_and(|h| !h.is_finished()))
+    }
+
+    pub fn start(&mut self, config: TranscriptionConfig) -> Result<()> {
+        if self.is_running() || self.is_stopping() {
+            return Ok(());
+        }
+        let active: Vec<InterlocutorProfile> = self.profiles.iter().filter(|p| p.is_active).cloned().collect();
+        if active.is_empty() {
+            return Err(anyhow!(tr!("live.no_active")));
+        }
+
+        let (tx, rx) = channel();
+        let stop = StopSignal::new();
+        let model = self.model.clone();
+        let signal = stop.clone();
+        let n = active.len();
+        self.worker = Some(thread::spawn(move || {
+            if let Err(e) = audio_thread_main(model, tx.clone(), signal, active, config, None, PreRollAudio::default()) {
+                let _ = tx.send(AudioMessage::Error(format!("{:?}", e)));
+            }
+        }));
+        self.rx = Some(rx);
+        self.stop = Some(stop);
+        self.segments.clear();
+        self.status = tr!("live.starting", n = n);
+        tracing::info!("Sala '{}' iniciada con {} fuentes", self.name, n);
+        Ok(())
+    }
+
+    /// Detiene la captura. Las fuentes aún entregan lo que tenían a medias
+    /// (hasta `SHUTDOWN_TIMEOUT`), así que la minuta se guarda en `poll`
+    /// cuando termina el hilo de captura.
+    pub fn stop(&mut self) {
+        let Some(stop) = self.stop.take() else { return };
+        stop.stop();
+        self.saving_pending = true;
+        self.status = tr!("live.stopped_saving");
+    }
+
+    /// Detiene la captura, espera a que se cierre y guarda la minuta.
+    /// Devuelve el hilo de guardado para esperarlo (al cerrar la aplicación).
+    pub fn stop_and_wait(&mut self, settings: &AppSettings) -> Option<thread::JoinHandle<()>> {
+        self.stop();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        self.receive();
+        self.save_pending(settings)
+    }
+
+    /// Guarda la minuta si estaba pendiente.
+    fn save_pending(&mut self, settings: &AppSettings) -> Option<thread::JoinHandle<()>> {
+        if !std::mem::take(&mut self.saving_pending) {
+            return None;
+        }
+        self.save(settings)
+    }
+
+    fn save(&self, settings: &AppSettings) -> Option<thread::JoinHandle<()>> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        let doc = if settings.normalize_numbers { self.document().normalize_numbers(None) } else { self.document() };
+        let original = doc.with_topics(None, &settings.topics);
+        let doc = redact_document(&original, &settings.redaction);
+        let settings = settings.clone();
+        let output_dir = self.output_dir.clone();
+        let name = self.name.replace(' ', "_");
+        Some(thread::spawn(move || {
+            if let Err(e) = save_local_session(&original, &settings, &output_dir, &name) {
+                tracing::error!("Error al guardar la sesión sin anonimizar de la sala: {:?}", e);
+            }
+            match save_minuta(&doc, &settings, &output_dir, &name) {
+                Ok(path) => {
+                    if settings.export_per_speaker {
+                        if let Err(e) = save_speaker_minutas(&doc, &settings, &output_dir, &name) {
+                            tracing::error!("Error al guardar los archivos por interlocutor de la sala: {:?}", e);
+                        }
+                    }
+                    hooks::run(&settings.hooks, HookEvent::SessionSaved { path: &path });
+                    actions::track(&original, &settings);
+                    storage::upload_in_background(&settings.active_preset().upload, &path, doc.recording.as_deref());
+                    dataset::export_in_background(&settings.dataset, &settings.redaction, &doc, &path);
+                    integrations::deliver(&doc, &path, &settings.integrations);
+                }
+                Err(e) => tracing::error!("Error al guardar la minuta de la sala: {:?}", e),
+            }
+        }))
+    }
+
+    /// Recoge los mensajes del hilo de captura; devuelve si ha cambiado algo.
+    /// Cuando termina el hilo (al detenerla o solo, por un error) guarda lo
+    /// que hubiera.
+    pub fn poll(&mut self, settings: &AppSettings) -> bool {
+        // Antes de vaciar la cola: si ya ha terminado no llegará nada más
+        let finished = self.worker.as_ref().is_none_or(|h| h.is_finished());
+        let mut changed = self.receive();
+        if finished && self.stop.take().is_some() {
+            self.saving_pending = true;
+        }
+        if finished && self.saving_pending {
+            self.save_pending(settings);
+            changed = true;
+        }
+        changed
+    }
+
+    /// Pasa a la sala lo que haya llegado del hilo de captura.
+    fn receive(&mut self) -> bool {
+        let Some(rx) = &self.rx else { return false };
+        let mut changed = false;
+        let mut new_segments = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            changed = true;
+            match msg {
+                AudioMessage::Status(s) => self.status = s,
+                AudioMessage::Segment { speaker, start_secs, end_secs, text, words, lang, .. } if !text.trim().is_empty() => {
+                    new_segments.push(TranscriptSegment {
+                        name: speaker, text, start_secs, end_secs, words,
+                        reviewed: false, lang, original: None, edits: Vec::new(),
+                    });
+                }
+                AudioMessage::StreamError { profile, message, .. } => {
+                    self.status = tr!("common.error", e = format!("{}: {}", profile, message));
+                }
+                AudioMessage::Error(e) => self.status = tr!("common.error", e = e),
+                AudioMessage::Stopped { .. } => self.status = tr!("audio.finished"),
+                _ => {}
+            }
+        }
+        for segment in new_segments {
+            // Las fuentes van a ritmos distintos: se ordena por inicio
+            let pos = self.segments.partition_point(|s| s.start_secs <= segment.start_secs);
+            self.segments.insert(pos, segment);
+        }
+        changed
+    }
+
+    pub fn document(&self) -> MinutaDocument {
+        let attendees = self.profiles.iter().filter(|p| p.is_active).map(|p| p.name.clone()).collect();
+        let body = self.segments.iter().map(|s| format!("{}\n", s.line())).collect();
+        MinutaDocument::new(self.name.clone(), attendees, body)
+            .with_segments(&self.segments)
+            .with_stats(compute_stats(&self.segments))
+    }
+}
+
+impl Drop for Room {
+    fn drop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            stop.stop();
+        }
+    }
+}
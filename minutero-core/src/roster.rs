@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use crate::data::{DeviceInfo, InterlocutorProfile, SourceType};
+
+// ── Participantes habituales ───────────────────────────────────────────────
+//
+// Lista persistente de quienes suelen estar en las reuniones, con su cargo y
+// el dispositivo que usan normalmente. Al configurar los interlocutores se
+// eligen de aquí (con autocompletado) en vez de reescribir "Interlocutor 0"
+// en cada reunión, y el cargo acompaña al nombre en los asistentes.
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Participant {
+    pub name: String,
+    pub role: String,
+    /// Dispositivo habitual, por nombre técnico (ver `devices`).
+    pub device: Option<String>,
+    /// Huella de voz (ver `speaker_id`); vacía si no se ha registrado.
+    pub voice: Vec<f32>,
+}
+
+impl Participant {
+    /// Participante con el nombre y el dispositivo de un interlocutor.
+    pub fn from_profile(profile: &InterlocutorProfile) -> Self {
+        let device = match profile.source_type {
+            SourceType::Input | SourceType::Output => profile.technical_name.clone(),
+            SourceType::Network | SourceType::Pipe | SourceType::Discord => None,
+        };
+        Self { name: profile.name.trim().to_string(), device, ..Self::default() }
+    }
+
+    /// Da al interlocutor su nombre y, si está conectado y es del mismo tipo
+    /// de fuente, su dispositivo habitual.
+    pub fn apply(&self, profile: &mut InterlocutorProfile, inputs: &[DeviceInfo], outputs: &[DeviceInfo]) {
+        profile.name = self.name.clone();
+        let devices = match profile.source_type {
+            SourceType::Input => inputs,
+            SourceType::Output => outputs,
+            SourceType::Network | SourceType::Pipe | SourceType::Discord => return,
+        };
+        let Some(device) = devices.iter().find(|d| d.technical_name.is_some() && d.technical_name == self.device) else {
+            return;
+        };
+        profile.device_id = device.id;
+        profile.technical_name = device.technical_name.clone();
+        profile.channel = None;
+    }
+
+    /// "Ana Pérez (Dirección)", o solo el nombre si no tiene cargo.
+    pub fn label(&self) -> String {
+        match self.role.trim() {
+            "" => self.name.clone(),
+            role => format!("{} ({})", self.name, role),
+        }
+    }
+}
+
+/// Participantes en los que alguna palabra del nombre empieza por `query`,
+/// sin mirar mayúsculas ni tildes. Con `query` vacío, todos.
+pub fn suggestions<'a>(roster: &'a [Participant], query: &str) -> Vec<&'a Participant> {
+    let query = fold(query.trim());
+    roster.iter()
+        .filter(|p| !p.name.trim().is_empty())
+        .filter(|p| {
+            let name = fold(&p.name);
+            name != query && (query.is_empty() || name.split_whitespace().any(|w| w.starts_with(&query)) || name.starts_with(&query))
+        })
+        .collect()
+}
+
+/// Guarda el interlocutor en la lista, o actualiza el dispositivo del
+/// participante que ya se llama así.
+pub fn remember(roster: &mut Vec<Participant>, profile: &InterlocutorProfile) {
+    let new = Participant::from_profile(profile);
+    if new.name.is_empty() {
+        return;
+    }
+    match roster.iter_mut().find(|p| fold(&p.name) == fold(&new.name)) {
+        Some(existing) => existing.device = new.device.or(existing.device.take()),
+        None => roster.push(new),
+    }
+}
+
+/// Asistentes con su cargo, si están en la lista.
+pub fn attendee_labels(roster: &[Participant], names: Vec<String>) -> Vec<String> {
+    names.into_iter()
+        .map(|name| match roster.iter().find(|p| fold(&p.name) == fold(&name)) {
+            Some(p) => p.label(),
+            None => name,
+        })
+        .collect()
+}
+
+/// Minúsculas y sin tildes, para comparar nombres.
+pub(crate) fn fold(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ñ' => 'n',
+            'ç' => 'c',
+            c => c,
+        })
+        .collect()
+}
@@ -0,0 +1,213 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::data::{ModelSource, Preset, SessionType, TranscriptionStyle, WhisperTuning, DEFAULT_REVIEW_THRESHOLD};
+use crate::capture_tools::CaptureTool;
+use crate::dataset::DatasetSettings;
+use crate::hooks::HookSettings;
+use crate::devices::DeviceSettings;
+use crate::export::{ExportFormat, ExportTemplates, PdfBranding, DEFAULT_FILENAME_PATTERN};
+use crate::encryption::EncryptionSettings;
+use crate::i18n::UiLanguage;
+use crate::integrations::IntegrationSettings;
+use crate::integrity::IntegritySettings;
+use crate::logging::LogLevel;
+use crate::multilingual::MultilingualSettings;
+use crate::preroll::PreRollSettings;
+use crate::recording::RecordingSettings;
+use crate::roster::Participant;
+use crate::split::SplitSettings;
+use crate::speaker_id::SpeakerIdSettings;
+use crate::redaction::RedactionSettings;
+use crate::resources::ResourceLimits;
+use crate::template::{default_summary_prompts, SummaryPrompt};
+use crate::topics::TopicSettings;
+use crate::notify::NotificationSettings;
+use crate::web::WebSettings;
+
+/// Archivo de preferencias, junto a `models/` en el directorio de trabajo.
+const SETTINGS_FILE: &str = "settings.json";
+
+pub const MIN_TRANSCRIPT_FONT_SIZE: f32 = 10.0;
+pub const MAX_TRANSCRIPT_FONT_SIZE: f32 = 40.0;
+
+// ── Tema de la interfaz ────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: &'static [Theme] = &[Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Theme::System => "settings.theme_system",
+            Theme::Light  => "settings.theme_light",
+            Theme::Dark   => "settings.theme_dark",
+        }
+    }
+}
+
+// ── Preferencias persistentes ──────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub ui_language: UiLanguage,
+    pub theme: Theme,
+    pub transcript_font_size: f32,
+    pub presets: Vec<Preset>,
+    pub active_preset: usize,
+    /// Lista de vigilancia: una palabra clave por línea.
+    pub alert_keywords: String,
+    pub alert_notifications: bool,
+    pub notifications: NotificationSettings,
+    pub tuning: WhisperTuning,
+    /// Idioma detectado por fragmento, ver `multilingual`.
+    pub multilingual: MultilingualSettings,
+    /// Modelo propio y modo sin conexión.
+    pub model_source: ModelSource,
+    /// Cargar el modelo al abrir la aplicación, ver `preload`.
+    pub preload_model: bool,
+    /// Memoria y modelos como mucho para las capturas, ver `resources`.
+    pub limits: ResourceLimits,
+    pub export_format: ExportFormat,
+    /// Además de la minuta, un archivo por interlocutor.
+    pub export_per_speaker: bool,
+    /// Números y fechas en cifras al exportar, ver `itn`.
+    pub normalize_numbers: bool,
+    /// Patrón del nombre de las minutas guardadas, ver `export::file_stem`.
+    pub filename_pattern: String,
+    pub pdf_branding: PdfBranding,
+    pub export_templates: ExportTemplates,
+    /// Anonimización de lo que se exporta o se envía a integraciones.
+    pub redaction: RedactionSettings,
+    /// Etiquetas de temas de las minutas guardadas, ver `topics`.
+    pub topics: TopicSettings,
+    /// Apunta las tareas de cada minuta guardada, ver `actions`.
+    pub track_actions: bool,
+    /// Plantillas de resumen y la elegida para la sesión, ver
+    /// `template::render_prompt`.
+    pub summary_prompts: Vec<SummaryPrompt>,
+    pub active_summary_prompt: usize,
+    /// Cadena de hashes y firma de las minutas exportadas.
+    pub integrity: IntegritySettings,
+    /// Cifrado AES-GCM de las minutas exportadas.
+    pub encryption: EncryptionSettings,
+    pub integrations: IntegrationSettings,
+    /// Órdenes propias al empezar, por intervención y al guardar.
+    pub hooks: HookSettings,
+    /// Página web con la minuta en vivo, ver `web`.
+    pub web: WebSettings,
+    /// Formato al copiar la minuta al portapapeles.
+    pub copy_as_markdown: bool,
+    /// Ofrecer iniciar la captura cuando Zoom, Teams, etc. empiezan a sonar.
+    pub detect_conferencing: bool,
+    /// Reunión o entrevista (minuta en pares pregunta/respuesta).
+    pub session_type: SessionType,
+    /// Literal o limpia; se aplica al empezar la captura.
+    pub transcription_style: TranscriptionStyle,
+    /// Probabilidad media por debajo de la cual un segmento va a la cola de
+    /// revisión.
+    pub review_threshold: f32,
+    /// Verbosidad del archivo de log y de la vista "Log".
+    pub log_level: LogLevel,
+    /// Grabación de toda la reunión en un solo archivo de audio.
+    pub recording: RecordingSettings,
+    /// Intervenciones recortadas de la grabación para afinar Whisper, ver
+    /// `dataset`.
+    pub dataset: DatasetSettings,
+    /// Últimos segundos de los dispositivos guardados antes de empezar.
+    pub preroll: PreRollSettings,
+    /// Minuta nueva cada N horas o tras un silencio largo.
+    pub split: SplitSettings,
+    /// Host de cpal (ALSA, JACK, WASAPI, ASIO…); `None` = automático.
+    pub audio_host: Option<String>,
+    /// Programa de captura en Linux (ver `capture_tools`); `None` = automático.
+    pub capture_tool: Option<CaptureTool>,
+    /// Alias de dispositivos e interlocutores guardados.
+    pub devices: DeviceSettings,
+    /// Participantes habituales, ver `roster`.
+    pub roster: Vec<Participant>,
+    /// Reconocimiento de su voz en fuentes compartidas.
+    pub speaker_id: SpeakerIdSettings,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            ui_language: UiLanguage::Es,
+            theme: Theme::System,
+            transcript_font_size: 14.0,
+            presets: vec![Preset::default()],
+            active_preset: 0,
+            alert_keywords: String::new(),
+            alert_notifications: true,
+            notifications: NotificationSettings::default(),
+            tuning: WhisperTuning::default(),
+            multilingual: MultilingualSettings::default(),
+            model_source: ModelSource::default(),
+            preload_model: false,
+            limits: ResourceLimits::default(),
+            export_format: ExportFormat::Markdown,
+            export_per_speaker: false,
+            normalize_numbers: false,
+            filename_pattern: DEFAULT_FILENAME_PATTERN.into(),
+            pdf_branding: PdfBranding::default(),
+            export_templates: ExportTemplates::default(),
+            redaction: RedactionSettings::default(),
+            topics: TopicSettings::default(),
+            track_actions: true,
+            summary_prompts: default_summary_prompts(),
+            active_summary_prompt: 0,
+            integrity: IntegritySettings::default(),
+            encryption: EncryptionSettings::default(),
+            integrations: IntegrationSettings::default(),
+            hooks: HookSettings::default(),
+            web: WebSettings::default(),
+            copy_as_markdown: false,
+            detect_conferencing: true,
+            session_type: SessionType::Meeting,
+            transcription_style: TranscriptionStyle::Clean,
+            review_threshold: DEFAULT_REVIEW_THRESHOLD,
+            log_level: LogLevel::Info,
+            recording: RecordingSettings::default(),
+            dataset: DatasetSettings::default(),
+            preroll: PreRollSettings::default(),
+            split: SplitSettings::default(),
+            audio_host: None,
+            capture_tool: None,
+            devices: DeviceSettings::default(),
+            roster: Vec::new(),
+            speaker_id: SpeakerIdSettings::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Carga las preferencias guardadas. Si el archivo no existe o está
+    /// corrupto se usan los valores por defecto.
+    pub fn load() -> Self {
+        std::fs::read_to_string(SETTINGS_FILE)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn active_preset(&self) -> Preset {
+        self.presets.get(self.active_preset).cloned().unwrap_or_default()
+    }
+
+    pub fn active_summary_prompt(&self) -> Option<&SummaryPrompt> {
+        self.summary_prompts.get(self.active_summary_prompt)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(SETTINGS_FILE, json)?;
+        Ok(())
+    }
+}
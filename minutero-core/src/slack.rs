@@ -0,0 +1,120 @@
+use anyhow::{Result, anyhow};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use crate::export::MinutaDocument;
+use crate::i18n::tr;
+use crate::integrations::overview;
+
+// ── Slack ──────────────────────────────────────────────────────────────────
+//
+// Dos modos:
+//   · Webhook entrante: solo texto (resumen + ruta local de la minuta).
+//   · Token de bot + ID de canal: además adjunta el archivo de la minuta.
+
+const API: &str = "https://slack.com/api";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlackConfig {
+    pub enabled: bool,
+    pub webhook_url: String,
+    /// Token `xoxb-...` con permisos `chat:write` y `files:write`.
+    pub bot_token: String,
+    /// ID del canal (`C0123...`); la API de archivos no acepta nombres.
+    pub channel_id: String,
+    pub attach_file: bool,
+}
+
+impl SlackConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && (self.uses_bot() || !self.webhook_url.trim().is_empty())
+    }
+
+    fn uses_bot(&self) -> bool {
+        !self.bot_token.trim().is_empty() && !self.channel_id.trim().is_empty()
+    }
+}
+
+pub async fn post_minuta(config: &SlackConfig, doc: &MinutaDocument, path: &Path) -> Result<()> {
+    let client = Client::new();
+    let text = overview(doc);
+
+    if !config.uses_bot() {
+        let text = format!("{}\n\n{}", text, tr!("integrations.saved_at", path = path.display()));
+        let response = client
+            .post(config.webhook_url.trim())
+            .header("Content-Type", "application/json")
+            .body(json!({ "text": text }).to_string())
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("webhook HTTP {}", response.status()));
+        }
+        return Ok(());
+    }
+
+    if config.attach_file {
+        upload_file(&client, config, path, &text).await
+    } else {
+        api_call(&client, config, "chat.postMessage", json!({
+            "channel": config.channel_id.trim(),
+            "text": text,
+        })).await.map(|_| ())
+    }
+}
+
+/// Subida en tres pasos que exige la API actual de Slack.
+async fn upload_file(client: &Client, config: &SlackConfig, path: &Path, comment: &str) -> Result<()> {
+    let data = std::fs::read(path)?;
+    let filename = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    let response = client
+        .post(format!("{}/files.getUploadURLExternal", API))
+        .bearer_auth(config.bot_token.trim())
+        .form(&[("filename", filename.as_str()), ("length", &data.len().to_string())])
+        .send()
+        .await?;
+    let ticket: Value = serde_json::from_str(&response.text().await?)?;
+    check_ok(&ticket)?;
+    let upload_url = ticket["upload_url"].as_str().ok_or_else(|| anyhow!("respuesta sin upload_url"))?;
+    let file_id = ticket["file_id"].as_str().ok_or_else(|| anyhow!("respuesta sin file_id"))?;
+
+    let response = client.post(upload_url).body(data).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("subida HTTP {}", response.status()));
+    }
+
+    api_call(client, config, "files.completeUploadExternal", json!({
+        "files": [{ "id": file_id, "title": doc_title(&filename) }],
+        "channel_id": config.channel_id.trim(),
+        "initial_comment": comment,
+    })).await.map(|_| ())
+}
+
+async fn api_call(client: &Client, config: &SlackConfig, method: &str, body: Value) -> Result<Value> {
+    let response = client
+        .post(format!("{}/{}", API, method))
+        .bearer_auth(config.bot_token.trim())
+        .header("Content-Type", "application/json; charset=utf-8")
+        .body(body.to_string())
+        .send()
+        .await?;
+    let value: Value = serde_json::from_str(&response.text().await?)?;
+    check_ok(&value)?;
+    Ok(value)
+}
+
+/// Slack responde 200 incluso en error; el resultado real va en `ok`.
+fn check_ok(response: &Value) -> Result<()> {
+    if response["ok"].as_bool() == Some(true) {
+        Ok(())
+    } else {
+        Err(anyhow!("API de Slack: {}", response["error"].as_str().unwrap_or("error desconocido")))
+    }
+}
+
+fn doc_title(filename: &str) -> &str {
+    filename.rsplit_once('.').map_or(filename, |(stem, _)| stem)
+}
@@ -0,0 +1,222 @@
+use anyhow::{anyhow, Result};
+use realfft::RealFftPlanner;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+use crate::data::{InterlocutorProfile, TranscriptionConfig, WHISPER_SAMPLE_RATE};
+use crate::device_test::DeviceTest;
+use crate::i18n::tr;
+use crate::roster::Participant;
+
+// ── Identificación de interlocutores por la voz ───────────────────────────
+//
+// Cuando varias personas llegan por la misma fuente (la salida de una
+// videollamada, el micrófono de una sala) todas salen con el nombre del
+// perfil. En los perfiles marcados, cada intervención se compara con las
+// voces registradas de los participantes habituales y, si se parece lo
+// bastante a una, lleva su nombre.
+//
+// La huella de voz es la media y la desviación de los coeficientes MFCC de
+// los tramos con voz. No necesita modelos ni dependencias aparte y separa
+// bien a unas pocas personas de voces distintas, aunque no tanto como un
+// modelo neuronal. Las huellas se comparan centradas en la media de las
+// registradas, así que hacen falta al menos dos. Cada voz se registra
+// leyendo un párrafo en voz alta (ver `Enrollment`).
+
+const FRAME_LEN: usize = 400; // 25 ms a 16 kHz
+const FRAME_HOP: usize = 160; // 10 ms
+const FFT_LEN: usize = 512;
+const MEL_BANDS: usize = 40;
+/// c1..c19; c0 es la energía y depende del volumen, no de la voz.
+const CEPSTRA: usize = 20;
+const LIFTER: f32 = 22.0;
+const MIN_HZ: f32 = 60.0;
+const MAX_HZ: f32 = 7_600.0;
+/// Tramos por debajo de esta fracción de la energía media: silencio.
+const VOICED_ENERGY: f32 = 0.3;
+/// Voz mínima para calcular una huella.
+pub const MIN_VOICED_SECS: f32 = 1.0;
+/// Segundos que se graban al registrar una voz.
+pub const ENROLL_SECS: u32 = 20;
+/// Grabación mínima al registrar: con menos, la huella sale poco fiable.
+const MIN_ENROLL_SECS: f32 = 5.0;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeakerIdSettings {
+    /// Parecido mínimo (coseno, de -1 a 1) para poner el nombre.
+    pub threshold: f32,
+}
+
+impl Default for SpeakerIdSettings {
+    fn default() -> Self {
+        Self { threshold: 0.5 }
+    }
+}
+
+/// Huella de voz de `samples` (16 kHz mono). `None` si hay menos de
+/// `MIN_VOICED_SECS` de voz.
+pub fn voiceprint(samples: &[f32]) -> Option<Vec<f32>> {
+    if samples.len() < FRAME_LEN {
+        return None;
+    }
+    let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_LEN);
+    let mut input = fft.make_input_vec();
+    let mut spectrum = fft.make_output_vec();
+    let filters = mel_filters();
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| 0.54 - 0.46 * (2.0 * PI * i as f32 / (FRAME_LEN - 1) as f32).cos())
+        .collect();
+
+    // (energía, cepstro) de cada tramo
+    let mut frames: Vec<(f32, Vec<f32>)> = Vec::new();
+    for start in (0..=samples.len() - FRAME_LEN).step_by(FRAME_HOP) {
+        let frame = &samples[start..start + FRAME_LEN];
+        let energy = frame.iter().map(|s| s * s).sum::<f32>() / FRAME_LEN as f32;
+        input.fill(0.0);
+        for (i, (x, w)) in input.iter_mut().zip(&window).enumerate() {
+            // Preénfasis
+            let previous = if i > 0 { frame[i - 1] } else { 0.0 };
+            *x = (frame[i] - 0.97 * previous) * w;
+        }
+        fft.process(&mut input, &mut spectrum).ok()?;
+        let log_mel: Vec<f32> = filters.iter()
+            .map(|band| band.iter().map(|&(bin, w)| spectrum[bin].norm_sqr() * w).sum::<f32>().max(1e-10).ln())
+            .collect();
+        let cepstrum = (1..CEPSTRA)
+            .map(|k| {
+                log_mel.iter().enumerate()
+                    .map(|(m, e)| e * (PI * k as f32 * (m as f32 + 0.5) / MEL_BANDS as f32).cos())
+                    .sum()
+            })
+            .collect();
+        frames.push((energy, cepstrum));
+    }
+
+    let mean_energy = frames.iter().map(|f| f.0).sum::<f32>() / frames.len().max(1) as f32;
+    let voiced: Vec<&[f32]> = frames.iter()
+        .filter(|f| f.0 > mean_energy * VOICED_ENERGY)
+        .map(|f| f.1.as_slice())
+        .collect();
+    if (voiced.len() * FRAME_HOP) as f32 / (WHISPER_SAMPLE_RATE as f32) < MIN_VOICED_SECS {
+        return None;
+    }
+
+    let dims = CEPSTRA - 1;
+    let n = voiced.len() as f32;
+    let mean: Vec<f32> = (0..dims).map(|d| voiced.iter().map(|c| c[d]).sum::<f32>() / n).collect();
+    let deviation: Vec<f32> = (0..dims)
+        .map(|d| (voiced.iter().map(|c| (c[d] - mean[d]).powi(2)).sum::<f32>() / n).sqrt())
+        .collect();
+    // El lifter da a los coeficientes altos, más pequeños, un peso parecido
+    let lifter = |d: usize| 1.0 + LIFTER / 2.0 * (PI * (d + 1) as f32 / LIFTER).sin();
+    Some(mean.iter().chain(&deviation).enumerate().map(|(i, v)| v * lifter(i % dims)).collect())
+}
+
+/// Filtros triangulares en escala mel: (bin, peso) de cada banda.
+fn mel_filters() -> Vec<Vec<(usize, f32)>> {
+    let mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+    let bin_hz = WHISPER_SAMPLE_RATE as f32 / FFT_LEN as f32;
+    let edges: Vec<f32> = (0..MEL_BANDS + 2)
+        .map(|i| hz(mel(MIN_HZ) + (mel(MAX_HZ) - mel(MIN_HZ)) * i as f32 / (MEL_BANDS + 1) as f32))
+        .collect();
+    edges.windows(3)
+        .map(|e| {
+            (0..=FFT_LEN / 2)
+                .filter_map(|bin| {
+                    let f = bin as f32 * bin_hz;
+                    let w = if f < e[1] { (f - e[0]) / (e[1] - e[0]) } else { (e[2] - f) / (e[2] - e[1]) };
+                    (w > 0.0).then_some((bin, w))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    dot / (norm(a) * norm(b)).max(1e-10)
+}
+
+/// Voces registradas, listas para comparar.
+#[derive(Clone, Debug, Default)]
+pub struct VoiceMatcher {
+    /// (nombre, huella ya centrada)
+    voices: Vec<(String, Vec<f32>)>,
+    center: Vec<f32>,
+    threshold: f32,
+}
+
+impl VoiceMatcher {
+    pub fn new(roster: &[Participant], settings: &SpeakerIdSettings) -> Self {
+        let enrolled: Vec<&Participant> = roster.iter()
+            .filter(|p| !p.name.trim().is_empty() && !p.voice.is_empty())
+            .collect();
+        let dims = enrolled.first().map_or(0, |p| p.voice.len());
+        let enrolled: Vec<&Participant> = enrolled.into_iter().filter(|p| p.voice.len() == dims).collect();
+        if enrolled.len() < 2 {
+            return Self::default();
+        }
+        let center: Vec<f32> = (0..dims)
+            .map(|d| enrolled.iter().map(|p| p.voice[d]).sum::<f32>() / enrolled.len() as f32)
+            .collect();
+        let voices = enrolled.iter()
+            .map(|p| (p.name.clone(), p.voice.iter().zip(&center).map(|(v, c)| v - c).collect()))
+            .collect();
+        Self { voices, center, threshold: settings.threshold }
+    }
+
+    /// Sin al menos dos voces registradas no se identifica a nadie.
+    pub fn is_empty(&self) -> bool {
+        self.voices.is_empty()
+    }
+
+    /// Nombre del participante que habla en `samples`, si se parece lo
+    /// bastante a alguno.
+    pub fn identify(&self, samples: &[f32]) -> Option<&str> {
+        if self.is_empty() {
+            return None;
+        }
+        let print = voiceprint(samples)?;
+        let print: Vec<f32> = print.iter().zip(&self.center).map(|(v, c)| v - c).collect();
+        let (name, score) = self.voices.iter()
+            .map(|(name, voice)| (name, cosine(&print, voice)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+        tracing::debug!("Voz más parecida: {} ({:.2})", name, score);
+        (score >= self.threshold).then_some(name.as_str())
+    }
+}
+
+/// Registro de la voz de un participante: lee un párrafo en voz alta por
+/// el micrófono y de la grabación sale su huella.
+pub struct Enrollment {
+    recording: DeviceTest,
+}
+
+impl Enrollment {
+    pub fn start(profile: &InterlocutorProfile, config: TranscriptionConfig) -> Self {
+        tracing::info!("Registrando la voz de '{}'", profile.name);
+        Self { recording: DeviceTest::record(profile, config, ENROLL_SECS) }
+    }
+
+    /// Parte grabada, de 0 a 1.
+    pub fn progress(&self) -> f32 {
+        self.recording.progress()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.recording.is_done()
+    }
+
+    /// Cierra el micrófono y calcula la huella. Falla si no se pudo grabar o
+    /// si apenas hay voz.
+    pub fn finish(self) -> Result<Vec<f32>> {
+        let samples = self.recording.finish()?;
+        let recorded = samples.len() as f32 / WHISPER_SAMPLE_RATE as f32;
+        match voiceprint(&samples) {
+            Some(print) if recorded >= MIN_ENROLL_SECS => Ok(print),
+            _ => Err(anyhow!(tr!("enroll.too_little_voice"))),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::{anyhow, Result};
+use std::io::{ErrorKind, Write};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use crate::i18n::{current_language, tr, UiLanguage};
+
+// ── Lectura en voz alta ────────────────────────────────────────────────────
+//
+// Para escuchar los datos de la sesión mientras se recoge, o por
+// accesibilidad. Igual que con las notificaciones, se delega en el
+// sintetizador del sistema en vez de enlazar uno: espeak-ng (o espeak) y
+// speech-dispatcher en Linux, `say` en macOS y System.Speech en Windows. El
+// texto va por stdin, así que no hay límite de longitud ni que escapar nada.
+
+/// Lectura en curso. Se corta al pararla o al soltarla.
+#[derive(Default)]
+pub struct Reader {
+    child: Option<(Child, &'static str)>,
+}
+
+impl Reader {
+    /// Empieza a leer `text` con una voz del idioma de la interfaz. Corta lo
+    /// que estuviera leyendo.
+    pub fn speak(&mut self, text: &str) -> Result<()> {
+        self.stop();
+        let lang = match current_language() {
+            UiLanguage::Es => "es",
+            UiLanguage::En => "en",
+        };
+        for (program, mut cmd) in commands(lang) {
+            cmd.stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::null());
+            match cmd.spawn() {
+                Ok(mut child) => {
+                    if let Some(mut stdin) = child.stdin.take() {
+                        // En otro hilo: un texto largo llenaría la tubería
+                        let text = text.to_string();
+                        thread::spawn(move || { let _ = stdin.write_all(text.as_bytes()); });
+                    }
+                    tracing::info!("Leyendo en voz alta con {}", program);
+                    self.child = Some((child, program));
+                    return Ok(());
+                }
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(anyhow!(tr!("speech.error", program = program, e = e))),
+            }
+        }
+        Err(anyhow!(tr!("speech.no_engine")))
+    }
+
+    pub fn is_speaking(&mut self) -> bool {
+        match &mut self.child {
+            Some((child, _)) => matches!(child.try_wait(), Ok(None)),
+            None => false,
+        }
+    }
+
+    pub fn stop(&mut self) {
+        let Some((mut child, program)) = self.child.take() else { return };
+        let _ = child.kill();
+        let _ = child.wait();
+        // spd-say solo encarga la lectura; quien habla es speech-dispatcher
+        if program == "spd-say" {
+            let _ = Command::new("spd-say").arg("--cancel").stderr(Stdio::null()).status();
+        }
+    }
+}
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Sintetizadores que se prueban, en orden, con el programa de cada uno.
+#[cfg(target_os = "linux")]
+fn commands(lang: &str) -> Vec<(&'static str, Command)> {
+    let mut espeak_ng = Command::new("espeak-ng");
+    espeak_ng.args(["-v", lang, "--stdin"]);
+    let mut espeak = Command::new("espeak");
+    espeak.args(["-v", lang, "--stdin"]);
+    let mut spd_say = Command::new("spd-say");
+    spd_say.args(["--wait", "--pipe-mode", "--language", lang]);
+    vec![("espeak-ng", espeak_ng), ("espeak", espeak), ("spd-say", spd_say)]
+}
+
+#[cfg(target_os = "macos")]
+fn commands(_lang: &str) -> Vec<(&'static str, Command)> {
+    // Sin texto ni archivo, `say` lee stdin con la voz del sistema
+    vec![("say", Command::new("say"))]
+}
+
+#[cfg(target_os = "windows")]
+fn commands(lang: &str) -> Vec<(&'static str, Command)> {
+    let culture = if lang == "es" { "es-ES" } else { "en-US" };
+    let script = format!(
+        "[Console]::InputEncoding = [Text.Encoding]::UTF8; \
+         $text = [Console]::In.ReadToEnd(); \
+         Add-Type -AssemblyName System.Speech; \
+         $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+         try {{ $s.SelectVoiceByHints('NotSet', 'NotSet', 0, [Globalization.CultureInfo]'{}') }} catch {{}}; \
+         $s.Speak($text)",
+        culture,
+    );
+    let mut cmd = Command::new("powershell");
+    cmd.args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", &script]);
+    vec![("powershell", cmd)]
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn commands(_lang: &str) -> Vec<(&'static str, Command)> {
+    Vec::new()
+}
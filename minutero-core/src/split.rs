@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// ── Minutas por bloques ────────────────────────────────────────────────────
+//
+// En jornadas largas (talleres de todo el día) una sola minuta se hace
+// inmanejable. Con esta opción la captura sigue sin cortes, pero la minuta
+// se guarda y se empieza una nueva cada cierto tiempo o tras un rato sin
+// que nadie hable (una pausa para comer). Cada archivo lleva en el título
+// el número de bloque.
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SplitSettings {
+    /// Horas por bloque; 0 = sin límite.
+    pub every_hours: f32,
+    /// Minutos de silencio seguidos que cierran el bloque; 0 = nunca.
+    pub after_silence_mins: f32,
+}
+
+/// Por qué se cierra un bloque.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitReason {
+    Duration,
+    Silence,
+}
+
+impl SplitSettings {
+    pub fn is_enabled(&self) -> bool {
+        self.every_hours > 0.0 || self.after_silence_mins > 0.0
+    }
+
+    /// Si toca cerrar el bloque que empezó en `block_started`, cuya última
+    /// intervención llegó en `last_segment` (`None` = bloque vacío, que no
+    /// se cierra nunca).
+    pub fn due(&self, block_started: Instant, last_segment: Option<Instant>) -> Option<SplitReason> {
+        let last_segment = last_segment?;
+        if self.every_hours > 0.0 && block_started.elapsed() >= Duration::from_secs_f32(self.every_hours * 3600.0) {
+            return Some(SplitReason::Duration);
+        }
+        if self.after_silence_mins > 0.0 && last_segment.elapsed() >= Duration::from_secs_f32(self.after_silence_mins * 60.0) {
+            return Some(SplitReason::Silence);
+        }
+        None
+    }
+}
@@ -0,0 +1,95 @@
+use crate::data::TranscriptSegment;
+use crate::i18n::tr;
+
+// ── Estadísticas de participación ──────────────────────────────────────────
+//
+// Se calculan a partir de los tiempos de cada segmento. Son aproximadas:
+// Whisper solo marca dónde hay texto, no cada pausa dentro de una frase.
+
+#[derive(Clone, Debug, Default)]
+pub struct SpeakerStats {
+    pub name: String,
+    pub speaking_secs: f64,
+    pub words: usize,
+    pub segments: usize,
+    /// Intervención ininterrumpida más larga (segmentos seguidos del mismo interlocutor).
+    pub longest_monologue_secs: f64,
+    /// Veces que empezó a hablar antes de que terminara otro interlocutor.
+    pub interruptions: usize,
+}
+
+/// Estadísticas por interlocutor, en orden de primera intervención.
+pub fn compute_stats(segments: &[TranscriptSegment]) -> Vec<SpeakerStats> {
+    let mut ordered: Vec<&TranscriptSegment> = segments.iter().filter(|s| !s.is_pause()).collect();
+    ordered.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+
+    let mut stats: Vec<SpeakerStats> = Vec::new();
+    let mut previous: Option<&TranscriptSegment> = None;
+    // (interlocutor, inicio) de la intervención en curso
+    let mut run: Option<(String, f64)> = None;
+
+    for seg in ordered {
+        let idx = match stats.iter().position(|s| s.name == seg.name) {
+            Some(i) => i,
+            None => {
+                stats.push(SpeakerStats { name: seg.name.clone(), ..Default::default() });
+                stats.len() - 1
+            }
+        };
+
+        let entry = &mut stats[idx];
+        entry.speaking_secs += (seg.end_secs - seg.start_secs).max(0.0);
+        entry.words += seg.text.split_whitespace().count();
+        entry.segments += 1;
+
+        if let Some(prev) = previous {
+            if prev.name != seg.name && seg.start_secs < prev.end_secs {
+                entry.interruptions += 1;
+            }
+        }
+
+        let run_start = match &run {
+            Some((name, start)) if *name == seg.name => *start,
+            _ => seg.start_secs,
+        };
+        entry.longest_monologue_secs = entry.longest_monologue_secs.max(seg.end_secs - run_start);
+        run = Some((seg.name.clone(), run_start));
+
+        previous = Some(seg);
+    }
+
+    stats
+}
+
+/// Tabla Markdown que se añade al final de la minuta exportada.
+pub fn markdown_table(stats: &[SpeakerStats]) -> String {
+    if stats.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!(
+        "\n\n## {}\n\n| {} | {} | {} | {} | {} |\n|---|---|---|---|---|\n",
+        tr!("stats.heading"),
+        tr!("stats.speaker"),
+        tr!("stats.speaking_time"),
+        tr!("stats.words"),
+        tr!("stats.longest"),
+        tr!("stats.interruptions"),
+    );
+    for s in stats {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            s.name,
+            format_duration(s.speaking_secs),
+            s.words,
+            format_duration(s.longest_monologue_secs),
+            s.interruptions,
+        ));
+    }
+    out
+}
+
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
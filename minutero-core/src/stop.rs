@@ -0,0 +1,85 @@
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+// ── Orden de parar ─────────────────────────────────────────────────────────
+//
+// Una captura tiene muchos hilos: el coordinador, uno por dispositivo, uno
+// por perfil y los lectores de los procesos de captura. Con un `AtomicBool`
+// cada uno lo miraba cada 50-100 ms entre esperas, así que parar tardaba lo
+// que la espera más larga y un `read` bloqueado no paraba nunca. Ahora la
+// orden despierta en el acto a quien espera: en una cola (con `select!` sobre
+// `channel`), en una pausa (`sleep`) o en código async (`cancelled`).
+
+/// Orden de parar compartida por los hilos de una captura (o de una
+/// re-transcripción). Se clona barato; todas las copias paran juntas.
+#[derive(Clone, Default)]
+pub struct StopSignal {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    stopped: AtomicBool,
+    /// Nadie envía nada: al parar se suelta y `rx` queda desconectado.
+    tx: Mutex<Option<Sender<()>>>,
+    rx: Receiver<()>,
+    notify: Notify,
+}
+
+impl Default for Inner {
+    fn default() -> Self {
+        let (tx, rx) = crossbeam_channel::bounded(0);
+        Self { stopped: AtomicBool::new(false), tx: Mutex::new(Some(tx)), rx, notify: Notify::new() }
+    }
+}
+
+impl StopSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pide parar y despierta a todos los que esperan. Se puede llamar más
+    /// de una vez.
+    pub fn stop(&self) {
+        if !self.inner.stopped.swap(true, Ordering::SeqCst) {
+            self.inner.tx.lock().unwrap_or_else(|e| e.into_inner()).take();
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.inner.stopped.load(Ordering::SeqCst)
+    }
+
+    /// Cola que se cierra al parar, para esperar a la vez que otra con
+    /// `crossbeam_channel::select!`: el brazo `recv(stop.channel())` salta
+    /// al parar.
+    pub fn channel(&self) -> &Receiver<()> {
+        &self.inner.rx
+    }
+
+    /// Espera hasta que se pida parar.
+    pub fn wait(&self) {
+        let _ = self.inner.rx.recv();
+    }
+
+    /// Pausa de como mucho `timeout` que se corta al parar. Devuelve `true`
+    /// si se ha pedido parar.
+    pub fn sleep(&self, timeout: Duration) -> bool {
+        !matches!(self.inner.rx.recv_timeout(timeout), Err(RecvTimeoutError::Timeout))
+    }
+
+    /// Termina al pedir parar, para `tokio::select!`.
+    pub async fn cancelled(&self) {
+        let notified = self.inner.notify.notified();
+        tokio::pin!(notified);
+        // Apuntarse antes de mirar, para no perder un aviso entre medias
+        notified.as_mut().enable();
+        if self.is_stopped() {
+            return;
+        }
+        notified.await;
+    }
+}
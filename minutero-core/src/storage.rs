@@ -0,0 +1,311 @@
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use crate::i18n::tr;
+
+// ── Copia en almacenamiento remoto ─────────────────────────────────────────
+//
+// Al guardar, la minuta (y la grabación, si se quiere) se sube a un bucket
+// S3 o compatible (MinIO, Wasabi, R2…), a una carpeta WebDAV o a Nextcloud,
+// que es WebDAV con la ruta de la cuenta ya puesta. Cada plantilla de sesión
+// tiene su destino: las minutas de clientes a un sitio y las internas a otro.
+// Va en su propio hilo y reintenta con espera creciente si el servidor no
+// responde; la minuta local ya está guardada pase lo que pase. Cómo acaba
+// cada archivo queda en `take_outcomes`, para que la interfaz lo muestre.
+
+/// Intentos por archivo antes de darlo por perdido.
+const MAX_ATTEMPTS: u32 = 4;
+/// Espera antes del primer reintento; se dobla en cada uno.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Cómo acabó la subida de un archivo, con el mensaje para el usuario.
+#[derive(Clone, Debug)]
+pub enum UploadOutcome {
+    Uploaded(String),
+    Failed(String),
+}
+
+/// (archivos por subir, resultados que la interfaz aún no ha recogido).
+static UPLOADS: Mutex<(usize, Vec<UploadOutcome>)> = Mutex::new((0, Vec::new()));
+
+/// Si queda alguna subida en marcha.
+pub fn uploads_pending() -> bool {
+    UPLOADS.lock().unwrap().0 > 0
+}
+
+/// Resultados de las subidas terminadas desde la última llamada.
+pub fn take_outcomes() -> Vec<UploadOutcome> {
+    std::mem::take(&mut UPLOADS.lock().unwrap().1)
+}
+
+fn finish_upload(outcome: UploadOutcome) {
+    let mut uploads = UPLOADS.lock().unwrap();
+    uploads.0 = uploads.0.saturating_sub(1);
+    uploads.1.push(outcome);
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageKind {
+    #[default]
+    S3,
+    WebDav,
+    Nextcloud,
+}
+
+impl StorageKind {
+    pub const ALL: &'static [StorageKind] = &[StorageKind::S3, StorageKind::WebDav, StorageKind::Nextcloud];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StorageKind::S3 => "S3",
+            StorageKind::WebDav => "WebDAV",
+            StorageKind::Nextcloud => "Nextcloud",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UploadSettings {
+    pub enabled: bool,
+    pub kind: StorageKind,
+    /// S3: el endpoint (`https://s3.eu-west-1.amazonaws.com`). WebDAV: la URL
+    /// de la carpeta. Nextcloud: la del servidor.
+    pub url: String,
+    /// Solo S3.
+    pub bucket: String,
+    pub region: String,
+    /// Carpeta (o prefijo en S3) dentro del destino; vacía = la raíz.
+    pub folder: String,
+    /// Clave de acceso en S3; usuario en WebDAV y Nextcloud.
+    pub username: String,
+    /// Clave secreta en S3; contraseña (mejor de aplicación) en los demás.
+    pub password: String,
+    pub include_audio: bool,
+}
+
+impl UploadSettings {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.url.trim().is_empty() && (self.kind != StorageKind::S3 || !self.bucket.trim().is_empty())
+    }
+
+    /// Carpeta de destino como segmentos de ruta.
+    fn folder_segments(&self) -> Vec<&str> {
+        self.folder.split('/').map(str::trim).filter(|s| !s.is_empty()).collect()
+    }
+}
+
+/// Sube en segundo plano la minuta de `path` y, si se pide, la grabación
+/// `recording` que está a su lado.
+pub fn upload_in_background(settings: &UploadSettings, path: &Path, recording: Option<&str>) {
+    if !settings.is_enabled() {
+        return;
+    }
+    let mut files = vec![path.to_path_buf()];
+    if settings.include_audio {
+        if let (Some(dir), Some(recording)) = (path.parent(), recording) {
+            files.push(dir.join(recording));
+        }
+    }
+    files.retain(|f| f.exists());
+    UPLOADS.lock().unwrap().0 += files.len();
+    let settings = settings.clone();
+    std::thread::spawn(move || {
+        let rt = match Runtime::new() {
+            Ok(rt) => rt,
+            Err(e) => {
+                tracing::error!("Subida: no se pudo crear el runtime: {:?}", e);
+                for file in &files {
+                    finish_upload(UploadOutcome::Failed(tr!("storage.failed", file = file.display(), e = e)));
+                }
+                return;
+            }
+        };
+        for file in &files {
+            finish_upload(rt.block_on(upload_with_retry(&settings, file)));
+        }
+    });
+}
+
+async fn upload_with_retry(settings: &UploadSettings, file: &Path) -> UploadOutcome {
+    let client = Client::new();
+    let mut delay = RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match upload(&client, settings, file).await {
+            Ok(url) => {
+                let message = tr!("storage.uploaded", file = file.display(), url = url);
+                tracing::info!("{}", message);
+                return UploadOutcome::Uploaded(message);
+            }
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                tracing::warn!("Subida de {} ({}/{}): {:?}; se reintenta en {:?}", file.display(), attempt, MAX_ATTEMPTS, e, delay);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => {
+                let message = tr!("storage.failed", file = file.display(), e = e);
+                tracing::error!("{}", message);
+                return UploadOutcome::Failed(message);
+            }
+        }
+    }
+}
+
+async fn upload(client: &Client, settings: &UploadSettings, file: &Path) -> Result<Url> {
+    let data = std::fs::read(file)?;
+    let name = file.file_name().map(|n| n.to_string_lossy().to_string()).ok_or_else(|| anyhow!("archivo sin nombre"))?;
+    match settings.kind {
+        StorageKind::S3 => put_s3(client, settings, &name, data).await,
+        StorageKind::WebDav => put_webdav(client, settings, &[], &name, data).await,
+        StorageKind::Nextcloud => {
+            let account = ["remote.php", "dav", "files", settings.username.trim()];
+            put_webdav(client, settings, &account, &name, data).await
+        }
+    }
+}
+
+fn base_url(settings: &UploadSettings, segments: &[&str]) -> Result<Url> {
+    let mut url = Url::parse(settings.url.trim())?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("URL no válida: {}", settings.url))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+async fn check(response: reqwest::Response) -> Result<()> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(anyhow!("HTTP {}: {}", status, response.text().await.unwrap_or_default().trim()))
+    }
+}
+
+// ── WebDAV / Nextcloud ─────────────────────────────────────────────────────
+
+async fn put_webdav(client: &Client, settings: &UploadSettings, prefix: &[&str], name: &str, data: Vec<u8>) -> Result<Url> {
+    let mkcol = Method::from_bytes(b"MKCOL")?;
+    let mut dir: Vec<&str> = prefix.to_vec();
+    // Crea la carpeta nivel a nivel; si ya existe el servidor responde 405
+    for segment in settings.folder_segments() {
+        dir.push(segment);
+        let response = client
+            .request(mkcol.clone(), base_url(settings, &dir)?)
+            .basic_auth(settings.username.trim(), Some(&settings.password))
+            .send()
+            .await?;
+        if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+            check(response).await?;
+        }
+    }
+    dir.push(name);
+    let url = base_url(settings, &dir)?;
+    let response = client
+        .put(url.clone())
+        .basic_auth(settings.username.trim(), Some(&settings.password))
+        .body(data)
+        .send()
+        .await?;
+    check(response).await?;
+    Ok(url)
+}
+
+// ── S3 (firma AWS Signature Version 4) ─────────────────────────────────────
+
+async fn put_s3(client: &Client, settings: &UploadSettings, name: &str, data: Vec<u8>) -> Result<Url> {
+    let region = match settings.region.trim() {
+        "" => "us-east-1",
+        region => region,
+    };
+    let mut key = vec![settings.bucket.trim()];
+    key.extend(settings.folder_segments());
+    key.push(name);
+    // Direccionamiento por ruta: lo admiten AWS y todos los compatibles.
+    // La ruta se codifica como pide SigV4 y se envía tal cual se firma.
+    let mut url = base_url(settings, &[])?;
+    let path = s3_path(&url, &key);
+    url.set_path(&path);
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return Err(anyhow!("URL sin servidor: {}", settings.url)),
+    };
+
+    let now = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let day = now.format("%Y%m%d").to_string();
+    let payload_hash = hex(&Sha256::digest(&data));
+    let canonical = format!(
+        "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+        path, host, payload_hash, amz_date, payload_hash,
+    );
+    let scope = format!("{}/{}/s3/aws4_request", day, region);
+    let to_sign = format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", amz_date, scope, hex(&Sha256::digest(canonical.as_bytes())));
+    let mut key = hmac(format!("AWS4{}", settings.password.trim()).as_bytes(), day.as_bytes());
+    for part in [region, "s3", "aws4_request"] {
+        key = hmac(&key, part.as_bytes());
+    }
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+        settings.username.trim(), scope, hex(&hmac(&key, to_sign.as_bytes())),
+    );
+
+    let response = client
+        .put(url.clone())
+        .header("x-amz-date", amz_date)
+        .header("x-amz-content-sha256", payload_hash)
+        .header("Authorization", authorization)
+        .body(data)
+        .send()
+        .await?;
+    check(response).await?;
+    Ok(url)
+}
+
+/// Ruta canónica de SigV4: la del endpoint más `key`, con cada byte que no
+/// sea `A-Z a-z 0-9 - _ . ~` como `%XX` (RFC 3986). `Url` deja sin escapar
+/// `( ) ! * '` y otros, y la firma no coincidiría.
+fn s3_path(base: &Url, key: &[&str]) -> String {
+    // Los segmentos del endpoint ya vienen escapados por `Url`
+    let base = base.path_segments().into_iter().flatten().filter(|s| !s.is_empty()).map(|s| uri_encode(s, true));
+    let key = key.iter().map(|s| uri_encode(s, false));
+    base.chain(key).map(|segment| format!("/{}", segment)).collect()
+}
+
+/// Codifica un segmento para SigV4. Con `escaped`, los `%XX` que ya traiga
+/// se dejan como están.
+fn uri_encode(segment: &str, escaped: bool) -> String {
+    let bytes = segment.as_bytes();
+    let mut out = String::with_capacity(bytes.len());
+    for (i, &b) in bytes.iter().enumerate() {
+        let already = escaped && b == b'%'
+            && bytes.get(i + 1..i + 3).is_some_and(|h| h.iter().all(u8::is_ascii_hexdigit));
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            b'%' if already => out.push('%'),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC admite claves de cualquier longitud");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use std::io::Write;
+use crate::export::{MinutaDocument, MinutaEntry};
+
+// ── WebVTT ─────────────────────────────────────────────────────────────────
+//
+// Un cue por intervención con la voz del interlocutor (`<v Nombre>`). Si hay
+// tiempos por palabra se intercalan marcas `<hh:mm:ss.mmm>` para que los
+// reproductores y alineadores sepan cuándo se dice cada una.
+
+/// Duración de un cue cuando no se conoce su final.
+const DEFAULT_CUE_SECS: f64 = 5.0;
+
+pub fn write_vtt(doc: &MinutaDocument, mut out: impl Write) -> Result<()> {
+    out.write_all(render_vtt(doc).as_bytes())?;
+    Ok(())
+}
+
+fn render_vtt(doc: &MinutaDocument) -> String {
+    let mut out = String::from("WEBVTT\n");
+    let starts: Vec<Option<f64>> = doc.entries.iter().map(entry_start).collect();
+
+    let mut previous_end = 0.0;
+    for (i, entry) in doc.entries.iter().enumerate() {
+        let start = starts[i].unwrap_or(previous_end);
+        let next_start = starts.get(i + 1).copied().flatten();
+        let end = entry.end_secs
+            .or(next_start)
+            .unwrap_or(start + DEFAULT_CUE_SECS)
+            .max(start);
+        previous_end = end;
+
+        out.push_str(&format!("\n{} --> {}\n", vtt_time(start), vtt_time(end)));
+        if let Some(speaker) = &entry.speaker {
+            out.push_str(&format!("<v {}>", escape(speaker)));
+        }
+        out.push_str(&cue_text(entry));
+        out.push('\n');
+    }
+    out
+}
+
+/// Texto del cue: las palabras con sus marcas de tiempo si las hay y, si no,
+/// el texto ya post-procesado.
+fn cue_text(entry: &MinutaEntry) -> String {
+    if entry.words.is_empty() {
+        return escape(&entry.text);
+    }
+    entry.words.iter()
+        .enumerate()
+        .map(|(i, w)| {
+            if i == 0 {
+                escape(&w.word)
+            } else {
+                format!("<{}>{}", vtt_time(w.start_secs), escape(&w.word))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Inicio exacto o, en minutas de vídeo, el `[MM:SS]` / `[HH:MM:SS]` del texto.
+fn entry_start(entry: &MinutaEntry) -> Option<f64> {
+    entry.start_secs.or_else(|| {
+        let parts: Vec<f64> = entry.timestamp.as_ref()?
+            .split(':')
+            .map(|p| p.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        Some(parts.iter().fold(0.0, |acc, p| acc * 60.0 + p))
+    })
+}
+
+pub(crate) fn vtt_time(secs: f64) -> String {
+    let millis = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000,
+    )
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait};
 use crate::data::DeviceInfo;
+use crate::process_loopback;
+use crate::i18n::{t, tr};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LoopbackStatus {
@@ -32,7 +34,7 @@ pub fn check_loopback_status() -> Result<LoopbackInfo> {
         "macos"   => Ok(check_macos_loopback()),
         _ => Ok(LoopbackInfo {
             status: LoopbackStatus::Unsupported,
-            message: "Sistema operativo no soportado".to_string(),
+            message: tr!("loopback.unsupported"),
             instructions: vec![],
             loopback_devices: vec![],
         }),
@@ -51,30 +53,15 @@ fn check_windows_loopback() -> LoopbackInfo {
     if !devices.is_empty() {
         LoopbackInfo {
             status: LoopbackStatus::Available,
-            message: format!("✅ {} dispositivos de captura de sistema detectados", devices.len()),
-            instructions: vec![
-                "Dispositivos disponibles para capturar audio del sistema:".into(),
-                "".into(),
-                "Úsalos en Configuración como fuentes de tipo SALIDA.".into(),
-            ],
+            message: tr!("loopback.win_found", n = devices.len()),
+            instructions: instruction_lines("loopback.win_found_help"),
             loopback_devices: devices,
         }
     } else {
         LoopbackInfo {
             status: LoopbackStatus::NeedsConfiguration,
-            message: "⚠️ No se detecta 'Stereo Mix' u otro dispositivo loopback".to_string(),
-            instructions: vec![
-                "Para capturar el audio del sistema en Windows:".into(),
-                "".into(),
-                "1. Click derecho en el icono de volumen (barra de tareas)".into(),
-                "2. Selecciona 'Configuración de sonido' → 'Más opciones de sonido'".into(),
-                "3. Pestaña 'Grabación' → click derecho → 'Mostrar dispositivos deshabilitados'".into(),
-                "4. Busca 'Mezcla estéreo' o 'Stereo Mix' → click derecho → 'Habilitar'".into(),
-                "".into(),
-                "Si no aparece Stereo Mix, tu tarjeta de sonido no lo soporta.".into(),
-                "Alternativa: instala 'VB-Audio Virtual Cable' (gratuito):".into(),
-                "  https://vb-audio.com/Cable/".into(),
-            ],
+            message: tr!("loopback.win_missing"),
+            instructions: instruction_lines("loopback.win_missing_help"),
             loopback_devices: vec![],
         }
     }
@@ -82,48 +69,54 @@ fn check_windows_loopback() -> LoopbackInfo {
 
 fn get_windows_loopback_devices() -> Vec<DeviceInfo> {
     // WASAPI expone Stereo Mix / What U Hear / Wave Out Mix como inputs normales
-    enumerate_loopback_inputs(&[
+    let mut devices = enumerate_loopback_inputs(&[
         "stereo mix", "mezcla estéreo", "what u hear",
         "wave out mix", "loopback", "virtual cable", "vb-audio",
         "cable output", // VB-Audio Cable
-    ])
+    ]);
+    // Y cada aplicación abierta por separado, sin necesidad de Stereo Mix
+    for mut process in process_loopback::list_processes() {
+        process.id = devices.len();
+        devices.push(process);
+    }
+    devices
 }
 
 // ── Linux ─────────────────────────────────────────────────────────────────
 
-fn check_linux_loopback() -> LoopbackInfo {
+/// Servidor de sonido que responde a `pactl info`, o `None` si no hay
+/// (sistemas mínimos o sin sesión gráfica: solo queda ALSA).
+pub fn linux_sound_server() -> Option<&'static str> {
     use std::process::Command;
 
-    let audio_sys = {
-        let out = Command::new("pactl").args(&["info"]).output().ok();
-        out.and_then(|o| String::from_utf8(o.stdout).ok())
-            .map(|s| if s.to_lowercase().contains("pipewire") { "PipeWire" } else { "PulseAudio" })
-            .unwrap_or("PulseAudio")
-            .to_string()
+    let out = Command::new("pactl").args(&["info"]).output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let info = String::from_utf8_lossy(&out.stdout).to_lowercase();
+    Some(if info.contains("pipewire") { "PipeWire" } else { "PulseAudio" })
+}
+
+fn check_linux_loopback() -> LoopbackInfo {
+    let Some(audio_sys) = linux_sound_server() else {
+        return check_alsa_loopback();
     };
 
     let devices = get_linux_loopback_devices();
+    tracing::info!("Sistema de audio: {}; monitores: {:?}", audio_sys, devices.iter().map(|d| &d.name).collect::<Vec<_>>());
 
     if !devices.is_empty() {
         LoopbackInfo {
             status: LoopbackStatus::Available,
-            message: format!("✅ {} — {} dispositivos monitor detectados", audio_sys, devices.len()),
-            instructions: vec![
-                "Los dispositivos '.monitor' capturan el audio de salida del sistema.".into(),
-                "Úsalos en Configuración como fuentes de tipo SALIDA.".into(),
-            ],
+            message: tr!("loopback.linux_found", system = audio_sys, n = devices.len()),
+            instructions: instruction_lines("loopback.linux_found_help"),
             loopback_devices: devices,
         }
     } else {
         LoopbackInfo {
             status: LoopbackStatus::RequiresSetup,
-            message: format!("⚠️ {}: no se detectan dispositivos monitor", audio_sys),
-            instructions: vec![
-                "Verifica que tu tarjeta de audio esté activa:".into(),
-                "  pactl list sinks short".into(),
-                "".into(),
-                "Los dispositivos '.monitor' deberían aparecer automáticamente.".into(),
-            ],
+            message: tr!("loopback.linux_missing", system = audio_sys),
+            instructions: instruction_lines("loopback.linux_missing_help"),
             loopback_devices: vec![],
         }
     }
@@ -132,6 +125,10 @@ fn check_linux_loopback() -> LoopbackInfo {
 pub fn get_linux_loopback_devices() -> Vec<DeviceInfo> {
     use std::process::Command;
 
+    if linux_sound_server().is_none() {
+        return get_alsa_loopback_devices();
+    }
+
     let mut devices = vec![];
 
     let output = Command::new("pactl").args(&["list", "sources", "short"]).output();
@@ -164,6 +161,7 @@ pub fn get_linux_loopback_devices() -> Vec<DeviceInfo> {
                     id: devices.len(),
                     name: description,
                     technical_name: Some(tech_name),
+                    channels: crate::audio::pactl_channels(&parts),
                 });
             }
         }
@@ -172,6 +170,37 @@ pub fn get_linux_loopback_devices() -> Vec<DeviceInfo> {
     devices
 }
 
+// ── Linux sin servidor de sonido (ALSA directo) ──────────────────────────
+//
+// Sin PulseAudio/PipeWire no hay monitores. El equivalente en ALSA es el
+// módulo `snd-aloop`: lo que se reproduce en un lado del Loopback se puede
+// capturar en el otro, y cpal lo lista como un input más.
+
+fn check_alsa_loopback() -> LoopbackInfo {
+    let devices = get_alsa_loopback_devices();
+    tracing::info!("Sistema de audio: ALSA directo; loopback: {:?}", devices.iter().map(|d| &d.name).collect::<Vec<_>>());
+
+    if !devices.is_empty() {
+        LoopbackInfo {
+            status: LoopbackStatus::Available,
+            message: tr!("loopback.alsa_found", n = devices.len()),
+            instructions: instruction_lines("loopback.alsa_found_help"),
+            loopback_devices: devices,
+        }
+    } else {
+        LoopbackInfo {
+            status: LoopbackStatus::RequiresSetup,
+            message: tr!("loopback.alsa_missing"),
+            instructions: instruction_lines("loopback.alsa_missing_help"),
+            loopback_devices: vec![],
+        }
+    }
+}
+
+fn get_alsa_loopback_devices() -> Vec<DeviceInfo> {
+    enumerate_loopback_inputs(&["loopback"])
+}
+
 // ── macOS ─────────────────────────────────────────────────────────────────
 //
 // CoreAudio no tiene loopback nativo. BlackHole o Soundflower se instalan
@@ -183,33 +212,15 @@ fn check_macos_loopback() -> LoopbackInfo {
     if !devices.is_empty() {
         LoopbackInfo {
             status: LoopbackStatus::Available,
-            message: format!("✅ {} dispositivos de audio virtual detectados", devices.len()),
-            instructions: vec![
-                "Asegúrate de que las aplicaciones envíen el audio a este dispositivo virtual.".into(),
-                "En la mayoría de casos se configura con un 'Dispositivo Agregado' en Audio MIDI Setup.".into(),
-            ],
+            message: tr!("loopback.mac_found", n = devices.len()),
+            instructions: instruction_lines("loopback.mac_found_help"),
             loopback_devices: devices,
         }
     } else {
         LoopbackInfo {
             status: LoopbackStatus::RequiresSetup,
-            message: "⚠️ macOS requiere software de audio virtual".to_string(),
-            instructions: vec![
-                "macOS no tiene captura de salida nativa.".into(),
-                "".into(),
-                "Opción recomendada — BlackHole (gratuito):".into(),
-                "  https://github.com/ExistentialAudio/BlackHole".into(),
-                "".into(),
-                "Instalación:".into(),
-                "  1. Descarga e instala BlackHole 2ch".into(),
-                "  2. Abre 'Audio MIDI Setup' (en /Aplicaciones/Utilidades/)".into(),
-                "  3. Crea un 'Dispositivo Agregado' con tu salida habitual + BlackHole".into(),
-                "  4. Úsalo como salida del sistema en Preferencias de Sonido".into(),
-                "  5. BlackHole aparecerá aquí como dispositivo disponible".into(),
-                "".into(),
-                "Alternativa de pago — Loopback (Rogue Amoeba):".into(),
-                "  https://rogueamoeba.com/loopback/".into(),
-            ],
+            message: tr!("loopback.mac_missing"),
+            instructions: instruction_lines("loopback.mac_missing_help"),
             loopback_devices: vec![],
         }
     }
@@ -222,7 +233,12 @@ fn get_macos_loopback_devices() -> Vec<DeviceInfo> {
     ])
 }
 
-// ── Helper compartido ─────────────────────────────────────────────────────
+// ── Helpers compartidos ───────────────────────────────────────────────────
+
+/// Divide un bloque de instrucciones traducido en líneas para el diálogo.
+fn instruction_lines(key: &'static str) -> Vec<String> {
+    t(key).lines().map(String::from).collect()
+}
 
 /// Busca en los inputs de cpal dispositivos cuyo nombre (en minúsculas)
 /// contenga alguna de las palabras clave dadas.
@@ -235,11 +251,13 @@ fn enumerate_loopback_inputs(keywords: &[&str]) -> Vec<DeviceInfo> {
             if let Ok(desc) = device.description() {
                 let name = desc.name().to_string();
                 let lower = name.to_lowercase();
+                tracing::debug!("Input cpal: {}", name);
                 if keywords.iter().any(|kw| lower.contains(kw)) {
                     devices.push(DeviceInfo {
                         id: devices.len(),
                         name: name.clone(),
                         technical_name: Some(name),
+                        channels: device.default_input_config().map(|c| c.channels()).unwrap_or(0),
                     });
                 }
             }
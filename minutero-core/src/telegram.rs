@@ -0,0 +1,157 @@
+use anyhow::{Result, anyhow};
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use crate::data::TranscriptSegment;
+use crate::export::MinutaDocument;
+use crate::i18n::tr;
+use crate::integrations::overview;
+use crate::redaction::{RedactionSettings, Redactor};
+
+// ── Telegram ───────────────────────────────────────────────────────────────
+//
+// Con un bot (token de @BotFather) y el ID del chat: al guardar se envía el
+// resumen, y opcionalmente el archivo de la minuta. En directo, además, cada
+// intervención puede ir al chat según se transcribe, como con MQTT; se
+// agrupan las que llegan seguidas para no pasar del límite de mensajes de la
+// API, y si Telegram no responde se pierden para el chat pero la captura
+// sigue.
+
+const API: &str = "https://api.telegram.org";
+/// Límite de caracteres de un mensaje.
+const MAX_MESSAGE: usize = 4096;
+/// Telegram limita a un mensaje por segundo en el mismo chat.
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelegramConfig {
+    pub enabled: bool,
+    pub bot_token: String,
+    /// ID numérico del chat (negativo en los grupos) o `@canal`.
+    pub chat_id: String,
+    pub attach_file: bool,
+    /// Enviar también la transcripción en directo.
+    pub live: bool,
+}
+
+impl TelegramConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.bot_token.trim().is_empty() && !self.chat_id.trim().is_empty()
+    }
+
+    fn url(&self, method: &str) -> String {
+        format!("{}/bot{}/{}", API, self.bot_token.trim(), method)
+    }
+}
+
+pub async fn post_minuta(config: &TelegramConfig, doc: &MinutaDocument, path: &Path) -> Result<()> {
+    let client = Client::new();
+    let text = overview(doc);
+    if !config.attach_file {
+        let text = format!("{}\n\n{}", text, tr!("integrations.saved_at", path = path.display()));
+        return send_message(&client, config, &text).await;
+    }
+
+    let filename = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let form = Form::new()
+        .text("chat_id", config.chat_id.trim().to_string())
+        .text("caption", truncate(&text, 1024).to_string())
+        .part("document", Part::bytes(std::fs::read(path)?).file_name(filename));
+    let response = client.post(config.url("sendDocument")).multipart(form).send().await?;
+    check_ok(&serde_json::from_str(&response.text().await?)?)
+}
+
+async fn send_message(client: &Client, config: &TelegramConfig, text: &str) -> Result<()> {
+    let response = client
+        .post(config.url("sendMessage"))
+        .header("Content-Type", "application/json")
+        .body(json!({ "chat_id": config.chat_id.trim(), "text": truncate(text, MAX_MESSAGE) }).to_string())
+        .send()
+        .await?;
+    check_ok(&serde_json::from_str(&response.text().await?)?)
+}
+
+/// La API responde `{"ok": false, "description": ...}` en los errores.
+fn check_ok(response: &Value) -> Result<()> {
+    if response["ok"].as_bool() == Some(true) {
+        Ok(())
+    } else {
+        Err(anyhow!("API de Telegram: {}", response["description"].as_str().unwrap_or("error desconocido")))
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+// ── En directo ─────────────────────────────────────────────────────────────
+
+/// Envío en directo de una sesión. Al soltarlo se vacía la cola y se cierra.
+pub struct TelegramLive {
+    tx: Sender<String>,
+    redactor: Option<Redactor>,
+}
+
+impl TelegramLive {
+    pub fn start(config: &TelegramConfig, redaction: &RedactionSettings) -> Self {
+        let (tx, rx) = channel();
+        let config = config.clone();
+        std::thread::spawn(move || match Runtime::new() {
+            Ok(rt) => rt.block_on(send_live(&config, rx)),
+            Err(e) => tracing::error!("Telegram: no se pudo crear el runtime: {:?}", e),
+        });
+        Self { tx, redactor: redaction.enabled.then(|| Redactor::new(redaction)) }
+    }
+
+    /// Encola una intervención sin bloquear.
+    pub fn publish(&self, segment: &TranscriptSegment) {
+        let text = match &self.redactor {
+            Some(redactor) => redactor.redact(&segment.text),
+            None => segment.text.clone(),
+        };
+        let _ = self.tx.send(format!("{}: {}", segment.name, text));
+    }
+}
+
+async fn send_live(config: &TelegramConfig, rx: Receiver<String>) {
+    let client = Client::new();
+    let mut last_sent = Instant::now() - MIN_INTERVAL;
+    // Sale cuando se suelta el `TelegramLive` (el canal se cierra)
+    while let Ok(first) = rx.recv() {
+        // Lo que llegue mientras se respeta el intervalo va en el mismo mensaje
+        tokio::time::sleep(MIN_INTERVAL.saturating_sub(last_sent.elapsed())).await;
+        let mut lines = vec![first];
+        lines.extend(rx.try_iter());
+        for text in batches(&lines) {
+            tokio::time::sleep(MIN_INTERVAL.saturating_sub(last_sent.elapsed())).await;
+            if let Err(e) = send_message(&client, config, &text).await {
+                tracing::warn!("Telegram: intervención no enviada: {}", e);
+            }
+            last_sent = Instant::now();
+        }
+    }
+}
+
+/// Junta las líneas en mensajes que caben en el límite de la API.
+fn batches(lines: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for line in lines {
+        match out.last_mut() {
+            Some(current) if current.chars().count() + line.chars().count() < MAX_MESSAGE => {
+                current.push('\n');
+                current.push_str(line);
+            }
+            _ => out.push(line.clone()),
+        }
+    }
+    out
+}
@@ -0,0 +1,279 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tera::{Context, Tera};
+use crate::actions::ActionItem;
+use crate::export::MinutaDocument;
+use crate::i18n::tr;
+use crate::stats::{format_duration, markdown_table};
+
+// ── Plantillas de exportación (Tera) ───────────────────────────────────────
+//
+// Variables disponibles en las plantillas:
+//   title, date, attendees[], tags[] (temas), recording (archivo de audio o
+//   vacío), body,
+//   segments[] {timestamp, speaker, text, question, chapter, lang},
+//   chapters[] {title, timestamp} (solo en el modo clase),
+//   stats[] {name, speaking_time, words, segments, longest, interruptions},
+//   stats_table (tabla Markdown ya formateada) y labels.* (textos traducidos).
+//
+// En la del orden del día: date, actions[] {task, owner, due, session, date}
+// (las tareas abiertas) y labels.*.
+//
+// Las plantillas de resumen usan las mismas variables que las de la minuta;
+// el texto resultante es la petición que se pega en un modelo de lenguaje
+// (Minutero no resume por sí mismo).
+
+/// Equivale al formato que se usaba antes de admitir plantillas.
+pub const DEFAULT_MARKDOWN: &str =
+    "# {{ title }}\n\n{{ labels.date }}: {{ date }}\n\n{% if recording %}{{ labels.recording }}: [{{ recording }}]({{ recording }})\n\n{% endif %}---\n\n{{ body }}{{ stats_table }}";
+
+pub const DEFAULT_AGENDA: &str =
+    "# {{ labels.agenda }}\n\n{{ labels.date }}: {{ date }}\n\n## {{ labels.open_actions }}\n\n{% for a in actions %}- [ ] {{ a.task }}{% if a.owner %} — {{ a.owner }}{% endif %}{% if a.due %} ({{ a.due }}){% endif %} _{{ a.session }}, {{ a.date }}_\n{% endfor %}";
+
+pub const DEFAULT_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{ title }}</title>
+<style>
+  body { font-family: sans-serif; max-width: 50em; margin: 2em auto; line-height: 1.5; }
+  .ts { color: #888; }
+  table { border-collapse: collapse; }
+  th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }
+</style>
+</head>
+<body>
+<h1>{{ title }}</h1>
+<p>{{ labels.date }}: {{ date }}</p>
+{% if attendees %}
+<h2>{{ labels.attendees }}</h2>
+<ul>
+{% for name in attendees %}  <li>{{ name }}</li>
+{% endfor %}</ul>
+{% endif %}
+{% if tags %}
+<p>{{ labels.tags }}: {{ tags | join(sep=", ") }}</p>
+{% endif %}
+{% if recording %}
+<p>{{ labels.recording }}: <a href="{{ recording }}">{{ recording }}</a></p>
+<audio controls preload="none" src="{{ recording }}"></audio>
+{% endif %}
+<hr>
+{% if chapters %}
+<h2>{{ labels.contents }}</h2>
+<ol>
+{% for c in chapters %}  <li>{% if c.timestamp %}<span class="ts">[{{ c.timestamp }}]</span> {% endif %}{{ c.title }}</li>
+{% endfor %}</ol>
+{% endif %}
+<h2>{{ labels.transcript }}</h2>
+{% for s in segments %}{% if s.chapter %}<h3>{{ s.chapter }}</h3>
+{% endif %}<p>{% if s.timestamp %}<span class="ts">[{{ s.timestamp }}]</span> {% endif %}{% if s.speaker %}<strong>{{ s.speaker }}:</strong> {% endif %}{{ s.text }}</p>
+{% endfor %}
+{% if stats %}
+<h2>{{ labels.stats_heading }}</h2>
+<table>
+<tr><th>{{ labels.speaker }}</th><th>{{ labels.speaking_time }}</th><th>{{ labels.words }}</th><th>{{ labels.longest }}</th><th>{{ labels.interruptions }}</th></tr>
+{% for s in stats %}<tr><td>{{ s.name }}</td><td>{{ s.speaking_time }}</td><td>{{ s.words }}</td><td>{{ s.longest }}</td><td>{{ s.interruptions }}</td></tr>
+{% endfor %}</table>
+{% endif %}
+</body>
+</html>
+"#;
+
+#[derive(Serialize)]
+struct SegmentVars<'a> {
+    timestamp: Option<&'a str>,
+    speaker: Option<&'a str>,
+    text: &'a str,
+    /// Solo en el modo entrevista.
+    question: bool,
+    /// Capítulo que empieza aquí; solo en el modo clase.
+    chapter: Option<&'a str>,
+    /// Idioma detectado; solo en el modo multilingüe.
+    lang: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct ChapterVars<'a> {
+    title: &'a str,
+    timestamp: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct StatsVars<'a> {
+    name: &'a str,
+    speaking_time: String,
+    words: usize,
+    segments: usize,
+    longest: String,
+    interruptions: usize,
+}
+
+#[derive(Serialize)]
+struct Labels {
+    meeting: String,
+    date: String,
+    attendees: String,
+    tags: String,
+    recording: String,
+    transcript: String,
+    contents: String,
+    stats_heading: String,
+    speaker: String,
+    speaking_time: String,
+    words: String,
+    longest: String,
+    interruptions: String,
+}
+
+#[derive(Serialize)]
+struct TemplateVars<'a> {
+    title: &'a str,
+    date: &'a str,
+    attendees: &'a [String],
+    tags: &'a [String],
+    recording: Option<&'a str>,
+    body: &'a str,
+    segments: Vec<SegmentVars<'a>>,
+    chapters: Vec<ChapterVars<'a>>,
+    stats: Vec<StatsVars<'a>>,
+    stats_table: String,
+    labels: Labels,
+}
+
+fn context(doc: &MinutaDocument) -> Result<Context> {
+    let vars = TemplateVars {
+        title: &doc.title,
+        date: &doc.date,
+        attendees: &doc.attendees,
+        tags: &doc.tags,
+        recording: doc.recording.as_deref(),
+        body: &doc.body,
+        segments: doc.entries.iter().map(|e| SegmentVars {
+            timestamp: e.timestamp.as_deref(),
+            speaker: e.speaker.as_deref(),
+            text: &e.text,
+            question: e.question,
+            chapter: e.chapter.as_deref(),
+            lang: e.lang.as_deref(),
+        }).collect(),
+        chapters: doc.entries.iter()
+            .filter_map(|e| Some(ChapterVars { title: e.chapter.as_deref()?, timestamp: e.timestamp.as_deref() }))
+            .collect(),
+        stats: doc.stats.iter().map(|s| StatsVars {
+            name: &s.name,
+            speaking_time: format_duration(s.speaking_secs),
+            words: s.words,
+            segments: s.segments,
+            longest: format_duration(s.longest_monologue_secs),
+            interruptions: s.interruptions,
+        }).collect(),
+        stats_table: markdown_table(&doc.stats),
+        labels: Labels {
+            meeting: tr!("summary.meeting"),
+            date: tr!("export.date"),
+            attendees: tr!("export.attendees"),
+            tags: tr!("export.tags"),
+            recording: tr!("export.recording"),
+            transcript: tr!("export.transcript"),
+            contents: tr!("lecture.contents"),
+            stats_heading: tr!("stats.heading"),
+            speaker: tr!("stats.speaker"),
+            speaking_time: tr!("stats.speaking_time"),
+            words: tr!("stats.words"),
+            longest: tr!("stats.longest"),
+            interruptions: tr!("stats.interruptions"),
+        },
+    };
+    Ok(Context::from_serialize(vars)?)
+}
+
+/// Renderiza la minuta con la plantilla de `custom_path` o, si está vacía,
+/// con la integrada. Si la plantilla del usuario falla se avisa y se usa la
+/// integrada: perder la minuta por un error de sintaxis sería peor.
+pub fn render(doc: &MinutaDocument, custom_path: &str, builtin: &str, autoescape: bool) -> Result<String> {
+    render_context(&context(doc)?, custom_path, builtin, autoescape)
+}
+
+fn render_context(context: &Context, custom_path: &str, builtin: &str, autoescape: bool) -> Result<String> {
+    let custom_path = custom_path.trim();
+    if !custom_path.is_empty() {
+        let rendered = std::fs::read_to_string(custom_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|source| Ok(Tera::one_off(&source, context, autoescape)?));
+        match rendered {
+            Ok(text) => return Ok(text),
+            Err(e) => tracing::warn!("Plantilla '{}' no válida, se usa la integrada: {:?}", custom_path, e),
+        }
+    }
+    Ok(Tera::one_off(builtin, context, autoescape)?)
+}
+
+#[derive(Serialize)]
+struct AgendaLabels {
+    agenda: String,
+    date: String,
+    open_actions: String,
+}
+
+#[derive(Serialize)]
+struct AgendaVars<'a> {
+    date: String,
+    actions: Vec<&'a ActionItem>,
+    labels: AgendaLabels,
+}
+
+/// Orden del día en Markdown con las tareas abiertas de `items`, con la
+/// plantilla de `custom_path` o la integrada.
+pub fn render_agenda(items: &[ActionItem], custom_path: &str) -> Result<String> {
+    let vars = AgendaVars {
+        date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        actions: items.iter().filter(|i| !i.done).collect(),
+        labels: AgendaLabels {
+            agenda: tr!("actions.agenda_title"),
+            date: tr!("export.date"),
+            open_actions: tr!("actions.open_heading"),
+        },
+    };
+    render_context(&Context::from_serialize(vars)?, custom_path, DEFAULT_AGENDA, false)
+}
+
+// ── Plantillas de resumen ──────────────────────────────────────────────────
+
+/// Petición de resumen con nombre ("Daily", "Retrospectiva"…), elegible en
+/// cada sesión.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SummaryPrompt {
+    pub name: String,
+    pub template: String,
+}
+
+/// Las cabeceras van con `labels.*`, así que salen en el idioma de la
+/// interfaz al rellenarla.
+const TRANSCRIPT_BLOCK: &str =
+    "\n\n{{ labels.meeting }}: {{ title }} ({{ date }})\n{% if attendees %}{{ labels.attendees }}: {{ attendees | join(sep=\", \") }}\n{% endif %}\n{{ labels.transcript }}:\n{{ body }}";
+
+/// Las que trae una configuración nueva, en el idioma de la interfaz.
+pub fn default_summary_prompts() -> Vec<SummaryPrompt> {
+    let prompt = |(name, ask): (&'static str, &'static str)| SummaryPrompt {
+        name: tr!(name),
+        template: format!("{}{}", tr!(ask), TRANSCRIPT_BLOCK),
+    };
+    [
+        ("summary.general_name", "summary.general_prompt"),
+        ("summary.daily_name", "summary.daily_prompt"),
+        ("summary.retro_name", "summary.retro_prompt"),
+        ("summary.client_name", "summary.client_prompt"),
+    ].into_iter().map(prompt).collect()
+}
+
+/// La petición de `prompt` con los datos de `doc`.
+pub fn render_prompt(doc: &MinutaDocument, prompt: &SummaryPrompt) -> Result<String> {
+    render_str(doc, &prompt.template)
+}
+
+/// Una plantilla corta (un mensaje, un nombre) con las variables de la minuta.
+pub fn render_str(doc: &MinutaDocument, source: &str) -> Result<String> {
+    Ok(Tera::one_off(source, &context(doc)?, false)?)
+}
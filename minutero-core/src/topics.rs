@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::iter;
+use crate::export::{MinutaDocument, MinutaEntry};
+use crate::lecture::term_counts;
+
+// ── Temas de la sesión ─────────────────────────────────────────────────────
+//
+// Al guardar, la minuta se etiqueta con sus temas ("presupuesto",
+// "contratación", "release"): las palabras con contenido que más se repiten
+// y en más intervenciones distintas, con las mismas palabras vacías que los
+// capítulos del modo clase. No hace falta ningún modelo y tarda nada. Las
+// etiquetas van en la minuta JSON (`tags`) y el historial busca por ellas
+// (ver `history`).
+
+/// Menciones mínimas para que una palabra sea tema.
+const MIN_MENTIONS: usize = 3;
+/// Intervenciones distintas en las que tiene que salir: una palabra que solo
+/// repite una persona en un párrafo no es tema de la reunión.
+const MIN_ENTRIES: usize = 2;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TopicSettings {
+    pub enabled: bool,
+    pub max_tags: usize,
+    /// Palabras que nunca son tema (el nombre de la empresa, muletillas),
+    /// separadas por comas.
+    pub ignore: String,
+}
+
+impl Default for TopicSettings {
+    fn default() -> Self {
+        Self { enabled: true, max_tags: 5, ignore: String::new() }
+    }
+}
+
+/// Temas de `entries`, del más al menos presente; empate por orden de
+/// aparición.
+pub fn topic_tags(entries: &[MinutaEntry], lang: Option<&str>, settings: &TopicSettings) -> Vec<String> {
+    let ignore: Vec<String> = settings.ignore.split(',')
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    // (menciones, intervenciones, primera aparición)
+    let mut terms: HashMap<String, (usize, usize, usize)> = HashMap::new();
+    for entry in entries {
+        for (word, n) in term_counts(iter::once(entry), lang) {
+            let order = terms.len();
+            let term = terms.entry(word).or_insert((0, 0, order));
+            term.0 += n;
+            term.1 += 1;
+        }
+    }
+    let mut tags: Vec<(String, (usize, usize, usize))> = terms.into_iter()
+        .filter(|(word, (mentions, spread, _))| {
+            *mentions >= MIN_MENTIONS && *spread >= MIN_ENTRIES
+                && !word.chars().all(|c| c.is_numeric())
+                && !ignore.contains(word)
+        })
+        .collect();
+    // Pesa más lo que sale en muchas intervenciones que lo muy repetido en pocas
+    let score = |(mentions, spread, _): &(usize, usize, usize)| *mentions as f64 * (1.0 + *spread as f64).ln();
+    tags.sort_by(|a, b| score(&b.1).total_cmp(&score(&a.1)).then(a.1.2.cmp(&b.1.2)));
+    tags.into_iter().take(settings.max_tags).map(|(word, _)| word).collect()
+}
+
+impl MinutaDocument {
+    /// Etiqueta la minuta con sus temas, si está activado.
+    pub fn with_topics(mut self, lang: Option<&str>, settings: &TopicSettings) -> Self {
+        if settings.enabled {
+            self.tags = topic_tags(&self.entries, lang, settings);
+        }
+        self
+    }
+}
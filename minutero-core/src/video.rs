@@ -1,35 +1,39 @@
 use anyhow::{Result, anyhow};
 use std::io::Read;
 use std::process::{Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
 use tokio::runtime::Runtime;
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext};
+use whisper_rs::WhisperContext;
 
-use crate::audio::download_whisper_model;
-use crate::data::{LanguageConfig, VideoMessage, WHISPER_SAMPLE_RATE};
+use crate::audio::{download_status, download_whisper_model, model_is_cached, run_whisper};
+use crate::i18n::tr;
+use crate::stop::StopSignal;
+use crate::data::{TranscriptionConfig, VideoMessage, WHISPER_SAMPLE_RATE};
+use crate::hallucinations::HallucinationFilter;
+use crate::postprocess::PostProcessor;
 
 /// Chunks de 30 segundos — ventana nativa de Whisper, calidad óptima.
 const VIDEO_CHUNK_SECS: u32 = 30;
 
-pub fn video_transcription_thread(
-    file_path: String,
-    model_name: String,
-    lang_config: LanguageConfig,
-    tx: std::sync::mpsc::Sender<VideoMessage>,
-    stop_signal: Arc<AtomicBool>,
-) -> Result<()> {
-    // ── 1. Descargar / localizar modelo ────────────────────────────────────
-    let _ = tx.send(VideoMessage::Status("Verificando modelo...".into()));
-    let model_path = Runtime::new()?
-        .block_on(download_whisper_model(&model_name))?;
+/// Decodifica cualquier archivo que entienda ffmpeg a mono f32 a 16 kHz.
+pub fn decode_audio(file_path: &str) -> Result<Vec<f32>> {
+    decode(file_path, None)
+}
 
-    // ── 2. Extraer audio con ffmpeg ────────────────────────────────────────
-    let _ = tx.send(VideoMessage::Status("Extrayendo audio con ffmpeg...".into()));
+/// Solo `duration_secs` a partir de `start_secs`: ffmpeg salta al inicio sin
+/// decodificar lo anterior. Vacío si el tramo cae fuera del archivo.
+pub fn decode_audio_range(file_path: &str, start_secs: f64, duration_secs: f64) -> Result<Vec<f32>> {
+    decode(file_path, Some((start_secs, duration_secs)))
+}
 
-    let mut child = Command::new("ffmpeg")
+fn decode(file_path: &str, range: Option<(f64, f64)>) -> Result<Vec<f32>> {
+    let mut cmd = Command::new("ffmpeg");
+    if let Some((start, duration)) = range {
+        // Antes de -i, para que busque en la entrada en vez de decodificar
+        cmd.args(["-ss", &format!("{:.3}", start), "-t", &format!("{:.3}", duration)]);
+    }
+    let mut child = cmd
         .args(&[
-            "-i", &file_path,
+            "-i", file_path,
             "-ar", &WHISPER_SAMPLE_RATE.to_string(),
             "-ac", "1",      // mono
             "-f", "f32le",   // float 32-bit little-endian, sin cabecera
@@ -39,17 +43,17 @@ pub fn video_transcription_thread(
         .stdout(Stdio::piped())
         .stderr(Stdio::null()) // silenciar output de ffmpeg
         .spawn()
-        .map_err(|e| anyhow!("Error iniciando ffmpeg: {:?}\n¿Está ffmpeg instalado?", e))?;
+        .map_err(|e| anyhow!(tr!("video.ffmpeg_start", e = format!("{:?}", e))))?;
 
     let mut stdout = child.stdout.take()
-        .ok_or_else(|| anyhow!("No se pudo obtener stdout de ffmpeg"))?;
+        .ok_or_else(|| anyhow!(tr!("video.ffmpeg_stdout")))?;
 
     let mut audio_bytes = Vec::new();
     stdout.read_to_end(&mut audio_bytes)?;
     let _ = child.wait();
 
-    if audio_bytes.is_empty() {
-        return Err(anyhow!("ffmpeg no produjo audio. ¿Es un archivo de vídeo/audio válido?"));
+    if audio_bytes.is_empty() && range.is_none() {
+        return Err(anyhow!(tr!("video.ffmpeg_empty")));
     }
 
     // Convertir bytes a muestras f32
@@ -57,21 +61,48 @@ pub fn video_transcription_thread(
         .chunks_exact(4)
         .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
         .collect();
+    Ok(audio)
+}
+
+pub fn video_transcription_thread(
+    file_path: String,
+    model_name: String,
+    config: TranscriptionConfig,
+    tx: std::sync::mpsc::Sender<VideoMessage>,
+    stop_signal: StopSignal,
+) -> Result<()> {
+    // ── 1. Descargar / localizar modelo ────────────────────────────────────
+    let _ = tx.send(VideoMessage::Status(tr!("video.checking_model")));
+    let cached = model_is_cached(&model_name, &config.model_source);
+    let model_path = Runtime::new()?
+        .block_on(download_whisper_model(&model_name, &config.model_source, |downloaded, total| {
+            let _ = tx.send(VideoMessage::Status(download_status(&model_name, downloaded, total)));
+        }))?;
+    if !cached {
+        let _ = tx.send(VideoMessage::ModelDownloaded(model_name.clone()));
+    }
+
+    // ── 2. Extraer audio con ffmpeg ────────────────────────────────────────
+    let _ = tx.send(VideoMessage::Status(tr!("video.extracting")));
+
+    let audio = decode_audio(&file_path)?;
 
     let total_samples = audio.len();
     let total_secs = total_samples as f64 / WHISPER_SAMPLE_RATE as f64;
 
-    let _ = tx.send(VideoMessage::Status(format!(
-        "Audio extraído: {} ({} muestras). Cargando modelo...",
-        format_timestamp(total_secs),
-        total_samples,
+    let _ = tx.send(VideoMessage::Status(tr!(
+        "video.extracted",
+        duration = format_timestamp(total_secs),
+        samples = total_samples,
     )));
 
     // ── 3. Cargar modelo Whisper ───────────────────────────────────────────
     let ctx = WhisperContext::new_with_params(&model_path, Default::default())
-        .map_err(|e| anyhow!("Error cargando modelo: {:?}", e))?;
+        .map_err(|e| anyhow!(tr!("audio.model_load", e = format!("{:?}", e))))?;
     let mut state = ctx.create_state()
-        .map_err(|e| anyhow!("Error creando estado: {:?}", e))?;
+        .map_err(|e| anyhow!(tr!("audio.state_create", e = format!("{:?}", e))))?;
+    let post = PostProcessor::new(&config.preset, config.style);
+    let hallucinations = HallucinationFilter::new(&config.tuning);
 
     // ── 4. Transcribir chunk a chunk ───────────────────────────────────────
     let chunk_samples = (WHISPER_SAMPLE_RATE * VIDEO_CHUNK_SECS) as usize;
@@ -79,8 +110,8 @@ pub fn video_transcription_thread(
     let total_chunks = starts.len();
 
     for (chunk_idx, &chunk_start) in starts.iter().enumerate() {
-        if stop_signal.load(Ordering::SeqCst) {
-            let _ = tx.send(VideoMessage::Status("Transcripción cancelada.".into()));
+        if stop_signal.is_stopped() {
+            let _ = tx.send(VideoMessage::Status(tr!("video.cancelled")));
             return Ok(());
         }
 
@@ -90,33 +121,23 @@ pub fn video_transcription_thread(
 
         let progress = (chunk_idx + 1) as f32 / total_chunks as f32;
         let _ = tx.send(VideoMessage::Progress(progress));
-        let _ = tx.send(VideoMessage::Status(format!(
-            "Fragmento {}/{} [{}]",
-            chunk_idx + 1,
-            total_chunks,
-            format_timestamp(time_offset_secs),
+        let _ = tx.send(VideoMessage::Status(tr!(
+            "video.chunk",
+            i = chunk_idx + 1,
+            total = total_chunks,
+            time = format_timestamp(time_offset_secs),
         )));
 
-        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-        params.set_language(lang_config.source_lang);
-        params.set_translate(lang_config.translate_to_english);
-        params.set_print_special(false);
-        params.set_print_progress(false);
-        params.set_print_realtime(false);
-        params.set_print_timestamps(false);
-        params.set_suppress_blank(true);
-        params.set_suppress_nst(true);
-        params.set_no_speech_thold(0.6);
-
-        match state.full(params, chunk) {
+        match run_whisper(&mut state, &config, chunk) {
             Ok(_) => {
                 let n = state.full_n_segments();
                 for i in 0..n {
                     if let Some(segment) = state.get_segment(i) {
                         let text = segment.to_string().trim().to_string();
-                        if text.is_empty() || text.len() <= 1 {
+                        if text.len() <= 1 || hallucinations.is_hallucination(&text) {
                             continue;
                         }
+                        let text = post.apply(&text);
 
                         let _ = tx.send(VideoMessage::Segment {
                             timestamp: format_timestamp(time_offset_secs),
@@ -125,7 +146,7 @@ pub fn video_transcription_thread(
                     }
                 }
             }
-            Err(e) => eprintln!("Error en chunk {}: {:?}", chunk_idx, e),
+            Err(e) => tracing::error!("Error en chunk {}: {:?}", chunk_idx, e),
         }
     }
 
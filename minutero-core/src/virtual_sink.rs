@@ -0,0 +1,96 @@
+use anyhow::{Result, anyhow};
+use std::process::Command;
+use crate::i18n::tr;
+
+// ── Salida virtual «Minutero» (PulseAudio/PipeWire) ────────────────────────
+//
+// En vez de pedir al usuario que cree el sink a mano, se cargan con pactl:
+//   - `module-null-sink`: una salida «Minutero» a la que se envía el audio
+//     de la reunión; su `.monitor` es la fuente que se transcribe.
+//   - `module-loopback`: reenvía ese audio a la salida real para que se siga
+//     oyendo.
+// Los módulos se descargan al soltar `VirtualSink` (al salir de la
+// aplicación). Si la aplicación murió sin hacerlo, se limpian los restos la
+// próxima vez que se cree.
+
+const SINK_NAME: &str = "minutero";
+
+/// Módulos de pactl cargados por la aplicación.
+pub struct VirtualSink {
+    modules: Vec<u32>,
+}
+
+impl VirtualSink {
+    pub fn create() -> Result<Self> {
+        unload_leftovers();
+        let output = default_sink()?;
+
+        let mut sink = Self { modules: Vec::new() };
+        sink.modules.push(load_module(&[
+            "module-null-sink",
+            &format!("sink_name={}", SINK_NAME),
+            "sink_properties=device.description=Minutero",
+        ])?);
+        sink.modules.push(load_module(&[
+            "module-loopback",
+            &format!("source={}", monitor_name()),
+            &format!("sink={}", output),
+            "latency_msec=30",
+        ])?);
+        tracing::info!("Salida virtual creada (módulos {:?}), reenviando a {}", sink.modules, output);
+        Ok(sink)
+    }
+}
+
+impl Drop for VirtualSink {
+    fn drop(&mut self) {
+        // Primero el loopback, que depende del monitor del sink
+        for module in self.modules.iter().rev() {
+            let _ = Command::new("pactl").args(["unload-module", &module.to_string()]).output();
+        }
+        tracing::info!("Salida virtual eliminada");
+    }
+}
+
+/// Nombre técnico del monitor del sink, el que se elige como fuente.
+pub fn monitor_name() -> String {
+    format!("{}.monitor", SINK_NAME)
+}
+
+fn load_module(args: &[&str]) -> Result<u32> {
+    let output = Command::new("pactl")
+        .arg("load-module")
+        .args(args)
+        .output()
+        .map_err(|e| anyhow!(tr!("loopback.sink_error", e = e)))?;
+    if !output.status.success() {
+        return Err(anyhow!(tr!("loopback.sink_error", e = String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<u32>()
+        .map_err(|e| anyhow!(tr!("loopback.sink_error", e = e)))
+}
+
+fn default_sink() -> Result<String> {
+    let output = Command::new("pactl").arg("get-default-sink").output()?;
+    let sink = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if sink.is_empty() || sink == SINK_NAME {
+        return Err(anyhow!(tr!("loopback.sink_no_output")));
+    }
+    Ok(sink)
+}
+
+/// Descarga los módulos de una sesión anterior que no llegó a limpiarlos.
+fn unload_leftovers() {
+    let Ok(output) = Command::new("pactl").args(["list", "modules", "short"]).output() else { return };
+    let ours = [format!("sink_name={}", SINK_NAME), format!("source={}", monitor_name())];
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if ours.iter().any(|arg| line.contains(arg.as_str())) {
+            if let Some(id) = line.split_whitespace().next() {
+                tracing::info!("Descargando módulo de una sesión anterior: {}", line);
+                let _ = Command::new("pactl").args(["unload-module", id]).output();
+            }
+        }
+    }
+}
@@ -0,0 +1,423 @@
+use anyhow::Result;
+#[cfg(not(target_os = "windows"))]
+use crate::{audio::stream_failure, data::StreamErrorKind, i18n::tr};
+
+// ── WASAPI en modo exclusivo ──────────────────────────────────────────────
+//
+// En modo compartido el audio pasa por el mezclador de Windows, que
+// remuestrea y añade su propio búfer; con algunas interfaces profesionales
+// eso da cortes que Whisper convierte en frases sin sentido. En modo
+// exclusivo la aplicación se queda el dispositivo en su formato nativo y
+// elige el periodo del búfer. El dispositivo se abre por el id del endpoint
+// que da cpal, y se despierta con un evento en cada periodo.
+//
+// cpal solo abre WASAPI en modo compartido, así que se llama directamente a
+// mmdevapi/COM, como en `process_loopback`.
+
+/// Captura en modo exclusivo en curso. Se usa desde un solo hilo.
+pub struct ExclusiveCapture {
+    #[cfg(target_os = "windows")]
+    inner: win::Capture,
+}
+
+impl ExclusiveCapture {
+    /// Abre en exclusivo el endpoint `endpoint_id` con su frecuencia y sus
+    /// canales de siempre. `buffer_frames` = 0 usa el periodo del
+    /// dispositivo. `name` solo sirve para los mensajes de error.
+    pub fn start(endpoint_id: &str, sample_rate: u32, channels: u16, buffer_frames: u32, name: &str) -> Result<Self> {
+        #[cfg(target_os = "windows")]
+        return Ok(Self { inner: win::Capture::start(endpoint_id, sample_rate, channels, buffer_frames, name)? });
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (endpoint_id, sample_rate, channels, buffer_frames);
+            Err(stream_failure(StreamErrorKind::Device, tr!("audio.exclusive_unsupported", name = name)))
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        #[cfg(target_os = "windows")]
+        return self.inner.sample_rate();
+        #[cfg(not(target_os = "windows"))]
+        0
+    }
+
+    pub fn channels(&self) -> usize {
+        #[cfg(target_os = "windows")]
+        return self.inner.channels();
+        #[cfg(not(target_os = "windows"))]
+        0
+    }
+
+    /// Frecuencia, canales, bits y periodo, para el panel de salud.
+    pub fn format(&self) -> String {
+        #[cfg(target_os = "windows")]
+        return self.inner.format();
+        #[cfg(not(target_os = "windows"))]
+        String::new()
+    }
+
+    /// Espera al siguiente periodo (como mucho 100 ms) y deja en `samples`
+    /// las muestras entrelazadas que haya. El búfer se reutiliza.
+    pub fn read(&mut self, samples: &mut Vec<f32>) -> Result<()> {
+        samples.clear();
+        #[cfg(target_os = "windows")]
+        return self.inner.read(samples);
+        #[cfg(not(target_os = "windows"))]
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win {
+    use anyhow::Result;
+    use std::ffi::c_void;
+    use std::ptr;
+    use crate::audio::stream_failure;
+    use crate::data::StreamErrorKind;
+    use crate::i18n::tr;
+
+    type Hresult = i32;
+    const S_OK: Hresult = 0;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Guid(u32, u16, u16, [u8; 8]);
+
+    const CLSID_MM_DEVICE_ENUMERATOR: Guid = Guid(0xBCDE_0395, 0xE52F, 0x467C, [0x8E, 0x3D, 0xC4, 0x57, 0x92, 0x91, 0x69, 0x2E]);
+    const IID_IMM_DEVICE_ENUMERATOR: Guid = Guid(0xA956_64D2, 0x9614, 0x4F35, [0xA7, 0x46, 0xDE, 0x8D, 0xB6, 0x36, 0x17, 0xE6]);
+    const IID_IAUDIO_CLIENT: Guid = Guid(0x1CB9_AD4C, 0xDBFA, 0x4C32, [0xB1, 0x78, 0xC2, 0xF5, 0x68, 0xA7, 0x03, 0xB2]);
+    const IID_IAUDIO_CAPTURE_CLIENT: Guid = Guid(0xC8AD_BD64, 0xE71E, 0x48A0, [0xA4, 0xDE, 0x18, 0x5C, 0x39, 0x5C, 0xD3, 0x17]);
+    const SUBTYPE_PCM: Guid = Guid(0x0000_0001, 0x0000, 0x0010, [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71]);
+    const SUBTYPE_IEEE_FLOAT: Guid = Guid(0x0000_0003, 0x0000, 0x0010, [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71]);
+
+    const COINIT_MULTITHREADED: u32 = 0;
+    const CLSCTX_ALL: u32 = 0x17;
+    const AUDCLNT_SHAREMODE_EXCLUSIVE: i32 = 1;
+    const AUDCLNT_STREAMFLAGS_EVENTCALLBACK: u32 = 0x0004_0000;
+    const AUDCLNT_BUFFERFLAGS_SILENT: u32 = 0x2;
+    const AUDCLNT_E_DEVICE_IN_USE: Hresult = 0x8889_000A_u32 as i32;
+    const AUDCLNT_E_EXCLUSIVE_MODE_NOT_ALLOWED: Hresult = 0x8889_000E_u32 as i32;
+    const AUDCLNT_E_BUFFER_SIZE_NOT_ALIGNED: Hresult = 0x8889_0019_u32 as i32;
+    const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+    const SPEAKER_FRONT_LEFT: u32 = 0x1;
+    const SPEAKER_FRONT_RIGHT: u32 = 0x2;
+    const SPEAKER_FRONT_CENTER: u32 = 0x4;
+    const WAIT_OBJECT_0: u32 = 0;
+    /// Espera máxima a un periodo, para ver `stop_signal` a menudo.
+    const WAIT_MS: u32 = 100;
+
+    #[repr(C)]
+    struct UnknownVtbl {
+        query_interface: usize,
+        add_ref: usize,
+        release: unsafe extern "system" fn(*mut c_void) -> u32,
+    }
+
+    #[repr(C)]
+    struct DeviceEnumeratorVtbl {
+        base: UnknownVtbl,
+        enum_audio_endpoints: usize,
+        get_default_audio_endpoint: usize,
+        get_device: unsafe extern "system" fn(*mut c_void, *const u16, *mut *mut c_void) -> Hresult,
+    }
+
+    #[repr(C)]
+    struct DeviceVtbl {
+        base: UnknownVtbl,
+        activate: unsafe extern "system" fn(*mut c_void, *const Guid, u32, *const c_void, *mut *mut c_void) -> Hresult,
+    }
+
+    #[repr(C)]
+    struct AudioClientVtbl {
+        base: UnknownVtbl,
+        initialize: unsafe extern "system" fn(*mut c_void, i32, u32, i64, i64, *const WaveFormatExtensible, *const Guid) -> Hresult,
+        get_buffer_size: unsafe extern "system" fn(*mut c_void, *mut u32) -> Hresult,
+        get_stream_latency: usize,
+        get_current_padding: usize,
+        is_format_supported: unsafe extern "system" fn(*mut c_void, i32, *const WaveFormatExtensible, *mut *mut c_void) -> Hresult,
+        get_mix_format: usize,
+        get_device_period: unsafe extern "system" fn(*mut c_void, *mut i64, *mut i64) -> Hresult,
+        start: unsafe extern "system" fn(*mut c_void) -> Hresult,
+        stop: unsafe extern "system" fn(*mut c_void) -> Hresult,
+        reset: usize,
+        set_event_handle: unsafe extern "system" fn(*mut c_void, *mut c_void) -> Hresult,
+        get_service: unsafe extern "system" fn(*mut c_void, *const Guid, *mut *mut c_void) -> Hresult,
+    }
+
+    #[repr(C)]
+    struct CaptureClientVtbl {
+        base: UnknownVtbl,
+        get_buffer: unsafe extern "system" fn(*mut c_void, *mut *mut u8, *mut u32, *mut u32, *mut u64, *mut u64) -> Hresult,
+        release_buffer: unsafe extern "system" fn(*mut c_void, u32) -> Hresult,
+        get_next_packet_size: unsafe extern "system" fn(*mut c_void, *mut u32) -> Hresult,
+    }
+
+    #[repr(C, packed(1))]
+    #[derive(Clone, Copy)]
+    struct WaveFormatExtensible {
+        format_tag: u16,
+        channels: u16,
+        samples_per_sec: u32,
+        avg_bytes_per_sec: u32,
+        block_align: u16,
+        bits_per_sample: u16,
+        size: u16,
+        valid_bits_per_sample: u16,
+        channel_mask: u32,
+        sub_format: Guid,
+    }
+
+    #[link(name = "ole32")]
+    extern "system" {
+        fn CoInitializeEx(reserved: *mut c_void, co_init: u32) -> Hresult;
+        fn CoUninitialize();
+        fn CoCreateInstance(clsid: *const Guid, outer: *mut c_void, context: u32, iid: *const Guid, out: *mut *mut c_void) -> Hresult;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateEventW(attributes: *mut c_void, manual_reset: i32, initial_state: i32, name: *const u16) -> *mut c_void;
+        fn WaitForSingleObject(handle: *mut c_void, millis: u32) -> u32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    unsafe fn vtbl<T>(object: *mut c_void) -> &'static T {
+        &**(object as *mut *const T)
+    }
+
+    unsafe fn release(object: *mut c_void) {
+        if !object.is_null() {
+            (vtbl::<UnknownVtbl>(object).release)(object);
+        }
+    }
+
+    fn check(hr: Hresult, what: &str, name: &str) -> Result<()> {
+        let message = match hr {
+            hr if hr >= 0 => return Ok(()),
+            AUDCLNT_E_DEVICE_IN_USE => tr!("audio.exclusive_in_use", name = name),
+            AUDCLNT_E_EXCLUSIVE_MODE_NOT_ALLOWED => tr!("audio.exclusive_not_allowed", name = name),
+            hr => tr!("audio.exclusive_error", what = what, hr = format!("0x{:08X}", hr as u32)),
+        };
+        Err(stream_failure(StreamErrorKind::Device, message))
+    }
+
+    /// Cómo vienen las muestras en el búfer.
+    #[derive(Clone, Copy)]
+    enum Sample {
+        I16,
+        /// 24 bits en 3 bytes.
+        I24,
+        /// 24 bits alineados a la izquierda en 4 bytes, o 32 bits.
+        I32,
+        F32,
+    }
+
+    impl Sample {
+        fn bytes(self) -> usize {
+            match self {
+                Sample::I16 => 2,
+                Sample::I24 => 3,
+                Sample::I32 | Sample::F32 => 4,
+            }
+        }
+    }
+
+    /// (bits válidos, muestra) que se prueban, de más a menos resolución.
+    const FORMATS: &[(u16, Sample)] = &[
+        (24, Sample::I32),
+        (24, Sample::I24),
+        (32, Sample::I32),
+        (32, Sample::F32),
+        (16, Sample::I16),
+    ];
+
+    fn wave_format(sample_rate: u32, channels: u16, valid_bits: u16, sample: Sample) -> WaveFormatExtensible {
+        let block_align = channels * sample.bytes() as u16;
+        let channel_mask = match channels {
+            1 => SPEAKER_FRONT_CENTER,
+            2 => SPEAKER_FRONT_LEFT | SPEAKER_FRONT_RIGHT,
+            _ => 0,
+        };
+        WaveFormatExtensible {
+            format_tag: WAVE_FORMAT_EXTENSIBLE,
+            channels,
+            samples_per_sec: sample_rate,
+            avg_bytes_per_sec: sample_rate * block_align as u32,
+            block_align,
+            bits_per_sample: sample.bytes() as u16 * 8,
+            size: 22,
+            valid_bits_per_sample: valid_bits,
+            channel_mask,
+            sub_format: if matches!(sample, Sample::F32) { SUBTYPE_IEEE_FLOAT } else { SUBTYPE_PCM },
+        }
+    }
+
+    /// Tramas → unidades de 100 ns, redondeando.
+    fn frames_to_hns(frames: u32, sample_rate: u32) -> i64 {
+        (10_000_000.0 * frames as f64 / sample_rate as f64).round() as i64
+    }
+
+    unsafe fn activate(device: *mut c_void, name: &str) -> Result<*mut c_void> {
+        let mut client: *mut c_void = ptr::null_mut();
+        check((vtbl::<DeviceVtbl>(device).activate)(device, &IID_IAUDIO_CLIENT, CLSCTX_ALL, ptr::null(), &mut client), "IMMDevice::Activate", name)?;
+        Ok(client)
+    }
+
+    pub struct Capture {
+        device: *mut c_void,
+        client: *mut c_void,
+        capture: *mut c_void,
+        event: *mut c_void,
+        format: WaveFormatExtensible,
+        sample: Sample,
+        period: i64,
+        name: String,
+    }
+
+    impl Capture {
+        pub fn start(endpoint_id: &str, sample_rate: u32, channels: u16, buffer_frames: u32, name: &str) -> Result<Self> {
+            unsafe {
+                check(CoInitializeEx(ptr::null_mut(), COINIT_MULTITHREADED), "CoInitializeEx", name)?;
+                // A partir de aquí Drop deshace CoInitializeEx aunque falle algo
+                let mut this = Self {
+                    device: ptr::null_mut(),
+                    client: ptr::null_mut(),
+                    capture: ptr::null_mut(),
+                    event: ptr::null_mut(),
+                    format: wave_format(sample_rate, channels, 16, Sample::I16),
+                    sample: Sample::I16,
+                    period: 0,
+                    name: name.to_string(),
+                };
+
+                let mut enumerator: *mut c_void = ptr::null_mut();
+                check(CoCreateInstance(&CLSID_MM_DEVICE_ENUMERATOR, ptr::null_mut(), CLSCTX_ALL, &IID_IMM_DEVICE_ENUMERATOR, &mut enumerator), "CoCreateInstance", name)?;
+                let id: Vec<u16> = endpoint_id.encode_utf16().chain(Some(0)).collect();
+                let hr = (vtbl::<DeviceEnumeratorVtbl>(enumerator).get_device)(enumerator, id.as_ptr(), &mut this.device);
+                release(enumerator);
+                check(hr, "IMMDeviceEnumerator::GetDevice", name)?;
+                this.client = activate(this.device, name)?;
+
+                // Formato nativo: mismos canales y frecuencia que en compartido
+                let client = vtbl::<AudioClientVtbl>(this.client);
+                let (format, sample) = FORMATS.iter()
+                    .map(|&(bits, sample)| (wave_format(sample_rate, channels, bits, sample), sample))
+                    .find(|(format, _)| (client.is_format_supported)(this.client, AUDCLNT_SHAREMODE_EXCLUSIVE, format, ptr::null_mut()) == S_OK)
+                    .ok_or_else(|| stream_failure(StreamErrorKind::Device, tr!("audio.exclusive_format", name = name, rate = sample_rate, channels = channels)))?;
+                this.format = format;
+                this.sample = sample;
+
+                let mut default_period = 0i64;
+                let mut min_period = 0i64;
+                check((client.get_device_period)(this.client, &mut default_period, &mut min_period), "GetDevicePeriod", name)?;
+                this.period = match buffer_frames {
+                    0 => default_period,
+                    frames => frames_to_hns(frames, sample_rate).max(min_period),
+                };
+
+                // Con eventos, búfer y periodo son iguales. Si el tamaño no
+                // encaja con el hardware, Windows dice cuál usar y hay que
+                // empezar con un cliente nuevo.
+                let mut hr = (client.initialize)(this.client, AUDCLNT_SHAREMODE_EXCLUSIVE, AUDCLNT_STREAMFLAGS_EVENTCALLBACK, this.period, this.period, &this.format, ptr::null());
+                if hr == AUDCLNT_E_BUFFER_SIZE_NOT_ALIGNED {
+                    let mut frames = 0u32;
+                    check((client.get_buffer_size)(this.client, &mut frames), "GetBufferSize", name)?;
+                    release(this.client);
+                    this.client = ptr::null_mut();
+                    this.client = activate(this.device, name)?;
+                    this.period = frames_to_hns(frames, sample_rate);
+                    let client = vtbl::<AudioClientVtbl>(this.client);
+                    hr = (client.initialize)(this.client, AUDCLNT_SHAREMODE_EXCLUSIVE, AUDCLNT_STREAMFLAGS_EVENTCALLBACK, this.period, this.period, &this.format, ptr::null());
+                }
+                check(hr, "IAudioClient::Initialize", name)?;
+
+                let client = vtbl::<AudioClientVtbl>(this.client);
+                this.event = CreateEventW(ptr::null_mut(), 0, 0, ptr::null());
+                if this.event.is_null() {
+                    return Err(stream_failure(StreamErrorKind::Device, tr!("audio.exclusive_error", what = "CreateEventW", hr = "null")));
+                }
+                check((client.set_event_handle)(this.client, this.event), "SetEventHandle", name)?;
+                check((client.get_service)(this.client, &IID_IAUDIO_CAPTURE_CLIENT, &mut this.capture), "GetService", name)?;
+                check((client.start)(this.client), "IAudioClient::Start", name)?;
+                tracing::info!("WASAPI exclusivo en {}: {}", name, this.format());
+                Ok(this)
+            }
+        }
+
+        pub fn sample_rate(&self) -> u32 {
+            self.format.samples_per_sec
+        }
+
+        pub fn channels(&self) -> usize {
+            self.format.channels as usize
+        }
+
+        pub fn format(&self) -> String {
+            let WaveFormatExtensible { samples_per_sec, channels, valid_bits_per_sample, .. } = self.format;
+            format!(
+                "WASAPI exclusivo {}Hz, {}ch, {} bits, {:.1} ms",
+                samples_per_sec, channels, valid_bits_per_sample, self.period as f64 / 10_000.0,
+            )
+        }
+
+        pub fn read(&mut self, samples: &mut Vec<f32>) -> Result<()> {
+            let channels = self.channels();
+            let name = self.name.as_str();
+            unsafe {
+                if WaitForSingleObject(self.event, WAIT_MS) != WAIT_OBJECT_0 {
+                    return Ok(());
+                }
+                let capture = vtbl::<CaptureClientVtbl>(self.capture);
+                loop {
+                    let mut packet = 0u32;
+                    check((capture.get_next_packet_size)(self.capture, &mut packet), "GetNextPacketSize", name)?;
+                    if packet == 0 {
+                        break;
+                    }
+                    let mut data: *mut u8 = ptr::null_mut();
+                    let mut frames = 0u32;
+                    let mut flags = 0u32;
+                    check((capture.get_buffer)(self.capture, &mut data, &mut frames, &mut flags, ptr::null_mut(), ptr::null_mut()), "GetBuffer", name)?;
+                    let count = frames as usize * channels;
+                    if flags & AUDCLNT_BUFFERFLAGS_SILENT != 0 || data.is_null() {
+                        samples.resize(samples.len() + count, 0.0);
+                    } else {
+                        let bytes = std::slice::from_raw_parts(data, count * self.sample.bytes());
+                        decode(bytes, self.sample, samples);
+                    }
+                    check((capture.release_buffer)(self.capture, frames), "ReleaseBuffer", name)?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Muestras del búfer a `f32` en [-1, 1].
+    fn decode(bytes: &[u8], sample: Sample, out: &mut Vec<f32>) {
+        const I32_SCALE: f32 = 2_147_483_648.0;
+        match sample {
+            Sample::I16 => out.extend(bytes.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32_768.0)),
+            Sample::I24 => out.extend(bytes.chunks_exact(3).map(|b| i32::from_le_bytes([0, b[0], b[1], b[2]]) as f32 / I32_SCALE)),
+            Sample::I32 => out.extend(bytes.chunks_exact(4).map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / I32_SCALE)),
+            Sample::F32 => out.extend(bytes.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))),
+        }
+    }
+
+    impl Drop for Capture {
+        fn drop(&mut self) {
+            unsafe {
+                if !self.client.is_null() {
+                    (vtbl::<AudioClientVtbl>(self.client).stop)(self.client);
+                }
+                release(self.capture);
+                release(self.client);
+                release(self.device);
+                if !self.event.is_null() {
+                    CloseHandle(self.event);
+                }
+                CoUninitialize();
+            }
+        }
+    }
+}
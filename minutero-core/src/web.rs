@@ -0,0 +1,593 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+use crate::data::TranscriptSegment;
+use crate::redaction::{RedactionSettings, Redactor};
+use crate::subtitles::{escape, vtt_time};
+
+// ── Página web de acompañamiento ───────────────────────────────────────────
+//
+// Un servidor HTTP mínimo dentro de la aplicación sirve una página que
+// muestra la minuta en vivo y un botón de iniciar/detener, para seguir los
+// subtítulos desde el móvil u otro equipo de la red. La página consulta
+// `/api/live` cada segundo; los botones hacen `POST /api/start` y
+// `/api/stop`, que llegan a la interfaz como `WebCommand`.
+//
+// Con varias sesiones a la vez (ver `rooms`), `GET /api/sessions` las lista
+// y cada una tiene `GET /api/sessions/<id>` y `POST /api/sessions/<id>/start`
+// o `/stop`; la página muestra la de `?session=<id>`. La sesión
+// `MAIN_SESSION` es la de la ventana, y las rutas cortas apuntan a ella.
+//
+// Para eventos híbridos, cada sesión da también subtítulos en directo que
+// pueden leer OBS, vMix o un reproductor: `captions.vtt` (WebVTT con lo
+// último dicho), `captions.m3u8` (playlist HLS de subtítulos, con segmentos
+// `captions/<n>.vtt`) y `captions.txt` (solo la última línea, para fuentes
+// de texto). Cuelgan de `/api/sessions/<id>/` o, los de la ventana, de `/`.
+// Un reproductor web en otro origen solo puede leerlos con el token
+// configurado en la URL: sin token no se manda `Access-Control-Allow-Origin`,
+// para que cualquier web abierta en el navegador no lea la reunión de
+// `127.0.0.1`.
+//
+// Por defecto solo escucha en 127.0.0.1. Abierto a la red, conviene poner un
+// token: se pasa como `?token=` en la URL de la página. Como todo lo que sale
+// del equipo, el texto va anonimizado si la anonimización está activada.
+//
+// Iniciar y detener piden siempre token, para que cualquier web abierta en
+// el navegador no pueda mandar un formulario a `127.0.0.1`: el configurado o,
+// sin él y solo en local, uno que se genera al abrir el servidor y va dentro
+// de la página. La página lo manda en la cabecera `X-Minutero-Token`, que
+// otro origen no puede poner sin permiso CORS, y además se rechaza el POST
+// cuyo `Origin` no sea el propio servidor. En la red sin token configurado,
+// la página solo se puede ver.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Escuchar en todas las interfaces en vez de solo en 127.0.0.1.
+    pub lan: bool,
+    /// Vacío = sin token.
+    pub token: String,
+}
+
+impl Default for WebSettings {
+    fn default() -> Self {
+        Self { enabled: false, port: 8765, lan: false, token: String::new() }
+    }
+}
+
+impl WebSettings {
+    fn address(&self) -> String {
+        let host = if self.lan { "0.0.0.0" } else { "127.0.0.1" };
+        format!("{}:{}", host, self.port)
+    }
+
+    /// Token para iniciar y detener: el configurado o, solo en local, uno
+    /// nuevo en cada arranque del servidor.
+    fn control_token(&self) -> Option<String> {
+        match self.token.trim() {
+            "" if self.lan => None,
+            "" => {
+                let mut bytes = [0u8; 16];
+                OsRng.fill_bytes(&mut bytes);
+                Some(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+            }
+            token => Some(token.to_string()),
+        }
+    }
+
+    /// Dirección de la página en este equipo, con el token si lo hay.
+    pub fn local_url(&self) -> String {
+        self.local_url_for("")
+    }
+
+    /// Igual, para otra ruta de la sesión de la ventana (`captions.m3u8`…).
+    pub fn local_url_for(&self, path: &str) -> String {
+        match self.token.trim() {
+            "" => format!("http://127.0.0.1:{}/{}", self.port, path),
+            token => format!("http://127.0.0.1:{}/{}?token={}", self.port, path, percent_encode(token)),
+        }
+    }
+}
+
+/// Sesión de la ventana; las salas van a partir de 1.
+pub const MAIN_SESSION: usize = 0;
+
+/// Lo que cubre `captions.vtt`, hacia atrás desde lo último transcrito.
+const CAPTION_WINDOW_SECS: f64 = 30.0;
+/// Duración de cada segmento HLS y cuántos lista la playlist en directo.
+const HLS_SEGMENT_SECS: f64 = 6.0;
+const HLS_PLAYLIST_SEGMENTS: u64 = 5;
+
+/// Lo que ve la página de una sesión.
+#[derive(Clone, Debug, Default, Serialize)]
+struct LiveView {
+    id: usize,
+    title: String,
+    running: bool,
+    status: String,
+    segments: Vec<LiveSegment>,
+}
+
+/// Entrada de `GET /api/sessions`.
+#[derive(Serialize)]
+struct SessionSummary<'a> {
+    id: usize,
+    title: &'a str,
+    running: bool,
+    status: &'a str,
+    segments: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct LiveSegment {
+    speaker: String,
+    text: String,
+    start_secs: f64,
+    end_secs: f64,
+}
+
+/// Órdenes de la página para la interfaz.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebCommand {
+    Start(usize),
+    Stop(usize),
+}
+
+/// Servidor en marcha. Al soltarlo deja de escuchar.
+pub struct WebServer {
+    views: Arc<Mutex<BTreeMap<usize, LiveView>>>,
+    commands: Receiver<WebCommand>,
+    stop: Arc<AtomicBool>,
+    settings: WebSettings,
+    redactor: Option<Redactor>,
+}
+
+impl WebServer {
+    pub fn start(settings: &WebSettings, redaction: &RedactionSettings) -> Result<Self> {
+        let address = settings.address();
+        let server = Server::http(&address).map_err(|e| anyhow!("{}: {}", address, e))?;
+        let views = Arc::new(Mutex::new(BTreeMap::new()));
+        let (tx, commands) = channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handler = Handler {
+            views: views.clone(),
+            tx,
+            token: settings.token.trim().to_string(),
+            control_token: settings.control_token(),
+            lan: settings.lan,
+            port: settings.port,
+        };
+        let stopped = stop.clone();
+        thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                match server.recv_timeout(Duration::from_millis(200)) {
+                    Ok(Some(request)) => handler.handle(request),
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::error!("Servidor web: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+        tracing::info!("Página web en http://{}/", address);
+        Ok(Self {
+            views,
+            commands,
+            stop,
+            settings: settings.clone(),
+            redactor: redaction.enabled.then(|| Redactor::new(redaction)),
+        })
+    }
+
+    /// Configuración con la que se abrió; si cambia hay que reabrirlo.
+    pub fn settings(&self) -> &WebSettings {
+        &self.settings
+    }
+
+    /// Sustituye lo que muestra la página de la sesión `id`.
+    pub fn publish(&self, id: usize, title: &str, running: bool, status: &str, segments: &[TranscriptSegment]) {
+        let segments = segments.iter()
+            .map(|s| LiveSegment {
+                speaker: s.name.clone(),
+                text: match &self.redactor {
+                    Some(redactor) => redactor.redact(&s.text),
+                    None => s.text.clone(),
+                },
+                start_secs: s.start_secs,
+                end_secs: s.end_secs,
+            })
+            .collect();
+        let view = LiveView { id, title: title.to_string(), running, status: status.to_string(), segments };
+        self.views.lock().unwrap().insert(id, view);
+    }
+
+    /// Olvida las sesiones que ya no existen.
+    pub fn retain_sessions(&self, ids: &[usize]) {
+        self.views.lock().unwrap().retain(|id, _| ids.contains(id));
+    }
+
+    /// Órdenes recibidas desde la última llamada.
+    pub fn commands(&self) -> Vec<WebCommand> {
+        self.commands.try_iter().collect()
+    }
+}
+
+impl Drop for WebServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+struct Handler {
+    views: Arc<Mutex<BTreeMap<usize, LiveView>>>,
+    tx: Sender<WebCommand>,
+    token: String,
+    /// `None`: no se puede iniciar ni detener (red sin token).
+    control_token: Option<String>,
+    lan: bool,
+    port: u16,
+}
+
+impl Handler {
+    fn handle(&self, request: Request) {
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+        let query_token = query.split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .map(percent_decode);
+        let authorized = self.token.is_empty() || query_token.as_deref() == Some(self.token.as_str());
+        // En local solo se atiende a 127.0.0.1 o localhost, para que otro
+        // dominio que resuelva a este equipo no pase por el propio origen
+        let local_host = self.lan || header(&request, "Host")
+            .is_some_and(|host| host == format!("127.0.0.1:{}", self.port) || host == format!("localhost:{}", self.port));
+
+        let session = path.strip_prefix("/api/sessions/").map(|rest| match rest.split_once('/') {
+            Some((id, action)) => (id.parse::<usize>().ok(), action),
+            None => (rest.parse::<usize>().ok(), ""),
+        });
+
+        let response = match (request.method(), path, session) {
+            _ if !authorized || !local_host => text(403, "403 Forbidden"),
+            (Method::Post, _, _) if !self.may_control(&request, query_token.as_deref()) => text(403, "403 Forbidden"),
+            (Method::Get, "/", _) => {
+                // Como literal JSON, que también vale en JavaScript
+                let control = serde_json::to_string(self.control_token.as_deref().unwrap_or_default()).unwrap_or_default();
+                let page = PAGE.replace("\"{{CONTROL_TOKEN}}\"", &control);
+                Response::from_string(page).with_header(content_type("text/html; charset=utf-8"))
+            }
+            (Method::Get, "/api/sessions", _) => {
+                let views = self.views.lock().unwrap();
+                let summaries: Vec<SessionSummary> = views.values()
+                    .map(|v| SessionSummary {
+                        id: v.id,
+                        title: &v.title,
+                        running: v.running,
+                        status: &v.status,
+                        segments: v.segments.len(),
+                    })
+                    .collect();
+                json(&summaries)
+            }
+            (Method::Get, "/api/live", _) => self.live(MAIN_SESSION),
+            (Method::Get, _, Some((Some(id), action))) if action.starts_with("captions") => {
+                self.captions(id, action, query)
+            }
+            (Method::Get, _, None) if path.starts_with("/captions") => self.captions(MAIN_SESSION, &path[1..], query),
+            (Method::Post, "/api/start", _) => self.command(WebCommand::Start(MAIN_SESSION)),
+            (Method::Post, "/api/stop", _) => self.command(WebCommand::Stop(MAIN_SESSION)),
+            (Method::Get, _, Some((Some(id), ""))) => self.live(id),
+            (Method::Post, _, Some((Some(id), "start"))) => self.command(WebCommand::Start(id)),
+            (Method::Post, _, Some((Some(id), "stop"))) => self.command(WebCommand::Stop(id)),
+            _ => text(404, "404 Not Found"),
+        };
+        if let Err(e) = request.respond(response) {
+            tracing::debug!("Servidor web: {}", e);
+        }
+    }
+
+    /// Un POST que cambia algo: con el token de control y desde la propia
+    /// página (o sin `Origin`, como curl).
+    fn may_control(&self, request: &Request, query_token: Option<&str>) -> bool {
+        let Some(control) = &self.control_token else { return false };
+        let token_ok = query_token == Some(control.as_str())
+            || header(request, "X-Minutero-Token").is_some_and(|t| t == *control);
+        let same_origin = match (header(request, "Origin"), header(request, "Host")) {
+            (None, _) => true,
+            (Some(origin), Some(host)) => origin == format!("http://{}", host),
+            (Some(_), None) => false,
+        };
+        token_ok && same_origin
+    }
+
+    fn live(&self, id: usize) -> Response<std::io::Cursor<Vec<u8>>> {
+        match self.views.lock().unwrap().get(&id) {
+            Some(view) => json(view),
+            None => text(404, "404 Not Found"),
+        }
+    }
+
+    /// Subtítulos en directo de la sesión `id`; `action` es la ruta a
+    /// partir de la sesión (`captions.vtt`, `captions/3.vtt`…).
+    fn captions(&self, id: usize, action: &str, query: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+        let views = self.views.lock().unwrap();
+        let Some(view) = views.get(&id) else { return text(404, "404 Not Found") };
+        // La "hora" del directo: hasta donde hay transcrito
+        let now = view.segments.iter().map(|s| s.end_secs).fold(0.0, f64::max);
+        let response = match action {
+            "captions.vtt" => {
+                let recent = view.segments.iter().filter(|s| s.end_secs >= now - CAPTION_WINDOW_SECS);
+                Response::from_string(render_cues(recent, "")).with_header(content_type("text/vtt; charset=utf-8"))
+            }
+            "captions.txt" => {
+                let last = view.segments.last().map(|s| format!("{}: {}", s.speaker, s.text)).unwrap_or_default();
+                text(200, &last)
+            }
+            "captions.m3u8" => Response::from_string(playlist(now, view.running, query))
+                .with_header(content_type("application/vnd.apple.mpegurl")),
+            _ => {
+                let Some(n) = action.strip_prefix("captions/")
+                    .and_then(|rest| rest.strip_suffix(".vtt"))
+                    .and_then(|n| n.parse::<u64>().ok())
+                else {
+                    return text(404, "404 Not Found");
+                };
+                let (start, end) = (n as f64 * HLS_SEGMENT_SECS, (n + 1) as f64 * HLS_SEGMENT_SECS);
+                let cues = view.segments.iter().filter(|s| s.end_secs > start && s.start_secs < end);
+                // Sin vídeo de referencia, el tiempo 0 es el inicio de la sesión
+                Response::from_string(render_cues(cues, "X-TIMESTAMP-MAP=MPEGTS:0,LOCAL:00:00:00.000\n"))
+                    .with_header(content_type("text/vtt; charset=utf-8"))
+            }
+        };
+        // Los reproductores web suelen estar en otro origen, pero solo se
+        // les deja leer con el token configurado (que ya ha pasado
+        // `authorized`); el generado para iniciar y detener no cuenta
+        if self.token.is_empty() {
+            return response;
+        }
+        response.with_header(Header::from_bytes("Access-Control-Allow-Origin", "*").expect("cabecera válida"))
+    }
+
+    fn command(&self, command: WebCommand) -> Response<std::io::Cursor<Vec<u8>>> {
+        let id = match command {
+            WebCommand::Start(id) | WebCommand::Stop(id) => id,
+        };
+        if !self.views.lock().unwrap().contains_key(&id) {
+            return text(404, "404 Not Found");
+        }
+        let _ = self.tx.send(command);
+        text(202, "202 Accepted")
+    }
+}
+
+fn render_cues<'a>(segments: impl Iterator<Item = &'a LiveSegment>, header: &str) -> String {
+    let mut out = format!("WEBVTT\n{}", header);
+    for s in segments {
+        out.push_str(&format!(
+            "\n{} --> {}\n<v {}>{}\n",
+            vtt_time(s.start_secs),
+            vtt_time(s.end_secs.max(s.start_secs)),
+            escape(&s.speaker),
+            escape(&s.text),
+        ));
+    }
+    out
+}
+
+/// Playlist HLS de subtítulos con los últimos segmentos completos. Al parar
+/// la sesión se cierra con `#EXT-X-ENDLIST`.
+fn playlist(now: f64, running: bool, query: &str) -> String {
+    let complete = (now / HLS_SEGMENT_SECS).floor() as u64;
+    let first = complete.saturating_sub(HLS_PLAYLIST_SEGMENTS);
+    let query = if query.is_empty() { String::new() } else { format!("?{}", query) };
+    let mut out = format!(
+        "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:{}\n#EXT-X-MEDIA-SEQUENCE:{}\n",
+        HLS_SEGMENT_SECS as u64, first,
+    );
+    for n in first..complete {
+        out.push_str(&format!("#EXTINF:{:.3},\ncaptions/{}.vtt{}\n", HLS_SEGMENT_SECS, n, query));
+    }
+    if !running {
+        out.push_str("#EXT-X-ENDLIST\n");
+    }
+    out
+}
+
+fn header(request: &Request, name: &'static str) -> Option<String> {
+    request.headers().iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str().to_string())
+}
+
+/// Valor de la query con `%XX` y `+` decodificados.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3)
+            .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => out.push(b' '),
+            (b, _) => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Para poner el token en la URL de la página.
+fn percent_encode(value: &str) -> String {
+    value.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn json(value: &impl Serialize) -> Response<std::io::Cursor<Vec<u8>>> {
+    let body = serde_json::to_string(value).unwrap_or_default();
+    Response::from_string(body).with_header(content_type("application/json"))
+}
+
+fn content_type(value: &str) -> Header {
+    Header::from_bytes("Content-Type", value).expect("cabecera válida")
+}
+
+fn text(status: u16, body: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(body)
+        .with_status_code(status)
+        .with_header(content_type("text/plain; charset=utf-8"))
+}
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="es">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Minutero</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 0; background: #111; color: #eee; }
+  header { position: sticky; top: 0; display: flex; gap: 1em; align-items: center;
+           padding: .6em 1em; background: #222; }
+  h1 { font-size: 1.1em; margin: 0; flex: 1; }
+  button { font-size: 1em; padding: .4em 1em; }
+  #status { padding: .3em 1em; color: #aaa; font-size: .9em; }
+  #segments { padding: 0 1em 2em; font-size: 1.2em; line-height: 1.5; }
+  .speaker { color: #7cc7ff; font-weight: bold; }
+  .time { color: #888; font-size: .8em; margin-right: .4em; }
+</style>
+</head>
+<body>
+<header><h1 id="title">Minutero</h1><button id="toggle">▶</button></header>
+<div id="status"></div>
+<div id="segments"></div>
+<script>
+const query = location.search;
+const control = "{{CONTROL_TOKEN}}";
+const session = new URLSearchParams(query).get("session") || "0";
+const api = `/api/sessions/${session}`;
+let running = false, shown = -1;
+const time = s => `${String(Math.floor(s / 60)).padStart(2, "0")}:${String(Math.floor(s % 60)).padStart(2, "0")}`;
+async function refresh() {
+  try {
+    const live = await (await fetch(api + query)).json();
+    running = live.running;
+    document.getElementById("title").textContent = live.title || "Minutero";
+    document.getElementById("status").textContent = live.status;
+    document.getElementById("toggle").textContent = running ? "⏹" : "▶";
+    if (live.segments.length !== shown) {
+      const box = document.getElementById("segments");
+      const follow = innerHeight + scrollY >= document.body.scrollHeight - 40;
+      box.replaceChildren(...live.segments.map(s => {
+        const p = document.createElement("p");
+        p.innerHTML = `<span class="time"></span><span class="speaker"></span> `;
+        p.children[0].textContent = time(s.start_secs);
+        p.children[1].textContent = s.speaker + ":";
+        p.append(s.text);
+        return p;
+      }));
+      shown = live.segments.length;
+      if (follow) scrollTo(0, document.body.scrollHeight);
+    }
+  } catch (e) {
+    document.getElementById("status").textContent = "⚠ " + e;
+  }
+}
+document.getElementById("toggle").hidden = !control;
+document.getElementById("toggle").onclick = () =>
+  fetch(api + (running ? "/stop" : "/start") + query, { method: "POST", headers: { "X-Minutero-Token": control } }).then(refresh);
+refresh();
+setInterval(refresh, 1000);
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    fn segment(text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            name: "Ana".into(),
+            text: text.into(),
+            start_secs: 0.0,
+            end_secs: 2.0,
+            words: Vec::new(),
+            reviewed: false,
+            lang: None,
+            original: None,
+            edits: Vec::new(),
+        }
+    }
+
+    /// Servidor en un puerto libre con una sesión publicada.
+    fn server(token: &str) -> WebServer {
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let settings = WebSettings { enabled: true, port, lan: false, token: token.into() };
+        let server = WebServer::start(&settings, &RedactionSettings::default()).unwrap();
+        server.publish(MAIN_SESSION, "Junta", true, "", &[segment("Aprobado el presupuesto")]);
+        server
+    }
+
+    /// Respuesta completa (cabeceras y cuerpo) a un GET desde `origin`.
+    fn get(server: &WebServer, path: &str, origin: &str) -> String {
+        let port = server.settings().port;
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nOrigin: {}\r\nConnection: close\r\n\r\n",
+            path, port, origin,
+        ).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn allows_cross_origin(response: &str) -> bool {
+        response.lines().any(|l| l.to_ascii_lowercase().starts_with("access-control-allow-origin"))
+    }
+
+    #[test]
+    fn captions_stay_same_origin_without_token() {
+        let server = server("");
+        for path in ["/captions.txt", "/captions.vtt", "/captions.m3u8", "/api/sessions/0/captions.txt"] {
+            let response = get(&server, path, "http://evil.example");
+            assert!(response.starts_with("HTTP/1.1 200"), "{}: {}", path, response);
+            assert!(!allows_cross_origin(&response), "{}: {}", path, response);
+        }
+    }
+
+    #[test]
+    fn captions_cross_origin_with_token() {
+        let server = server("s3cr3t");
+        let response = get(&server, "/captions.txt?token=s3cr3t", "http://player.example");
+        assert!(response.contains("Ana: Aprobado el presupuesto"), "{}", response);
+        assert!(allows_cross_origin(&response), "{}", response);
+
+        let response = get(&server, "/captions.txt", "http://player.example");
+        assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+        assert!(!allows_cross_origin(&response), "{}", response);
+    }
+}
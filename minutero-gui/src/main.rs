@@ -1,16 +1,19 @@
-mod data;
-mod audio;
 mod ui;
-mod video;
-mod system_audio;
 use anyhow::Result;
 use eframe::egui;
-use crate::ui::TranscriptorApp;
+use minutero_core::{bench, crash, logging, settings};
+use crate::ui::{theme_preference, TranscriptorApp};
 use std::env;
 
 fn main() -> Result<()> {
     env::set_var("ALSA_CONFIG_PATH", "/dev/null");
 
+    // `--bench [archivo]`: mide los modelos descargados y sale sin abrir la ventana.
+    let args: Vec<String> = env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--bench") {
+        return bench::run_cli(args.get(pos + 1).map(String::as_str));
+    }
+
     #[cfg(target_os = "linux")]
     {
         use std::fs::OpenOptions;
@@ -20,6 +23,10 @@ fn main() -> Result<()> {
         }
     }
 
+    logging::init(settings::AppSettings::load().log_level);
+    crash::install();
+    tracing::info!("Minutero {} iniciado", env!("CARGO_PKG_VERSION"));
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([700.0, 600.0]),
@@ -29,6 +36,10 @@ fn main() -> Result<()> {
     eframe::run_native(
         "Minutador de Transcripción Multicanal",
         options,
-        Box::new(|_cc| Ok(Box::new(TranscriptorApp::default()))),
+        Box::new(|cc| {
+            let app = TranscriptorApp::default();
+            cc.egui_ctx.set_theme(theme_preference(app.settings.theme));
+            Ok(Box::new(app))
+        }),
     ).map_err(|e| anyhow::anyhow!("Error en eframe: {:?}", e))
 }
\ No newline at end of file
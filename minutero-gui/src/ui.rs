@@ -0,0 +1,4976 @@
+use anyhow::{Result, anyhow};
+use eframe::egui;
+use std::sync::mpsc::{Receiver, channel};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use minutero_core::data::{
+    AudioMessage, DeviceInfo, InterlocutorProfile, LanguageConfig, MuteControl, PcmEncoding, PcmFormat, Preset, ReplacementRule, RestartPolicy, RetranscribeMessage,
+    SessionType, SourceType, TranscriptSegment, TranscriptionConfig, TranscriptionStyle, VideoMessage, WhisperTuning,
+    SOURCE_LANGUAGES, MAX_CHUNK_SECS, MAX_ENERGY_SAVER_SECS, MAX_LATENCY_SECS, MAX_OVERLAP_PERCENT, MIN_CHUNK_SECS,
+};
+use minutero_core::audio::{audio_thread_main, download_status, get_available_devices, host_names, DEFAULT_MODEL_MIRROR};
+use minutero_core::video::video_transcription_thread;
+use minutero_core::alerts::{matched_keywords, parse_keywords};
+use minutero_core::notify::desktop_notification;
+use minutero_core::postprocess::compile_rule;
+use minutero_core::hallucinations;
+use minutero_core::punctuation;
+use minutero_core::redaction::{self, redact_document};
+use minutero_core::encryption::{self, EncryptionSettings, KeySource, ENCRYPTED_EXTENSION};
+use minutero_core::integrity::{self, ChainCheck};
+use minutero_core::preload::{self, preload_thread};
+use minutero_core::stats::{compute_stats, format_duration};
+use minutero_core::export::{
+    absolute_output_dir, check_output_dir, entries_markdown, file_stem, save_local_session, save_minuta, save_speaker_minutas, unique_path,
+    read_minuta_json, ExportFormat, MinutaDocument, FILENAME_VARIABLES,
+};
+use minutero_core::playback::Player;
+use minutero_core::speech::Reader;
+use minutero_core::history::{list_sessions, SessionEntry};
+use minutero_core::actions::{self, ActionItem};
+use minutero_core::retranscribe::retranscribe_thread;
+use minutero_core::preroll::{preroll_profiles, PreRoll, MAX_PREROLL_SECS};
+use minutero_core::device_test::{self, DeviceTest};
+use minutero_core::recording::{has_tracks, MixRecorder, RecordingFormat};
+use minutero_core::dataset::{self, DatasetFormat};
+use minutero_core::resources;
+use minutero_core::split::SplitReason;
+use minutero_core::stop::StopSignal;
+use minutero_core::template::{self, SummaryPrompt, DEFAULT_MARKDOWN};
+use minutero_core::system_audio::{
+    check_loopback_status, detect_os, get_loopback_devices, linux_sound_server, LoopbackStatus, LoopbackInfo,
+};
+use minutero_core::virtual_sink::{monitor_name, VirtualSink};
+use minutero_core::process_loopback;
+use minutero_core::capture_tools::CaptureTool;
+use minutero_core::i18n::{self, tr, UiLanguage};
+use minutero_core::hooks::{self, HookEvent};
+use minutero_core::integrations;
+use minutero_core::mqtt::MqttPublisher;
+use minutero_core::telegram::TelegramLive;
+use minutero_core::discord::DiscordLive;
+use minutero_core::ndi::NdiOutput;
+use minutero_core::storage::{self, StorageKind};
+use minutero_core::rooms::Room;
+use minutero_core::devices::{self, DeviceSettings, StreamOptions};
+use minutero_core::roster::{self, Participant};
+use minutero_core::speaker_id::{self, Enrollment, VoiceMatcher};
+use minutero_core::web::{WebCommand, WebServer, WebSettings, MAIN_SESSION};
+use minutero_core::calendar::CalendarState;
+use minutero_core::conferencing::{self, ConferencingWatcher};
+use minutero_core::logging::{self, LogLevel};
+use minutero_core::crash;
+use minutero_core::diagnostics;
+use minutero_core::settings::{AppSettings, Theme, MAX_TRANSCRIPT_FONT_SIZE, MIN_TRANSCRIPT_FONT_SIZE};
+
+pub fn theme_preference(theme: Theme) -> egui::ThemePreference {
+    match theme {
+        Theme::System => egui::ThemePreference::System,
+        Theme::Light  => egui::ThemePreference::Light,
+        Theme::Dark   => egui::ThemePreference::Dark,
+    }
+}
+
+const COPY_ALL_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT), egui::Key::C);
+const COPY_SELECTION_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT), egui::Key::S);
+
+/// Cuánto puede adelantarse una intervención que llega tarde, en segundos de
+/// sesión, además de un fragmento: lo que tarde Whisper en la fuente lenta.
+const REORDER_MARGIN_SECS: f64 = 10.0;
+
+/// Niveles que se guardan por fuente para la tira de forma de onda: llegan
+/// cinco por segundo, así que son los últimos 30 s.
+const LEVEL_HISTORY: usize = 150;
+
+/// Color del retraso de una fuente: verde si el texto llega antes de que se
+/// complete el siguiente fragmento, amarillo si va hasta uno por detrás y
+/// rojo si el equipo no da abasto con el modelo.
+fn latency_color(latency_secs: f32, chunk_secs: f32) -> egui::Color32 {
+    if latency_secs <= chunk_secs {
+        egui::Color32::from_rgb(80, 170, 80)
+    } else if latency_secs <= 2.0 * chunk_secs {
+        egui::Color32::from_rgb(230, 160, 40)
+    } else {
+        egui::Color32::from_rgb(220, 60, 60)
+    }
+}
+
+/// Estado de una fuente en captura, para el panel de salud.
+#[derive(Default)]
+pub struct StreamHealth {
+    pub device: String,
+    /// Host, frecuencia y canales tal como se abrieron.
+    pub format: String,
+    pub last_audio: Option<Instant>,
+    pub rtf: Option<f32>,
+    pub backlog_secs: f32,
+    /// Del audio captado al texto, en el último fragmento.
+    pub latency_secs: Option<f32>,
+    pub last_error: Option<String>,
+    /// Último reintento tras un fallo: (intento, máximo, cuándo se reabre).
+    pub restart: Option<(u32, u32, Instant)>,
+    /// Se agotaron los reintentos (cuántos había).
+    pub gave_up: Option<u32>,
+}
+
+/// Un fragmento transcrito con los dos modelos, en el modo comparación.
+pub struct ComparedChunk {
+    pub profile: String,
+    pub start_secs: f64,
+    pub model: String,
+    pub text: String,
+    pub rtf: f32,
+    pub compared_model: String,
+    pub compared_text: String,
+    pub compared_rtf: f32,
+}
+
+/// Asistente para registrar la voz de un participante habitual.
+pub struct EnrollWizard {
+    /// Índice en `settings.roster`.
+    pub participant: usize,
+    /// Micrófono elegido, por id en `all_input_devices`.
+    pub device_id: usize,
+    pub recording: Option<Enrollment>,
+    /// Resultado de la última grabación; con `Ok` la voz ya está guardada.
+    pub result: Option<Result<(), String>>,
+}
+
+// Enum para la navegación
+#[derive(Debug, PartialEq, Eq)]
+pub enum View {
+    Transcription,
+    Rooms,
+    Review,
+    Video,
+    History,
+    Actions,
+    Stats,
+    Log,
+    Settings,
+}
+
+// Pestañas dentro de Configuración
+#[derive(Debug, PartialEq, Eq)]
+pub enum SettingsTab {
+    General,
+    PostProcessing,
+    Integrations,
+}
+
+enum ExitChoice {
+    SaveAndQuit,
+    Quit,
+    Cancel,
+}
+
+pub struct TranscriptorApp {
+    // ── Navegación ─────────────────────────────────────────────────────────
+    pub current_view: View,
+    pub settings_tab: SettingsTab,
+
+    // ── Transcripción en tiempo real ───────────────────────────────────────
+    /// Intervenciones de la minuta en vivo. El texto completo no se guarda
+    /// aparte: se compone al exportar, copiar o guardar.
+    pub segments: Vec<TranscriptSegment>,
+    /// Sube con cada cambio en el texto de las intervenciones, para notar
+    /// ediciones sin recorrer la minuta entera cada fotograma.
+    pub transcript_version: usize,
+    /// Hora de reloj del segundo 0 de la minuta en pantalla.
+    pub session_started_at: Option<chrono::DateTime<chrono::Local>>,
+    /// Segundos que se suman a lo que llega de la captura en curso: la
+    /// distancia al principio de la sesión cuando se continúa una minuta.
+    pub time_offset: f64,
+    /// Última selección en la minuta (primera y última intervención).
+    pub transcript_selection: Option<(usize, usize)>,
+    /// Intervención marcada en la minuta.
+    pub transcript_cursor: Option<usize>,
+    /// Ver la minuta tal como salió de Whisper, sin las correcciones.
+    pub show_transcribed: bool,
+    pub status_message: String,
+    pub model_name: String,
+    /// Segundo modelo para comparar con `model_name` en la captura; no se
+    /// guarda, porque duplica el trabajo de Whisper.
+    pub compare_model: Option<String>,
+    /// Fragmentos transcritos con los dos modelos en la última captura.
+    pub comparison: Vec<ComparedChunk>,
+    pub is_running: bool,
+    pub all_input_devices: Vec<DeviceInfo>,
+    pub all_output_devices: Vec<DeviceInfo>,
+    pub interlocutors: Vec<InterlocutorProfile>,
+    pub output_dir: String,
+    /// Resultado de la última comprobación de escritura de `output_dir`.
+    pub output_dir_error: Option<String>,
+    pub ui_rx: Option<Receiver<AudioMessage>>,
+    /// Mensajes de la precarga del modelo, mientras dura.
+    pub preload_rx: Option<Receiver<AudioMessage>>,
+    /// Último nivel de entrada (rms, pico) de cada fuente en captura.
+    pub input_levels: HashMap<String, (f32, f32)>,
+    /// Últimos niveles rms de cada fuente, del más antiguo al más reciente.
+    pub level_history: HashMap<String, VecDeque<f32>>,
+    /// Dispositivo, rendimiento y último error de cada fuente en captura.
+    pub stream_health: BTreeMap<String, StreamHealth>,
+    /// Publicación en directo de la sesión en curso, si MQTT está activado.
+    pub mqtt: Option<MqttPublisher>,
+    pub telegram: Option<TelegramLive>,
+    pub discord: Option<DiscordLive>,
+    pub ndi: Option<NdiOutput>,
+    /// Página web con la minuta en vivo, si está activada.
+    pub web: Option<WebServer>,
+    /// Configuración con la que falló el último intento de abrirla, para no
+    /// reintentar en cada frame.
+    pub web_error: Option<(WebSettings, String)>,
+    /// Forma de la sesión la última vez que se publicó en la página.
+    pub web_snapshot: Option<(usize, usize, usize, bool, usize)>,
+    /// Alguna sala ha cambiado desde la última vez que se publicaron.
+    pub web_rooms_dirty: bool,
+    /// Silenciar / solo de la captura en curso.
+    pub mute: MuteControl,
+    pub stop_signal: Option<StopSignal>,
+    /// Hilo coordinador de la captura; termina poco después de `stop_signal`.
+    pub audio_thread: Option<thread::JoinHandle<()>>,
+    /// Dispositivos escuchando en espera, si la pre-grabación está activada.
+    pub preroll: Option<PreRoll>,
+    /// Prueba de dispositivo en curso (grabando unos segundos).
+    pub device_test: Option<DeviceTest>,
+    /// Asistente de registro de voz abierto.
+    pub enrollment: Option<EnrollWizard>,
+    /// Grabación de la sesión actual (o de la sesión abierta), para enlazarla
+    /// en la minuta y reproducir intervenciones.
+    pub recording_path: Option<PathBuf>,
+    pub player: Player,
+    /// Lectura en voz alta del resumen.
+    pub reader: Reader,
+    /// Minutas de la carpeta de salida; se vuelve a leer al entrar en la
+    /// pestaña o al pulsar Actualizar.
+    pub history: Option<Vec<SessionEntry>>,
+    pub history_query: String,
+    /// Tareas de `actions.json`; se vuelven a leer al entrar en la pestaña.
+    pub action_items: Option<Vec<ActionItem>>,
+    pub show_done_actions: bool,
+    pub agenda_result: Option<String>,
+    /// Re-transcripción en curso de las pistas de `recording_path`.
+    pub retranscribe_rx: Option<Receiver<RetranscribeMessage>>,
+    pub retranscribe_stop: Option<StopSignal>,
+    pub retranscribe_progress: f32,
+    /// Se pidió cerrar la ventana con una captura en marcha.
+    pub show_exit_confirm: bool,
+    /// Ruta (o error) del último informe de diagnóstico generado.
+    pub diagnostics_result: Option<String>,
+    /// Resultado de la última verificación de cadena y firma.
+    pub integrity_result: Option<String>,
+    pub exit_confirmed: bool,
+    /// Minuta que dejó sin guardar un fallo anterior, pendiente de decidir.
+    pub recovery: Option<PathBuf>,
+    /// Forma de la sesión en la última copia de recuperación, para no
+    /// serializarla en cada frame.
+    pub crash_snapshot: (usize, usize, usize),
+    /// Inicio del bloque de minuta actual y última intervención, para
+    /// partirla (ver `split`).
+    pub block_started: Instant,
+    pub last_segment_at: Option<Instant>,
+    /// Número del bloque actual; `None` mientras no se haya partido nunca.
+    pub split_block: Option<usize>,
+
+    // ── Otras sesiones a la vez ────────────────────────────────────────────
+    pub rooms: Vec<Room>,
+    pub next_room_id: usize,
+
+    // ── Configuración de idioma (global) ───────────────────────────────────
+    pub lang_config: LanguageConfig,
+
+    // ── Preferencias persistentes (idioma de la UI, tema, fuente) ──────────
+    pub settings: AppSettings,
+
+    // ── Loopback ───────────────────────────────────────────────────────────
+    pub loopback_info: Option<LoopbackInfo>,
+    pub show_loopback_setup: bool,
+    /// Salida «Minutero» creada desde el diálogo; se elimina al salir.
+    pub virtual_sink: Option<VirtualSink>,
+    /// Hay PulseAudio/PipeWire para crear la salida virtual.
+    pub virtual_sink_supported: bool,
+
+    // ── Transcripción de vídeo ─────────────────────────────────────────────
+    pub video_file_path: Option<String>,
+    pub video_transcription: String,
+    pub video_status: String,
+    pub video_progress: f32,
+    pub video_is_running: bool,
+    pub video_rx: Option<Receiver<VideoMessage>>,
+    pub video_stop_signal: Option<StopSignal>,
+
+    // ── Sesión y calendario ────────────────────────────────────────────────
+    /// Título de la reunión; vacío = título genérico.
+    pub session_title: String,
+    /// Asistentes separados por comas; vacío = interlocutores activos.
+    pub session_attendees: String,
+    /// Título que puso el calendario, para saber si el usuario lo ha cambiado.
+    pub prefilled_title: String,
+    pub calendar: CalendarState,
+    pub conferencing: ConferencingWatcher,
+}
+
+impl Default for TranscriptorApp {
+    fn default() -> Self {
+        let settings = AppSettings::load();
+        i18n::set_language(settings.ui_language);
+
+        let all_input_devices = get_available_devices(settings.audio_host.as_deref(), true);
+        let all_output_devices = get_loopback_devices();
+
+        let mut app = Self {
+            current_view: View::Transcription,
+            settings_tab: SettingsTab::General,
+            transcript_version: 0,
+            session_started_at: None,
+            time_offset: 0.0,
+            segments: Vec::new(),
+            transcript_selection: None,
+            transcript_cursor: None,
+            show_transcribed: false,
+            status_message: tr!("live.idle"),
+            model_name: String::from("large-v3"),
+            compare_model: None,
+            comparison: Vec::new(),
+            is_running: false,
+            all_input_devices,
+            all_output_devices,
+            interlocutors: Vec::new(),
+            output_dir: String::from("./minutas"),
+            output_dir_error: None,
+            ui_rx: None,
+            preload_rx: None,
+            input_levels: HashMap::new(),
+            level_history: HashMap::new(),
+            stream_health: BTreeMap::new(),
+            mqtt: None,
+            telegram: None,
+            discord: None,
+            ndi: None,
+            web: None,
+            web_error: None,
+            web_snapshot: None,
+            web_rooms_dirty: false,
+            rooms: Vec::new(),
+            next_room_id: 1,
+            mute: MuteControl::default(),
+            stop_signal: None,
+            audio_thread: None,
+            preroll: None,
+            device_test: None,
+            enrollment: None,
+            recording_path: None,
+            player: Player::default(),
+            reader: Reader::default(),
+            history: None,
+            history_query: String::new(),
+            action_items: None,
+            show_done_actions: false,
+            agenda_result: None,
+            retranscribe_rx: None,
+            retranscribe_stop: None,
+            retranscribe_progress: 0.0,
+            show_exit_confirm: false,
+            diagnostics_result: None,
+            integrity_result: None,
+            exit_confirmed: false,
+            recovery: crash::pending_recovery(),
+            crash_snapshot: (0, 0, 0),
+            block_started: Instant::now(),
+            last_segment_at: None,
+            split_block: None,
+            lang_config: LanguageConfig::default(),
+            settings,
+            loopback_info: None,
+            show_loopback_setup: false,
+            virtual_sink: None,
+            virtual_sink_supported: detect_os() == "linux" && linux_sound_server().is_some(),
+            video_file_path: None,
+            video_transcription: String::new(),
+            video_status: tr!("video.idle"),
+            video_progress: 0.0,
+            video_is_running: false,
+            video_rx: None,
+            video_stop_signal: None,
+            session_title: String::new(),
+            session_attendees: String::new(),
+            prefilled_title: String::new(),
+            calendar: CalendarState::default(),
+            conferencing: ConferencingWatcher::default(),
+        };
+
+        if app.settings.devices.profiles.is_empty() {
+            if !app.all_input_devices.is_empty() {
+                app.add_new_profile(SourceType::Input);
+            }
+        } else {
+            app.interlocutors = app.settings.devices.profiles.clone();
+            app.reconcile_profiles();
+        }
+        if app.all_output_devices.is_empty() {
+            app.check_and_prompt_loopback();
+        }
+        app.start_preload();
+
+        app
+    }
+}
+
+impl eframe::App for TranscriptorApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // ── Procesar mensajes de audio en tiempo real ──────────────────────
+        let mut new_segments = Vec::new();
+        let mut session_failed = false;
+        if let Some(rx) = &self.ui_rx {
+            while let Ok(msg) = rx.try_recv() {
+                match msg {
+                    AudioMessage::Status(s) => self.status_message = s,
+                    AudioMessage::ModelDownloadProgress { model, downloaded, total } => {
+                        self.status_message = download_status(&model, downloaded, total);
+                    }
+                    AudioMessage::StreamStarted { profile, source_type, device, format } => {
+                        let icon = match source_type {
+                            SourceType::Input => "🎤",
+                            SourceType::Output => "🔊",
+                            SourceType::Network => "🌐",
+                            SourceType::Pipe => "⏩",
+                            SourceType::Discord => "🎮",
+                        };
+                        let health = self.stream_health.entry(profile).or_default();
+                        health.device = format!("{} {}", icon, device);
+                        health.format = format;
+                        self.status_message = tr!(
+                            "live.streams_running",
+                            n = self.stream_health.len(),
+                            source = self.lang_config.source_label(),
+                            dest = self.lang_config.dest_label(),
+                        );
+                    }
+                    AudioMessage::Level { profile, rms, peak } => {
+                        self.stream_health.entry(profile.clone()).or_default().last_audio = Some(Instant::now());
+                        let history = self.level_history.entry(profile.clone()).or_default();
+                        if history.len() == LEVEL_HISTORY {
+                            history.pop_front();
+                        }
+                        history.push_back(rms);
+                        self.input_levels.insert(profile, (rms, peak));
+                    }
+                    AudioMessage::StreamHealth { profile, rtf, backlog_secs, latency_secs } => {
+                        let health = self.stream_health.entry(profile).or_default();
+                        health.rtf = Some(rtf);
+                        health.backlog_secs = backlog_secs;
+                        health.latency_secs = latency_secs.or(health.latency_secs);
+                    }
+                    AudioMessage::Segment { speaker, start_secs, end_secs, text, words, lang, .. } => {
+                        if !text.trim().is_empty() {
+                            new_segments.push(TranscriptSegment {
+                                name: speaker, text, start_secs, end_secs, words,
+                                reviewed: false, lang, original: None, edits: Vec::new(),
+                            });
+                        }
+                    }
+                    AudioMessage::ModelDownloaded(model) => {
+                        if self.settings.notifications.downloads {
+                            desktop_notification(&tr!("notify.model_ready"), &tr!("notify.model_ready_body", model = model));
+                        }
+                    }
+                    AudioMessage::Comparison { profile, start_secs, model, text, rtf, compared_model, compared_text, compared_rtf } => {
+                        self.comparison.push(ComparedChunk { profile, start_secs, model, text, rtf, compared_model, compared_text, compared_rtf });
+                    }
+                    AudioMessage::StreamError { profile, message, .. } => {
+                        if self.settings.notifications.errors {
+                            desktop_notification(&tr!("notify.stream_error"), &message);
+                        }
+                        self.stream_health.entry(profile).or_default().last_error = Some(message);
+                        self.status_message = tr!("health.stream_failed");
+                    }
+                    AudioMessage::StreamRestarting { profile, attempt, max, delay_secs } => {
+                        let reopen = Instant::now() + Duration::from_secs_f32(delay_secs);
+                        self.stream_health.entry(profile).or_default().restart = Some((attempt, max, reopen));
+                    }
+                    AudioMessage::StreamGaveUp { profile, max } => {
+                        self.stream_health.entry(profile).or_default().gave_up = Some(max);
+                    }
+                    AudioMessage::Error(e) => {
+                        if self.settings.notifications.errors {
+                            desktop_notification(&tr!("notify.stream_error"), &e);
+                        }
+                        self.status_message = tr!("common.error", e = e);
+                    }
+                    AudioMessage::Stopped { pending } => {
+                        self.mqtt = None;
+                        self.telegram = None;
+                        self.discord = None;
+                        self.ndi = None;
+                        // Si sigue en marcha la ha parado un perfil que pide
+                        // parar la sesión al fallar: se guarda como al parar
+                        session_failed = self.is_running;
+                        self.status_message = if pending.is_empty() {
+                            tr!("audio.finished")
+                        } else {
+                            tr!("audio.shutdown_timeout", names = pending.join(", "))
+                        };
+                    }
+                }
+            }
+        }
+        for segment in new_segments {
+            self.insert_segment(segment);
+        }
+        if session_failed {
+            self.stop_capture();
+        }
+
+        // ── Procesar mensajes de la precarga del modelo ────────────────────
+        if let Some(rx) = &self.preload_rx {
+            while let Ok(msg) = rx.try_recv() {
+                // Una captura en marcha tiene sus propios mensajes de estado
+                let idle = !self.is_running;
+                match msg {
+                    AudioMessage::Status(s) if idle => self.status_message = s,
+                    AudioMessage::ModelDownloadProgress { model, downloaded, total } if idle => {
+                        self.status_message = download_status(&model, downloaded, total);
+                    }
+                    AudioMessage::ModelDownloaded(model) => {
+                        if self.settings.notifications.downloads {
+                            desktop_notification(&tr!("notify.model_ready"), &tr!("notify.model_ready_body", model = model));
+                        }
+                    }
+                    AudioMessage::Error(e) => self.status_message = tr!("preload.error", e = e),
+                    _ => {}
+                }
+            }
+        }
+
+        // ── Procesar mensajes de vídeo ─────────────────────────────────────
+        if let Some(rx) = &self.video_rx {
+            while let Ok(msg) = rx.try_recv() {
+                match msg {
+                    VideoMessage::Status(s) => self.video_status = s,
+                    VideoMessage::Progress(p) => self.video_progress = p,
+                    VideoMessage::Segment { timestamp, text } => {
+                        self.video_transcription
+                            .push_str(&format!("[{}] {}\n", timestamp, text));
+                    }
+                    VideoMessage::ModelDownloaded(model) => {
+                        if self.settings.notifications.downloads {
+                            desktop_notification(&tr!("notify.model_ready"), &tr!("notify.model_ready_body", model = model));
+                        }
+                    }
+                    VideoMessage::Done => {
+                        self.video_is_running = false;
+                        self.video_status = tr!("video.done");
+                        if let Err(e) = self.save_video_transcript() {
+                            self.video_status = tr!("common.save_error", e = format!("{:?}", e));
+                        }
+                    }
+                    VideoMessage::Error(e) => {
+                        self.video_is_running = false;
+                        self.video_status = tr!("common.error", e = e);
+                    }
+                }
+            }
+        }
+
+        // ── Procesar mensajes de re-transcripción ──────────────────────────
+        let mut retranscribed = None;
+        let mut retranscribe_finished = false;
+        if let Some(rx) = &self.retranscribe_rx {
+            while let Ok(msg) = rx.try_recv() {
+                match msg {
+                    RetranscribeMessage::Status(s) => self.status_message = s,
+                    RetranscribeMessage::Progress(p) => self.retranscribe_progress = p,
+                    RetranscribeMessage::ModelDownloaded(model) => {
+                        if self.settings.notifications.downloads {
+                            desktop_notification(&tr!("notify.model_ready"), &tr!("notify.model_ready_body", model = model));
+                        }
+                    }
+                    RetranscribeMessage::Done { model, segments } => {
+                        retranscribed = Some((model, segments));
+                        retranscribe_finished = true;
+                    }
+                    RetranscribeMessage::Error(e) => {
+                        self.status_message = tr!("common.error", e = e);
+                        retranscribe_finished = true;
+                    }
+                }
+            }
+        }
+        if retranscribe_finished {
+            self.retranscribe_rx = None;
+            self.retranscribe_stop = None;
+        }
+        if let Some((model, segments)) = retranscribed {
+            self.apply_retranscription(&model, segments);
+        }
+
+        // ── Salas ──────────────────────────────────────────────────────────
+        for room in &mut self.rooms {
+            self.web_rooms_dirty |= room.poll(&self.settings);
+        }
+
+        self.sync_saved_profiles();
+        self.sync_split();
+        self.sync_preroll();
+        self.sync_device_test();
+        self.sync_enrollment();
+        self.sync_crash_snapshot();
+        self.sync_web();
+        self.sync_uploads(ctx);
+
+        // ── Calendario ─────────────────────────────────────────────────────
+        if self.calendar.poll(&self.settings.integrations.calendar) {
+            self.prefill_from_calendar();
+        }
+        self.conferencing.poll(self.settings.detect_conferencing);
+
+        // ── UI ─────────────────────────────────────────────────────────────
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.selectable_value(&mut self.current_view, View::Transcription, tr!("nav.transcription"));
+                let running = self.rooms.iter().filter(|r| r.is_running()).count();
+                let rooms = if running > 0 { tr!("nav.rooms_count", n = running) } else { tr!("nav.rooms") };
+                ui.selectable_value(&mut self.current_view, View::Rooms, rooms);
+                let pending = self.review_queue().len();
+                let review = if pending > 0 { tr!("nav.review_count", n = pending) } else { tr!("nav.review") };
+                ui.selectable_value(&mut self.current_view, View::Review, review);
+                ui.selectable_value(&mut self.current_view, View::Video, tr!("nav.video"));
+                if ui.selectable_value(&mut self.current_view, View::History, tr!("nav.history")).clicked() {
+                    self.history = None;
+                }
+                if ui.selectable_value(&mut self.current_view, View::Actions, tr!("nav.actions")).clicked() {
+                    self.action_items = None;
+                }
+                ui.selectable_value(&mut self.current_view, View::Stats, tr!("nav.stats"));
+                ui.selectable_value(&mut self.current_view, View::Log, tr!("nav.log"));
+                ui.selectable_value(&mut self.current_view, View::Settings, tr!("nav.settings"));
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.add_space(10.0);
+                    match Path::new(self.settings.model_source.custom_path.trim()).file_name() {
+                        Some(file) => ui.label(tr!("nav.model_custom", file = file.to_string_lossy())),
+                        None => ui.label(tr!("nav.model", model = self.model_name)),
+                    };
+                    if self.settings.model_source.offline {
+                        ui.label(tr!("nav.offline"));
+                    }
+                });
+            });
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            match self.current_view {
+                View::Transcription => self.transcriber_ui(ui),
+                View::Rooms => self.rooms_ui(ui),
+                View::Review => self.review_ui(ui),
+                View::Video => self.video_ui(ui),
+                View::History => self.history_ui(ui),
+                View::Actions => self.actions_ui(ui),
+                View::Stats => self.stats_ui(ui),
+                View::Log => self.log_ui(ui),
+                View::Settings => self.settings_ui(ui),
+            }
+        });
+
+        if self.show_loopback_setup {
+            self.show_loopback_dialog(ctx);
+        }
+        if self.enrollment.is_some() {
+            self.show_enrollment_dialog(ctx);
+        }
+
+        // ── Cierre con captura en marcha ───────────────────────────────────
+        let capturing = self.is_running || self.rooms.iter().any(Room::is_running);
+        if ctx.input(|i| i.viewport().close_requested()) && capturing && !self.exit_confirmed {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.show_exit_confirm = true;
+        }
+        if self.show_exit_confirm {
+            self.show_exit_dialog(ctx);
+        }
+        if self.recovery.is_some() {
+            self.show_recovery_dialog(ctx);
+        }
+        if self.is_running {
+            self.conferencing.prompt = None;
+        } else {
+            self.show_call_prompt(ctx);
+            self.show_conferencing_prompt(ctx);
+        }
+
+        ctx.request_repaint();
+    }
+}
+
+impl TranscriptorApp {
+    // ── Pestaña: Transcripción en tiempo real ──────────────────────────────
+
+    fn check_and_prompt_loopback(&mut self) {
+        if let Ok(info) = check_loopback_status() {
+            if info.status == LoopbackStatus::NeedsConfiguration
+                || info.status == LoopbackStatus::RequiresSetup
+            {
+                self.loopback_info = Some(info);
+                self.show_loopback_setup = true;
+            }
+        }
+    }
+
+    fn start_audio_capture(&mut self) {
+        self.start_capture(false);
+    }
+
+    /// Empieza la captura con una minuta nueva o, con `resume`, a
+    /// continuación de la que hay en pantalla.
+    fn start_capture(&mut self, resume: bool) {
+        let active: Vec<InterlocutorProfile> = self.interlocutors
+            .iter().filter(|p| p.is_active).cloned().collect();
+
+        if active.is_empty() {
+            self.status_message = tr!("live.no_sources");
+            return;
+        }
+        let missing: Vec<&str> = active.iter()
+            .filter(|p| devices::is_missing(p, &self.all_input_devices, &self.all_output_devices))
+            .map(|p| p.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            self.status_message = tr!("devices.missing_capture", names = missing.join(", "));
+            return;
+        }
+        if let Some(error) = self.memory_estimate(active.len()).error() {
+            self.status_message = error;
+            return;
+        }
+
+        let (tx, rx) = channel::<AudioMessage>();
+        self.ui_rx = Some(rx);
+        self.input_levels.clear();
+        self.level_history.clear();
+        self.stream_health.clear();
+        self.comparison.clear();
+        let mqtt = &self.settings.integrations.mqtt;
+        self.mqtt = mqtt.is_enabled().then(|| {
+            MqttPublisher::connect(mqtt, &self.settings.redaction, self.session_title.trim())
+        });
+        let telegram = &self.settings.integrations.telegram;
+        self.telegram = (telegram.is_enabled() && telegram.live).then(|| {
+            TelegramLive::start(telegram, &self.settings.redaction)
+        });
+        let discord = &self.settings.integrations.discord;
+        self.discord = discord.posts_live().then(|| DiscordLive::start(discord, &self.settings.redaction));
+        let ndi = &self.settings.integrations.ndi;
+        self.ndi = None;
+        if ndi.is_enabled() {
+            match NdiOutput::start(ndi, &self.settings.redaction) {
+                Ok(output) => self.ndi = Some(output),
+                Err(e) => self.status_message = tr!("ndi.error", e = e),
+            }
+        }
+
+        let stop = StopSignal::new();
+        self.stop_signal = Some(stop.clone());
+        self.mute = MuteControl::default();
+
+        let model = self.model_name.clone();
+        let n = active.len();
+        let config = self.transcription_config();
+        // Libera los dispositivos en espera antes de abrirlos para la captura
+        let _ = self.device_test.take().map(DeviceTest::finish);
+        if let Some(wizard) = &mut self.enrollment {
+            wizard.recording = None;
+        }
+        let preroll = self.preroll.take().map(PreRoll::finish).unwrap_or_default();
+        // Sin grabación se transcribe igual; solo se avisa.
+        let origin = preroll.origin().unwrap_or_else(Instant::now);
+        let (recorder, recording_error) = match self.start_recording(origin) {
+            Ok(recorder) => (recorder, None),
+            Err(e) => (None, Some(tr!("live.recording_error", e = e))),
+        };
+
+        self.audio_thread = Some(thread::spawn(move || {
+            if let Err(e) = audio_thread_main(model, tx.clone(), stop, active, config, recorder, preroll) {
+                let _ = tx.send(AudioMessage::Error(format!("{:?}", e)));
+            }
+        }));
+
+        self.is_running = true;
+        hooks::run(&self.settings.hooks, HookEvent::CaptureStarted { title: self.session_title.trim() });
+        let started_at = chrono::Local::now() - chrono::Duration::from_std(origin.elapsed()).unwrap_or_default();
+        if resume {
+            self.resume_session(started_at);
+        } else {
+            self.segments.clear();
+            self.session_started_at = Some(started_at);
+            self.time_offset = 0.0;
+        }
+        self.block_started = Instant::now();
+        self.last_segment_at = None;
+        self.split_block = None;
+        self.status_message = recording_error.unwrap_or_else(|| tr!("live.starting", n = n));
+        if self.settings.notifications.capture {
+            desktop_notification(&tr!("notify.capture_started"), &self.status_message);
+        }
+    }
+
+    /// Continúa la minuta en pantalla (tras la comida, por ejemplo): lo nuevo
+    /// sigue contando desde el principio de la sesión y una línea marca la
+    /// pausa. `started_at` es la hora del segundo 0 de la captura nueva.
+    fn resume_session(&mut self, started_at: chrono::DateTime<chrono::Local>) {
+        let last_end = self.segments.iter().map(|s| s.end_secs).fold(0.0, f64::max);
+        // Sin hora de inicio (minuta sin fecha) la pausa no cuenta
+        let elapsed = self.session_started_at
+            .map_or(last_end, |origin| (started_at - origin).num_milliseconds() as f64 / 1000.0);
+        self.time_offset = elapsed.max(last_end);
+        let mins = ((self.time_offset - last_end) / 60.0).round() as u64;
+        tracing::info!("Minuta continuada tras {} min de pausa", mins);
+        self.segments.push(TranscriptSegment::pause(
+            self.time_offset,
+            tr!("resume.marker", mins = mins, time = started_at.format("%H:%M")),
+        ));
+        self.transcript_version += 1;
+    }
+
+    /// Detiene la captura y guarda la minuta en segundo plano. Devuelve el
+    /// hilo de guardado por si hay que esperarlo (al cerrar la aplicación).
+    fn stop_capture(&mut self) -> thread::JoinHandle<()> {
+        if let Some(sig) = self.stop_signal.take() {
+            sig.stop();
+        }
+        self.is_running = false;
+        // Guardar en hilo separado para no bloquear el render loop
+        // justo cuando el driver está liberando recursos de GPU.
+        // Lo que sale del equipo va anonimizado; la minuta en pantalla y la
+        // copia local de la sesión no.
+        self.status_message = tr!("live.stopped_saving");
+        self.save_live_minuta(tr!("notify.capture_stopped"))
+    }
+
+    /// Guarda la minuta en pantalla y la entrega a las integraciones en un
+    /// hilo aparte. `notice` es el título de la notificación de escritorio.
+    fn save_live_minuta(&self, notice: String) -> thread::JoinHandle<()> {
+        let original = self.export_document().with_topics(self.transcript_lang(), &self.settings.topics);
+        let doc = redact_document(&original, &self.settings.redaction);
+        let settings = self.settings.clone();
+        let output_dir = self.output_dir.clone();
+        let names = self.active_names_stem();
+        thread::spawn(move || {
+            if let Err(e) = save_local_session(&original, &settings, &output_dir, &names) {
+                tracing::error!("Error al guardar la sesión sin anonimizar: {:?}", e);
+            }
+            match save_minuta(&doc, &settings, &output_dir, &names) {
+                Ok(path) => {
+                    if settings.export_per_speaker {
+                        if let Err(e) = save_speaker_minutas(&doc, &settings, &output_dir, &names) {
+                            tracing::error!("Error al guardar los archivos por interlocutor: {:?}", e);
+                        }
+                    }
+                    hooks::run(&settings.hooks, HookEvent::SessionSaved { path: &path });
+                    actions::track(&original, &settings);
+                    storage::upload_in_background(&settings.active_preset().upload, &path, doc.recording.as_deref());
+                    dataset::export_in_background(&settings.dataset, &settings.redaction, &doc, &path);
+                    if settings.notifications.capture {
+                        desktop_notification(
+                            &notice,
+                            &tr!("integrations.saved_at", path = path.display()),
+                        );
+                    }
+                    integrations::deliver(&doc, &path, &settings.integrations);
+                }
+                Err(e) => {
+                    tracing::error!("Error al guardar minuta: {:?}", e);
+                    if settings.notifications.errors {
+                        desktop_notification(&tr!("notify.save_failed"), &format!("{:?}", e));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Una fila por fuente: de dónde captura, cuándo llegó audio por última
+    /// vez, cuánto espera, cuánto tarda su texto, si Whisper va a tiempo real
+    /// y su último error.
+    fn health_ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("stream_health_grid").striped(true).num_columns(8).show(ui, |ui| {
+            for key in [
+                "health.source", "health.device", "health.last_audio", "health.backlog",
+                "health.latency", "health.rtf", "health.error", "health.restarts",
+            ] {
+                ui.label(egui::RichText::new(tr!(key)).strong());
+            }
+            ui.end_row();
+            for (name, health) in &self.stream_health {
+                ui.label(name);
+                ui.label(&health.device).on_hover_text(&health.format);
+                match health.last_audio {
+                    // El nivel llega varias veces por segundo mientras hay audio
+                    Some(t) if t.elapsed() > Duration::from_secs(2) => {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(230, 160, 40),
+                            tr!("health.seconds_ago", secs = t.elapsed().as_secs()),
+                        );
+                    }
+                    Some(_) => { ui.label(tr!("health.now")); }
+                    None => { ui.label("—"); }
+                }
+                ui.label(format!("{:.1} s", health.backlog_secs));
+                match health.latency_secs {
+                    Some(latency) => {
+                        ui.colored_label(latency_color(latency, self.settings.tuning.chunk_secs), format!("{:.1} s", latency));
+                    }
+                    None => { ui.label("—"); }
+                }
+                match health.rtf {
+                    Some(rtf) => {
+                        let color = if rtf > 1.0 { egui::Color32::from_rgb(220, 60, 60) } else { egui::Color32::from_rgb(80, 170, 80) };
+                        ui.colored_label(color, format!("{:.2}", rtf));
+                    }
+                    None => { ui.label("—"); }
+                }
+                match &health.last_error {
+                    Some(e) => { ui.colored_label(egui::Color32::RED, e).on_hover_text(e); }
+                    None => { ui.label("—"); }
+                }
+                // Los hablantes de Discord no tienen perfil propio
+                let policy = self.interlocutors.iter().find(|p| &p.name == name).map(|p| p.on_error);
+                match (health.gave_up, health.restart) {
+                    (Some(max), _) => {
+                        ui.colored_label(egui::Color32::RED, tr!("health.gave_up", max = max));
+                    }
+                    (None, Some((n, max, reopen))) if reopen > Instant::now() => {
+                        let secs = (reopen - Instant::now()).as_secs() + 1;
+                        ui.colored_label(
+                            egui::Color32::from_rgb(230, 160, 40),
+                            tr!("health.restart_in", n = n, max = max, secs = secs),
+                        );
+                    }
+                    (None, Some((n, max, _))) => {
+                        ui.label(format!("{} · {}/{}", policy.unwrap_or_default().label(), n, max));
+                    }
+                    (None, None) => { ui.label(policy.map_or("—", |p| p.label())); }
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Los dos modelos lado a lado, fragmento a fragmento, con su RTF medio.
+    fn comparison_ui(&self, ui: &mut egui::Ui) {
+        let Some(last) = self.comparison.last() else { return };
+        let n = self.comparison.len() as f32;
+        let rtf = self.comparison.iter().map(|c| c.rtf).sum::<f32>() / n;
+        let compared_rtf = self.comparison.iter().map(|c| c.compared_rtf).sum::<f32>() / n;
+        ui.label(tr!(
+            "compare.summary",
+            model = last.model, rtf = format!("{:.2}", rtf),
+            compared = last.compared_model, compared_rtf = format!("{:.2}", compared_rtf),
+        ));
+        let column = (ui.available_width() - 120.0).max(200.0) / 2.0;
+        egui::ScrollArea::vertical()
+            .id_salt("comparison_scroll")
+            .max_height(250.0)
+            .auto_shrink([false, true])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                egui::Grid::new("comparison_grid").striped(true).num_columns(3).max_col_width(column).show(ui, |ui| {
+                    ui.label("");
+                    ui.label(egui::RichText::new(&last.model).strong());
+                    ui.label(egui::RichText::new(&last.compared_model).strong());
+                    ui.end_row();
+                    for chunk in &self.comparison {
+                        let secs = (chunk.start_secs + self.time_offset) as u64;
+                        ui.label(egui::RichText::new(format!("{:02}:{:02} {}", secs / 60, secs % 60, chunk.profile)).weak());
+                        for text in [&chunk.text, &chunk.compared_text] {
+                            if text.is_empty() {
+                                ui.weak("—");
+                            } else {
+                                ui.add(egui::Label::new(text.as_str()).wrap());
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+
+    /// Retraso de cada fuente junto al estado, en colores.
+    fn latency_ui(&self, ui: &mut egui::Ui) {
+        let chunk_secs = self.settings.tuning.chunk_secs;
+        for (name, health) in &self.stream_health {
+            let Some(latency) = health.latency_secs else { continue };
+            ui.separator();
+            ui.colored_label(latency_color(latency, chunk_secs), format!("⏱ {} {:.1} s", name, latency))
+                .on_hover_text(tr!("health.latency_hint"));
+        }
+    }
+
+    /// Medidor de entrada por fuente, para ver de un vistazo cuál no llega,
+    /// con silenciar y "solo" para dejar de transcribirla sin parar.
+    fn levels_ui(&self, ui: &mut egui::Ui) {
+        let mut levels: Vec<_> = self.input_levels.iter().collect();
+        levels.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, &(rms, peak)) in levels {
+            ui.horizontal(|ui| {
+                ui.add_sized([120.0, 14.0], egui::Label::new(name.as_str()).truncate());
+                let color = if peak >= 0.99 {
+                    egui::Color32::from_rgb(220, 60, 60)
+                } else {
+                    egui::Color32::from_rgb(80, 170, 80)
+                };
+                // Escala aproximada en dB: -60 dBFS vacío, 0 dBFS lleno
+                let db = 20.0 * rms.max(1e-6).log10();
+                ui.add(
+                    egui::ProgressBar::new(((db + 60.0) / 60.0).clamp(0.0, 1.0))
+                        .desired_width(160.0)
+                        .desired_height(8.0)
+                        .fill(color),
+                );
+                if let Some(history) = self.level_history.get(name) {
+                    waveform_strip(ui, history, self.mute.is_audible(name));
+                }
+                let muted = self.mute.is_muted(name);
+                if ui.selectable_label(muted, "🔇").on_hover_text(tr!("live.mute_hint")).clicked() {
+                    self.mute.set_muted(name, !muted);
+                }
+                let solo = self.mute.is_solo(name);
+                if ui.selectable_label(solo, "S").on_hover_text(tr!("live.solo_hint")).clicked() {
+                    self.mute.set_solo(name, !solo);
+                }
+                if !self.mute.is_audible(name) {
+                    ui.label(egui::RichText::new(tr!("live.not_transcribing")).small().weak());
+                }
+            });
+        }
+    }
+
+    fn transcriber_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("live.heading"));
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("live.model"));
+            let before = self.model_name.clone();
+            egui::ComboBox::from_label("")
+                .selected_text(&self.model_name)
+                .width(150.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.model_name, "medium".into(), "Medium");
+                    ui.selectable_value(&mut self.model_name, "large-v3".into(), "Large-v3");
+                });
+            if self.model_name != before {
+                self.start_preload();
+            }
+
+            // Modo comparación: el mismo audio con un segundo modelo
+            ui.add_enabled_ui(!self.is_running, |ui| {
+                ui.label(tr!("compare.label"));
+                let label = |model: &Option<String>| model.clone().unwrap_or_else(|| tr!("compare.none"));
+                egui::ComboBox::from_id_salt("compare_model")
+                    .selected_text(label(&self.compare_model))
+                    .width(120.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.compare_model, None, tr!("compare.none"));
+                        for (model, name) in [("medium", "Medium"), ("large-v3", "Large-v3")] {
+                            if model != self.model_name {
+                                ui.selectable_value(&mut self.compare_model, Some(model.to_string()), name);
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("compare.hint"));
+            });
+            if self.compare_model.as_ref() == Some(&self.model_name) {
+                self.compare_model = None;
+            }
+        });
+
+        self.session_ui(ui);
+
+        ui.add_space(10.0);
+
+        let stopping = self.is_stopping();
+        let btn = if self.is_running {
+            tr!("live.stop")
+        } else if stopping {
+            tr!("live.stopping")
+        } else {
+            tr!("live.start")
+        };
+        let enabled = !self.is_running && !stopping && !self.interlocutors.is_empty() || self.is_running;
+
+        let can_resume = !self.is_running && !stopping && !self.segments.is_empty();
+        let mut resume = false;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(enabled, egui::Button::new(btn)).clicked() {
+                if self.is_running {
+                    self.stop_capture();
+                } else if self.interlocutors.iter().any(|p| p.is_active) {
+                    self.start_audio_capture();
+                } else {
+                    self.status_message = tr!("live.no_active");
+                }
+            }
+            if can_resume && ui.button(tr!("resume.button")).on_hover_text(tr!("resume.hint")).clicked() {
+                resume = true;
+            }
+        });
+        if resume {
+            if self.interlocutors.iter().any(|p| p.is_active) {
+                self.start_capture(true);
+            } else {
+                self.status_message = tr!("live.no_active");
+            }
+        }
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("common.status"));
+            if stopping {
+                ui.spinner();
+            }
+            ui.colored_label(
+                if self.is_running { egui::Color32::GREEN } else { egui::Color32::GRAY },
+                &self.status_message,
+            );
+            if self.is_running {
+                self.latency_ui(ui);
+            }
+        });
+        if self.is_running && !self.input_levels.is_empty() {
+            self.levels_ui(ui);
+        }
+        if !self.stream_health.is_empty() {
+            egui::CollapsingHeader::new(tr!("health.heading"))
+                .id_salt("stream_health")
+                .show(ui, |ui| self.health_ui(ui));
+        }
+        // Que se está escuchando antes de empezar tiene que verse siempre
+        if self.preroll.is_some() {
+            ui.colored_label(
+                egui::Color32::from_rgb(230, 160, 40),
+                tr!("live.preroll_active", secs = self.settings.preroll.seconds),
+            );
+        }
+
+        ui.add_space(10.0);
+        ui.label(tr!("live.transcript_label"));
+
+        // Las líneas con palabras clave vigiladas se resaltan
+        let keywords = parse_keywords(&self.settings.alert_keywords);
+        let font = egui::FontId::monospace(self.settings.transcript_font_size);
+        let row_height = ui.fonts_mut(|f| f.row_height(&font));
+
+        // La grabación solo está completa cuando la captura ha terminado.
+        let can_play = self.recording_path.is_some() && !self.is_running && !stopping;
+        let mut play_clicked = false;
+
+        // Tras horas de reunión hay miles de líneas: solo se maquetan las
+        // que se ven. Una línea por intervención, sin partir, para que todas
+        // midan lo mismo. Clic marca una línea; mayúsculas + clic, un tramo.
+        if self.segments.is_empty() {
+            ui.weak(tr!("live.placeholder"));
+        }
+        egui::ScrollArea::both()
+            .max_height(400.0)
+            .auto_shrink([false, true])
+            .stick_to_bottom(true)
+            .show_rows(ui, row_height, self.segments.len(), |ui, rows| {
+                for i in rows {
+                    let selected = match self.transcript_selection {
+                        Some((start, end)) => (start..=end).contains(&i),
+                        None => self.transcript_cursor == Some(i),
+                    };
+                    let background = ui.painter().add(egui::Shape::Noop);
+                    let segment = &self.segments[i];
+                    let job = highlight_segment(ui, segment, &keywords, &font, self.show_transcribed);
+                    let mut response = ui.add(
+                        egui::Label::new(job)
+                            .extend()
+                            .selectable(false)
+                            .sense(egui::Sense::click()),
+                    );
+                    if !segment.edits.is_empty() {
+                        response = response.on_hover_text(edit_history(segment));
+                    }
+                    if selected {
+                        let rect = response.rect.with_max_x(ui.max_rect().right());
+                        ui.painter().set(background, egui::Shape::rect_filled(rect, 2.0, ui.visuals().selection.bg_fill));
+                    }
+                    if response.clicked() {
+                        match self.transcript_cursor.filter(|_| ui.input(|i| i.modifiers.shift)) {
+                            Some(anchor) => self.transcript_selection = Some((anchor.min(i), anchor.max(i))),
+                            None => {
+                                self.transcript_cursor = Some(i);
+                                self.transcript_selection = Some((i, i));
+                            }
+                        }
+                    }
+                    // Doble clic en una intervención: escucharla en la grabación
+                    if response.double_clicked() && can_play {
+                        self.transcript_cursor = Some(i);
+                        play_clicked = true;
+                    }
+                }
+            });
+        if play_clicked {
+            self.play_segment_at_cursor();
+        }
+        if !self.comparison.is_empty() {
+            egui::CollapsingHeader::new(tr!("compare.heading"))
+                .id_salt("model_comparison")
+                .default_open(true)
+                .show(ui, |ui| self.comparison_ui(ui));
+        }
+
+        let copy_all = ui.input_mut(|i| i.consume_shortcut(&COPY_ALL_SHORTCUT));
+        let copy_selection = ui.input_mut(|i| i.consume_shortcut(&COPY_SELECTION_SHORTCUT));
+
+        ui.horizontal(|ui| {
+            if ui.button(tr!("common.clear")).clicked() {
+                self.segments.clear();
+                self.transcript_selection = None;
+                self.transcript_cursor = None;
+            }
+            ui.separator();
+
+            if self.segments.iter().any(|s| s.original.is_some()) {
+                ui.selectable_value(&mut self.show_transcribed, false, tr!("edits.as_edited"));
+                ui.selectable_value(&mut self.show_transcribed, true, tr!("edits.as_transcribed"))
+                    .on_hover_text(tr!("edits.as_transcribed_hint"));
+                ui.separator();
+            }
+
+            let before = self.settings.copy_as_markdown;
+            ui.radio_value(&mut self.settings.copy_as_markdown, false, tr!("copy.plain"));
+            ui.radio_value(&mut self.settings.copy_as_markdown, true, "Markdown");
+            if self.settings.copy_as_markdown != before {
+                self.save_settings();
+            }
+
+            let all_btn = ui.button(tr!("copy.all"))
+                .on_hover_text(ui.ctx().format_shortcut(&COPY_ALL_SHORTCUT));
+            if all_btn.clicked() || copy_all {
+                let text = self.clipboard_transcript();
+                self.copy_to_clipboard(ui.ctx(), text);
+            }
+            let selection_btn = ui.add_enabled(
+                self.transcript_selection.is_some(),
+                egui::Button::new(tr!("copy.selection")),
+            ).on_hover_text(ui.ctx().format_shortcut(&COPY_SELECTION_SHORTCUT));
+            if selection_btn.clicked() || (copy_selection && self.transcript_selection.is_some()) {
+                let text = self.clipboard_selection();
+                self.copy_to_clipboard(ui.ctx(), text);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.is_running && !stopping, egui::Button::new(tr!("playback.open")))
+                .on_hover_text(tr!("playback.open_hint"))
+                .clicked()
+            {
+                self.open_session();
+            }
+            let retranscribing = self.retranscribe_rx.is_some();
+            let has_tracks = self.recording_path.as_deref().is_some_and(has_tracks);
+            if ui.add_enabled(
+                has_tracks && !self.is_running && !stopping && !retranscribing,
+                egui::Button::new(tr!("retranscribe.button")),
+            )
+                .on_hover_text(tr!("retranscribe.hint", model = self.model_name))
+                .on_disabled_hover_text(tr!("retranscribe.no_tracks_hint"))
+                .clicked()
+            {
+                self.start_retranscription();
+            }
+            if retranscribing {
+                ui.add(egui::ProgressBar::new(self.retranscribe_progress).desired_width(150.0).show_percentage());
+                if ui.button(tr!("common.cancel")).clicked() {
+                    if let Some(stop) = self.retranscribe_stop.take() {
+                        stop.stop();
+                    }
+                    self.retranscribe_rx = None;
+                    self.status_message = tr!("video.cancelled");
+                }
+            }
+            if can_play {
+                ui.separator();
+                if ui.add_enabled(self.transcript_cursor.is_some(), egui::Button::new(tr!("playback.play")))
+                    .on_hover_text(tr!("playback.play_hint"))
+                    .clicked()
+                {
+                    self.play_segment_at_cursor();
+                }
+                if self.player.is_playing() && ui.button(tr!("playback.stop")).clicked() {
+                    self.player.stop();
+                }
+            }
+
+            ui.separator();
+            if self.reader.is_speaking() {
+                if ui.button(tr!("speech.stop")).clicked() {
+                    self.reader.stop();
+                }
+                // Para volver al botón de leer cuando acabe
+                ui.ctx().request_repaint_after(Duration::from_millis(500));
+            } else if ui.add_enabled(!self.segments.is_empty(), egui::Button::new(tr!("speech.read")))
+                .on_hover_text(tr!("speech.read_hint"))
+                .clicked()
+            {
+                // Título, fecha, asistentes y tiempos: no hay resumen generado
+                let details = integrations::overview(&self.live_document());
+                if let Err(e) = self.reader.speak(&details) {
+                    self.status_message = e.to_string();
+                }
+            }
+
+            ui.separator();
+            self.summary_prompt_combo(ui);
+            if ui.add_enabled(!self.segments.is_empty(), egui::Button::new(tr!("summary.copy")))
+                .on_hover_text(tr!("summary.copy_hint"))
+                .clicked()
+            {
+                if let Some(prompt) = self.settings.active_summary_prompt() {
+                    match template::render_prompt(&self.export_document(), prompt) {
+                        Ok(text) => self.copy_to_clipboard(ui.ctx(), text),
+                        Err(e) => self.status_message = tr!("summary.error", e = e),
+                    }
+                }
+            }
+        });
+    }
+
+    // ── Sesión y calendario ────────────────────────────────────────────────
+
+    fn session_ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("session_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr!("session.title"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.session_title)
+                    .hint_text(tr!("export.minutes_title"))
+                    .desired_width(300.0),
+            );
+            ui.end_row();
+
+            ui.label(tr!("session.attendees"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.session_attendees)
+                    .hint_text(tr!("session.attendees_hint"))
+                    .desired_width(300.0),
+            );
+            ui.end_row();
+
+            ui.label(tr!("session.type"));
+            let before = self.settings.session_type;
+            egui::ComboBox::from_id_salt("session_type")
+                .selected_text(i18n::t(before.label_key()))
+                .show_ui(ui, |ui| {
+                    for &kind in SessionType::ALL {
+                        ui.selectable_value(&mut self.settings.session_type, kind, i18n::t(kind.label_key()));
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr!("session.style"));
+            let before_style = self.settings.transcription_style;
+            ui.horizontal(|ui| {
+                for &style in TranscriptionStyle::ALL {
+                    ui.selectable_value(&mut self.settings.transcription_style, style, i18n::t(style.label_key()));
+                }
+            });
+            ui.end_row();
+            if self.settings.session_type != before || self.settings.transcription_style != before_style {
+                self.save_settings();
+            }
+        });
+
+        if let Some(current) = &self.calendar.current {
+            let label = tr!(
+                "calendar.current",
+                title = current.event.title,
+                start = current.start.format("%H:%M"),
+                end = current.end.format("%H:%M")
+            );
+            let mut use_event = false;
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(label).small());
+                if let Some(link) = &current.event.call_link {
+                    ui.hyperlink_to(egui::RichText::new("🔗").small(), link);
+                }
+                use_event = ui.small_button(tr!("calendar.use_event")).clicked();
+            });
+            if use_event {
+                self.prefilled_title.clear();
+                self.session_title.clear();
+                self.prefill_from_calendar();
+            }
+        }
+    }
+
+    /// Rellena título y asistentes con el evento en curso, salvo que el
+    /// usuario ya haya escrito otro título o haya una captura en marcha.
+    fn prefill_from_calendar(&mut self) {
+        let Some(current) = &self.calendar.current else { return };
+        let untouched = self.session_title.trim().is_empty() || self.session_title == self.prefilled_title;
+        if self.is_running || !untouched {
+            return;
+        }
+        self.session_title = current.event.title.clone();
+        self.prefilled_title = self.session_title.clone();
+        self.session_attendees = current.event.attendees.join(", ");
+    }
+
+    fn show_call_prompt(&mut self, ctx: &egui::Context) {
+        let Some(occurrence) = self.calendar.pending_call_prompt(&self.settings.integrations.calendar) else {
+            return;
+        };
+        let key = occurrence.key();
+        let title = occurrence.event.title.clone();
+        let mut answer = None;
+
+        egui::Window::new(tr!("calendar.prompt_title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+            .show(ctx, |ui| {
+                ui.label(tr!("calendar.prompt_body", title = title));
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("live.start")).clicked() {
+                        answer = Some(true);
+                    }
+                    if ui.button(tr!("calendar.not_now")).clicked() {
+                        answer = Some(false);
+                    }
+                });
+            });
+
+        if let Some(start) = answer {
+            self.calendar.prompted.insert(key);
+            if start {
+                self.prefill_from_calendar();
+                self.current_view = View::Transcription;
+                if self.interlocutors.iter().any(|p| p.is_active) {
+                    self.start_audio_capture();
+                } else {
+                    self.status_message = tr!("live.no_active");
+                }
+            }
+        }
+    }
+
+    /// Aviso discreto cuando una aplicación de videoconferencia empieza a sonar.
+    fn show_conferencing_prompt(&mut self, ctx: &egui::Context) {
+        // El aviso del calendario ya pregunta lo mismo.
+        if self.calendar.pending_call_prompt(&self.settings.integrations.calendar).is_some() {
+            return;
+        }
+        let Some(app) = self.conferencing.prompt.clone() else { return };
+        let mut answer = None;
+
+        egui::Window::new(tr!("conferencing.prompt_title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+            .show(ctx, |ui| {
+                ui.label(tr!("conferencing.prompt_body", app = app));
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("live.start")).clicked() {
+                        answer = Some(true);
+                    }
+                    if ui.button(tr!("calendar.not_now")).clicked() {
+                        answer = Some(false);
+                    }
+                });
+            });
+
+        if let Some(start) = answer {
+            self.conferencing.prompt = None;
+            if start {
+                self.current_view = View::Transcription;
+                if self.interlocutors.iter().any(|p| p.is_active) {
+                    self.start_audio_capture();
+                } else {
+                    self.status_message = tr!("live.no_active");
+                }
+            }
+        }
+    }
+
+    // ── Pestaña: Salas ─────────────────────────────────────────────────────
+
+    /// La sesión de la ventana y las salas que capturan a la vez que ella.
+    fn rooms_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("rooms.heading"));
+        ui.label(egui::RichText::new(tr!("rooms.hint")).small().weak());
+        ui.separator();
+
+        // La sesión principal se maneja desde su pestaña
+        ui.horizontal(|ui| {
+            ui.label(if self.is_running { "🔴" } else { "⚪" });
+            let title = match self.session_title.trim() {
+                "" => tr!("rooms.main"),
+                t => t.to_string(),
+            };
+            if ui.link(title).clicked() {
+                self.current_view = View::Transcription;
+            }
+            ui.label(egui::RichText::new(tr!("rooms.segments", n = self.segments.len())).weak());
+        });
+        ui.add_space(6.0);
+
+        if ui.button(tr!("rooms.add")).on_hover_text(tr!("rooms.add_hint")).clicked() {
+            self.add_room();
+        }
+        ui.add_space(6.0);
+
+        let input_devices = &self.all_input_devices;
+        let output_devices = &self.all_output_devices;
+        let device_settings = &self.settings.devices;
+        let mut to_start = None;
+        let mut to_stop = None;
+        let mut to_remove = None;
+        let mut changed = false;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for room in &mut self.rooms {
+                let running = room.is_running();
+                let editable = !running && !room.is_stopping();
+                let header = format!("{} {}", if running { "🔴" } else { "⚪" }, room.name);
+                egui::CollapsingHeader::new(header).id_salt(("room", room.id)).default_open(true).show(ui, |ui| {
+                    ui.add_enabled_ui(editable, |ui| {
+                        egui::Grid::new(("room_grid", room.id)).num_columns(2).show(ui, |ui| {
+                            ui.label(tr!("rooms.name"));
+                            changed |= ui.add(egui::TextEdit::singleline(&mut room.name).desired_width(200.0)).changed();
+                            ui.end_row();
+
+                            ui.label(tr!("live.model"));
+                            egui::ComboBox::from_id_salt(("room_model", room.id))
+                                .selected_text(&room.model)
+                                .width(150.0)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut room.model, "medium".into(), "Medium");
+                                    ui.selectable_value(&mut room.model, "large-v3".into(), "Large-v3");
+                                });
+                            ui.end_row();
+
+                            ui.label(tr!("settings.output_dir"));
+                            ui.add(egui::TextEdit::singleline(&mut room.output_dir).desired_width(300.0));
+                            ui.end_row();
+                        });
+                        for profile in &mut room.profiles {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut profile.is_active, "");
+                                let device = match profile.source_type {
+                                    SourceType::Network | SourceType::Pipe | SourceType::Discord => profile.technical_name.clone().unwrap_or_default(),
+                                    _ => Self::get_device_name_static(input_devices, output_devices, device_settings, profile),
+                                };
+                                ui.add_sized([220.0, 18.0], egui::Label::new(device).truncate());
+                                ui.add(egui::TextEdit::singleline(&mut profile.name).desired_width(130.0));
+                            });
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let label = if running { tr!("live.stop") } else { tr!("live.start") };
+                        if ui.add_enabled(running || editable, egui::Button::new(label)).clicked() {
+                            if running { to_stop = Some(room.id) } else { to_start = Some(room.id) }
+                        }
+                        if ui.add_enabled(editable, egui::Button::new("🗑")).clicked() {
+                            to_remove = Some(room.id);
+                        }
+                        ui.label(egui::RichText::new(tr!("rooms.segments", n = room.segments.len())).weak());
+                        ui.colored_label(
+                            if running { egui::Color32::GREEN } else { egui::Color32::GRAY },
+                            &room.status,
+                        );
+                    });
+                    for segment in room.segments.iter().rev().take(3).rev() {
+                        ui.label(egui::RichText::new(format!("({}) {}", segment.name, segment.text)).small());
+                    }
+                });
+            }
+        });
+
+        if let Some(id) = to_start {
+            self.start_room(id);
+        }
+        if let Some(id) = to_stop {
+            self.stop_room(id);
+        }
+        if let Some(id) = to_remove {
+            self.rooms.retain(|r| r.id != id);
+            changed = true;
+        }
+        self.web_rooms_dirty |= changed;
+    }
+
+    /// Nueva sala con los perfiles activos, el modelo y la carpeta de la
+    /// ventana; luego se ajustan por separado.
+    fn add_room(&mut self) {
+        let profiles: Vec<InterlocutorProfile> = self.interlocutors.iter().filter(|p| p.is_active).cloned().collect();
+        if profiles.is_empty() {
+            self.status_message = tr!("live.no_active");
+            return;
+        }
+        let id = self.next_room_id;
+        self.next_room_id += 1;
+        let name = tr!("rooms.default_name", n = id);
+        self.rooms.push(Room::new(id, name, self.model_name.clone(), self.output_dir.clone(), profiles));
+        self.web_rooms_dirty = true;
+    }
+
+    fn start_room(&mut self, id: usize) {
+        // El silenciar de la ventana no afecta a las salas
+        let config = TranscriptionConfig { mute: MuteControl::default(), compare_model: None, ..self.transcription_config() };
+        if let Some(room) = self.rooms.iter_mut().find(|r| r.id == id) {
+            if let Err(e) = room.start(config) {
+                room.status = tr!("common.error", e = e);
+            }
+            self.web_rooms_dirty = true;
+        }
+    }
+
+    fn stop_room(&mut self, id: usize) {
+        if let Some(room) = self.rooms.iter_mut().find(|r| r.id == id) {
+            room.stop();
+            self.web_rooms_dirty = true;
+        }
+    }
+
+    // ── Portapapeles ───────────────────────────────────────────────────────
+
+    fn clipboard_transcript(&self) -> String {
+        if !self.settings.copy_as_markdown {
+            return transcript_text(&self.segments);
+        }
+        let doc = self.export_document();
+        template::render(&doc, &self.settings.export_templates.markdown, DEFAULT_MARKDOWN, false)
+            .unwrap_or_else(|_| entries_markdown(&doc.entries))
+    }
+
+    /// Las intervenciones seleccionadas, siempre completas.
+    fn clipboard_selection(&self) -> String {
+        let Some((start, end)) = self.transcript_selection else {
+            return String::new();
+        };
+        let end = end.min(self.segments.len().saturating_sub(1));
+        let lines = transcript_text(self.segments.get(start..=end).unwrap_or_default());
+
+        if self.settings.copy_as_markdown {
+            let doc = MinutaDocument::new(String::new(), Vec::new(), lines);
+            entries_markdown(&doc.entries)
+        } else {
+            lines
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, ctx: &egui::Context, text: String) {
+        if text.trim().is_empty() {
+            self.status_message = tr!("copy.empty");
+            return;
+        }
+        ctx.copy_text(text);
+        self.status_message = tr!("copy.done");
+    }
+
+    // ── Pestaña: Estadísticas ──────────────────────────────────────────────
+
+    // ── Pestaña: Revisión ──────────────────────────────────────────────────
+
+    /// Segmentos sin revisar por debajo del umbral, del menos fiable al más.
+    fn review_queue(&self) -> Vec<usize> {
+        let threshold = self.settings.review_threshold;
+        let mut queue: Vec<usize> = (0..self.segments.len())
+            .filter(|&i| !self.segments[i].reviewed && self.segments[i].confidence() < threshold)
+            .collect();
+        queue.sort_by(|&a, &b| self.segments[a].confidence().total_cmp(&self.segments[b].confidence()));
+        queue
+    }
+
+    fn review_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("review.heading"));
+        ui.separator();
+
+        let before = self.settings.review_threshold;
+        ui.horizontal(|ui| {
+            ui.label(tr!("review.threshold"));
+            ui.add(egui::Slider::new(&mut self.settings.review_threshold, 0.1..=1.0).fixed_decimals(2));
+        });
+        if self.settings.review_threshold != before {
+            self.save_settings();
+        }
+        ui.label(egui::RichText::new(tr!("review.hint")).small().weak());
+        ui.add_space(6.0);
+
+        let queue = self.review_queue();
+        if queue.is_empty() {
+            ui.label(egui::RichText::new(tr!("review.empty")).weak());
+            return;
+        }
+
+        let can_play = self.recording_path.is_some() && !self.is_running && !self.is_stopping();
+        let mut play = None;
+        let mut edited = false;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for &i in &queue {
+                let segment = &mut self.segments[i];
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!(
+                        "[{}] {} · {:.0} %",
+                        format_duration(segment.start_secs), segment.name, segment.confidence() * 100.0,
+                    )).strong());
+                    if ui.add_enabled(can_play, egui::Button::new("▶"))
+                        .on_hover_text(tr!("review.play"))
+                        .clicked()
+                    {
+                        play = Some(i);
+                    }
+                    if ui.button(tr!("review.accept")).clicked() {
+                        segment.reviewed = true;
+                        edited = true;
+                    }
+                });
+                let before = segment.text.clone();
+                let response = ui.add(
+                    egui::TextEdit::multiline(&mut segment.text)
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+                if response.changed() {
+                    segment.begin_edit(&before);
+                    edited = true;
+                }
+                if response.lost_focus() {
+                    segment.finish_edit();
+                    edited = true;
+                }
+                if let Some(original) = &segment.original {
+                    ui.label(egui::RichText::new(tr!("edits.original", text = original)).small().weak());
+                }
+                ui.add_space(4.0);
+            }
+        });
+
+        if edited {
+            self.transcript_version += 1;
+        }
+        if let Some(i) = play {
+            self.play_segment(i);
+        }
+    }
+
+    fn stats_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("stats.heading"));
+        ui.separator();
+
+        let stats = compute_stats(&self.segments);
+        if stats.is_empty() {
+            ui.label(egui::RichText::new(tr!("stats.empty")).weak());
+            return;
+        }
+
+        let total_secs: f64 = stats.iter().map(|s| s.speaking_secs).sum();
+
+        egui::Grid::new("stats_grid").striped(true).num_columns(6).show(ui, |ui| {
+            ui.strong(tr!("stats.speaker"));
+            ui.strong(tr!("stats.speaking_time"));
+            ui.strong(tr!("stats.share"));
+            ui.strong(tr!("stats.words"));
+            ui.strong(tr!("stats.longest"));
+            ui.strong(tr!("stats.interruptions"));
+            ui.end_row();
+
+            for s in &stats {
+                let share = if total_secs > 0.0 { (s.speaking_secs / total_secs) as f32 } else { 0.0 };
+                ui.label(&s.name);
+                ui.label(format_duration(s.speaking_secs));
+                ui.add(egui::ProgressBar::new(share).show_percentage().desired_width(120.0));
+                ui.label(s.words.to_string());
+                ui.label(format_duration(s.longest_monologue_secs));
+                ui.label(s.interruptions.to_string());
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(6.0);
+        ui.label(
+            egui::RichText::new(tr!("stats.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+    }
+
+    // ── Pestaña: Historial ─────────────────────────────────────────────────
+
+    fn history_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("history.heading"));
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("history.search"));
+            ui.add(egui::TextEdit::singleline(&mut self.history_query)
+                .hint_text(tr!("history.search_hint"))
+                .desired_width(260.0));
+            if ui.button(tr!("history.refresh")).clicked() {
+                self.history = None;
+            }
+        });
+        ui.add_space(6.0);
+
+        let dir = absolute_output_dir(&self.output_dir);
+        let sessions = self.history.get_or_insert_with(|| list_sessions(&dir, &self.settings.encryption));
+        if sessions.is_empty() {
+            ui.label(egui::RichText::new(tr!("history.empty", path = dir.display())).weak());
+            return;
+        }
+
+        let mut open = None;
+        let mut query = None;
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            egui::Grid::new("history_grid").striped(true).num_columns(4).show(ui, |ui| {
+                ui.strong(tr!("history.date"));
+                ui.strong(tr!("history.title"));
+                ui.strong(tr!("export.tags"));
+                ui.label("");
+                ui.end_row();
+
+                for session in sessions.iter().filter(|s| s.matches(&self.history_query)) {
+                    ui.label(session.saved_at.map(|at| at.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default());
+                    ui.label(&session.title)
+                        .on_hover_text(session.attendees.join(", "));
+                    ui.horizontal_wrapped(|ui| {
+                        for tag in &session.tags {
+                            if ui.small_button(tag).on_hover_text(tr!("history.tag_hint")).clicked() {
+                                query = Some(tag.clone());
+                            }
+                        }
+                    });
+                    if ui.add_enabled(!self.is_running, egui::Button::new(tr!("history.open")))
+                        .on_hover_text(session.path.display().to_string())
+                        .clicked()
+                    {
+                        open = Some(session.path.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(tag) = query {
+            self.history_query = tag;
+        }
+        if let Some(path) = open {
+            self.load_session(&path);
+            self.current_view = View::Transcription;
+        }
+    }
+
+    // ── Pestaña: Tareas ────────────────────────────────────────────────────
+
+    fn actions_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("actions.heading"));
+        ui.separator();
+
+        let items = self.action_items.get_or_insert_with(actions::load);
+        let open = items.iter().filter(|i| !i.done).count();
+        ui.horizontal(|ui| {
+            ui.label(tr!("actions.open_count", n = open));
+            ui.checkbox(&mut self.show_done_actions, tr!("actions.show_done"));
+            if ui.button(tr!("history.refresh")).clicked() {
+                self.action_items = None;
+            }
+        });
+        ui.horizontal(|ui| {
+            let mut changed = false;
+            ui.label(tr!("actions.agenda_template"));
+            let path = &mut self.settings.export_templates.agenda;
+            changed |= ui.add(egui::TextEdit::singleline(path).desired_width(250.0)).changed();
+            if ui.button(tr!("export.browse")).clicked() {
+                if let Some(picked) = rfd::FileDialog::new().add_filter("Tera", &["md", "tera"]).pick_file() {
+                    *path = picked.display().to_string();
+                    changed = true;
+                }
+            }
+            if changed {
+                self.save_settings();
+            }
+            if ui.add_enabled(open > 0, egui::Button::new(tr!("actions.agenda")))
+                .on_hover_text(tr!("actions.agenda_hint"))
+                .clicked()
+            {
+                self.agenda_result = Some(match self.save_agenda() {
+                    Ok(path) => tr!("common.saved_to", path = path.display()),
+                    Err(e) => tr!("common.save_error", e = e),
+                });
+            }
+        });
+        if let Some(result) = &self.agenda_result {
+            ui.label(result);
+        }
+        ui.add_space(6.0);
+
+        let Some(items) = &self.action_items else { return };
+        if items.is_empty() {
+            ui.label(egui::RichText::new(tr!("actions.empty")).weak());
+            return;
+        }
+
+        let mut done = None;
+        let mut remove = None;
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            egui::Grid::new("actions_grid").striped(true).num_columns(6).show(ui, |ui| {
+                ui.label("");
+                ui.strong(tr!("actions.task"));
+                ui.strong(tr!("actions.owner"));
+                ui.strong(tr!("actions.due"));
+                ui.strong(tr!("actions.session"));
+                ui.label("");
+                ui.end_row();
+
+                for item in items.iter().filter(|i| self.show_done_actions || !i.done) {
+                    let mut checked = item.done;
+                    if ui.checkbox(&mut checked, "").changed() {
+                        done = Some((item.id, checked));
+                    }
+                    let task = if item.done {
+                        egui::RichText::new(&item.task).strikethrough().weak()
+                    } else {
+                        egui::RichText::new(&item.task)
+                    };
+                    ui.add(egui::Label::new(task).wrap());
+                    ui.label(item.owner.as_deref().unwrap_or("—"));
+                    ui.label(item.due.as_deref().unwrap_or(""));
+                    ui.label(format!("{} · {}", item.session, item.date))
+                        .on_hover_text(item.timestamp.as_deref().unwrap_or(""));
+                    if ui.small_button("🗑").on_hover_text(tr!("actions.remove")).clicked() {
+                        remove = Some(item.id);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+        let result = match (done, remove) {
+            (Some((id, done)), _) => Some(actions::set_done(id, done)),
+            (_, Some(id)) => Some(actions::remove(id)),
+            _ => None,
+        };
+        if let Some(result) = result {
+            if let Err(e) = result {
+                self.status_message = tr!("common.save_error", e = e);
+            }
+            self.action_items = None;
+        }
+    }
+
+    /// Guarda el orden del día con las tareas abiertas en la carpeta de salida.
+    fn save_agenda(&self) -> Result<PathBuf> {
+        let items = self.action_items.as_deref().unwrap_or_default();
+        let text = template::render_agenda(items, &self.settings.export_templates.agenda)?;
+        let dir = absolute_output_dir(&self.output_dir);
+        std::fs::create_dir_all(&dir)?;
+        let stem = format!("{}_{}", tr!("actions.agenda_file"), chrono::Local::now().format("%Y-%m-%d"));
+        let path = unique_path(&dir, &stem, "md");
+        std::fs::write(&path, text)?;
+        Ok(path)
+    }
+
+    // ── Pestaña: Log ───────────────────────────────────────────────────────
+
+    fn log_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("log.heading"));
+        ui.separator();
+
+        let lines = logging::recent_lines();
+        ui.horizontal(|ui| {
+            if ui.button(tr!("log.copy")).clicked() {
+                let text: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+                self.copy_to_clipboard(ui.ctx(), text.join("\n"));
+            }
+            if ui.button(tr!("common.clear")).clicked() {
+                logging::clear_recent();
+            }
+            if ui.button(tr!("diagnostics.generate")).on_hover_text(tr!("diagnostics.hint")).clicked() {
+                self.diagnostics_result = Some(
+                    match diagnostics::write_report(&self.settings, &self.model_name, &self.output_dir) {
+                        Ok(path) => tr!("common.saved_to", path = path.display()),
+                        Err(e) => tr!("common.save_error", e = e),
+                    },
+                );
+            }
+            ui.label(
+                egui::RichText::new(tr!("log.file", path = logging::log_path().display()))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        });
+        if let Some(result) = &self.diagnostics_result {
+            ui.label(result);
+        }
+        ui.add_space(6.0);
+
+        if lines.is_empty() {
+            ui.label(egui::RichText::new(tr!("log.empty")).weak());
+            return;
+        }
+
+        egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for line in &lines {
+                    let color = match line.level {
+                        LogLevel::Error => egui::Color32::RED,
+                        LogLevel::Warn => egui::Color32::from_rgb(230, 160, 0),
+                        LogLevel::Info => ui.visuals().text_color(),
+                        LogLevel::Debug | LogLevel::Trace => egui::Color32::GRAY,
+                    };
+                    ui.label(egui::RichText::new(&line.text).monospace().color(color));
+                }
+            });
+    }
+
+    // ── Pestaña: Transcripción de vídeo ────────────────────────────────────
+
+    fn video_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("video.heading"));
+        ui.separator();
+
+        // Selector de archivo
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!self.video_is_running, |ui| {
+                if ui.button(tr!("video.pick")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter(
+                            i18n::t("video.filter"),
+                            &["mp4", "mkv", "avi", "mov", "webm", "mp3", "wav", "flac", "ogg", "m4a"],
+                        )
+                        .pick_file()
+                    {
+                        self.video_file_path = Some(path.to_string_lossy().to_string());
+                        self.video_transcription.clear();
+                        self.video_progress = 0.0;
+                        self.video_status = tr!("video.selected");
+                    }
+                }
+            });
+
+            match &self.video_file_path {
+                Some(p) => {
+                    // Mostrar solo el nombre del archivo, no la ruta completa
+                    let name = Path::new(p)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| p.clone());
+                    ui.label(egui::RichText::new(&name).strong());
+                }
+                None => { ui.label(egui::RichText::new(tr!("video.no_file")).weak()); }
+            }
+        });
+
+        ui.add_space(6.0);
+
+        // Modelo + botón de inicio/parada
+        ui.horizontal(|ui| {
+            ui.label(tr!("common.model_label"));
+            ui.add_enabled_ui(!self.video_is_running, |ui| {
+                egui::ComboBox::from_id_salt("video_model")
+                    .selected_text(&self.model_name)
+                    .width(150.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.model_name, "medium".into(), "Medium");
+                        ui.selectable_value(&mut self.model_name, "large-v3".into(), "Large-v3");
+                    });
+            });
+
+            ui.add_space(10.0);
+
+            let can_start = self.video_file_path.is_some() && !self.video_is_running;
+
+            if self.video_is_running {
+                if ui.button(tr!("video.cancel")).clicked() {
+                    if let Some(sig) = self.video_stop_signal.take() {
+                        sig.stop();
+                    }
+                }
+            } else if ui.add_enabled(can_start, egui::Button::new(tr!("video.transcribe"))).clicked() {
+                self.start_video_transcription();
+            }
+        });
+
+        ui.add_space(6.0);
+
+        // Barra de progreso
+        if self.video_is_running || self.video_progress > 0.0 {
+            let bar = egui::ProgressBar::new(self.video_progress)
+                .show_percentage()
+                .animate(self.video_is_running);
+            ui.add(bar);
+        }
+
+        // Estado
+        ui.horizontal(|ui| {
+            ui.label(tr!("common.status"));
+            ui.colored_label(
+                if self.video_is_running { egui::Color32::GREEN } else { egui::Color32::GRAY },
+                &self.video_status,
+            );
+        });
+
+        ui.separator();
+
+        // Transcripción
+        ui.label(tr!("video.transcript_label"));
+
+        egui::ScrollArea::vertical()
+            .max_height(380.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.video_transcription)
+                        .desired_width(f32::INFINITY)
+                        .font(egui::FontId::monospace(self.settings.transcript_font_size))
+                        .interactive(!self.video_is_running),
+                );
+            });
+
+        ui.horizontal(|ui| {
+            if ui.button(tr!("common.clear")).clicked() {
+                self.video_transcription.clear();
+                self.video_progress = 0.0;
+            }
+            if !self.video_transcription.is_empty() && !self.video_is_running {
+                if ui.button(tr!("common.save")).clicked() {
+                    match self.save_video_transcript() {
+                        Ok(p) => self.video_status = tr!("common.saved_to", path = p.display()),
+                        Err(e) => self.video_status = tr!("common.save_error", e = format!("{:?}", e)),
+                    }
+                }
+            }
+        });
+    }
+
+    fn start_video_transcription(&mut self) {
+        let file_path = match &self.video_file_path {
+            Some(p) => p.clone(),
+            None => return,
+        };
+
+        let (tx, rx) = channel::<VideoMessage>();
+        self.video_rx = Some(rx);
+
+        let stop = StopSignal::new();
+        self.video_stop_signal = Some(stop.clone());
+
+        let model = self.model_name.clone();
+        let config = self.transcription_config();
+
+        thread::spawn(move || {
+            if let Err(e) = video_transcription_thread(file_path, model, config, tx.clone(), stop) {
+                let _ = tx.send(VideoMessage::Error(format!("{:?}", e)));
+            }
+        });
+
+        self.video_is_running = true;
+        self.video_transcription.clear();
+        self.video_progress = 0.0;
+        self.video_status = tr!("video.starting");
+    }
+
+    fn save_video_transcript(&self) -> Result<PathBuf> {
+        if self.video_transcription.trim().is_empty() {
+            return Err(anyhow!(tr!("common.nothing_to_save")));
+        }
+
+        let stem = self.video_file_path
+            .as_deref()
+            .and_then(|p| Path::new(p).file_stem())
+            .map(|s| s.to_string_lossy().replace(' ', "_"))
+            .unwrap_or_else(|| "video".into());
+
+        let doc = MinutaDocument::new(
+            tr!("export.video_title", name = stem),
+            Vec::new(),
+            self.video_transcription.clone(),
+        );
+        let doc = if self.settings.normalize_numbers { doc.normalize_numbers(self.transcript_lang()) } else { doc };
+        let original = doc.with_topics(self.transcript_lang(), &self.settings.topics);
+        save_local_session(&original, &self.settings, &self.output_dir, &stem)?;
+        let doc = redact_document(&original, &self.settings.redaction);
+        let path = save_minuta(&doc, &self.settings, &self.output_dir, &stem)?;
+        hooks::run(&self.settings.hooks, HookEvent::SessionSaved { path: &path });
+        actions::track(&original, &self.settings);
+        storage::upload_in_background(&self.settings.active_preset().upload, &path, doc.recording.as_deref());
+        dataset::export_in_background(&self.settings.dataset, &self.settings.redaction, &doc, &path);
+        Ok(path)
+    }
+
+    // ── Pestaña: Configuración ─────────────────────────────────────────────
+
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("settings.heading"));
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.settings_tab, SettingsTab::General, tr!("settings.tab_general"));
+            ui.selectable_value(&mut self.settings_tab, SettingsTab::PostProcessing, tr!("settings.tab_postprocessing"));
+            ui.selectable_value(&mut self.settings_tab, SettingsTab::Integrations, tr!("settings.tab_integrations"));
+        });
+        ui.separator();
+
+        match self.settings_tab {
+            SettingsTab::General => self.general_settings_ui(ui),
+            SettingsTab::PostProcessing => self.postprocessing_ui(ui),
+            SettingsTab::Integrations => self.integrations_ui(ui),
+        }
+    }
+
+    fn general_settings_ui(&mut self, ui: &mut egui::Ui) {
+        // Idioma de la interfaz y apariencia
+        let before = self.settings.clone();
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("settings.ui_language"));
+            egui::ComboBox::from_id_salt("ui_language")
+                .selected_text(self.settings.ui_language.label())
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    for lang in UiLanguage::ALL {
+                        ui.selectable_value(&mut self.settings.ui_language, *lang, lang.label());
+                    }
+                });
+
+            ui.add_space(16.0);
+
+            ui.label(tr!("settings.theme"));
+            egui::ComboBox::from_id_salt("ui_theme")
+                .selected_text(i18n::t(self.settings.theme.label_key()))
+                .width(140.0)
+                .show_ui(ui, |ui| {
+                    for theme in Theme::ALL {
+                        ui.selectable_value(&mut self.settings.theme, *theme, i18n::t(theme.label_key()));
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("settings.font_size"));
+            ui.add(egui::Slider::new(
+                &mut self.settings.transcript_font_size,
+                MIN_TRANSCRIPT_FONT_SIZE..=MAX_TRANSCRIPT_FONT_SIZE,
+            ).step_by(1.0));
+        });
+
+        if self.settings != before {
+            // Las plantillas de resumen de serie, sin tocar, pasan al idioma
+            // nuevo; las editadas se quedan como están
+            let relabel = self.settings.ui_language != before.ui_language
+                && self.settings.summary_prompts == template::default_summary_prompts();
+            i18n::set_language(self.settings.ui_language);
+            if relabel {
+                self.settings.summary_prompts = template::default_summary_prompts();
+            }
+            ui.ctx().set_theme(theme_preference(self.settings.theme));
+            self.save_settings();
+        }
+
+        ui.add_space(10.0);
+
+        // Idioma
+        ui.label(egui::RichText::new(tr!("settings.language")).strong());
+        ui.add_space(4.0);
+
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("settings.source_lang"));
+                egui::ComboBox::from_id_salt("lang_source")
+                    .selected_text(self.lang_config.source_label())
+                    .width(160.0)
+                    .show_ui(ui, |ui| {
+                        for (label, code) in SOURCE_LANGUAGES {
+                            let label = if code.is_none() { i18n::t("lang.auto_detect") } else { *label };
+                            ui.selectable_value(&mut self.lang_config.source_lang, *code, label);
+                        }
+                    });
+
+                ui.add_space(16.0);
+
+                ui.label(tr!("settings.dest_lang"));
+                egui::ComboBox::from_id_salt("lang_dest")
+                    .selected_text(self.lang_config.dest_label())
+                    .width(200.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut self.lang_config.translate_to_english,
+                            false,
+                            tr!("lang.original"),
+                        );
+                        ui.selectable_value(
+                            &mut self.lang_config.translate_to_english,
+                            true,
+                            tr!("lang.to_english"),
+                        );
+                    });
+            });
+
+            ui.label(
+                egui::RichText::new(tr!("settings.translate_hint"))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+
+            let before = self.settings.multilingual.clone();
+            ui.checkbox(&mut self.settings.multilingual.enabled, tr!("multilingual.enabled"))
+                .on_hover_text(tr!("multilingual.hint"));
+            ui.add_enabled_ui(self.settings.multilingual.enabled, |ui| {
+                ui.checkbox(&mut self.settings.multilingual.translate_others, tr!("multilingual.translate_others"));
+            });
+            if self.settings.multilingual != before {
+                self.save_settings();
+            }
+        });
+
+        ui.add_space(6.0);
+
+        // Alertas por palabra clave
+        egui::CollapsingHeader::new(egui::RichText::new(tr!("alerts.heading")).strong())
+            .id_salt("keyword_alerts")
+            .show(ui, |ui| {
+                let mut changed = false;
+                ui.label(tr!("alerts.keywords"));
+                changed |= ui.add(
+                    egui::TextEdit::multiline(&mut self.settings.alert_keywords)
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(3)
+                        .hint_text(tr!("alerts.keywords_hint")),
+                ).changed();
+                changed |= ui.checkbox(
+                    &mut self.settings.alert_notifications,
+                    tr!("alerts.notify"),
+                ).changed();
+                if changed {
+                    self.save_settings();
+                }
+            });
+
+        // Notificaciones de escritorio
+        egui::CollapsingHeader::new(egui::RichText::new(tr!("notify.heading")).strong())
+            .id_salt("desktop_notifications")
+            .show(ui, |ui| {
+                let before = self.settings.notifications.clone();
+                let notifications = &mut self.settings.notifications;
+                ui.checkbox(&mut notifications.capture, tr!("notify.on_capture"));
+                ui.checkbox(&mut notifications.errors, tr!("notify.on_errors"));
+                ui.checkbox(&mut notifications.downloads, tr!("notify.on_downloads"));
+                if self.settings.notifications != before {
+                    self.save_settings();
+                }
+            });
+
+        // Detección de videoconferencias
+        ui.add_enabled_ui(conferencing::is_supported(), |ui| {
+            if ui.checkbox(&mut self.settings.detect_conferencing, tr!("conferencing.detect"))
+                .on_disabled_hover_text(tr!("conferencing.unsupported"))
+                .changed()
+            {
+                self.save_settings();
+            }
+        });
+
+        // Verbosidad del log
+        ui.horizontal(|ui| {
+            ui.label(tr!("log.level"));
+            let before = self.settings.log_level;
+            egui::ComboBox::from_id_salt("log_level")
+                .selected_text(before.label())
+                .show_ui(ui, |ui| {
+                    for level in LogLevel::ALL {
+                        ui.selectable_value(&mut self.settings.log_level, *level, level.label());
+                    }
+                });
+            if self.settings.log_level != before {
+                logging::set_level(self.settings.log_level);
+                self.save_settings();
+            }
+        });
+
+        // Rendimiento de Whisper
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            egui::CollapsingHeader::new(egui::RichText::new(tr!("tuning.heading")).strong())
+                .id_salt("whisper_tuning")
+                .show(ui, |ui| self.tuning_ui(ui));
+        });
+
+        // Origen del modelo
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            egui::CollapsingHeader::new(egui::RichText::new(tr!("model.heading")).strong())
+                .id_salt("model_source")
+                .show(ui, |ui| self.model_source_ui(ui));
+        });
+
+        ui.add_space(10.0);
+        ui.separator();
+
+        // Host de audio
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("settings.audio_host"));
+                let before = self.settings.audio_host.clone();
+                let label = |host: &Option<String>| host.clone().unwrap_or_else(|| tr!("settings.audio_host_auto"));
+                egui::ComboBox::from_id_salt("audio_host")
+                    .selected_text(label(&self.settings.audio_host))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.settings.audio_host, None, tr!("settings.audio_host_auto"));
+                        for name in host_names() {
+                            ui.selectable_value(&mut self.settings.audio_host, Some(name.to_string()), name);
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("settings.audio_host_hint"));
+                if self.settings.audio_host != before {
+                    self.save_settings();
+                    self.reload_input_devices();
+                }
+            });
+
+            // Programa de captura (solo Linux)
+            if cfg!(target_os = "linux") {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("settings.capture_tool"));
+                    let before = self.settings.capture_tool;
+                    let label = |tool: Option<CaptureTool>| tool.map_or_else(|| tr!("settings.capture_tool_auto"), |t| t.program().to_string());
+                    egui::ComboBox::from_id_salt("capture_tool")
+                        .selected_text(label(self.settings.capture_tool))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.settings.capture_tool, None, tr!("settings.capture_tool_auto"));
+                            for &tool in CaptureTool::ALL {
+                                ui.selectable_value(&mut self.settings.capture_tool, Some(tool), tool.program());
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr!("settings.capture_tool_hint"));
+                    if self.settings.capture_tool != before {
+                        self.save_settings();
+                    }
+                });
+            }
+        });
+
+        // Loopback
+        ui.horizontal(|ui| {
+            if ui.button(tr!("settings.loopback_btn")).clicked() {
+                self.loopback_info = check_loopback_status().ok();
+                self.show_loopback_setup = true;
+            }
+            let n = self.all_output_devices.len();
+            if n > 0 {
+                ui.colored_label(egui::Color32::GREEN, tr!("settings.loopback_count", n = n));
+            } else {
+                ui.colored_label(egui::Color32::YELLOW, tr!("settings.no_output"));
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Interlocutores
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            ui.label(tr!("settings.add_source"));
+            ui.horizontal(|ui| {
+                if ui.button(tr!("settings.add_input")).clicked() {
+                    self.add_new_profile(SourceType::Input);
+                }
+                if ui.button(tr!("settings.add_output")).clicked() {
+                    if self.all_output_devices.is_empty() {
+                        self.status_message = tr!("settings.loopback_first");
+                        self.loopback_info = check_loopback_status().ok();
+                        self.show_loopback_setup = true;
+                    } else {
+                        self.add_new_profile(SourceType::Output);
+                    }
+                }
+                if ui.button(tr!("settings.add_network")).clicked() {
+                    self.add_new_profile(SourceType::Network);
+                }
+                if ui.button(tr!("settings.add_pipe")).clicked() {
+                    self.add_new_profile(SourceType::Pipe);
+                }
+                if ui.button(tr!("settings.add_discord")).clicked() {
+                    self.add_new_profile(SourceType::Discord);
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+        ui.label(tr!("settings.profiles"));
+
+        let input_devices = &self.all_input_devices;
+        let output_devices = &self.all_output_devices;
+        let device_settings = &self.settings.devices;
+        let roster = &self.settings.roster;
+        let mut to_remove: Option<usize> = None;
+        let mut to_remember: Option<usize> = None;
+        let mut to_split: Option<(usize, u16)> = None;
+        let mut to_test: Option<usize> = None;
+        let can_test = !self.is_running && !self.is_stopping() && self.device_test.is_none() && self.enrollment.is_none();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (idx, profile) in self.interlocutors.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut profile.is_active, "");
+
+                    let (devices_to_show, icon): (&[DeviceInfo], &str) = match profile.source_type {
+                        SourceType::Input => (input_devices, "🎤"),
+                        SourceType::Output => (output_devices, "📊"),
+                        SourceType::Network => (&[], "🌐"),
+                        SourceType::Pipe => (&[], "⏩"),
+                        SourceType::Discord => (&[], "🎮"),
+                    };
+                    ui.label(icon);
+
+                    if profile.source_type == SourceType::Pipe {
+                        let path = profile.technical_name.get_or_insert_with(String::new);
+                        ui.add(
+                            egui::TextEdit::singleline(path)
+                                .desired_width(140.0)
+                                .hint_text(tr!("settings.pipe_stdin")),
+                        )
+                        .on_hover_text(tr!("settings.pipe_hint"));
+                        let pcm = &mut profile.pcm;
+                        egui::ComboBox::from_id_salt(("pcm_encoding", profile.id))
+                            .selected_text(pcm.encoding.label())
+                            .width(60.0)
+                            .show_ui(ui, |ui| {
+                                for encoding in PcmEncoding::ALL {
+                                    ui.selectable_value(&mut pcm.encoding, *encoding, encoding.label());
+                                }
+                            });
+                        ui.add(egui::DragValue::new(&mut pcm.sample_rate).range(8000..=192_000).suffix(" Hz"));
+                        ui.add(egui::DragValue::new(&mut pcm.channels).range(1..=32).suffix(" ch"));
+                    } else if profile.source_type == SourceType::Network {
+                        let url = profile.technical_name.get_or_insert_with(String::new);
+                        ui.add(
+                            egui::TextEdit::singleline(url)
+                                .desired_width(220.0)
+                                .hint_text("https://… / rtp://…"),
+                        )
+                        .on_hover_text(tr!("settings.network_url_hint"));
+                    } else if profile.source_type == SourceType::Discord {
+                        let channel = profile.technical_name.get_or_insert_with(String::new);
+                        ui.add(
+                            egui::TextEdit::singleline(channel)
+                                .desired_width(220.0)
+                                .hint_text(tr!("discord.voice_channel")),
+                        )
+                        .on_hover_text(tr!("discord.voice_channel_hint"));
+                    } else {
+                        let device_name = Self::get_device_name_static(
+                            input_devices,
+                            output_devices,
+                            device_settings,
+                            profile,
+                        );
+                        let device_name = if devices::is_missing(profile, input_devices, output_devices) {
+                            egui::RichText::new(device_name).color(egui::Color32::from_rgb(230, 160, 40))
+                        } else {
+                            egui::RichText::new(device_name)
+                        };
+
+                        egui::ComboBox::from_id_salt(profile.id)
+                            .selected_text(device_name)
+                            .width(220.0)
+                            .show_ui(ui, |ui| {
+                                for device in devices_to_show {
+                                    let r = ui.selectable_value(
+                                        &mut profile.device_id,
+                                        device.id,
+                                        device_settings.display_name(device),
+                                    );
+                                    if r.clicked() {
+                                        profile.technical_name = device.technical_name.clone();
+                                        profile.channel = None;
+                                    }
+                                }
+                            });
+                    }
+
+                    // Interfaces multicanal: un interlocutor por canal
+                    let device_channels = match profile.source_type {
+                        SourceType::Pipe => profile.pcm.channels,
+                        _ => devices::find(profile, input_devices, output_devices)
+                            .map_or(0, |d| d.channels),
+                    };
+                    if device_channels > 1 {
+                        let channel_label = |c: Option<u16>| match c {
+                            Some(c) => tr!("settings.channel_n", n = c + 1),
+                            None => tr!("settings.channel_mix"),
+                        };
+                        egui::ComboBox::from_id_salt(("channel", profile.id))
+                            .selected_text(channel_label(profile.channel))
+                            .width(80.0)
+                            .show_ui(ui, |ui| {
+                                for c in std::iter::once(None).chain((0..device_channels).map(Some)) {
+                                    ui.selectable_value(&mut profile.channel, c, channel_label(c));
+                                }
+                            });
+                        if ui.button("⫼").on_hover_text(tr!("settings.split_channels", n = device_channels)).clicked() {
+                            to_split = Some((idx, device_channels));
+                        }
+                    }
+
+                    let name = ui.add(
+                        egui::TextEdit::singleline(&mut profile.name)
+                            .desired_width(130.0)
+                            .hint_text(tr!("common.speaker", n = profile.id)),
+                    );
+                    // Autocompletado con los participantes habituales
+                    let matches = roster::suggestions(roster, &profile.name);
+                    egui::Popup::from_response(&name)
+                        .open_memory(name.has_focus().then_some(egui::SetOpenCommand::Bool(!matches.is_empty())))
+                        .close_behavior(egui::PopupCloseBehavior::CloseOnClick)
+                        .show(|ui| {
+                            for participant in matches {
+                                if ui.selectable_label(false, participant.label()).clicked() {
+                                    participant.apply(profile, input_devices, output_devices);
+                                }
+                            }
+                        });
+                    if ui.small_button("⭐").on_hover_text(tr!("roster.remember")).clicked() {
+                        to_remember = Some(idx);
+                    }
+                    ui.checkbox(&mut profile.agc, "AGC").on_hover_text(tr!("settings.agc_hint"));
+                    ui.checkbox(&mut profile.identify_voices, "🗣").on_hover_text(tr!("speaker_id.profile_hint"));
+                    egui::ComboBox::from_id_salt(("on_error", profile.id))
+                        .selected_text(profile.on_error.label())
+                        .width(120.0)
+                        .show_ui(ui, |ui| {
+                            for &policy in RestartPolicy::ALL {
+                                ui.selectable_value(&mut profile.on_error, policy, policy.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr!("restart.hint"));
+                    if profile.on_error == RestartPolicy::Retry {
+                        ui.add(egui::DragValue::new(&mut profile.max_restarts).range(1..=20).suffix("×"))
+                            .on_hover_text(tr!("restart.max_hint"));
+                    }
+
+                    if matches!(profile.source_type, SourceType::Input | SourceType::Output)
+                        && ui.add_enabled(can_test, egui::Button::new("🔊"))
+                            .on_hover_text(tr!("device_test.hint", secs = device_test::TEST_SECS))
+                            .clicked()
+                    {
+                        to_test = Some(idx);
+                    }
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(idx);
+                    }
+                });
+            }
+        });
+
+        if let Some(idx) = to_remove {
+            self.remove_profile(idx);
+        }
+        if let Some((idx, channels)) = to_split {
+            self.split_profile_channels(idx, channels);
+        }
+        if let Some(idx) = to_test {
+            self.start_device_test(idx);
+        }
+        if let Some(profile) = to_remember.and_then(|idx| self.interlocutors.get(idx)) {
+            roster::remember(&mut self.settings.roster, profile);
+            self.status_message = tr!("roster.remembered", name = profile.name);
+            self.save_settings();
+        }
+        if let Some(test) = &self.device_test {
+            ui.horizontal(|ui| {
+                ui.label(tr!("device_test.recording", secs = device_test::TEST_SECS, name = test.profile));
+                ui.add(egui::ProgressBar::new(test.progress()).desired_width(120.0));
+            });
+        }
+
+        if self.is_running {
+            ui.label(
+                egui::RichText::new(tr!("settings.stop_to_edit"))
+                    .color(egui::Color32::YELLOW),
+            );
+        }
+
+        egui::CollapsingHeader::new(tr!("devices.aliases")).show(ui, |ui| {
+            ui.label(egui::RichText::new(tr!("devices.aliases_hint")).small().weak());
+            let before = self.settings.devices.aliases.clone();
+            egui::Grid::new("device_aliases_grid").num_columns(2).show(ui, |ui| {
+                for device in self.all_input_devices.iter().chain(&self.all_output_devices) {
+                    let Some(technical_name) = &device.technical_name else { continue };
+                    ui.label(&device.name).on_hover_text(technical_name);
+                    let alias = self.settings.devices.aliases.entry(technical_name.clone()).or_default();
+                    ui.add(egui::TextEdit::singleline(alias).desired_width(200.0).hint_text(tr!("devices.alias_hint")));
+                    ui.end_row();
+                }
+            });
+            self.settings.devices.aliases.retain(|_, alias| !alias.trim().is_empty());
+            if self.settings.devices.aliases != before {
+                self.save_settings();
+            }
+        });
+
+        egui::CollapsingHeader::new(tr!("devices.streams")).show(ui, |ui| {
+            ui.label(egui::RichText::new(tr!("devices.streams_hint")).small().weak());
+            let before = self.settings.devices.streams.clone();
+            let device_settings = &mut self.settings.devices;
+            ui.add_enabled_ui(!self.is_running, |ui| {
+                egui::Grid::new("device_streams_grid").num_columns(3).show(ui, |ui| {
+                    for device in self.all_input_devices.iter().chain(&self.all_output_devices) {
+                        let Some(technical_name) = &device.technical_name else { continue };
+                        if process_loopback::is_process_source(Some(technical_name.as_str())) {
+                            continue;
+                        }
+                        ui.label(device_settings.display_name(device)).on_hover_text(technical_name);
+                        let options = device_settings.streams.entry(technical_name.clone()).or_default();
+                        let label = |frames: u32| match frames {
+                            0 => tr!("devices.buffer_default"),
+                            n => tr!("devices.buffer_frames", n = n, ms = format!("{:.1}", n as f32 / 48.0)),
+                        };
+                        egui::ComboBox::from_id_salt(("stream_buffer", technical_name))
+                            .selected_text(label(options.buffer_frames))
+                            .show_ui(ui, |ui| {
+                                for &frames in devices::BUFFER_FRAMES {
+                                    ui.selectable_value(&mut options.buffer_frames, frames, label(frames));
+                                }
+                            });
+                        if cfg!(target_os = "windows") {
+                            ui.checkbox(&mut options.exclusive, tr!("devices.exclusive"))
+                                .on_hover_text(tr!("devices.exclusive_hint"));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+            device_settings.streams.retain(|_, options| *options != StreamOptions::default());
+            if self.settings.devices.streams != before {
+                self.save_settings();
+            }
+        });
+
+        self.roster_ui(ui);
+
+        ui.separator();
+        ui.label(tr!("settings.output_dir"));
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            ui.horizontal(|ui| {
+                let resp = ui.add(egui::TextEdit::singleline(&mut self.output_dir).desired_width(300.0));
+                if resp.lost_focus() {
+                    self.output_dir_error = check_output_dir(&self.output_dir).err().map(|e| e.to_string());
+                }
+                if ui.button(tr!("export.browse")).clicked() {
+                    if let Some(dir) = rfd::FileDialog::new()
+                        .set_directory(absolute_output_dir(&self.output_dir))
+                        .pick_folder()
+                    {
+                        self.output_dir = dir.display().to_string();
+                        self.output_dir_error = check_output_dir(&self.output_dir).err().map(|e| e.to_string());
+                    }
+                }
+            });
+        });
+        match &self.output_dir_error {
+            Some(e) => {
+                ui.label(
+                    egui::RichText::new(tr!("settings.output_dir_not_writable", e = e))
+                        .small()
+                        .color(egui::Color32::RED),
+                );
+            }
+            None => {
+                ui.label(
+                    egui::RichText::new(absolute_output_dir(&self.output_dir).display().to_string())
+                        .small()
+                        .weak(),
+                );
+            }
+        }
+
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            ui.horizontal(|ui| {
+                let before = self.settings.recording.clone();
+                let recording = &mut self.settings.recording;
+                ui.checkbox(&mut recording.enabled, tr!("settings.recording"))
+                    .on_hover_text(tr!("settings.recording_hint"));
+                ui.add_enabled_ui(recording.enabled, |ui| {
+                    egui::ComboBox::from_id_salt("recording_format")
+                        .selected_text(recording.format.label())
+                        .show_ui(ui, |ui| {
+                            for format in RecordingFormat::ALL {
+                                ui.selectable_value(&mut recording.format, *format, format.label());
+                            }
+                        });
+                    ui.checkbox(&mut recording.keep_tracks, tr!("settings.recording_tracks"))
+                        .on_hover_text(tr!("settings.recording_tracks_hint"));
+                });
+                if self.settings.recording != before {
+                    self.save_settings();
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            let before = self.settings.dataset.clone();
+            let needs_tracks = !(self.settings.recording.enabled && self.settings.recording.keep_tracks);
+            let dataset = &mut self.settings.dataset;
+            ui.checkbox(&mut dataset.enabled, tr!("dataset.enabled"))
+                .on_hover_text(tr!("dataset.hint"));
+            ui.add_enabled_ui(dataset.enabled, |ui| {
+                egui::ComboBox::from_id_salt("dataset_format")
+                    .selected_text(dataset.format.label())
+                    .show_ui(ui, |ui| {
+                        for format in DatasetFormat::ALL {
+                            ui.selectable_value(&mut dataset.format, *format, format.label());
+                        }
+                    });
+                ui.add(egui::TextEdit::singleline(&mut dataset.dir).desired_width(120.0));
+                ui.checkbox(&mut dataset.only_corrected, tr!("dataset.only_corrected"));
+            });
+            if dataset.enabled && needs_tracks {
+                ui.colored_label(egui::Color32::from_rgb(230, 160, 40), tr!("dataset.needs_tracks"));
+            }
+            if self.settings.dataset != before {
+                self.save_settings();
+            }
+        });
+
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            ui.horizontal(|ui| {
+                let before = self.settings.preroll.clone();
+                let preroll = &mut self.settings.preroll;
+                ui.checkbox(&mut preroll.enabled, tr!("settings.preroll"))
+                    .on_hover_text(tr!("settings.preroll_hint"));
+                ui.add_enabled(
+                    preroll.enabled,
+                    egui::DragValue::new(&mut preroll.seconds).range(5..=MAX_PREROLL_SECS).suffix(" s"),
+                );
+                if self.settings.preroll != before {
+                    self.save_settings();
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            let before = self.settings.split.clone();
+            let split = &mut self.settings.split;
+            ui.label(tr!("split.label")).on_hover_text(tr!("split.hint"));
+            ui.add(egui::DragValue::new(&mut split.every_hours).range(0.0..=24.0).speed(0.25).suffix(" h"))
+                .on_hover_text(tr!("split.every_hours"));
+            ui.add(egui::DragValue::new(&mut split.after_silence_mins).range(0.0..=240.0).speed(1.0).suffix(" min"))
+                .on_hover_text(tr!("split.after_silence"));
+            if self.settings.split != before {
+                self.save_settings();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("export.filename"));
+            if ui.add(egui::TextEdit::singleline(&mut self.settings.filename_pattern).desired_width(250.0)).changed() {
+                self.save_settings();
+            }
+        });
+        ui.label(
+            egui::RichText::new(tr!("export.filename_hint", vars = FILENAME_VARIABLES)).small().weak(),
+        );
+        let example = MinutaDocument::new(tr!("export.minutes_title"), self.active_names(), String::new());
+        let stem = file_stem(&self.settings.filename_pattern, &example, &self.active_names_stem());
+        ui.label(
+            egui::RichText::new(tr!(
+                "export.filename_preview",
+                file = format!("{}.{}", stem, self.settings.export_format.extension())
+            )).small(),
+        );
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("export.format"));
+            let before = self.settings.export_format;
+            egui::ComboBox::from_id_salt("export_format")
+                .selected_text(before.label())
+                .show_ui(ui, |ui| {
+                    for format in ExportFormat::ALL {
+                        ui.selectable_value(&mut self.settings.export_format, *format, format.label());
+                    }
+                });
+            if self.settings.export_format != before {
+                self.save_settings();
+            }
+        });
+        if ui.checkbox(&mut self.settings.export_per_speaker, tr!("export.per_speaker"))
+            .on_hover_text(tr!("export.per_speaker_hint"))
+            .changed()
+        {
+            self.save_settings();
+        }
+        if ui.checkbox(&mut self.settings.normalize_numbers, tr!("export.normalize_numbers"))
+            .on_hover_text(tr!("export.normalize_numbers_hint"))
+            .changed()
+        {
+            self.save_settings();
+        }
+        ui.horizontal(|ui| {
+            let before = self.settings.topics.clone();
+            let topics = &mut self.settings.topics;
+            ui.checkbox(&mut topics.enabled, tr!("topics.enabled"))
+                .on_hover_text(tr!("topics.enabled_hint"));
+            ui.add_enabled_ui(topics.enabled, |ui| {
+                ui.add(egui::DragValue::new(&mut topics.max_tags).range(1..=20));
+                ui.label(tr!("topics.ignore"));
+                ui.add(egui::TextEdit::singleline(&mut topics.ignore)
+                    .hint_text(tr!("topics.ignore_hint"))
+                    .desired_width(200.0));
+            });
+            if self.settings.topics != before {
+                self.save_settings();
+            }
+        });
+        if ui.checkbox(&mut self.settings.track_actions, tr!("actions.track"))
+            .on_hover_text(tr!("actions.track_hint"))
+            .changed()
+        {
+            self.save_settings();
+        }
+
+        match self.settings.export_format {
+            ExportFormat::Markdown | ExportFormat::Html => self.template_ui(ui),
+            ExportFormat::Pdf => self.pdf_branding_ui(ui),
+            ExportFormat::Docx | ExportFormat::Json | ExportFormat::Vtt => {}
+        }
+        self.redaction_ui(ui);
+        self.integrity_ui(ui);
+        self.encryption_ui(ui);
+        self.summary_prompts_ui(ui);
+    }
+
+    fn roster_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr!("roster.heading")).show(ui, |ui| {
+            ui.label(egui::RichText::new(tr!("roster.hint")).small().weak());
+            let before = self.settings.roster.clone();
+            let speaker_id_before = self.settings.speaker_id.clone();
+            let mut to_remove: Option<usize> = None;
+            let mut to_enroll: Option<usize> = None;
+            // Con el asistente abierto la lista no cambia: guarda por índice
+            let editable = self.enrollment.is_none();
+            let can_enroll = editable && !self.is_running && !self.is_stopping() && self.device_test.is_none();
+            ui.add_enabled_ui(editable, |ui| egui::Grid::new("roster_grid").striped(true).num_columns(5).show(ui, |ui| {
+                ui.label(tr!("roster.name"));
+                ui.label(tr!("roster.role"));
+                ui.label(tr!("roster.device"));
+                ui.label(tr!("roster.voice"));
+                ui.end_row();
+                for (i, participant) in self.settings.roster.iter_mut().enumerate() {
+                    ui.add(egui::TextEdit::singleline(&mut participant.name).desired_width(150.0));
+                    ui.add(egui::TextEdit::singleline(&mut participant.role).desired_width(120.0));
+                    let device = participant.device.as_ref().and_then(|t| {
+                        self.all_input_devices.iter().chain(&self.all_output_devices)
+                            .find(|d| d.technical_name.as_ref() == Some(t))
+                    });
+                    match (device, &participant.device) {
+                        (Some(d), _) => ui.label(self.settings.devices.display_name(d)),
+                        (None, Some(t)) => ui.weak(t),
+                        (None, None) => ui.weak("—"),
+                    };
+                    ui.horizontal(|ui| {
+                        if participant.voice.is_empty() {
+                            ui.weak("—");
+                        } else if ui.small_button("✔ 🗑").on_hover_text(tr!("speaker_id.forget")).clicked() {
+                            participant.voice.clear();
+                        }
+                        let can_enroll = can_enroll && !participant.name.trim().is_empty();
+                        if ui.add_enabled(can_enroll, egui::Button::new("🎙").small())
+                            .on_hover_text(tr!("enroll.open"))
+                            .clicked()
+                        {
+                            to_enroll = Some(i);
+                        }
+                    });
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            }));
+            if let Some(i) = to_remove {
+                self.settings.roster.remove(i);
+            }
+            if let Some(i) = to_enroll {
+                self.open_enrollment(i);
+            }
+            if ui.add_enabled(editable, egui::Button::new(tr!("roster.add"))).clicked() {
+                self.settings.roster.push(Participant::default());
+            }
+            ui.horizontal(|ui| {
+                ui.label(tr!("speaker_id.threshold"));
+                ui.add(egui::Slider::new(&mut self.settings.speaker_id.threshold, 0.0..=0.95).step_by(0.05));
+            })
+            .response
+            .on_hover_text(tr!("speaker_id.threshold_hint"));
+            if self.settings.roster != before || self.settings.speaker_id != speaker_id_before {
+                self.save_settings();
+            }
+        });
+    }
+
+    fn template_ui(&mut self, ui: &mut egui::Ui) {
+        let (path, extensions) = match self.settings.export_format {
+            ExportFormat::Html => (&mut self.settings.export_templates.html, ["html", "tera"]),
+            _ => (&mut self.settings.export_templates.markdown, ["md", "tera"]),
+        };
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(tr!("export.template"));
+            changed |= ui.add(egui::TextEdit::singleline(path).desired_width(250.0)).changed();
+            if ui.button(tr!("export.browse")).clicked() {
+                if let Some(picked) = rfd::FileDialog::new()
+                    .add_filter("Tera", &extensions)
+                    .pick_file()
+                {
+                    *path = picked.display().to_string();
+                    changed = true;
+                }
+            }
+        });
+        ui.label(egui::RichText::new(tr!("export.template_hint")).small().weak());
+        if changed {
+            self.save_settings();
+        }
+    }
+
+    fn redaction_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr!("redaction.heading")).strong())
+            .id_salt("redaction")
+            .show(ui, |ui| {
+                let before = self.settings.redaction.clone();
+                let redaction = &mut self.settings.redaction;
+                ui.checkbox(&mut redaction.enabled, tr!("redaction.enabled"))
+                    .on_hover_text(tr!("redaction.hint"));
+                ui.add_enabled_ui(redaction.enabled, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.checkbox(&mut redaction.profanity, tr!("redaction.profanity"));
+                        ui.checkbox(&mut redaction.emails, tr!("redaction.emails"));
+                        ui.checkbox(&mut redaction.phones, tr!("redaction.phones"));
+                        ui.checkbox(&mut redaction.ids, tr!("redaction.ids"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("redaction.mask"));
+                        ui.add(egui::TextEdit::singleline(&mut redaction.mask).desired_width(150.0));
+                    });
+                    ui.label(tr!("redaction.custom"));
+                    ui.add(
+                        egui::TextEdit::multiline(&mut redaction.custom_patterns)
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(2)
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text(r"PRJ-\d{4}"),
+                    );
+                    for pattern in redaction::custom_patterns(&redaction.custom_patterns) {
+                        if let Err(e) = regex::Regex::new(pattern) {
+                            ui.colored_label(egui::Color32::RED, tr!("post.invalid", e = e));
+                        }
+                    }
+                });
+                if self.settings.redaction != before {
+                    self.save_settings();
+                }
+            });
+    }
+
+    fn integrity_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr!("integrity.heading")).strong())
+            .id_salt("integrity")
+            .show(ui, |ui| {
+                let before = self.settings.integrity.clone();
+                let integrity = &mut self.settings.integrity;
+                ui.checkbox(&mut integrity.hash_chain, tr!("integrity.hash_chain"))
+                    .on_hover_text(tr!("integrity.hash_chain_hint"));
+                ui.checkbox(&mut integrity.sign, tr!("integrity.sign"));
+                ui.add_enabled_ui(integrity.sign, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("integrity.key"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut integrity.signing_key)
+                                .desired_width(250.0)
+                                .hint_text("~/.ssh/id_ed25519"),
+                        ).on_hover_text(tr!("integrity.key_hint"));
+                        if ui.button(tr!("export.browse")).clicked() {
+                            if let Some(path) = rfd::FileDialog::new().pick_file() {
+                                integrity.signing_key = path.display().to_string();
+                            }
+                        }
+                    });
+                });
+                if self.settings.integrity != before {
+                    self.save_settings();
+                }
+
+                if ui.button(tr!("integrity.verify")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .set_directory(absolute_output_dir(&self.output_dir))
+                        .pick_file()
+                    {
+                        self.integrity_result = Some(verify_minuta(&path, &self.settings.encryption));
+                    }
+                }
+                if let Some(result) = &self.integrity_result {
+                    ui.label(egui::RichText::new(result).monospace());
+                }
+            });
+    }
+
+    fn summary_prompts_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr!("summary.heading")).strong())
+            .id_salt("summary_prompts")
+            .show(ui, |ui| {
+                let before = self.settings.clone();
+                ui.label(egui::RichText::new(tr!("summary.hint")).small().weak());
+                ui.horizontal(|ui| {
+                    self.summary_prompt_combo(ui);
+                    if ui.button(tr!("summary.new")).clicked() {
+                        self.settings.summary_prompts.push(SummaryPrompt {
+                            name: tr!("summary.default_name", n = self.settings.summary_prompts.len() + 1),
+                            ..SummaryPrompt::default()
+                        });
+                        self.settings.active_summary_prompt = self.settings.summary_prompts.len() - 1;
+                    }
+                    if ui.add_enabled(!self.settings.summary_prompts.is_empty(), egui::Button::new("🗑")).clicked() {
+                        self.settings.summary_prompts.remove(self.settings.active_summary_prompt);
+                        self.settings.active_summary_prompt = self.settings.active_summary_prompt.saturating_sub(1);
+                    }
+                    if ui.button(tr!("summary.restore")).clicked() {
+                        self.settings.summary_prompts = template::default_summary_prompts();
+                        self.settings.active_summary_prompt = 0;
+                    }
+                });
+                let idx = self.settings.active_summary_prompt;
+                if let Some(prompt) = self.settings.summary_prompts.get_mut(idx) {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("summary.name"));
+                        ui.add(egui::TextEdit::singleline(&mut prompt.name).desired_width(200.0));
+                    });
+                    ui.add(
+                        egui::TextEdit::multiline(&mut prompt.template)
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(6)
+                            .font(egui::TextStyle::Monospace),
+                    );
+                }
+                if self.settings != before {
+                    self.save_settings();
+                }
+            });
+    }
+
+    /// Elige la plantilla de resumen de la sesión.
+    fn summary_prompt_combo(&mut self, ui: &mut egui::Ui) {
+        let selected = self.settings.active_summary_prompt().map(|p| p.name.clone()).unwrap_or_default();
+        let before = self.settings.active_summary_prompt;
+        egui::ComboBox::from_id_salt("summary_prompt")
+            .selected_text(selected)
+            .width(140.0)
+            .show_ui(ui, |ui| {
+                for (i, prompt) in self.settings.summary_prompts.iter().enumerate() {
+                    ui.selectable_value(&mut self.settings.active_summary_prompt, i, &prompt.name);
+                }
+            });
+        if self.settings.active_summary_prompt != before {
+            self.save_settings();
+        }
+    }
+
+    fn encryption_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr!("encryption.heading")).strong())
+            .id_salt("encryption")
+            .show(ui, |ui| {
+                let before = self.settings.encryption.clone();
+                let encryption = &mut self.settings.encryption;
+                ui.checkbox(&mut encryption.enabled, tr!("encryption.enabled"))
+                    .on_hover_text(tr!("encryption.hint"));
+                ui.horizontal(|ui| {
+                    for source in KeySource::ALL {
+                        ui.radio_value(&mut encryption.key_source, *source, tr!(source.label_key()));
+                    }
+                });
+                match encryption.key_source {
+                    KeySource::Passphrase => {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut encryption.passphrase)
+                                .password(true)
+                                .desired_width(250.0),
+                        ).on_hover_text(tr!("encryption.passphrase_hint"));
+                    }
+                    KeySource::Keyfile => {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut encryption.keyfile).desired_width(250.0));
+                            if ui.button(tr!("export.browse")).clicked() {
+                                if let Some(path) = rfd::FileDialog::new().pick_file() {
+                                    encryption.keyfile = path.display().to_string();
+                                }
+                            }
+                        });
+                    }
+                }
+                if encryption.enabled {
+                    if let Err(e) = encryption::check_key(encryption) {
+                        ui.colored_label(egui::Color32::RED, e.to_string());
+                    }
+                    if self.settings.recording.enabled {
+                        ui.colored_label(egui::Color32::from_rgb(230, 160, 40), tr!("encryption.recording_plain"));
+                    }
+                    ui.label(egui::RichText::new(tr!("encryption.recovery")).small().weak());
+                }
+                if self.settings.encryption != before {
+                    self.save_settings();
+                }
+            });
+    }
+
+    fn pdf_branding_ui(&mut self, ui: &mut egui::Ui) {
+        let branding = &mut self.settings.pdf_branding;
+        let mut changed = false;
+        egui::Grid::new("pdf_branding").num_columns(2).show(ui, |ui| {
+            ui.label(tr!("export.pdf_company"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut branding.company).desired_width(250.0)).changed();
+            ui.end_row();
+
+            ui.label(tr!("export.pdf_logo"));
+            ui.horizontal(|ui| {
+                changed |= ui.add(egui::TextEdit::singleline(&mut branding.logo_path).desired_width(250.0)).changed();
+                if ui.button(tr!("export.browse")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("JPEG", &["jpg", "jpeg"])
+                        .pick_file()
+                    {
+                        branding.logo_path = path.display().to_string();
+                        changed = true;
+                    }
+                }
+            });
+            ui.end_row();
+
+            ui.label(tr!("export.pdf_footer"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut branding.footer).desired_width(250.0)).changed();
+            ui.end_row();
+        });
+        if changed {
+            self.save_settings();
+        }
+    }
+
+    // ── Pestaña: Configuración → Integraciones ────────────────────────────
+
+    fn integrations_ui(&mut self, ui: &mut egui::Ui) {
+        let before = self.settings.integrations.clone();
+        let hooks_before = self.settings.hooks.clone();
+        let web_before = self.settings.web.clone();
+        ui.label(egui::RichText::new(tr!("integrations.hint")).small().weak());
+        ui.add_space(6.0);
+
+        let mut refresh_calendar = false;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let calendar = &mut self.settings.integrations.calendar;
+            egui::CollapsingHeader::new(tr!("calendar.heading")).default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut calendar.enabled, tr!("integrations.enabled"));
+                egui::Grid::new("calendar_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("calendar.url"));
+                    ui.add(egui::TextEdit::singleline(&mut calendar.ics_url).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("calendar.username"));
+                    ui.add(egui::TextEdit::singleline(&mut calendar.username).desired_width(200.0));
+                    ui.end_row();
+
+                    ui.label(tr!("calendar.password"));
+                    ui.add(egui::TextEdit::singleline(&mut calendar.password).password(true).desired_width(200.0));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut calendar.prompt_on_call, tr!("calendar.prompt_on_call"));
+                ui.horizontal(|ui| {
+                    refresh_calendar = ui
+                        .add_enabled(calendar.is_enabled(), egui::Button::new(tr!("calendar.refresh")))
+                        .clicked();
+                    ui.label(egui::RichText::new(&self.calendar.status).small().weak());
+                });
+            });
+
+            let slack = &mut self.settings.integrations.slack;
+            egui::CollapsingHeader::new("Slack").default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut slack.enabled, tr!("integrations.enabled"));
+                egui::Grid::new("slack_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("integrations.slack_webhook"));
+                    ui.add(egui::TextEdit::singleline(&mut slack.webhook_url).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.slack_token"));
+                    ui.add(egui::TextEdit::singleline(&mut slack.bot_token).password(true).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.slack_channel"));
+                    ui.add(egui::TextEdit::singleline(&mut slack.channel_id).desired_width(150.0));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut slack.attach_file, tr!("integrations.slack_attach"));
+            });
+
+            let notion = &mut self.settings.integrations.notion;
+            egui::CollapsingHeader::new("Notion").default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut notion.enabled, tr!("integrations.enabled"));
+                egui::Grid::new("notion_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("integrations.notion_token"));
+                    ui.add(egui::TextEdit::singleline(&mut notion.token).password(true).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.notion_database"));
+                    ui.add(egui::TextEdit::singleline(&mut notion.database_id).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.notion_title_prop"));
+                    ui.add(egui::TextEdit::singleline(&mut notion.title_property).desired_width(150.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.notion_date_prop"));
+                    ui.add(egui::TextEdit::singleline(&mut notion.date_property).desired_width(150.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.notion_attendees_prop"));
+                    ui.add(egui::TextEdit::singleline(&mut notion.attendees_property).desired_width(150.0));
+                    ui.end_row();
+                });
+            });
+
+            let telegram = &mut self.settings.integrations.telegram;
+            egui::CollapsingHeader::new("Telegram").default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut telegram.enabled, tr!("integrations.enabled"));
+                egui::Grid::new("telegram_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("integrations.telegram_token"));
+                    ui.add(egui::TextEdit::singleline(&mut telegram.bot_token).password(true).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.telegram_chat"));
+                    ui.add(egui::TextEdit::singleline(&mut telegram.chat_id).desired_width(150.0));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut telegram.attach_file, tr!("integrations.telegram_attach"));
+                ui.checkbox(&mut telegram.live, tr!("integrations.telegram_live"))
+                    .on_hover_text(tr!("integrations.telegram_live_hint"));
+            });
+
+            let discord = &mut self.settings.integrations.discord;
+            egui::CollapsingHeader::new("Discord").default_open(true).show(ui, |ui| {
+                ui.label(egui::RichText::new(tr!("discord.hint")).small().weak());
+                egui::Grid::new("discord_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("discord.token"));
+                    ui.add(egui::TextEdit::singleline(&mut discord.bot_token).password(true).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("discord.guild"));
+                    ui.add(egui::TextEdit::singleline(&mut discord.guild_id).desired_width(200.0));
+                    ui.end_row();
+
+                    ui.label(tr!("discord.text_channel"));
+                    ui.add(egui::TextEdit::singleline(&mut discord.text_channel_id).desired_width(200.0));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut discord.post_live, tr!("discord.post_live"));
+            });
+
+            let matrix = &mut self.settings.integrations.matrix;
+            egui::CollapsingHeader::new("Matrix").default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut matrix.enabled, tr!("integrations.enabled"));
+                egui::Grid::new("matrix_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("integrations.matrix_homeserver"));
+                    ui.add(egui::TextEdit::singleline(&mut matrix.homeserver).desired_width(350.0).hint_text("https://matrix.org"));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.matrix_token"));
+                    ui.add(egui::TextEdit::singleline(&mut matrix.access_token).password(true).desired_width(350.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.matrix_room"));
+                    ui.add(egui::TextEdit::singleline(&mut matrix.room_id).desired_width(250.0).hint_text("!abc123:matrix.org"));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut matrix.attach_file, tr!("integrations.matrix_attach"));
+            });
+
+            let git = &mut self.settings.integrations.git;
+            egui::CollapsingHeader::new("Git").default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut git.enabled, tr!("integrations.enabled"))
+                    .on_hover_text(tr!("git.hint"));
+                egui::Grid::new("git_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("git.repo"));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut git.repo_path).desired_width(300.0));
+                        if ui.button(tr!("export.browse")).clicked() {
+                            if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                                git.repo_path = dir.display().to_string();
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr!("git.subfolder"));
+                    ui.add(egui::TextEdit::singleline(&mut git.subfolder).desired_width(200.0).hint_text("actas"));
+                    ui.end_row();
+
+                    ui.label(tr!("git.message"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut git.message)
+                            .desired_width(300.0)
+                            .font(egui::TextStyle::Monospace),
+                    );
+                    ui.end_row();
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut git.push, tr!("git.push"));
+                    ui.add_enabled(git.push, egui::TextEdit::singleline(&mut git.remote).desired_width(100.0));
+                });
+            });
+
+            let mqtt = &mut self.settings.integrations.mqtt;
+            egui::CollapsingHeader::new("MQTT").default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut mqtt.enabled, tr!("integrations.enabled"))
+                    .on_hover_text(tr!("integrations.mqtt_hint"));
+                egui::Grid::new("mqtt_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("integrations.mqtt_broker"));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut mqtt.host).desired_width(250.0).hint_text("localhost"));
+                        ui.add(egui::DragValue::new(&mut mqtt.port).range(1..=65535));
+                    });
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.mqtt_topic"));
+                    ui.add(egui::TextEdit::singleline(&mut mqtt.topic).desired_width(250.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.mqtt_username"));
+                    ui.add(egui::TextEdit::singleline(&mut mqtt.username).desired_width(200.0));
+                    ui.end_row();
+
+                    ui.label(tr!("integrations.mqtt_password"));
+                    ui.add(egui::TextEdit::singleline(&mut mqtt.password).password(true).desired_width(200.0));
+                    ui.end_row();
+                });
+            });
+
+            let ndi = &mut self.settings.integrations.ndi;
+            egui::CollapsingHeader::new("NDI").default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut ndi.enabled, tr!("integrations.enabled"))
+                    .on_hover_text(tr!("ndi.hint"));
+                ui.horizontal(|ui| {
+                    ui.label(tr!("ndi.source_name"));
+                    ui.add(egui::TextEdit::singleline(&mut ndi.source_name).desired_width(200.0));
+                });
+            });
+
+            let hooks = &mut self.settings.hooks;
+            egui::CollapsingHeader::new(tr!("hooks.heading")).default_open(true).show(ui, |ui| {
+                ui.label(egui::RichText::new(tr!("hooks.hint")).small().weak());
+                egui::Grid::new("hooks_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("hooks.on_capture_start"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut hooks.on_capture_start)
+                            .desired_width(350.0)
+                            .font(egui::TextStyle::Monospace),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr!("hooks.on_segment"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut hooks.on_segment)
+                            .desired_width(350.0)
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text(r#"echo "$MINUTERO_SPEAKER: $MINUTERO_TEXT" >> en_vivo.txt"#),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr!("hooks.on_session_saved"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut hooks.on_session_saved)
+                            .desired_width(350.0)
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text(r#"cp "$MINUTERO_PATH" ~/Nextcloud/actas/"#),
+                    );
+                    ui.end_row();
+                });
+            });
+
+            let web = &mut self.settings.web;
+            egui::CollapsingHeader::new(tr!("web.heading")).default_open(true).show(ui, |ui| {
+                ui.checkbox(&mut web.enabled, tr!("integrations.enabled"))
+                    .on_hover_text(tr!("web.hint"));
+                egui::Grid::new("web_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("web.port"));
+                    ui.add(egui::DragValue::new(&mut web.port).range(1..=65535));
+                    ui.end_row();
+
+                    ui.label(tr!("web.token"));
+                    ui.add(egui::TextEdit::singleline(&mut web.token).password(true).desired_width(200.0));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut web.lan, tr!("web.lan"))
+                    .on_hover_text(tr!("web.lan_hint"));
+                if self.web.is_some() {
+                    let url = web.local_url();
+                    ui.hyperlink_to(&url, &url);
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(tr!("web.captions")).on_hover_text(tr!("web.captions_hint"));
+                        for file in ["captions.m3u8", "captions.vtt", "captions.txt"] {
+                            ui.hyperlink_to(file, web.local_url_for(file));
+                        }
+                    });
+                }
+                if let Some((_, e)) = &self.web_error {
+                    ui.colored_label(egui::Color32::RED, tr!("web.error", e = e));
+                }
+            });
+        });
+
+        if self.settings.integrations != before
+            || self.settings.hooks != hooks_before
+            || self.settings.web != web_before
+        {
+            self.save_settings();
+        }
+        if refresh_calendar {
+            self.calendar.refresh(&self.settings.integrations.calendar);
+        }
+    }
+
+    // ── Pestaña: Configuración → Post-procesado ───────────────────────────
+
+    fn postprocessing_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| self.preset_ui(ui));
+        });
+
+        if self.is_running {
+            ui.label(
+                egui::RichText::new(tr!("settings.stop_to_edit"))
+                    .color(egui::Color32::YELLOW),
+            );
+        }
+    }
+
+    fn preset_ui(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("settings.preset"));
+            let selected = self.settings.active_preset().name;
+            egui::ComboBox::from_id_salt("preset")
+                .selected_text(selected)
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    for (i, preset) in self.settings.presets.iter().enumerate() {
+                        changed |= ui
+                            .selectable_value(&mut self.settings.active_preset, i, &preset.name)
+                            .changed();
+                    }
+                });
+
+            if ui.button(tr!("settings.preset_new")).clicked() {
+                self.settings.presets.push(Preset {
+                    name: tr!("settings.preset_default_name", n = self.settings.presets.len() + 1),
+                    ..Preset::default()
+                });
+                self.settings.active_preset = self.settings.presets.len() - 1;
+                changed = true;
+            }
+            if self.settings.presets.len() > 1 && ui.button("🗑").clicked() {
+                self.settings.presets.remove(self.settings.active_preset);
+                self.settings.active_preset = self.settings.active_preset.saturating_sub(1);
+                changed = true;
+            }
+        });
+
+        if self.settings.presets.is_empty() {
+            self.settings.presets.push(Preset::default());
+            self.settings.active_preset = 0;
+        }
+        let idx = self.settings.active_preset.min(self.settings.presets.len() - 1);
+        let preset = &mut self.settings.presets[idx];
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("settings.preset_name"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut preset.name).desired_width(200.0)).changed();
+        });
+
+        ui.add_space(8.0);
+        ui.label(egui::RichText::new(tr!("settings.vocabulary")).strong());
+        ui.label(tr!("settings.glossary"));
+        changed |= ui.add(
+            egui::TextEdit::multiline(&mut preset.glossary)
+                .desired_width(f32::INFINITY)
+                .desired_rows(3)
+                .hint_text(tr!("settings.glossary_hint")),
+        ).changed();
+        changed |= ui.checkbox(&mut preset.glossary_corrections, tr!("settings.glossary_corrections")).changed();
+
+        // Puntuación entre fragmentos
+        ui.add_space(8.0);
+        changed |= ui.checkbox(&mut preset.restore_punctuation, tr!("post.punctuation"))
+            .on_hover_text(tr!("post.punctuation_hint"))
+            .changed();
+
+        // Grafía fija
+        ui.add_space(8.0);
+        ui.label(egui::RichText::new(tr!("post.spellings")).strong());
+        ui.label(
+            egui::RichText::new(tr!("post.spellings_hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        let mut spelling_to_remove: Option<usize> = None;
+        egui::Grid::new("spellings").striped(true).show(ui, |ui| {
+            for (i, term) in preset.spellings.iter_mut().enumerate() {
+                changed |= ui.add(egui::TextEdit::singleline(term).desired_width(240.0)).changed();
+                if ui.button("🗑").clicked() {
+                    spelling_to_remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = spelling_to_remove {
+            preset.spellings.remove(i);
+            changed = true;
+        }
+        if ui.button(tr!("post.add_spelling")).clicked() {
+            preset.spellings.push(String::new());
+            changed = true;
+        }
+
+        // Reglas de sustitución
+        ui.add_space(8.0);
+        ui.label(egui::RichText::new(tr!("post.rules")).strong());
+        ui.label(
+            egui::RichText::new(tr!("post.rules_hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+
+        let mut to_remove: Option<usize> = None;
+        egui::Grid::new("replacement_rules").striped(true).show(ui, |ui| {
+            ui.label("");
+            ui.label(tr!("post.pattern"));
+            ui.label(tr!("post.replacement"));
+            ui.label("Aa");
+            ui.end_row();
+
+            for (i, rule) in preset.replacements.iter_mut().enumerate() {
+                changed |= ui.checkbox(&mut rule.enabled, "").changed();
+                changed |= ui.add(
+                    egui::TextEdit::singleline(&mut rule.pattern)
+                        .desired_width(180.0)
+                        .font(egui::TextStyle::Monospace),
+                ).changed();
+                changed |= ui.add(
+                    egui::TextEdit::singleline(&mut rule.replacement)
+                        .desired_width(180.0)
+                        .font(egui::TextStyle::Monospace),
+                ).changed();
+                let mut case_sensitive = !rule.case_insensitive;
+                if ui.checkbox(&mut case_sensitive, "")
+                    .on_hover_text(tr!("post.case_sensitive"))
+                    .changed()
+                {
+                    rule.case_insensitive = !case_sensitive;
+                    changed = true;
+                }
+                if ui.button("🗑").clicked() {
+                    to_remove = Some(i);
+                }
+                if let Err(e) = compile_rule(rule) {
+                    ui.colored_label(egui::Color32::RED, "⚠")
+                        .on_hover_text(tr!("post.invalid", e = e));
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(i) = to_remove {
+            preset.replacements.remove(i);
+            changed = true;
+        }
+        if ui.button(tr!("post.add_rule")).clicked() {
+            preset.replacements.push(ReplacementRule::default());
+            changed = true;
+        }
+
+        // Copia en almacenamiento remoto
+        ui.add_space(8.0);
+        ui.label(egui::RichText::new(tr!("storage.heading")).strong());
+        let upload = &mut preset.upload;
+        changed |= ui.checkbox(&mut upload.enabled, tr!("storage.enabled"))
+            .on_hover_text(tr!("storage.hint"))
+            .changed();
+        ui.add_enabled_ui(upload.enabled, |ui| {
+            egui::Grid::new("storage_grid").num_columns(2).show(ui, |ui| {
+                ui.label(tr!("storage.kind"));
+                ui.horizontal(|ui| {
+                    for kind in StorageKind::ALL {
+                        changed |= ui.selectable_value(&mut upload.kind, *kind, kind.label()).changed();
+                    }
+                });
+                ui.end_row();
+
+                let (url_label, url_hint) = match upload.kind {
+                    StorageKind::S3 => (tr!("storage.endpoint"), "https://s3.eu-west-1.amazonaws.com"),
+                    StorageKind::WebDav => (tr!("storage.webdav_url"), "https://dav.example.org/minutas"),
+                    StorageKind::Nextcloud => (tr!("storage.server"), "https://cloud.example.org"),
+                };
+                ui.label(url_label);
+                changed |= ui.add(egui::TextEdit::singleline(&mut upload.url).desired_width(300.0).hint_text(url_hint)).changed();
+                ui.end_row();
+
+                if upload.kind == StorageKind::S3 {
+                    ui.label(tr!("storage.bucket"));
+                    changed |= ui.add(egui::TextEdit::singleline(&mut upload.bucket).desired_width(200.0)).changed();
+                    ui.end_row();
+
+                    ui.label(tr!("storage.region"));
+                    changed |= ui.add(egui::TextEdit::singleline(&mut upload.region).desired_width(120.0).hint_text("us-east-1")).changed();
+                    ui.end_row();
+                }
+
+                ui.label(tr!("storage.folder"));
+                changed |= ui.add(egui::TextEdit::singleline(&mut upload.folder).desired_width(200.0).hint_text("minutas/clientes")).changed();
+                ui.end_row();
+
+                let (user_label, secret_label) = match upload.kind {
+                    StorageKind::S3 => (tr!("storage.access_key"), tr!("storage.secret_key")),
+                    StorageKind::WebDav | StorageKind::Nextcloud => (tr!("storage.username"), tr!("storage.password")),
+                };
+                ui.label(user_label);
+                changed |= ui.add(egui::TextEdit::singleline(&mut upload.username).desired_width(200.0)).changed();
+                ui.end_row();
+
+                ui.label(secret_label);
+                changed |= ui.add(egui::TextEdit::singleline(&mut upload.password).password(true).desired_width(200.0)).changed();
+                ui.end_row();
+            });
+            changed |= ui.checkbox(&mut upload.include_audio, tr!("storage.include_audio")).changed();
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+
+    fn model_source_ui(&mut self, ui: &mut egui::Ui) {
+        let before = self.settings.model_source.clone();
+        let source = &mut self.settings.model_source;
+        ui.horizontal(|ui| {
+            ui.label(tr!("model.custom_path"));
+            ui.add(
+                egui::TextEdit::singleline(&mut source.custom_path)
+                    .desired_width(300.0)
+                    .hint_text("models/ggml-large-v3.bin"),
+            ).on_hover_text(tr!("model.custom_path_hint"));
+            if ui.button(tr!("export.browse")).clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("ggml", &["bin"])
+                    .pick_file()
+                {
+                    source.custom_path = path.display().to_string();
+                }
+            }
+            if !source.custom_path.is_empty() && ui.small_button("✖").clicked() {
+                source.custom_path.clear();
+            }
+        });
+        let custom = source.custom_path.trim();
+        if !custom.is_empty() && !Path::new(custom).is_file() {
+            ui.colored_label(egui::Color32::RED, tr!("model.custom_missing", path = custom));
+        }
+        ui.checkbox(&mut source.offline, tr!("model.offline"))
+            .on_hover_text(tr!("model.offline_hint"));
+        ui.add_enabled_ui(!source.offline && source.custom_path.trim().is_empty(), |ui| {
+            egui::Grid::new("model_download").num_columns(2).show(ui, |ui| {
+                ui.label(tr!("model.mirror"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut source.mirror_url)
+                        .desired_width(300.0)
+                        .hint_text(DEFAULT_MODEL_MIRROR),
+                ).on_hover_text(tr!("model.mirror_hint"));
+                ui.end_row();
+                ui.label(tr!("model.proxy"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut source.proxy)
+                        .desired_width(300.0)
+                        .hint_text("http://proxy.empresa.local:3128"),
+                ).on_hover_text(tr!("model.proxy_hint"));
+                ui.end_row();
+            });
+        });
+        if self.settings.model_source != before {
+            self.save_settings();
+        }
+
+        if ui.checkbox(&mut self.settings.preload_model, tr!("preload.enabled"))
+            .on_hover_text(tr!("preload.hint"))
+            .changed()
+        {
+            self.save_settings();
+            if self.settings.preload_model {
+                self.start_preload();
+            } else {
+                preload::release();
+            }
+        }
+
+        ui.add_space(8.0);
+        let before = self.settings.limits.clone();
+        let limits = &mut self.settings.limits;
+        egui::Grid::new("limits_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr!("resources.max_memory"));
+            ui.add(
+                egui::DragValue::new(&mut limits.max_memory_mb)
+                    .range(0..=1_048_576)
+                    .speed(64)
+                    .custom_formatter(|v, _| if v == 0.0 { tr!("resources.auto") } else { format!("{} MB", v) }),
+            ).on_hover_text(tr!("resources.max_memory_hint"));
+            ui.end_row();
+            ui.label(tr!("resources.max_models"));
+            ui.add(
+                egui::DragValue::new(&mut limits.max_models)
+                    .range(0..=64)
+                    .custom_formatter(|v, _| if v == 0.0 { tr!("resources.unlimited") } else { format!("{}", v) }),
+            ).on_hover_text(tr!("resources.max_models_hint"));
+            ui.end_row();
+        });
+        if self.settings.limits != before {
+            self.save_settings();
+        }
+        let active = self.interlocutors.iter().filter(|p| p.is_active).count();
+        let estimate = self.memory_estimate(active);
+        let text = tr!(
+            "resources.estimate",
+            n = active,
+            required = resources::format_mb(estimate.required_mb),
+            available = estimate.limit_mb.map_or("?".to_string(), resources::format_mb),
+        );
+        match estimate.error() {
+            Some(error) => ui.colored_label(egui::Color32::RED, error),
+            None if estimate.is_tight() => ui.colored_label(egui::Color32::from_rgb(230, 160, 40), text),
+            None => ui.label(egui::RichText::new(text).small().weak()),
+        };
+    }
+
+    /// Memoria que pediría capturar `streams` fuentes con el modelo elegido.
+    fn memory_estimate(&self, streams: usize) -> resources::MemoryEstimate {
+        let compare = self.compare_model.as_deref().filter(|m| *m != self.model_name);
+        resources::estimate(&self.model_name, compare, streams, &self.settings.model_source, &self.settings.limits)
+    }
+
+    fn tuning_ui(&mut self, ui: &mut egui::Ui) {
+        let max_threads = thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(8);
+        let before = self.settings.tuning.clone();
+        let tuning = &mut self.settings.tuning;
+
+        egui::Grid::new("tuning_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr!("tuning.threads"));
+            ui.add(egui::Slider::new(&mut tuning.n_threads, 0..=max_threads)
+                .custom_formatter(|v, _| if v == 0.0 { tr!("tuning.auto") } else { format!("{}", v) }));
+            ui.end_row();
+
+            ui.label(tr!("tuning.beam_size"));
+            ui.add(egui::Slider::new(&mut tuning.beam_size, 1..=8));
+            ui.end_row();
+
+            ui.label(tr!("tuning.best_of"));
+            ui.add_enabled(tuning.beam_size <= 1, egui::Slider::new(&mut tuning.best_of, 1..=8));
+            ui.end_row();
+
+            ui.label(tr!("tuning.temperature"));
+            ui.add(egui::Slider::new(&mut tuning.temperature, 0.0..=1.0).step_by(0.05));
+            ui.end_row();
+
+            ui.label(tr!("tuning.no_speech"));
+            ui.add(egui::Slider::new(&mut tuning.no_speech_thold, 0.0..=1.0).step_by(0.05))
+                .on_hover_text(tr!("tuning.no_speech_hint"));
+            ui.end_row();
+
+            ui.label(tr!("tuning.chunk"));
+            ui.add(egui::Slider::new(&mut tuning.chunk_secs, MIN_CHUNK_SECS..=MAX_CHUNK_SECS).step_by(0.5).suffix(" s"));
+            ui.end_row();
+
+            ui.label(tr!("tuning.overlap"));
+            ui.add(egui::Slider::new(&mut tuning.overlap_percent, 0..=MAX_OVERLAP_PERCENT).suffix(" %"));
+            ui.end_row();
+
+            ui.label(tr!("tuning.max_latency"));
+            ui.add(egui::Slider::new(&mut tuning.max_latency_secs, 0.0..=MAX_LATENCY_SECS).step_by(5.0)
+                .custom_formatter(|v, _| if v == 0.0 { tr!("tuning.unlimited") } else { format!("{} s", v) }));
+            ui.end_row();
+
+            ui.label(tr!("tuning.energy_saver"));
+            ui.add(egui::Slider::new(&mut tuning.energy_saver_secs, 0..=MAX_ENERGY_SAVER_SECS).step_by(10.0)
+                .custom_formatter(|v, _| if v == 0.0 { tr!("tuning.off") } else { format!("{} s", v) }))
+                .on_hover_text(tr!("tuning.energy_saver_hint"));
+            ui.end_row();
+
+            ui.label(tr!("tuning.workers"));
+            ui.add(egui::Slider::new(&mut tuning.workers, 0..=max_threads)
+                .custom_formatter(|v, _| if v == 0.0 { tr!("tuning.workers_per_stream") } else { format!("{}", v) }))
+                .on_hover_text(tr!("tuning.workers_hint"));
+            ui.end_row();
+        });
+
+        ui.checkbox(&mut tuning.auto_downgrade, tr!("tuning.auto_downgrade"));
+        ui.add_enabled(tuning.workers > 0, egui::Checkbox::new(&mut tuning.mic_priority, tr!("tuning.mic_priority")));
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut tuning.filter_hallucinations, tr!("tuning.filter_hallucinations"));
+            if ui.small_button(tr!("tuning.hallucinations_restore")).clicked() {
+                tuning.hallucinations = hallucinations::default_list();
+            }
+        });
+        ui.add_enabled(
+            tuning.filter_hallucinations,
+            egui::TextEdit::multiline(&mut tuning.hallucinations)
+                .desired_width(f32::INFINITY)
+                .desired_rows(3),
+        ).on_hover_text(tr!("tuning.hallucinations_hint"));
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(tr!("tuning.hint"))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+            if ui.small_button(tr!("tuning.reset")).clicked() {
+                *tuning = WhisperTuning::default();
+            }
+        });
+
+        if self.settings.tuning != before {
+            self.save_settings();
+        }
+    }
+
+    fn show_exit_dialog(&mut self, ctx: &egui::Context) {
+        let mut choice = None;
+        egui::Window::new(tr!("exit.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(tr!("exit.body"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("exit.save_and_quit")).clicked() {
+                        choice = Some(ExitChoice::SaveAndQuit);
+                    }
+                    if ui.button(tr!("exit.quit_without_saving")).clicked() {
+                        choice = Some(ExitChoice::Quit);
+                    }
+                    if ui.button(tr!("common.cancel")).clicked() {
+                        choice = Some(ExitChoice::Cancel);
+                    }
+                });
+            });
+
+        match choice {
+            Some(ExitChoice::SaveAndQuit) => {
+                // Aquí sí se espera: el proceso termina justo después.
+                // Todas a la vez, para que se cierren en paralelo
+                for room in &mut self.rooms {
+                    room.stop();
+                }
+                let mut saving: Vec<_> = self.rooms.iter_mut().filter_map(|r| r.stop_and_wait(&self.settings)).collect();
+                if self.is_running {
+                    saving.push(self.stop_capture());
+                }
+                self.join_audio_thread();
+                for handle in saving {
+                    let _ = handle.join();
+                }
+                self.quit(ctx);
+            }
+            Some(ExitChoice::Quit) => {
+                if let Some(sig) = self.stop_signal.take() {
+                    sig.stop();
+                }
+                self.is_running = false;
+                self.join_audio_thread();
+                self.quit(ctx);
+            }
+            Some(ExitChoice::Cancel) => self.show_exit_confirm = false,
+            None => {}
+        }
+    }
+
+    /// Ofrece recuperar la minuta que se quedó sin guardar en un fallo.
+    fn show_recovery_dialog(&mut self, ctx: &egui::Context) {
+        let mut restore = None;
+        egui::Window::new(tr!("crash.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(tr!("crash.body"));
+                if self.settings.encryption.enabled {
+                    ui.label(egui::RichText::new(tr!("crash.encrypted")).small().weak());
+                }
+                if let Some(report) = crash::last_report() {
+                    ui.label(egui::RichText::new(tr!("crash.report", path = report.display())).small().weak());
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("crash.restore")).clicked() {
+                        restore = Some(true);
+                    }
+                    if ui.button(tr!("crash.discard")).clicked() {
+                        restore = Some(false);
+                    }
+                });
+            });
+
+        if let Some(restore) = restore {
+            // Si no se puede abrir (cifrada y aún sin frase de paso) se
+            // conserva para volver a intentarlo
+            let done = !restore || self.recovery.clone().is_some_and(|path| self.load_session(&path));
+            if done {
+                self.recovery = None;
+                crash::discard_recovery();
+            }
+        }
+    }
+
+    /// Mantiene al día la copia que guarda el gancho de panic.
+    fn sync_crash_snapshot(&mut self) {
+        let shape = (self.segments.len(), self.transcript_version, self.session_title.len());
+        if shape == self.crash_snapshot {
+            return;
+        }
+        self.crash_snapshot = shape;
+        if self.segments.is_empty() {
+            crash::set_session(None, &self.settings.encryption);
+        } else {
+            // Ruta completa de la grabación: la copia no está junto a ella
+            let doc = self.live_document()
+                .with_recording(self.recording_path.as_ref().map(|p| p.display().to_string()));
+            crash::set_session(Some(&doc), &self.settings.encryption);
+        }
+    }
+
+    /// Guarda la minuta y empieza un bloque nuevo, sin parar la captura,
+    /// cuando el bloque dura demasiado o lleva mucho en silencio.
+    fn sync_split(&mut self) {
+        if !self.is_running {
+            return;
+        }
+        let Some(reason) = self.settings.split.due(self.block_started, self.last_segment_at) else { return };
+        let block = self.split_block.unwrap_or(1);
+        self.split_block = Some(block);
+        tracing::info!("Bloque {} de la minuta cerrado ({:?})", block, reason);
+        self.save_live_minuta(tr!("split.saved", n = block));
+
+        self.segments.clear();
+        self.transcript_selection = None;
+        self.transcript_cursor = None;
+        self.split_block = Some(block + 1);
+        self.block_started = Instant::now();
+        self.last_segment_at = None;
+        self.status_message = match reason {
+            SplitReason::Duration => tr!("split.status_duration", n = block),
+            SplitReason::Silence => tr!("split.status_silence", n = block),
+        };
+    }
+
+    /// Muestra cómo acaban las subidas a la nube, que van en segundo plano.
+    fn sync_uploads(&mut self, ctx: &egui::Context) {
+        for outcome in storage::take_outcomes() {
+            match outcome {
+                storage::UploadOutcome::Uploaded(message) => self.status_message = message,
+                storage::UploadOutcome::Failed(message) => {
+                    if self.settings.notifications.errors {
+                        desktop_notification(&tr!("notify.upload_failed"), &message);
+                    }
+                    self.status_message = format!("⚠ {}", message);
+                }
+            }
+        }
+        if storage::uploads_pending() {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+
+    /// Abre o cierra la página web según la opción, atiende sus botones y le
+    /// pasa la minuta cuando cambia.
+    fn sync_web(&mut self) {
+        let settings = &self.settings.web;
+        if self.web.as_ref().is_some_and(|web| !settings.enabled || web.settings() != settings) {
+            self.web = None;
+        }
+        let failed = matches!(&self.web_error, Some((s, _)) if s == settings);
+        if settings.enabled && self.web.is_none() && !failed {
+            match WebServer::start(settings, &self.settings.redaction) {
+                Ok(web) => {
+                    self.web = Some(web);
+                    self.web_error = None;
+                    self.web_snapshot = None;
+                    self.web_rooms_dirty = true;
+                }
+                Err(e) => {
+                    tracing::error!("Página web: {:?}", e);
+                    self.web_error = Some((settings.clone(), e.to_string()));
+                }
+            }
+        }
+
+        let Some(web) = &self.web else { return };
+        for command in web.commands() {
+            match command {
+                WebCommand::Start(MAIN_SESSION) if !self.is_running && !self.is_stopping() => {
+                    if self.interlocutors.iter().any(|p| p.is_active) {
+                        self.start_audio_capture();
+                    } else {
+                        self.status_message = tr!("live.no_active");
+                    }
+                }
+                WebCommand::Stop(MAIN_SESSION) if self.is_running => {
+                    self.stop_capture();
+                }
+                WebCommand::Start(MAIN_SESSION) | WebCommand::Stop(MAIN_SESSION) => {}
+                WebCommand::Start(id) => self.start_room(id),
+                WebCommand::Stop(id) => self.stop_room(id),
+            }
+        }
+
+        if std::mem::take(&mut self.web_rooms_dirty) {
+            if let Some(web) = &self.web {
+                let ids: Vec<usize> = std::iter::once(MAIN_SESSION).chain(self.rooms.iter().map(|r| r.id)).collect();
+                web.retain_sessions(&ids);
+                for room in &self.rooms {
+                    web.publish(room.id, &room.name, room.is_running(), &room.status, &room.segments);
+                }
+            }
+        }
+
+        let shape = (
+            self.segments.len(),
+            self.transcript_version,
+            self.session_title.len(),
+            self.is_running,
+            self.status_message.len(),
+        );
+        if self.web_snapshot == Some(shape) {
+            return;
+        }
+        self.web_snapshot = Some(shape);
+        if let Some(web) = &self.web {
+            web.publish(MAIN_SESSION, self.session_title.trim(), self.is_running, &self.status_message, &self.segments);
+        }
+    }
+
+    /// Captura detenida pero con fuentes de audio aún cerrándose.
+    /// Abre, reabre o cierra la captura en espera según la opción, los
+    /// perfiles activos y si hay una captura en marcha.
+    fn sync_preroll(&mut self) {
+        let seconds = self.settings.preroll.seconds;
+        // Mientras se prueba un dispositivo o se registra una voz, la prueba
+        // lo tiene abierto
+        let enrolling = self.enrollment.as_ref().is_some_and(|w| w.recording.is_some());
+        let idle = !self.is_running && !self.is_stopping() && self.device_test.is_none() && !enrolling;
+        let active: Vec<InterlocutorProfile> = self.interlocutors
+            .iter().filter(|p| p.is_active).cloned().collect();
+        let profiles = preroll_profiles(&active);
+        let wanted = self.settings.preroll.enabled && idle && !profiles.is_empty();
+
+        if let Some(preroll) = &self.preroll {
+            for e in preroll.errors() {
+                self.status_message = tr!("preroll.error", e = e);
+            }
+            if wanted && preroll.matches(&profiles, &self.settings.audio_host, seconds) {
+                return;
+            }
+            self.preroll = None;
+        }
+        if wanted {
+            self.preroll = Some(PreRoll::start(profiles, self.transcription_config(), seconds));
+        }
+    }
+
+    /// Graba unos segundos del dispositivo del perfil `idx` para escucharlos.
+    /// La pre-grabación suelta sus dispositivos mientras tanto.
+    fn start_device_test(&mut self, idx: usize) {
+        let Some(profile) = self.interlocutors.get(idx).cloned() else { return };
+        self.preroll = None;
+        self.player.stop();
+        self.device_test = Some(DeviceTest::start(&profile, self.transcription_config()));
+    }
+
+    /// Al terminar la grabación de prueba, la reproduce.
+    fn sync_device_test(&mut self) {
+        if !self.device_test.as_ref().is_some_and(|t| t.is_done()) {
+            return;
+        }
+        let Some(test) = self.device_test.take() else { return };
+        let name = test.profile.clone();
+        self.status_message = match test.finish() {
+            Ok(samples) => match self.player.play_samples(&samples) {
+                Ok(()) if device_test::is_quiet(&samples) => tr!("device_test.quiet", name = name),
+                Ok(()) => tr!("device_test.playing", name = name),
+                Err(e) => tr!("playback.error", e = e),
+            },
+            Err(e) => tr!("device_test.error", name = name, e = e),
+        };
+    }
+
+    /// Abre el asistente de registro de voz del participante `idx`, con su
+    /// micrófono habitual si está conectado.
+    fn open_enrollment(&mut self, idx: usize) {
+        let Some(participant) = self.settings.roster.get(idx) else { return };
+        let device_id = self.all_input_devices.iter()
+            .find(|d| d.technical_name.is_some() && d.technical_name == participant.device)
+            .or(self.all_input_devices.first())
+            .map_or(0, |d| d.id);
+        self.enrollment = Some(EnrollWizard { participant: idx, device_id, recording: None, result: None });
+    }
+
+    /// Empieza a grabar el párrafo con el micrófono elegido en el asistente.
+    fn start_enrollment(&mut self) {
+        let Some(wizard) = &self.enrollment else { return };
+        let Some(device) = self.all_input_devices.iter().find(|d| d.id == wizard.device_id) else { return };
+        let Some(participant) = self.settings.roster.get(wizard.participant) else { return };
+        let profile = InterlocutorProfile {
+            source_type: SourceType::Input,
+            name: participant.name.clone(),
+            device_id: device.id,
+            technical_name: device.technical_name.clone(),
+            ..InterlocutorProfile::default()
+        };
+        self.preroll = None;
+        self.player.stop();
+        let config = self.transcription_config();
+        if let Some(wizard) = &mut self.enrollment {
+            wizard.result = None;
+            wizard.recording = Some(Enrollment::start(&profile, config));
+        }
+    }
+
+    /// Al terminar la grabación del asistente, guarda la huella de voz en el
+    /// participante.
+    fn sync_enrollment(&mut self) {
+        let Some(wizard) = &mut self.enrollment else { return };
+        if !wizard.recording.as_ref().is_some_and(Enrollment::is_done) {
+            return;
+        }
+        let Some(recording) = wizard.recording.take() else { return };
+        let result = recording.finish();
+        let saved = match (&result, self.settings.roster.get_mut(wizard.participant)) {
+            (Ok(voice), Some(participant)) => {
+                participant.voice = voice.clone();
+                true
+            }
+            _ => false,
+        };
+        wizard.result = Some(result.map(|_| ()).map_err(|e| e.to_string()));
+        if saved {
+            self.save_settings();
+        }
+    }
+
+    fn show_enrollment_dialog(&mut self, ctx: &egui::Context) {
+        let Some(wizard) = &mut self.enrollment else { return };
+        let name = self.settings.roster.get(wizard.participant).map(|p| p.name.clone()).unwrap_or_default();
+        let input_devices = &self.all_input_devices;
+        let device_settings = &self.settings.devices;
+        let mut start = false;
+        let mut cancel = false;
+        let mut close = false;
+
+        egui::Window::new(tr!("enroll.title", name = name))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                match &wizard.recording {
+                    Some(recording) => {
+                        ui.label(tr!("enroll.reading"));
+                        ui.add(egui::ProgressBar::new(recording.progress()).show_percentage());
+                    }
+                    None => {
+                        ui.label(tr!("enroll.intro", secs = speaker_id::ENROLL_SECS));
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("enroll.microphone"));
+                            let selected = input_devices.iter()
+                                .find(|d| d.id == wizard.device_id)
+                                .map(|d| device_settings.display_name(d))
+                                .unwrap_or_default();
+                            egui::ComboBox::from_id_salt("enroll_device")
+                                .selected_text(selected)
+                                .width(260.0)
+                                .show_ui(ui, |ui| {
+                                    for device in input_devices {
+                                        ui.selectable_value(&mut wizard.device_id, device.id, device_settings.display_name(device));
+                                    }
+                                });
+                        });
+                    }
+                }
+                ui.add_space(8.0);
+                ui.group(|ui| {
+                    ui.label(egui::RichText::new(tr!("enroll.paragraph")).size(16.0));
+                });
+                ui.add_space(8.0);
+                match &wizard.result {
+                    Some(Ok(())) => { ui.colored_label(egui::Color32::GREEN, tr!("enroll.done", name = name)); }
+                    Some(Err(e)) => { ui.colored_label(egui::Color32::RED, tr!("enroll.error", e = e)); }
+                    None => {}
+                }
+                ui.horizontal(|ui| {
+                    if wizard.recording.is_some() {
+                        if ui.button(tr!("common.cancel")).clicked() {
+                            cancel = true;
+                        }
+                        return;
+                    }
+                    let label = if wizard.result.is_some() { tr!("enroll.retry") } else { tr!("enroll.start") };
+                    if ui.add_enabled(!input_devices.is_empty(), egui::Button::new(label)).clicked() {
+                        start = true;
+                    }
+                    if ui.button(tr!("common.close")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if cancel {
+            wizard.recording = None;
+        }
+        if close {
+            self.enrollment = None;
+        }
+        if start {
+            self.start_enrollment();
+        }
+    }
+
+    fn is_stopping(&self) -> bool {
+        !self.is_running && self.audio_thread.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
+    /// Espera a que el hilo de captura libere los dispositivos. Está acotado:
+    /// `audio_thread_main` deja de esperar a los streams tras un tiempo límite.
+    fn join_audio_thread(&mut self) {
+        if let Some(handle) = self.audio_thread.take() {
+            let _ = handle.join();
+        }
+    }
+
+    fn quit(&mut self, ctx: &egui::Context) {
+        self.show_exit_confirm = false;
+        self.exit_confirmed = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    fn show_loopback_dialog(&mut self, ctx: &egui::Context) {
+        let mut close = false;
+        let mut create_sink = false;
+        let mut remove_sink = false;
+
+        egui::Window::new(tr!("loopback.title"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(650.0)
+            .show(ctx, |ui| {
+                if let Some(info) = &self.loopback_info {
+                    ui.label(egui::RichText::new(&info.message).size(16.0).strong());
+                    ui.add_space(10.0);
+
+                    match info.status {
+                        LoopbackStatus::Available => {
+                            ui.colored_label(egui::Color32::GREEN, tr!("loopback.ok"));
+                            for dev in &info.loopback_devices {
+                                ui.label(format!("  • {}", dev.name));
+                            }
+                        }
+                        _ => {
+                            ui.label(tr!("loopback.instructions"));
+                            ui.separator();
+                            egui::ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                                for line in &info.instructions {
+                                    if line.is_empty() {
+                                        ui.add_space(5.0);
+                                    } else {
+                                        ui.label(line);
+                                    }
+                                }
+                            });
+                        }
+                    }
+
+                    if self.virtual_sink_supported {
+                        ui.add_space(10.0);
+                        ui.separator();
+                        ui.label(tr!("loopback.sink_help"));
+                        ui.add_enabled_ui(!self.is_running, |ui| {
+                            if self.virtual_sink.is_none() {
+                                if ui.button(tr!("loopback.sink_create")).clicked() {
+                                    create_sink = true;
+                                }
+                            } else if ui.button(tr!("loopback.sink_remove")).clicked() {
+                                remove_sink = true;
+                            }
+                        });
+                    }
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!("loopback.refresh")).clicked() {
+                            self.all_output_devices = get_loopback_devices();
+                            self.reload_input_devices();
+                            let n = self.all_output_devices.len();
+                            self.status_message = if n > 0 {
+                                tr!("loopback.detected", n = n)
+                            } else {
+                                tr!("loopback.none")
+                            };
+                            if n > 0 { close = true; }
+                        }
+                        if ui.button(tr!("common.close")).clicked() { close = true; }
+                    });
+                }
+            });
+
+        if close { self.show_loopback_setup = false; }
+        if create_sink { self.create_virtual_sink(); }
+        if remove_sink {
+            self.virtual_sink = None;
+            self.all_output_devices = get_loopback_devices();
+            self.reconcile_profiles();
+            self.loopback_info = check_loopback_status().ok();
+            self.status_message = tr!("loopback.sink_removed");
+        }
+    }
+
+    /// Crea la salida «Minutero» y pone su monitor en el primer interlocutor
+    /// de salida (o en uno nuevo si no hay).
+    fn create_virtual_sink(&mut self) {
+        let sink = match VirtualSink::create() {
+            Ok(sink) => sink,
+            Err(e) => {
+                self.status_message = e.to_string();
+                return;
+            }
+        };
+        self.virtual_sink = Some(sink);
+        self.all_output_devices = get_loopback_devices();
+        self.loopback_info = check_loopback_status().ok();
+
+        let monitor = Some(monitor_name());
+        let Some(device) = self.all_output_devices.iter().find(|d| d.technical_name == monitor).cloned() else {
+            self.status_message = tr!("loopback.sink_created");
+            return;
+        };
+        if !self.interlocutors.iter().any(|p| p.source_type == SourceType::Output) {
+            self.add_new_profile(SourceType::Output);
+        }
+        if let Some(profile) = self.interlocutors.iter_mut().find(|p| p.source_type == SourceType::Output) {
+            profile.device_id = device.id;
+            profile.technical_name = device.technical_name.clone();
+            profile.channel = None;
+        }
+        self.status_message = tr!("loopback.sink_selected", name = device.name);
+    }
+
+    // ── Helpers ────────────────────────────────────────────────────────────
+
+    fn check_keyword_alerts(&self, name: &str, text: &str) {
+        if !self.settings.alert_notifications {
+            return;
+        }
+        let keywords = parse_keywords(&self.settings.alert_keywords);
+        let hits = matched_keywords(&keywords, text);
+        if !hits.is_empty() {
+            desktop_notification(
+                &tr!("alerts.title", keywords = hits.join(", ")),
+                &format!("({}) {}", name, text),
+            );
+        }
+    }
+
+    /// Carga el modelo elegido en segundo plano, si la opción está activada.
+    fn start_preload(&mut self) {
+        if !self.settings.preload_model {
+            return;
+        }
+        let (tx, rx) = channel::<AudioMessage>();
+        self.preload_rx = Some(rx);
+        let model = self.model_name.clone();
+        let source = self.settings.model_source.clone();
+        thread::spawn(move || {
+            if let Err(e) = preload_thread(model, source, tx.clone()) {
+                let _ = tx.send(AudioMessage::Error(format!("{}", e)));
+            }
+        });
+    }
+
+    fn transcription_config(&self) -> TranscriptionConfig {
+        TranscriptionConfig {
+            lang: self.lang_config.clone(),
+            preset: self.settings.active_preset(),
+            tuning: self.settings.tuning.clone(),
+            style: self.settings.transcription_style,
+            audio_host: self.settings.audio_host.clone(),
+            model_source: self.settings.model_source.clone(),
+            mute: self.mute.clone(),
+            multilingual: self.settings.multilingual.clone(),
+            voices: VoiceMatcher::new(&self.settings.roster, &self.settings.speaker_id),
+            streams: self.settings.devices.streams.clone(),
+            capture_tool: self.settings.capture_tool,
+            compare_model: self.compare_model.clone(),
+            discord: self.settings.integrations.discord.clone(),
+            limits: self.settings.limits.clone(),
+        }
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(e) = self.settings.save() {
+            self.status_message = tr!("settings.save_error", e = format!("{:?}", e));
+        }
+    }
+
+    /// Crea el archivo de la grabación de la reunión si está activada.
+    /// `started` es el origen de la sesión (antes de ahora con pre-grabación).
+    fn start_recording(&mut self, started: Instant) -> Result<Option<MixRecorder>> {
+        self.recording_path = None;
+        let settings = &self.settings.recording;
+        if !settings.enabled {
+            return Ok(None);
+        }
+        let dir = check_output_dir(&self.output_dir)?;
+        let stem = format!("grabacion_{}", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+        let path = unique_path(&dir, &stem, RecordingFormat::Wav.extension());
+        let recorder = MixRecorder::create(&path, settings, started)?;
+        self.recording_path = Some(path.with_extension(settings.format.extension()));
+        Ok(Some(recorder))
+    }
+
+    /// Reproduce la intervención marcada en la minuta.
+    fn play_segment_at_cursor(&mut self) {
+        if let Some(cursor) = self.transcript_cursor {
+            self.play_segment(cursor);
+        }
+    }
+
+    /// Reproduce la intervención `index` desde la grabación.
+    fn play_segment(&mut self, index: usize) {
+        let Some(path) = self.recording_path.clone() else { return };
+        // La grabación empieza donde se continuó la minuta
+        let Some(segment) = self.segments.get(index).filter(|s| !s.is_pause() && s.start_secs >= self.time_offset) else {
+            self.status_message = tr!("playback.no_segment");
+            return;
+        };
+        let (start, end) = (segment.start_secs - self.time_offset, segment.end_secs - self.time_offset);
+        if let Err(e) = self.player.play(&path, start, end) {
+            self.status_message = tr!("playback.error", e = e);
+        }
+    }
+
+    /// Vuelve a transcribir las pistas de la sesión con el modelo seleccionado.
+    fn start_retranscription(&mut self) {
+        let Some(recording) = self.recording_path.clone() else { return };
+        let (tx, rx) = channel::<RetranscribeMessage>();
+        let stop = StopSignal::new();
+        self.retranscribe_rx = Some(rx);
+        self.retranscribe_stop = Some(stop.clone());
+        self.retranscribe_progress = 0.0;
+        self.player.stop();
+
+        let model = self.model_name.clone();
+        let config = self.transcription_config();
+        thread::spawn(move || {
+            if let Err(e) = retranscribe_thread(recording, model, config, tx.clone(), stop) {
+                let _ = tx.send(RetranscribeMessage::Error(format!("{:?}", e)));
+            }
+        });
+        self.status_message = tr!("retranscribe.started", model = self.model_name);
+    }
+
+    /// Añade una intervención en su sitio por hora de captura: cada fuente
+    /// entrega al terminar su fragmento, y una más lenta llegaría después de
+    /// lo que otros dijeron más tarde. Solo se adelanta sobre las
+    /// intervenciones del último fragmento y `REORDER_MARGIN_SECS`, para no
+    /// mover líneas que ya se están leyendo.
+    fn insert_segment(&mut self, mut segment: TranscriptSegment) {
+        segment.shift(self.time_offset);
+        let window = self.settings.tuning.chunk_secs as f64 + REORDER_MARGIN_SECS;
+        let latest = self.segments.last().map_or(0.0, |s| s.start_secs);
+        let later = self.segments.iter().rev()
+            .take_while(|s| s.start_secs > segment.start_secs && latest - s.start_secs <= window)
+            .count();
+        let pos = self.segments.len() - later;
+
+        let mut rewritten = pos < self.segments.len();
+        if self.settings.active_preset().restore_punctuation {
+            let lang = self.transcript_lang();
+            let previous = self.segments[..pos].iter_mut().rev().find(|s| s.name == segment.name);
+            // Si se une a la anterior, cambia una línea ya mostrada
+            rewritten |= punctuation::join_segments(previous, &mut segment, lang);
+        }
+        self.check_keyword_alerts(&segment.name, &segment.text);
+        self.last_segment_at = Some(Instant::now());
+        if let Some(mqtt) = &self.mqtt {
+            mqtt.publish(&segment);
+        }
+        if let Some(telegram) = &self.telegram {
+            telegram.publish(&segment);
+        }
+        if let Some(discord) = &self.discord {
+            discord.publish(&segment);
+        }
+        if let Some(ndi) = &self.ndi {
+            ndi.publish(&segment);
+        }
+        hooks::run(&self.settings.hooks, HookEvent::Segment(&segment));
+
+        if rewritten {
+            self.transcript_version += 1;
+        }
+        self.segments.insert(pos, segment);
+    }
+
+    /// Sustituye la minuta por la re-transcrita y la guarda como versión nueva.
+    fn apply_retranscription(&mut self, model: &str, segments: Vec<TranscriptSegment>) {
+        self.segments = segments;
+        self.transcript_version += 1;
+        self.transcript_selection = None;
+        self.transcript_cursor = None;
+        let title = match self.session_title.trim() {
+            "" => tr!("export.minutes_title"),
+            t => t.to_string(),
+        };
+        self.session_title = tr!("retranscribe.version_title", title = title, model = model);
+        self.status_message = tr!("retranscribe.done", model = model);
+        self.save_live_minuta(tr!("notify.retranscribed"));
+    }
+
+    /// Carga una minuta JSON exportada, con su grabación si sigue al lado.
+    fn open_session(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json", ENCRYPTED_EXTENSION])
+            .set_directory(absolute_output_dir(&self.output_dir))
+            .pick_file()
+        else {
+            return;
+        };
+        self.load_session(&path);
+    }
+
+    /// `true` si se pudo abrir.
+    fn load_session(&mut self, path: &Path) -> bool {
+        match read_minuta_json(path, &self.settings.encryption) {
+            Ok(session) => {
+                self.player.stop();
+                self.session_title = session.title;
+                self.session_attendees = session.attendees.join(", ");
+                self.segments = session.segments;
+                self.transcript_version += 1;
+                let last_end = self.segments.iter().map(|s| s.end_secs).fold(0.0, f64::max);
+                self.session_started_at = session.saved_at
+                    .map(|at| at - chrono::Duration::milliseconds((last_end * 1000.0) as i64));
+                self.time_offset = 0.0;
+                self.transcript_selection = None;
+                self.transcript_cursor = None;
+                self.status_message = match &session.recording {
+                    Some(_) => tr!("playback.session_loaded", path = path.display()),
+                    None => tr!("playback.session_no_recording", path = path.display()),
+                };
+                self.recording_path = session.recording;
+                true
+            }
+            Err(e) => {
+                self.status_message = tr!("playback.open_error", e = e);
+                false
+            }
+        }
+    }
+
+    /// Vuelve a listar los micrófonos del host elegido. Los perfiles siguen
+    /// en su dispositivo si el nuevo host lo ofrece; si no, quedan como
+    /// desaparecidos hasta que se elija otro.
+    fn reload_input_devices(&mut self) {
+        self.all_input_devices = get_available_devices(self.settings.audio_host.as_deref(), true);
+        self.reconcile_profiles();
+    }
+
+    /// Enlaza los perfiles con los dispositivos actuales por nombre técnico y
+    /// avisa de los que faltan.
+    fn reconcile_profiles(&mut self) {
+        let missing = devices::reconcile(&mut self.interlocutors, &self.all_input_devices, &self.all_output_devices);
+        if !missing.is_empty() {
+            self.status_message = tr!("devices.missing", names = missing.join(", "));
+        }
+    }
+
+    /// Guarda los interlocutores cuando cambian, para la próxima sesión.
+    fn sync_saved_profiles(&mut self) {
+        if self.settings.devices.profiles != self.interlocutors {
+            self.settings.devices.profiles = self.interlocutors.clone();
+            self.save_settings();
+        }
+    }
+
+    fn add_new_profile(&mut self, source_type: SourceType) {
+        let raw: &[DeviceInfo] = match source_type {
+            SourceType::Input => &self.all_input_devices,
+            SourceType::Output => &self.all_output_devices,
+            SourceType::Network | SourceType::Pipe | SourceType::Discord => &[],
+        };
+        let device_id = raw.first().map(|d| d.id).unwrap_or(0);
+        let new_id = self.interlocutors.len();
+        self.interlocutors.push(InterlocutorProfile {
+            id: new_id,
+            device_id,
+            source_type,
+            name: tr!("common.speaker", n = new_id),
+            is_active: true,
+            technical_name: raw.first().and_then(|d| d.technical_name.clone()),
+            channel: None,
+            agc: true,
+            pcm: PcmFormat::default(),
+            identify_voices: false,
+            on_error: RestartPolicy::default(),
+            max_restarts: 3,
+        });
+    }
+
+    /// Sustituye el perfil por uno por canal de su dispositivo, para mesas de
+    /// mezclas con un micrófono por persona. La captura abre el dispositivo
+    /// una sola vez y reparte los canales.
+    fn split_profile_channels(&mut self, index: usize, channels: u16) {
+        let Some(base) = self.interlocutors.get(index).cloned() else { return };
+        let split: Vec<InterlocutorProfile> = (0..channels)
+            .map(|c| InterlocutorProfile {
+                name: tr!("settings.channel_speaker", name = base.name, n = c + 1),
+                channel: Some(c),
+                ..base.clone()
+            })
+            .collect();
+        self.interlocutors.splice(index..=index, split);
+        for (i, p) in self.interlocutors.iter_mut().enumerate() {
+            p.id = i;
+        }
+    }
+
+    fn remove_profile(&mut self, index: usize) {
+        if index < self.interlocutors.len() {
+            self.interlocutors.remove(index);
+            for (i, p) in self.interlocutors.iter_mut().enumerate() {
+                p.id = i;
+                p.name = tr!("common.speaker", n = i);
+            }
+        }
+    }
+
+    /// Minuta de la sesión en vivo, lista para cualquier formato de exportación.
+    fn live_document(&self) -> MinutaDocument {
+        let title = match self.session_title.trim() {
+            "" => tr!("export.minutes_title"),
+            t => t.to_string(),
+        };
+        let title = match self.split_block {
+            Some(n) => tr!("split.block_title", title = title, n = n),
+            None => title,
+        };
+        let attendees: Vec<String> = self.session_attendees.split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(str::to_string)
+            .collect();
+        let attendees = if attendees.is_empty() {
+            roster::attendee_labels(&self.settings.roster, self.active_names())
+        } else {
+            attendees
+        };
+        MinutaDocument::new(title, attendees, transcript_text(&self.segments))
+            .with_segments(&self.segments)
+            .with_recording(
+                self.recording_path.as_ref()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().to_string()),
+            )
+            .with_stats(compute_stats(&self.segments))
+    }
+
+    /// La minuta tal como se exporta: en pares pregunta/respuesta en el modo
+    /// entrevista, con capítulos en el modo clase y con los números en cifras
+    /// si así se ha elegido.
+    fn export_document(&self) -> MinutaDocument {
+        let doc = self.live_document();
+        let doc = match self.settings.session_type {
+            SessionType::Meeting => doc,
+            SessionType::Interview => doc.into_interview(self.transcript_lang()),
+            SessionType::Lecture => doc.into_lecture(self.transcript_lang()),
+        };
+        if self.settings.normalize_numbers { doc.normalize_numbers(self.transcript_lang()) } else { doc }
+    }
+
+    /// Idioma del texto de la minuta (`None` = detección automática).
+    fn transcript_lang(&self) -> Option<&'static str> {
+        if self.lang_config.translate_to_english { Some("en") } else { self.lang_config.source_lang }
+    }
+
+    fn active_names(&self) -> Vec<String> {
+        self.interlocutors.iter()
+            .filter(|p| p.is_active)
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    fn active_names_stem(&self) -> String {
+        self.interlocutors.iter()
+            .filter(|p| p.is_active)
+            .map(|p| p.name.replace(' ', "_"))
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    fn get_device_name_static(
+        inputs: &[DeviceInfo], outputs: &[DeviceInfo],
+        device_settings: &DeviceSettings, profile: &InterlocutorProfile,
+    ) -> String {
+        if let Some(device) = devices::find(profile, inputs, outputs) {
+            return device_settings.display_name(device);
+        }
+        // Se muestra lo que se buscaba, para saber qué conectar
+        match &profile.technical_name {
+            Some(name) => {
+                let alias = device_settings.aliases.get(name).unwrap_or(name);
+                tr!("settings.device_missing_named", name = alias)
+            }
+            None => tr!("settings.device_missing"),
+        }
+    }
+}
+
+/// Cadena (si es una minuta JSON) y firma de un archivo exportado, una
+/// comprobación por línea.
+fn verify_minuta(path: &Path, encryption: &EncryptionSettings) -> String {
+    let mut lines = Vec::new();
+    if encryption::plain_path(path).extension().is_some_and(|e| e == "json") {
+        lines.push(match integrity::verify_chain(path, encryption) {
+            Ok(ChainCheck::Intact { segments }) => tr!("integrity.chain_ok", n = segments),
+            Ok(ChainCheck::Broken { index }) => tr!("integrity.chain_broken", n = index + 1),
+            Ok(ChainCheck::Truncated { expected, found }) => tr!("integrity.chain_truncated", expected = expected, found = found),
+            Ok(ChainCheck::Missing) => tr!("integrity.chain_missing"),
+            Err(e) => tr!("common.error", e = e),
+        });
+    }
+    lines.push(match integrity::check_signature(path) {
+        Ok(Some((true, msg))) => tr!("integrity.signature_ok", msg = msg),
+        Ok(Some((false, msg))) => tr!("integrity.signature_bad", msg = msg),
+        Ok(None) => tr!("integrity.signature_missing"),
+        Err(e) => tr!("common.error", e = e),
+    });
+    lines.join("\n")
+}
+
+/// Tira con la envolvente de los últimos niveles de una fuente (escala en dB
+/// como el medidor). Plana = no llega audio; gris = no se está transcribiendo.
+fn waveform_strip(ui: &mut egui::Ui, history: &VecDeque<f32>, audible: bool) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(LEVEL_HISTORY as f32, 16.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let color = if audible { egui::Color32::from_rgb(80, 170, 80) } else { egui::Color32::GRAY };
+    let step = rect.width() / LEVEL_HISTORY as f32;
+    // Los más recientes a la derecha
+    let start = rect.right() - history.len() as f32 * step;
+    for (i, &rms) in history.iter().enumerate() {
+        let db = 20.0 * rms.max(1e-6).log10();
+        let half = ((db + 60.0) / 60.0).clamp(0.0, 1.0) * rect.height() / 2.0;
+        let x = start + (i as f32 + 0.5) * step;
+        painter.line_segment(
+            [egui::pos2(x, rect.center().y - half.max(0.5)), egui::pos2(x, rect.center().y + half.max(0.5))],
+            egui::Stroke::new(step, color),
+        );
+    }
+    response.on_hover_text(tr!("live.waveform_hint", secs = LEVEL_HISTORY / 5));
+}
+
+/// Texto de la minuta en vivo, una línea por intervención.
+fn transcript_text(segments: &[TranscriptSegment]) -> String {
+    segments.iter()
+        .map(|s| format!("{}\n", s.line()))
+        .collect()
+}
+
+/// Texto original y correcciones de una intervención, para el tooltip.
+fn edit_history(segment: &TranscriptSegment) -> String {
+    let mut lines = vec![tr!("edits.original", text = segment.transcribed_text())];
+    lines.extend(segment.edits.iter().map(|e| tr!("edits.entry", at = e.at, text = e.text)));
+    lines.join("\n")
+}
+
+/// Maqueta una línea de la minuta resaltándola si tiene palabras clave y
+/// marcando las palabras que Whisper reconoció con poca seguridad. Con
+/// `transcribed`, sin las correcciones a mano; si no, las corregidas llevan
+/// una marca.
+fn highlight_segment(
+    ui: &egui::Ui,
+    segment: &TranscriptSegment,
+    keywords: &[String],
+    font: &egui::FontId,
+    transcribed: bool,
+) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let line = if transcribed { segment.transcribed_line() } else { segment.line() };
+    let mut format = egui::TextFormat {
+        font_id: font.clone(),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    if !matched_keywords(keywords, &line).is_empty() {
+        format.background = ui.visuals().warn_fg_color.gamma_multiply(0.35);
+    }
+    let doubtful = egui::TextFormat {
+        color: ui.visuals().warn_fg_color,
+        italics: true,
+        ..format.clone()
+    };
+    let low_confidence: Vec<&str> = segment.words.iter()
+        .filter(|w| w.is_low_confidence())
+        .map(|w| w.word.as_str())
+        .collect();
+    append_marking_words(&mut job, &line, &low_confidence, &format, &doubtful);
+    if !transcribed && segment.original.is_some() {
+        let tag = egui::TextFormat { color: ui.visuals().weak_text_color(), ..format.clone() };
+        job.append("✏", 6.0, tag);
+    }
+    if let Some(lang) = &segment.lang {
+        let tag = egui::TextFormat { color: ui.visuals().weak_text_color(), ..format };
+        job.append(&format!("[{lang}]"), 6.0, tag);
+    }
+    job
+}
+
+/// Añade `line` marcando con `marked` la primera aparición de cada palabra, en
+/// orden y sin mirar el prefijo `(Nombre) `.
+fn append_marking_words(
+    job: &mut egui::text::LayoutJob,
+    line: &str,
+    words: &[&str],
+    format: &egui::TextFormat,
+    marked: &egui::TextFormat,
+) {
+    let mut cursor = line.find(") ").map_or(0, |p| p + 2);
+    job.append(&line[..cursor], 0.0, format.clone());
+    for word in words.iter().filter(|w| !w.is_empty()) {
+        if let Some(pos) = line[cursor..].find(word) {
+            let start = cursor + pos;
+            job.append(&line[cursor..start], 0.0, format.clone());
+            job.append(&line[start..start + word.len()], 0.0, marked.clone());
+            cursor = start + word.len();
+        }
+    }
+    job.append(&line[cursor..], 0.0, format.clone());
+}